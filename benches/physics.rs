@@ -0,0 +1,70 @@
+// Benchmarks for the simulation's physics and collision code.
+// Run with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId, black_box};
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::collision::ObjectCollider;
+use pong::game::{GameState, TICK_TIME};
+use pong::modes::Classic;
+
+// Build a collider from its top-left corner and size.
+fn rect(x: f32, y: f32, w: f32, h: f32) -> ObjectCollider {
+	ObjectCollider::from_bounds(Vec2::new(x, y), Vec2::new(x + w, y + h))
+}
+
+// Build a match in play with the given number of balls spread across the court.
+fn multi_ball_game(balls: usize) -> GameState {
+	let mut game = GameState::with_mode(&Classic);
+	game.set_seed(1);
+	game.control_id = None;
+
+	for _ in 0..balls {
+		game.objects.push(Object::new(ObjectType::Ball).set_size(25.0, 25.0));
+	}
+
+	game.resize(1920.0, 1080.0);
+	game.paused = false;
+
+	// Scatter the balls so they're not all stacked in the center.
+	for (i, obj) in game.objects.iter_mut().enumerate() {
+		if obj.obj_type == ObjectType::Ball {
+			obj.position = Vec2::new(200.0 + (i * 37 % 1500) as f32, 50.0 + (i * 53 % 950) as f32);
+		}
+	}
+
+	game
+}
+
+fn collision_check(c: &mut Criterion) {
+	let paddle = rect(100.0, 100.0, 25.0, 200.0);
+	let hit = rect(110.0, 150.0, 25.0, 25.0);
+	let miss = rect(500.0, 150.0, 25.0, 25.0);
+
+	c.bench_function("is_colliding/hit", |b| b.iter(|| black_box(&hit).is_colliding(black_box(&paddle))));
+	c.bench_function("is_colliding/miss", |b| b.iter(|| black_box(&miss).is_colliding(black_box(&paddle))));
+}
+
+fn swept_collision(c: &mut Criterion) {
+	let ball = rect(0.0, 100.0, 25.0, 25.0);
+	let paddle = rect(100.0, 50.0, 25.0, 200.0);
+
+	c.bench_function("sweep/hit", |b| b.iter(|| black_box(&ball).sweep(black_box(Vec2::new(500.0, 10.0)), black_box(&paddle))));
+	c.bench_function("sweep/miss", |b| b.iter(|| black_box(&ball).sweep(black_box(Vec2::new(50.0, 10.0)), black_box(&paddle))));
+}
+
+fn multi_ball_update(c: &mut Criterion) {
+	let mut group = c.benchmark_group("update");
+
+	for balls in [1, 100, 500] {
+		group.bench_with_input(BenchmarkId::new("balls", balls), &balls, |b, &balls| {
+			let mut game = multi_ball_game(balls);
+			b.iter(|| game.update(black_box(TICK_TIME)));
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, collision_check, swept_collision, multi_ball_update);
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+// The Android app, built as the library the app loads.
+// It's the same game as the desktop binary, so this only brings in its startup code, which ndk-glue calls once the app's activity starts.
+
+include!("../src/main.rs");
@@ -0,0 +1,81 @@
+// Fuzz the collision checks with random rects and movements.
+// Any finite input should give a finite answer, never NaN.
+// Run with `cargo +nightly fuzz run collision`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use arbitrary::Arbitrary;
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::collision::{self, ObjectCollider};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+	// Top-left corner and size of each rect.
+	a: (f32, f32, f32, f32),
+	b: (f32, f32, f32, f32),
+	// How far the first rect moves.
+	delta: (f32, f32),
+	velocity: (f32, f32)
+}
+
+// Build a collider, ignoring rects that aren't finite or have no area.
+fn rect((x, y, w, h): (f32, f32, f32, f32)) -> Option<ObjectCollider> {
+	let sane = |v: f32| v.is_finite() && v.abs() < 1.0e6;
+	if !(sane(x) && sane(y) && sane(w) && sane(h)) || w <= 0.0 || h <= 0.0 {
+		return None;
+	}
+	Some(ObjectCollider::from_bounds(Vec2::new(x, y), Vec2::new(x + w, y + h)))
+}
+
+fn is_finite(v: Vec2) -> bool {
+	v.x.is_finite() && v.y.is_finite()
+}
+
+fuzz_target!(|input: Input| {
+	let (Some(a), Some(b)) = (rect(input.a), rect(input.b)) else {
+		return;
+	};
+	let delta = Vec2::new(input.delta.0, input.delta.1);
+	let velocity = Vec2::new(input.velocity.0, input.velocity.1);
+	if !is_finite(delta) || !is_finite(velocity) || delta.x.abs() > 1.0e6 || delta.y.abs() > 1.0e6 || velocity.x.abs() > 1.0e3 || velocity.y.abs() > 1.0e3 {
+		return;
+	}
+
+	// Overlap is symmetric, and matches whether there's anything to push out.
+	assert_eq!(a.is_colliding(&b), b.is_colliding(&a));
+	let contact = a.contact(&b);
+	assert_eq!(a.is_colliding(&b), contact.is_some());
+
+	if let Some(contact) = contact {
+		// The contact normal is always a unit axis, and the contact point is inside both colliders.
+		let normal = contact.normal;
+		assert!(normal.x.abs() + normal.y.abs() == 1.0, "bad contact normal {:?}", normal);
+		for collider in [a, b] {
+			assert!((collider.min.x..=collider.max.x).contains(&contact.point.x), "contact point {:?} outside {:?}", contact.point, collider);
+			assert!((collider.min.y..=collider.max.y).contains(&contact.point.y), "contact point {:?} outside {:?}", contact.point, collider);
+		}
+
+		// Reflecting off an axis keeps the speed.
+		let reflected = collision::reflect(velocity, normal);
+		assert!(is_finite(reflected));
+		assert_eq!(reflected.x.abs(), velocity.x.abs());
+		assert_eq!(reflected.y.abs(), velocity.y.abs());
+	}
+
+	// A hit happens somewhere along the movement, on a face pointing back against it.
+	if let Some(sweep) = a.sweep(delta, &b) {
+		assert!((0.0..=1.0).contains(&sweep.time), "bad time of impact {}", sweep.time);
+		assert!(sweep.normal.x * delta.x + sweep.normal.y * delta.y <= 0.0, "normal {:?} points along {:?}", sweep.normal, delta);
+		assert!(is_finite(sweep.point), "bad contact point {:?}", sweep.point);
+	}
+
+	// Bouncing a ball off a paddle never produces NaN or a speed above the cap.
+	let mut ball = Object::new(ObjectType::Ball).set_size(a.max.x - a.min.x, a.max.y - a.min.y);
+	ball.position = a.min;
+	ball.velocity = velocity;
+	let bounce = collision::paddle_bounce(&ball, &b, std::f32::consts::FRAC_PI_3, 1.15);
+	assert!(is_finite(bounce), "bounce {:?} from {:?} off {:?}", bounce, ball, b);
+	assert!(bounce.x.abs() <= ball.max_velocity.x && bounce.y.abs() <= ball.max_velocity.y);
+});
@@ -0,0 +1,71 @@
+// Fuzz the simulation with random court sizes and ball states.
+// Positions must stay finite, and the ball must never get stuck inside a paddle.
+// Run with `cargo +nightly fuzz run physics`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use arbitrary::Arbitrary;
+
+use pong::structs::{Vec2, ObjectType};
+use pong::game::{GameState, TICK_RATE};
+use pong::modes::Classic;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+	seed: u64,
+	width: u16,
+	height: u16,
+	ball_position: (f32, f32),
+	ball_velocity: (f32, f32),
+	// Where each paddle starts, as a fraction of the court height.
+	paddles: (u8, u8),
+	ticks: u16
+}
+
+fuzz_target!(|input: Input| {
+	let (x, y) = input.ball_position;
+	let (vx, vy) = input.ball_velocity;
+	if ![x, y, vx, vy].iter().all(|v| v.is_finite()) {
+		return;
+	}
+
+	let mut game = GameState::with_mode(&Classic);
+	game.set_seed(input.seed);
+	game.control_id = None;
+	game.resize(input.width.clamp(200, 4000) as f32, input.height.clamp(200, 4000) as f32);
+	game.paused = false;
+
+	let (width, height) = (game.width, game.height);
+
+	// Start the ball somewhere in the court, moving no faster than a court width per tick.
+	// The ball never moves across the court slower than it's served, so neither does the fuzzed one.
+	let ball = &mut game.objects[0];
+	let serve_speed = ball.velocity.x.abs();
+	ball.position = Vec2::new(x.clamp(0.0, width - ball.size.x), y.clamp(0.0, height - ball.size.y));
+	ball.velocity = Vec2::new(vx.abs().clamp(serve_speed, width).copysign(vx), vy.clamp(-height, height));
+
+	for (obj, fraction) in game.objects[1..].iter_mut().zip([input.paddles.0, input.paddles.1]) {
+		obj.position.y = (height - obj.size.y) * fraction as f32 / 255.0;
+	}
+
+	// Count how many ticks in a row the ball has spent inside a paddle.
+	let mut stuck_ticks = 0;
+
+	for _ in 0..input.ticks.min(TICK_RATE as u16 * 30) {
+		game.step(1);
+
+		for obj in &game.objects {
+			let finite = [obj.position.x, obj.position.y, obj.velocity.x, obj.velocity.y].iter().all(|v| v.is_finite());
+			assert!(finite, "{:?} isn't finite", obj);
+		}
+
+		let ball = game.objects[0].get_collider();
+		let inside = game.objects.iter()
+			.filter(|obj| obj.obj_type != ObjectType::Ball)
+			.any(|paddle| ball.is_colliding(&paddle.get_collider()));
+
+		stuck_ticks = if inside { stuck_ticks + 1 } else { 0 };
+		assert!(stuck_ticks < TICK_RATE as u32, "ball stuck in a paddle: {:?}", game.objects);
+	}
+});
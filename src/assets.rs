@@ -0,0 +1,92 @@
+// Dependencies
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::paths;
+
+// Assets built into the game, by key.
+// Each of these can be overridden by placing a file with the same relative path in the overrides directory.
+
+static EMBEDDED: &[(&str, &[u8])] = &[
+	("shaders/vertex_shader.vsh", include_bytes!("./shaders/vertex_shader.vsh")),
+	("shaders/fragment_shader.frag", include_bytes!("./shaders/fragment_shader.frag")),
+	("shaders/gles/vertex_shader.vsh", include_bytes!("./shaders/gles/vertex_shader.vsh")),
+	("shaders/gles/fragment_shader.frag", include_bytes!("./shaders/gles/fragment_shader.frag")),
+	("locales/en.toml", include_bytes!("./locales/en.toml"))
+];
+
+// Implement an asset manager that loads assets (textures, fonts, shaders, sounds) by key and caches them.
+// Assets are looked up in the overrides directory first, falling back to the copies embedded in the game.
+
+pub struct Assets {
+	overrides: Option<PathBuf>,
+	cache: HashMap<String, Arc<[u8]>>
+}
+
+impl Assets {
+	// Build an asset manager that checks the given directory for overrides.
+	pub fn new(overrides: Option<PathBuf>) -> Self {
+		Self {
+			overrides,
+			cache: HashMap::new()
+		}
+	}
+
+	// Build an asset manager using the default overrides directory in the platform's data directory.
+	pub fn with_default_overrides() -> Self {
+		Self::new(paths::data_dir().map(|dir| dir.join("assets")))
+	}
+
+	// Get the raw bytes of an asset, loading and caching it on first use.
+	pub fn get(&mut self, key: &str) -> Option<Arc<[u8]>> {
+		if let Some(data) = self.cache.get(key) {
+			return Some(Arc::clone(data));
+		}
+
+		let data: Arc<[u8]> = match self.load_override(key) {
+			Some(data) => data.into(),
+			None => EMBEDDED.iter().find(|(name, _)| *name == key)?.1.into()
+		};
+
+		self.cache.insert(key.to_string(), Arc::clone(&data));
+		Some(data)
+	}
+
+	// Get an asset as text, e.g. shader source code.
+	// Returns None if the asset doesn't exist or isn't valid UTF-8.
+	pub fn text(&mut self, key: &str) -> Option<String> {
+		let data = self.get(key)?;
+		match std::str::from_utf8(&data) {
+			Ok(text) => Some(text.to_string()),
+			Err(_) => {
+				tracing::warn!("Asset {} isn't valid UTF-8", key);
+				None
+			}
+		}
+	}
+
+	// Forget every cached asset, so they're loaded again on next use.
+	pub fn clear_cache(&mut self) {
+		self.cache.clear();
+	}
+
+	// Try to load an asset from the overrides directory.
+	fn load_override(&self, key: &str) -> Option<Vec<u8>> {
+		// Don't let keys escape the overrides directory.
+		if key.split('/').any(|part| part == "..") {
+			return None;
+		}
+
+		let path = self.overrides.as_ref()?.join(key);
+		match fs::read(&path) {
+			Ok(data) => {
+				tracing::info!("Using asset override {}", path.display());
+				Some(data)
+			},
+			Err(_) => None
+		}
+	}
+}
@@ -0,0 +1,115 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::collision::ObjectCollider;
+
+// Implement a uniform grid for finding which colliders might be touching, before checking them properly.
+// Each collider is filed under every cell it covers, so only colliders sharing a cell need to be checked against each other.
+// This keeps collision checks from growing with the square of the object count when there are hundreds of balls in play.
+
+#[derive(Clone, Debug, Default)]
+pub struct Grid {
+	cell_size: f32,
+	columns: usize,
+	rows: usize,
+	// Where each cell's list of colliders starts in the entries, with one extra at the end marking where the last cell's list ends.
+	starts: Vec<usize>,
+	// Every cell's list of collider indices, one after another.
+	entries: Vec<usize>,
+	// Where the next index goes in each cell's list while it's being filled in.
+	cursors: Vec<usize>
+}
+
+impl Grid {
+	// Build a grid holding the given colliders, filed under their index in the list.
+	// The grid covers a play area of the given size, split into square cells. Anything outside it is filed under the nearest cell on the edge.
+	pub fn new(width: f32, height: f32, cell_size: f32, colliders: &[ObjectCollider]) -> Self {
+		let mut grid = Self::default();
+		grid.rebuild(width, height, cell_size, colliders);
+		grid
+	}
+
+	// Empty the grid and file the given colliders in it again, the same as building a new one.
+	// The lists are refilled in place, so once they've grown to fit, this doesn't allocate.
+	pub fn rebuild(&mut self, width: f32, height: f32, cell_size: f32, colliders: &[ObjectCollider]) {
+		self.cell_size = cell_size.max(1.0);
+		self.columns = ((width / self.cell_size).ceil() as usize).max(1);
+		self.rows = ((height / self.cell_size).ceil() as usize).max(1);
+		let cells = self.columns * self.rows;
+
+		self.starts.clear();
+		self.starts.resize(cells + 1, 0);
+
+		// Count how many colliders each cell holds, then work out where each cell's list starts from the counts before it.
+		for collider in colliders {
+			self.for_each_cell(collider, |grid, cell| grid.starts[cell + 1] += 1);
+		}
+		for cell in 0..cells {
+			self.starts[cell + 1] += self.starts[cell];
+		}
+
+		// Fill in each cell's list, using a copy of the cell's start as a cursor.
+		self.cursors.clear();
+		self.cursors.extend_from_slice(&self.starts);
+		self.entries.clear();
+		self.entries.resize(self.starts[cells], 0);
+		for (index, collider) in colliders.iter().enumerate() {
+			self.for_each_cell(collider, |grid, cell| {
+				grid.entries[grid.cursors[cell]] = index;
+				grid.cursors[cell] += 1;
+			});
+		}
+	}
+
+	// Get the index of every collider sharing a cell with the given area, in ascending order.
+	// These might be touching it, anything else definitely isn't.
+	pub fn query(&self, area: &ObjectCollider) -> Vec<usize> {
+		let mut found = vec![];
+		self.query_into(area, &mut found);
+		found
+	}
+
+	// The same as query, but filling in the given list instead of a new one, so it doesn't allocate once the list is big enough.
+	pub fn query_into(&self, area: &ObjectCollider, found: &mut Vec<usize>) {
+		// Every index found comes from a different entry, so the list never needs to hold more than all the entries.
+		found.clear();
+		found.reserve(self.entries.len());
+		let ((first_column, last_column), (first_row, last_row)) = self.cell_range(area);
+		for row in first_row..=last_row {
+			let cells = row * self.columns + first_column..=row * self.columns + last_column;
+			found.extend_from_slice(&self.entries[self.starts[*cells.start()]..self.starts[cells.end() + 1]]);
+		}
+
+		// Large colliders are filed under several cells, so only list each one once.
+		found.sort_unstable();
+		found.dedup();
+	}
+
+	// Run a function for every cell a collider covers.
+	fn for_each_cell(&mut self, collider: &ObjectCollider, mut func: impl FnMut(&mut Self, usize)) {
+		let ((first_column, last_column), (first_row, last_row)) = self.cell_range(collider);
+		for row in first_row..=last_row {
+			for column in first_column..=last_column {
+				func(self, row * self.columns + column);
+			}
+		}
+	}
+
+	// Get the columns and rows of cells a collider covers, from first to last.
+	fn cell_range(&self, collider: &ObjectCollider) -> ((usize, usize), (usize, usize)) {
+		let cell = |value: f32, count: usize| ((value / self.cell_size).floor().max(0.0) as usize).min(count - 1);
+
+		(
+			(cell(collider.min.x, self.columns), cell(collider.max.x, self.columns)),
+			(cell(collider.min.y, self.rows), cell(collider.max.y, self.rows))
+		)
+	}
+}
+
+// Get the area a collider covers over a movement, from where it starts to where it ends up.
+pub fn swept_bounds(collider: &ObjectCollider, delta: Vec2) -> ObjectCollider {
+	ObjectCollider::from_bounds(
+		Vec2::new(collider.min.x.min(collider.min.x + delta.x), collider.min.y.min(collider.min.y + delta.y)),
+		Vec2::new(collider.max.x.max(collider.max.x + delta.x), collider.max.y.max(collider.max.y + delta.y))
+	)
+}
@@ -0,0 +1,116 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+use crate::events::GameEvent;
+use crate::game::{GameState, TICK_TIME};
+use crate::locale::Locale;
+use crate::text;
+
+// How long a callout stays on screen, in milliseconds of match time, including the time it spends growing in and fading out.
+
+pub const CALLOUT_TIME: f32 = 1500.0;
+const CALLOUT_GROW_TIME: f32 = 150.0;
+const CALLOUT_FADE_TIME: f32 = 500.0;
+
+// How many hits apart rallies are called out, e.g. every 10 hits.
+
+pub const RALLY_CALLOUT: u32 = 10;
+
+// Implement a big line of text announced mid-court for a moment when something notable happens, e.g. "Ace!".
+// It's timed in ticks of the simulation, so it stays up while the game's paused and the players don't miss it.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Callout {
+	pub text: String,
+	// Tick the callout was made on.
+	pub start: u64
+}
+
+impl Callout {
+	// Get how long the callout has been up, in milliseconds.
+	pub fn age(&self, game: &GameState) -> f32 {
+		game.ticks.saturating_sub(self.start) as f32 * TICK_TIME
+	}
+
+	// How big the callout is, growing from half its size to full size as it appears.
+	pub fn size(&self, game: &GameState) -> f32 {
+		0.5 + 0.5 * (self.age(game) / CALLOUT_GROW_TIME).clamp(0.0, 1.0)
+	}
+
+	// How visible the callout is, from 1.0 until it starts fading out down to 0.0 when its time is up.
+	pub fn opacity(&self, game: &GameState) -> f32 {
+		((CALLOUT_TIME - self.age(game)) / CALLOUT_FADE_TIME).clamp(0.0, 1.0)
+	}
+}
+
+// Implement the announcer, which follows the match's events and calls out aces, long rallies and match points.
+// Only the latest callout is shown, replacing any still on screen.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Callouts {
+	pub current: Option<Callout>,
+	// Hits made in the point being played now.
+	rally: u32
+}
+
+impl Callouts {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Make a callout for anything notable in this frame's events.
+	// Match point is checked once all the events are through, on the game state they led to, so it wins over an ace scored to reach it.
+	pub fn record(&mut self, game: &GameState, events: &[GameEvent], locale: &Locale) {
+		let mut text = None;
+
+		for event in events {
+			match event {
+				GameEvent::BallHitPaddle { .. } => {
+					self.rally += 1;
+					if self.rally.is_multiple_of(RALLY_CALLOUT) {
+						text = Some(locale.format("callout.rally", &[("hits", &self.rally)]));
+					}
+				},
+				GameEvent::PointScored { .. } => {
+					if self.rally == 0 {
+						text = Some(locale.get("callout.ace").to_string());
+					}
+					self.rally = 0;
+				},
+				// A new match starts straight after, so nothing from the last one is worth calling out.
+				GameEvent::MatchEnded { .. } => {
+					self.rally = 0;
+					self.current = None;
+					return;
+				},
+				_ => ()
+			}
+		}
+
+		let scored = events.iter().any(|event| matches!(event, GameEvent::PointScored { .. }));
+		if scored && !game.match_point().is_empty() {
+			text = Some(locale.get("callout.match-point").to_string());
+		}
+
+		if let Some(text) = text {
+			self.current = Some(Callout { text, start: game.ticks });
+		}
+	}
+
+	// Forget the callout once its time is up.
+	pub fn update(&mut self, game: &GameState) {
+		if self.current.as_ref().is_some_and(|callout| callout.age(game) >= CALLOUT_TIME) {
+			self.current = None;
+		}
+	}
+
+	// Draw the callout being shown, if there is one, centered a little above the middle of a window of the given size.
+	pub fn render(&self, renderer: &mut dyn Renderer, game: &GameState, width: f32, height: f32) {
+		let Some(callout) = &self.current else { return };
+		let scale = (height / 40.0).floor().max(4.0) * callout.size(game);
+		let top = height * 0.4 - text::GLYPH_HEIGHT * scale / 2.0;
+
+		text::draw_faded(renderer, &callout.text, Vec2::new(width / 2.0 - text::width(&callout.text, scale) / 2.0, top), scale, callout.opacity(game));
+	}
+}
@@ -0,0 +1,98 @@
+// Dependencies
+
+// Clap is used to parse command-line arguments.
+// https://crates.io/crates/clap
+
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use pong::game::Difficulty;
+use pong::modes::{self, GameMode};
+use pong::telemetry::Format;
+
+// Implement the command-line arguments for the game.
+// These override the config file, so the game can be launched straight into a specific setup for kiosks, testing and scripts.
+
+#[derive(Parser)]
+#[command(name = "pong", version, about = "A game of Pong.")]
+pub struct Args {
+	#[arg(long, help = "Start in borderless fullscreen")]
+	pub fullscreen: bool,
+
+	#[arg(long, help = "Open a second window showing a big scoreboard, fullscreen on another screen if there is one")]
+	pub scoreboard: bool,
+
+	#[arg(long, help = "Draw with OpenGL ES 2.0 instead of desktop OpenGL, e.g. on a Raspberry Pi")]
+	pub gles: bool,
+
+	#[arg(long, default_value = "classic", value_parser = parse_mode, help = "Game mode to play (classic, survival, training)")]
+	pub mode: &'static dyn GameMode,
+
+	#[arg(long, help = "AI difficulty (easy, normal, hard)")]
+	pub ai_difficulty: Option<Difficulty>,
+
+	#[arg(long, value_name = "NAME", help = "Play as the named profile, creating it if needed, instead of choosing at the start")]
+	pub profile: Option<String>,
+
+	#[arg(long, help = "Seed for the random number generator, to make a match reproducible")]
+	pub seed: Option<u64>,
+
+	#[arg(long, help = "Simulate a match between two AI paddles without opening a window, printing the result")]
+	pub headless: bool,
+
+	#[arg(long, value_name = "PORT", requires = "headless", conflicts_with = "telemetry", help = "With --headless, keep playing matches in real time like a server, serving Prometheus metrics on the given port")]
+	pub metrics_port: Option<u16>,
+
+	#[arg(long, value_name = "ADDRESS", default_value = "127.0.0.1", requires = "metrics_port", help = "With --metrics-port, the address to serve metrics on, e.g. 0.0.0.0 for every network interface")]
+	pub metrics_address: IpAddr,
+
+	#[cfg(feature = "remote")]
+	#[arg(long, value_name = "PORT", conflicts_with_all = ["headless", "replay"], help = "Listen for remote control over HTTP on the given port, to start and stop matches, change the difficulty and check the score")]
+	pub remote_port: Option<u16>,
+
+	#[cfg(feature = "remote")]
+	#[arg(long, value_name = "TOKEN", requires = "remote_port", help = "With --remote-port, only accept requests with an `Authorization: Bearer TOKEN` header")]
+	pub remote_token: Option<String>,
+
+	#[cfg(feature = "remote")]
+	#[arg(long, requires = "remote_token", help = "With --remote-port, listen on every network interface instead of only this machine. Needs --remote-token")]
+	pub remote_listen_all: bool,
+
+	#[cfg(feature = "terminal")]
+	#[arg(long, help = "Play in the terminal, drawn with block characters, instead of opening a window")]
+	pub terminal: bool,
+
+	#[arg(long, value_name = "FILE", num_args = 0..=1, conflicts_with = "headless", help = "Record a video of the game with ffmpeg from the start, to the given .mp4 file or a new one in the videos folder. Recording can also be started and stopped in-game")]
+	pub record: Option<Option<PathBuf>>,
+
+	#[arg(long, value_name = "FILE", help = "Play back a recorded replay instead of starting a match")]
+	pub replay: Option<PathBuf>,
+
+	#[arg(long, value_name = "FILE", value_parser = parse_telemetry_path, help = "Export every tick of the match to a .json or .csv file for analysis. With --replay, exports the replay without playing it")]
+	pub telemetry: Option<PathBuf>,
+
+	#[arg(long, value_name = "FILE", help = "Export every setting to a file that can be imported on another machine, then exit")]
+	pub export_settings: Option<PathBuf>,
+
+	#[arg(long, value_name = "FILE", conflicts_with = "export_settings", help = "Import settings exported with --export-settings, replacing the config file, then exit")]
+	pub import_settings: Option<PathBuf>
+}
+
+// Look up a game mode by name from the registry.
+fn parse_mode(name: &str) -> Result<&'static dyn GameMode, String> {
+	modes::find(name).ok_or_else(|| {
+		let names: Vec<_> = modes::MODES.iter().map(|mode| mode.name()).collect();
+		format!("unknown mode '{}', expected one of: {}", name, names.join(", "))
+	})
+}
+
+// Check a telemetry file is one of the supported formats, so a typo is caught before the match is played.
+fn parse_telemetry_path(path: &str) -> Result<PathBuf, String> {
+	let path = PathBuf::from(path);
+	match Format::from_path(&path) {
+		Some(_) => Ok(path),
+		None => Err("expected a file ending in .json or .csv".to_string())
+	}
+}
@@ -0,0 +1,143 @@
+// Dependencies
+
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+use crate::paths;
+
+// How much smaller than the window clips are recorded, how often a frame is kept, and how far back a clip goes, in milliseconds.
+// GIF frame delays are in hundredths of a second, so the frame time should be a multiple of 10.
+
+pub const CLIP_SCALE: f32 = 0.25;
+pub const CLIP_FRAME_TIME: f32 = 50.0;
+pub const CLIP_LENGTH: f32 = 6000.0;
+
+// Implement a single downscaled frame of a clip, with one byte per pixel: 1 where something was drawn and 0 elsewhere.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClipFrame {
+	pub width: u16,
+	pub height: u16,
+	pub pixels: Vec<u8>
+}
+
+impl ClipFrame {
+	pub fn new(width: u16, height: u16) -> Self {
+		Self {
+			width,
+			height,
+			pixels: vec![0; width as usize * height as usize]
+		}
+	}
+}
+
+// Draw a scene into a clip frame, filling in every pixel a rect covers at least half of.
+// Everything in the game is a solid rect, so drawing them again in software is cheaper than reading frames back from the graphics card.
+
+impl Renderer for ClipFrame {
+	fn rect(&mut self, position: Vec2, size: Vec2) {
+		let start = position * CLIP_SCALE;
+		let end = (position + size) * CLIP_SCALE;
+
+		let left = (start.x.round().max(0.0) as usize).min(self.width as usize);
+		let right = (end.x.round().max(0.0) as usize).min(self.width as usize);
+		let top = (start.y.round().max(0.0) as usize).min(self.height as usize);
+		let bottom = (end.y.round().max(0.0) as usize).min(self.height as usize);
+
+		for y in top..bottom {
+			let row = y * self.width as usize;
+			self.pixels[row + left..row + right].fill(1);
+		}
+	}
+}
+
+// Implement a rolling buffer of the last few seconds of the game, which can be saved as an animated GIF to share a good rally.
+
+#[derive(Default)]
+pub struct ClipRecorder {
+	frames: VecDeque<ClipFrame>,
+	// Time since the last frame was kept, in milliseconds.
+	since_frame: f32
+}
+
+impl ClipRecorder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// The frames kept, oldest first.
+	pub fn frames(&self) -> impl Iterator<Item = &ClipFrame> {
+		self.frames.iter()
+	}
+
+	// Count the time since the last frame, in milliseconds, and keep a frame of a window of the given size if one is due.
+	// The closure draws the frame, and is only called when one is kept.
+	// Frames kept before the window was resized are thrown away, since a GIF can't change size.
+	pub fn update(&mut self, delta_time: f32, width: f32, height: f32, draw: impl FnOnce(&mut dyn Renderer)) {
+		self.since_frame += delta_time;
+		if !self.frames.is_empty() && self.since_frame < CLIP_FRAME_TIME {
+			return;
+		}
+		self.since_frame = (self.since_frame - CLIP_FRAME_TIME).clamp(0.0, CLIP_FRAME_TIME);
+
+		let mut frame = ClipFrame::new((width * CLIP_SCALE).round() as u16, (height * CLIP_SCALE).round() as u16);
+		if self.frames.back().is_some_and(|last| (last.width, last.height) != (frame.width, frame.height)) {
+			self.frames.clear();
+		}
+		draw(&mut frame);
+
+		self.frames.push_back(frame);
+		while self.frames.len() as f32 * CLIP_FRAME_TIME > CLIP_LENGTH {
+			self.frames.pop_front();
+		}
+	}
+
+	// Encode the frames kept as a looping animated GIF, in white on black like the game.
+	pub fn write_gif(&self, writer: impl Write) -> Result<(), String> {
+		let Some(first) = self.frames.front() else {
+			return Err("Nothing has been recorded yet".to_string());
+		};
+
+		let palette = [0, 0, 0, 255, 255, 255];
+		let mut encoder = gif::Encoder::new(writer, first.width, first.height, &palette).map_err(|err| err.to_string())?;
+		encoder.set_repeat(gif::Repeat::Infinite).map_err(|err| err.to_string())?;
+
+		for frame in &self.frames {
+			let mut gif_frame = gif::Frame::from_indexed_pixels(frame.width, frame.height, frame.pixels.clone(), None);
+			gif_frame.delay = (CLIP_FRAME_TIME / 10.0).round() as u16;
+			encoder.write_frame(&gif_frame).map_err(|err| err.to_string())?;
+		}
+		Ok(())
+	}
+
+	// Get the directory clips are saved to in the platform's data directory.
+	pub fn dir() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("clips"))
+	}
+
+	// Save the frames kept as a GIF in the clips directory, named after the current time.
+	// Returns the path it was saved to.
+	pub fn save(&self) -> Result<PathBuf, String> {
+		let dir = Self::dir().ok_or("No data directory on this platform")?;
+
+		let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_millis()).unwrap_or(0);
+		let path = dir.join(format!("clip-{}.gif", millis));
+		self.save_to(&path)?;
+		Ok(path)
+	}
+
+	// Write the frames kept as a GIF to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> Result<(), String> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+		}
+
+		let file = File::create(path).map_err(|err| format!("Failed to create {}: {}", path.display(), err))?;
+		self.write_gif(BufWriter::new(file)).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+	}
+}
@@ -0,0 +1,64 @@
+// Dependencies
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Implement a trait for a source of time.
+// Anything that measures elapsed time goes through a clock, so it can be driven by a mock clock in tests
+// or by a fixed tick in replay and lockstep modes instead of the wall clock.
+
+pub trait Clock: Send + Sync {
+	// Time elapsed since the clock started.
+	fn now(&self) -> Duration;
+}
+
+// Implement a clock that follows real time.
+
+pub struct SystemClock {
+	start: Instant
+}
+
+impl SystemClock {
+	pub fn new() -> Self {
+		Self {
+			start: Instant::now()
+		}
+	}
+}
+
+impl Default for SystemClock {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Clock for SystemClock {
+	fn now(&self) -> Duration {
+		self.start.elapsed()
+	}
+}
+
+// Implement a clock that only moves when it's told to.
+// Time stands still until advance is called, making timing fully deterministic.
+
+#[derive(Default)]
+pub struct ManualClock {
+	now: Mutex<Duration>
+}
+
+impl ManualClock {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Move the clock forward.
+	pub fn advance(&self, by: Duration) {
+		*self.now.lock().unwrap() += by;
+	}
+}
+
+impl Clock for ManualClock {
+	fn now(&self) -> Duration {
+		*self.now.lock().unwrap()
+	}
+}
@@ -0,0 +1,356 @@
+// Dependencies
+
+use std::ops::{BitOr, BitAnd, Not};
+
+use serde::{Serialize, Deserialize};
+
+use crate::structs::{Vec2, Object};
+use crate::events::Side;
+use crate::math;
+
+// Check if the points a, b and c are arranged counter-clockwise.
+fn ccw(a: &Vec2, b: &Vec2, c: &Vec2) -> bool {
+	(c.y - a.y) * (b.x - a.x) > (b.y - a.y) * (c.x - a.x)
+}
+
+// Check if the line segments a-b and c-d intersect.
+pub fn segments_intersect(a: &Vec2, b: &Vec2, c: &Vec2, d: &Vec2) -> bool {
+	ccw(a, c, d) != ccw(b, c, d) && ccw(a, b, c) != ccw(a, b, d)
+}
+
+// Reflect a velocity off a surface with the given unit normal.
+pub fn reflect(velocity: Vec2, normal: Vec2) -> Vec2 {
+	velocity - normal * (2.0 * velocity.dot(normal))
+}
+
+// Work out the ball's new velocity after it hits a paddle.
+// The ball heads back away from the paddle, at an angle set by how far from the paddle's center it hit, with its speed multiplied by the restitution.
+// A hit dead center sends it straight back, a hit on the very edge sends it off at the maximum angle, in radians from horizontal.
+pub fn paddle_bounce(ball: &Object, paddle: &ObjectCollider, max_angle: f32, restitution: f32) -> Vec2 {
+	let center = ball.get_center();
+
+	// How far from the paddle's center the ball hit, from -1.0 at the top edge to 1.0 at the bottom edge.
+	let reach = (paddle.max.y - paddle.min.y + ball.size.y) / 2.0;
+	let offset = if reach > 0.0 { ((center.y - paddle.center.y) / reach).clamp(-1.0, 1.0) } else { 0.0 };
+	let angle = offset * max_angle;
+
+	let speed = ball.velocity.length() * restitution;
+	let direction = if center.x < paddle.center.x { -1.0 } else { 1.0 };
+	let (sin, cos) = math::sin_cos(angle);
+
+	Vec2 {
+		x: (direction * speed * cos).clamp(-ball.max_velocity.x, ball.max_velocity.x),
+		y: (speed * sin).clamp(-ball.max_velocity.y, ball.max_velocity.y)
+	}
+}
+
+// Apply friction between a spinning ball and a surface it's bouncing off, returning the ball's new velocity and spin.
+// The ball's edge slides against the surface, which speeds up or slows down its spin and drags it along the surface.
+// A friction of 0.0 leaves the ball alone, 1.0 stops it sliding entirely so it rolls off the surface.
+pub fn apply_friction(velocity: Vec2, spin: f32, normal: Vec2, surface_velocity: Vec2, friction: f32) -> (Vec2, f32) {
+	// Direction along the surface. The edge of a ball spinning clockwise moves the opposite way at the point of contact.
+	let tangent = Vec2::new(-normal.y, normal.x);
+
+	// How fast the ball's edge slides over the surface at the point of contact.
+	let slip = velocity.dot(tangent) - spin - surface_velocity.dot(tangent);
+
+	// Like a solid disc, a third of the friction goes into slowing the ball along the surface and the rest into its spin.
+	let impulse = friction * slip;
+	(velocity + tangent * (-impulse / 3.0), spin + impulse * 2.0 / 3.0)
+}
+
+// Curve a spinning ball's flight over the given time, turning its velocity without changing its speed.
+pub fn curve(velocity: Vec2, spin: f32, strength: f32, delta_time: f32) -> Vec2 {
+	let (sin, cos) = math::sin_cos(spin * strength * delta_time);
+	Vec2::new(
+		velocity.x * cos - velocity.y * sin,
+		velocity.x * sin + velocity.y * cos
+	)
+}
+
+// How close to a corner a swept collider has to pass, in pixels, for a hit to count as hitting the corner rather than one of its faces.
+
+pub const CORNER_TOLERANCE: f32 = 0.5;
+
+// Implement a set of collision layers, stored as bit flags.
+// Every collider sits on some layers and has a mask of the layers it collides with. Two colliders only collide if each one's mask includes the other's layer.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Layers(pub u32);
+
+impl Layers {
+	pub const NONE: Self = Self(0);
+	pub const BALL: Self = Self(1 << 0);
+	pub const PADDLE: Self = Self(1 << 1);
+	pub const POWER_UP: Self = Self(1 << 2);
+	pub const OBSTACLE: Self = Self(1 << 3);
+	pub const ALL: Self = Self(u32::MAX);
+
+	// Get every layer, for colliders that should collide with anything.
+	pub fn all() -> Self {
+		Self::ALL
+	}
+
+	// Check if any of the given layers are in this set.
+	pub fn intersects(self, other: Self) -> bool {
+		self.0 & other.0 != 0
+	}
+}
+
+impl BitOr for Layers { // Implement | operator for combining layers
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl BitAnd for Layers { // Implement & operator for the layers in both sets
+	type Output = Self;
+
+	fn bitand(self, other: Self) -> Self {
+		Self(self.0 & other.0)
+	}
+}
+
+impl Not for Layers { // Implement ! operator for every layer not in this set
+	type Output = Self;
+
+	fn not(self) -> Self {
+		Self(!self.0)
+	}
+}
+
+// Where and how two overlapping colliders touch.
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contact {
+	// The middle of the area where the colliders overlap.
+	pub point: Vec2,
+	// Unit normal to push this collider out of the other along, pointing away from the other collider.
+	pub normal: Vec2,
+	// How far this collider has to move along the normal to stop overlapping.
+	pub penetration: f32
+}
+
+impl Contact {
+	// Get the movement needed to push this collider out of the other.
+	pub fn push(&self) -> Vec2 {
+		self.normal * self.penetration
+	}
+}
+
+// The result of a swept collision test.
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sweep {
+	// How far along the movement the first contact happens, from 0.0 (the start) to 1.0 (the end).
+	pub time: f32,
+	// Unit normal of the face that was hit, pointing away from the other collider.
+	// A hit on a corner has a diagonal normal.
+	pub normal: Vec2,
+	// Where the colliders first touch, in the middle of the edge they meet along.
+	pub point: Vec2
+}
+
+// Implement object colliders.
+// These are axis-aligned bounding boxes built from an object's position and size.
+
+#[derive(Copy, Clone, Debug)]
+pub struct ObjectCollider {
+	pub min: Vec2,
+	pub max: Vec2,
+	pub center: Vec2,
+	// The layers this collider is on, and the layers it collides with.
+	pub layer: Layers,
+	pub mask: Layers
+}
+
+impl ObjectCollider {
+	// Build a new ObjectCollider given an Object.
+	pub fn new(obj: &Object) -> Self {
+		Self {
+			min: obj.position,
+			max: obj.position + obj.size,
+			center: obj.get_center(),
+			layer: obj.layer,
+			mask: obj.mask
+		}
+	}
+
+	// Build a new ObjectCollider from its corners, colliding with everything.
+	pub fn from_bounds(min: Vec2, max: Vec2) -> Self {
+		Self {
+			min,
+			max,
+			center: Vec2 {
+				x: (min.x + max.x) / 2.0,
+				y: (min.y + max.y) / 2.0
+			},
+			layer: Layers::ALL,
+			mask: Layers::ALL
+		}
+	}
+
+	// Set the layers of this collider inline.
+	pub fn with_layers(mut self, layer: Layers, mask: Layers) -> Self {
+		self.layer = layer;
+		self.mask = mask;
+		self
+	}
+
+	// Check if this collider and another are on layers that collide with each other.
+	// This says nothing about whether they're actually touching.
+	pub fn can_collide(&self, other: &Self) -> bool {
+		self.mask.intersects(other.layer) && other.mask.intersects(self.layer)
+	}
+
+	// Get the four edges of this collider as line segments, for checking against lines with segments_intersect.
+	pub fn get_hitbox(&self) -> [[Vec2; 2]; 4] {
+		[
+			[Vec2::new(self.min.x, self.min.y), Vec2::new(self.max.x, self.min.y)],
+			[Vec2::new(self.min.x, self.min.y), Vec2::new(self.min.x, self.max.y)],
+			[Vec2::new(self.min.x, self.max.y), Vec2::new(self.max.x, self.max.y)],
+			[Vec2::new(self.max.x, self.max.y), Vec2::new(self.max.x, self.min.y)]
+		]
+	}
+
+	// Check if this collider overlaps another.
+	// Colliders that only touch along an edge don't count as overlapping.
+	pub fn is_colliding(&self, other: &Self) -> bool {
+		self.min.x < other.max.x && self.max.x > other.min.x &&
+		self.min.y < other.max.y && self.max.y > other.min.y
+	}
+
+	// Get where this collider touches another, and how to push it back out.
+	// The push is along whichever axis is quicker to escape on, which is the face of the other collider that was most likely hit.
+	// Returns None if the colliders aren't overlapping.
+	pub fn contact(&self, other: &Self) -> Option<Contact> {
+		if !self.is_colliding(other) {
+			return None;
+		}
+
+		// How far this collider would have to move either way on each axis to get out of the other, taking the shorter way.
+		let push_x = if self.center.x < other.center.x { other.min.x - self.max.x } else { other.max.x - self.min.x };
+		let push_y = if self.center.y < other.center.y { other.min.y - self.max.y } else { other.max.y - self.min.y };
+
+		let (normal, penetration) = if push_x.abs() < push_y.abs() {
+			(Vec2::new(push_x.signum(), 0.0), push_x.abs())
+		} else {
+			(Vec2::new(0.0, push_y.signum()), push_y.abs())
+		};
+
+		Some(Contact {
+			point: self.overlap_center(other),
+			normal,
+			penetration
+		})
+	}
+
+	// Get the middle of the area this collider shares with another.
+	// For colliders that only touch, this is the middle of the edge they share.
+	fn overlap_center(&self, other: &Self) -> Vec2 {
+		Vec2 {
+			x: (self.min.x.max(other.min.x) + self.max.x.min(other.max.x)) / 2.0,
+			y: (self.min.y.max(other.min.y) + self.max.y.min(other.max.y)) / 2.0
+		}
+	}
+
+	// Check if this collider will hit another while moving by the given delta.
+	// Returns when and where along the movement the first contact happens, and the normal of the face hit.
+	// Colliders that are already overlapping at the start of the movement don't count as a hit.
+	pub fn sweep(&self, delta: Vec2, other: &Self) -> Option<Sweep> {
+		// Find when this collider enters and leaves the other's extent on each axis.
+		let (entry_x, exit_x) = sweep_axis(self.min.x, self.max.x, delta.x, other.min.x, other.max.x)?;
+		let (entry_y, exit_y) = sweep_axis(self.min.y, self.max.y, delta.y, other.min.y, other.max.y)?;
+
+		// The colliders only touch once they overlap on both axes.
+		let entry = entry_x.max(entry_y);
+		let exit = exit_x.min(exit_y);
+
+		if entry > exit || !(0.0..=1.0).contains(&entry) {
+			return None;
+		}
+
+		// The face hit is on the axis that was entered last.
+		// If both axes are entered at practically the same point, the corner was hit square on, and the normal points diagonally out of it.
+		let normal_x = if delta.x > 0.0 { -1.0 } else { 1.0 };
+		let normal_y = if delta.y > 0.0 { -1.0 } else { 1.0 };
+		let normal = if (entry_x - entry_y).abs() * delta.length() <= CORNER_TOLERANCE {
+			Vec2::new(normal_x, normal_y) * std::f32::consts::FRAC_1_SQRT_2
+		} else if entry_x > entry_y {
+			Vec2::new(normal_x, 0.0)
+		} else {
+			Vec2::new(0.0, normal_y)
+		};
+
+		// Where the colliders meet, with this one moved up to the point of contact.
+		let moved = ObjectCollider::from_bounds(self.min + delta * entry, self.max + delta * entry);
+
+		Some(Sweep {
+			time: entry,
+			normal,
+			point: moved.overlap_center(other)
+		})
+	}
+}
+
+// Find when a moving range enters and leaves another range, as fractions of the movement.
+// Returns None if the ranges never overlap on this axis.
+fn sweep_axis(min: f32, max: f32, delta: f32, other_min: f32, other_max: f32) -> Option<(f32, f32)> {
+	if delta > 0.0 {
+		Some(((other_min - max) / delta, (other_max - min) / delta))
+	} else if delta < 0.0 {
+		Some(((other_max - min) / delta, (other_min - max) / delta))
+	} else if max > other_min && min < other_max {
+		// Not moving on this axis, but already overlapping on it for the whole movement.
+		Some((f32::NEG_INFINITY, f32::INFINITY))
+	} else {
+		None
+	}
+}
+
+// Implement the colliders for every object in a game, along with what's needed to resolve collisions with each object, in parallel lists indexed the same as the objects.
+// The lists are kept between ticks and refilled in place, so once they've grown to fit every object, building them doesn't allocate.
+
+#[derive(Clone, Debug, Default)]
+pub struct Colliders {
+	pub colliders: Vec<ObjectCollider>,
+	pub velocities: Vec<Vec2>,
+	pub restitutions: Vec<f32>,
+	pub inverse_masses: Vec<f32>,
+	// The side each object belongs to, if it's a paddle.
+	pub sides: Vec<Option<Side>>
+}
+
+impl Colliders {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Refill the lists from where the objects are now and how they're moving.
+	pub fn update(&mut self, objects: &[Object]) {
+		self.colliders.clear();
+		self.velocities.clear();
+		self.restitutions.clear();
+		self.inverse_masses.clear();
+		self.sides.clear();
+
+		for obj in objects {
+			self.colliders.push(obj.get_collider());
+			self.velocities.push(obj.velocity);
+			self.restitutions.push(obj.restitution);
+			self.inverse_masses.push(obj.inverse_mass());
+			self.sides.push(obj.obj_type.side());
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.colliders.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.colliders.is_empty()
+	}
+}
@@ -0,0 +1,417 @@
+// Dependencies
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+// Serde and TOML are used to read and write the config file.
+// https://crates.io/crates/serde
+// https://crates.io/crates/toml
+
+use serde::{Serialize, Deserialize};
+
+// Notify is used to watch the config file for changes while the game is running.
+// https://crates.io/crates/notify
+
+use notify::{Watcher, RecommendedWatcher, RecursiveMode, EventKind};
+
+use crate::game::Difficulty;
+use crate::input::{self, Key};
+use crate::paths;
+use crate::locale::DEFAULT_LANGUAGE;
+
+// The version of the settings export format. Exports from a newer version are refused, since they may hold settings this version would get wrong.
+
+pub const SETTINGS_VERSION: u32 = 1;
+
+// Implement the settings stored in the config file.
+// Any setting missing from the file falls back to its default, so old config files keep working as settings are added.
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+	// Name of the colour theme.
+	pub theme: String,
+	// Whether a holiday's theme is put on over the chosen one while it lasts, like snow in December.
+	pub seasonal_themes: bool,
+	// Language for the game's text, by the name of its file in the locales folder, e.g. "en".
+	pub language: String,
+	pub window: WindowConfig,
+	pub controls: ControlsConfig,
+	pub gameplay: GameplayConfig,
+	pub installation: InstallationConfig,
+	// Which key on the keyboard triggers each game key, e.g. `escape = "P"` to pause with P.
+	// Key names are the same as winit's VirtualKeyCode names. Problems are fixed when the file is loaded, see validate_keybinds.
+	pub keybinds: BTreeMap<String, String>
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+	pub width: u32,
+	pub height: u32,
+	pub vsync: bool,
+	// Whether the window is borderless fullscreen. Unlike the window's other settings, this can be changed while the game is running.
+	pub fullscreen: bool,
+	// Whether to open a second window showing a big scoreboard and the match clock, e.g. on a projector.
+	pub scoreboard: bool,
+	// Whether to draw with OpenGL ES 2.0 instead of desktop OpenGL, e.g. on a Raspberry Pi in an arcade cabinet.
+	// The game falls back to OpenGL ES by itself if desktop OpenGL isn't available or is too old.
+	pub gles: bool,
+	// Whether a match pauses when the window loses focus. Otherwise it carries on in the background, drawn and simulated less often to save power.
+	pub pause_in_background: bool
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlsConfig {
+	// How far the paddle moves for a given mouse movement.
+	pub sensitivity: f32,
+	// Whether a gamepad rumbles when the player hits the ball and when a point is scored.
+	pub rumble: bool
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+	pub ai_difficulty: Difficulty,
+	// Points needed to win a match, in modes that are won on points.
+	pub win_score: u32,
+	// Seconds counted down before each serve, or 0 to serve straight away.
+	pub serve_countdown: u32,
+	// Whether to show the tutorial over the next match, which is turned off once it's finished.
+	pub tutorial: bool,
+	// Whether aces, long rallies and match points are called out mid-court.
+	pub callouts: bool,
+	// Whether the last few seconds before each point are replayed in slow motion before the next serve.
+	pub instant_replays: bool
+}
+
+// Settings for driving the paddle from hardware at installations and events, see the installation module.
+// Both are off unless they're set.
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstallationConfig {
+	// Port to listen for OSC messages on, or 0 not to listen.
+	pub osc_port: u16,
+	// Address the paddle's position is sent to, as a number from 0.0 to 1.0.
+	pub osc_address: String,
+	// Raw MIDI device to read, e.g. "/dev/snd/midiC1D0" on Linux, or empty not to read one.
+	pub midi_device: String,
+	// Number of the control change that sets the paddle's position, e.g. 1 for the mod wheel.
+	pub midi_controller: u8
+}
+
+// Implement a file holding every setting, for sharing them or moving them to another machine.
+// It's the same as the config file, with the version of the format first.
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SettingsExport {
+	pub version: u32,
+	pub settings: Config
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			theme: "classic".into(),
+			seasonal_themes: true,
+			language: DEFAULT_LANGUAGE.into(),
+			window: WindowConfig::default(),
+			controls: ControlsConfig::default(),
+			gameplay: GameplayConfig::default(),
+			installation: InstallationConfig::default(),
+			keybinds: default_keybinds()
+		}
+	}
+}
+
+impl Default for WindowConfig {
+	fn default() -> Self {
+		Self {
+			width: 1024,
+			height: 768,
+			vsync: true,
+			fullscreen: false,
+			scoreboard: false,
+			gles: false,
+			pause_in_background: true
+		}
+	}
+}
+
+impl Default for ControlsConfig {
+	fn default() -> Self {
+		Self {
+			sensitivity: 1.0,
+			rumble: true
+		}
+	}
+}
+
+impl Default for GameplayConfig {
+	fn default() -> Self {
+		Self {
+			ai_difficulty: Difficulty::Normal,
+			win_score: 11,
+			serve_countdown: 3,
+			tutorial: true,
+			callouts: true,
+			instant_replays: true
+		}
+	}
+}
+
+impl Default for InstallationConfig {
+	fn default() -> Self {
+		Self {
+			osc_port: 0,
+			osc_address: "/pong/paddle".into(),
+			midi_device: String::new(),
+			midi_controller: 1
+		}
+	}
+}
+
+// Build the default keybinding for every game key.
+fn default_keybinds() -> BTreeMap<String, String> {
+	Key::ALL.into_iter().map(|key| {
+		let bound = match key {
+			Key::Escape => "Escape",
+			Key::Enter => "Return",
+			Key::Space => "Space",
+			Key::Up => "Up",
+			Key::Down => "Down",
+			Key::Left => "Left",
+			Key::Right => "Right",
+			Key::Scores => "Tab",
+			Key::Screenshot => "F12",
+			Key::Clip => "F9",
+			Key::Record => "F10"
+		};
+		(key.name().to_string(), bound.to_string())
+	}).collect()
+}
+
+impl GameplayConfig {
+	// How long the ball waits before each serve, in milliseconds.
+	pub fn serve_delay(&self) -> f32 {
+		self.serve_countdown as f32 * 1000.0
+	}
+}
+
+impl Config {
+	// Get the path of the config file in the platform's config directory.
+	pub fn path() -> Option<PathBuf> {
+		paths::config_dir().map(|dir| dir.join("config.toml"))
+	}
+
+	// Load the config file, writing one with the default settings if it doesn't exist yet.
+	// If the file can't be read or parsed, the defaults are used and the file is left alone so the player can fix it.
+	pub fn load() -> Self {
+		match Self::path() {
+			Some(path) => Self::load_from(&path),
+			None => {
+				tracing::warn!("No config directory on this platform, using default settings");
+				Self::default()
+			}
+		}
+	}
+
+	// Load the config from a given path, writing the defaults there if it doesn't exist yet.
+	pub fn load_from(path: &Path) -> Self {
+		if !path.exists() {
+			let config = Self::default();
+			if let Err(err) = config.save_to(path) {
+				tracing::warn!("Failed to write default config to {}: {}", path.display(), err);
+			}
+			return config;
+		}
+
+		Self::read_from(path).unwrap_or_else(|err| {
+			tracing::warn!("{}, using default settings", err);
+			Self::default()
+		})
+	}
+
+	// Read and parse the config at a given path, without falling back to the defaults.
+	// Any problems with the keybinds are reported and fixed rather than refusing the whole file.
+	pub fn read_from(path: &Path) -> Result<Self, String> {
+		let text = fs::read_to_string(path).map_err(|err| format!("Failed to read config file {}: {}", path.display(), err))?;
+		let mut config: Self = toml::from_str(&text).map_err(|err| format!("Invalid config file {}: {}", path.display(), err))?;
+
+		for problem in config.validate_keybinds() {
+			tracing::warn!("{} in config file {}", problem, path.display());
+		}
+		Ok(config)
+	}
+
+	// Save the config to the platform's config directory, reporting any failure.
+	pub fn save(&self) {
+		let Some(path) = Self::path() else { return };
+		match self.save_to(&path) {
+			Ok(()) => tracing::info!("Saved settings to {}", path.display()),
+			Err(err) => tracing::warn!("Failed to save settings to {}: {}", path.display(), err)
+		}
+	}
+
+	// Write the config to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let text = toml::to_string_pretty(self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		fs::write(path, text)
+	}
+
+	// Check the keybinds, fixing them so every game key has at most one keyboard key and no keyboard key triggers two game keys.
+	// Game keys missing from the file get their default, e.g. ones added since the file was written.
+	// Unknown game keys are dropped, and game keys bound to an unknown keyboard key fall back to their default.
+	// When two game keys share a keyboard key, a key the player chose wins over a default one, then the first in Key::ALL wins.
+	// The other falls back to its default if that's free, and is left unbound if not.
+	// Returns a description of each problem fixed, for reporting.
+	pub fn validate_keybinds(&mut self) -> Vec<String> {
+		let defaults = default_keybinds();
+		let mut problems = vec![];
+
+		for name in self.keybinds.keys().filter(|name| Key::from_name(name).is_none()) {
+			problems.push(format!("Unknown game key '{}' in keybinds", name));
+		}
+
+		// Work out what each game key should be bound to on its own.
+		let mut wanted: Vec<_> = Key::ALL.into_iter().map(|key| {
+			let default = &defaults[key.name()];
+			let bound = match self.keybinds.get(key.name()) {
+				Some(bound) if input::is_keyboard_key(bound) => bound.clone(),
+				Some(bound) => {
+					problems.push(format!("Unknown keyboard key '{}' for {}, using '{}'", bound, key.name(), default));
+					default.clone()
+				},
+				None => default.clone()
+			};
+			(key, bound)
+		}).collect();
+		wanted.sort_by_key(|(key, bound)| *bound == defaults[key.name()]);
+
+		// Then give out the keyboard keys, sorting out any clashes.
+		let mut keybinds = BTreeMap::new();
+		let mut taken = BTreeSet::new();
+		for (key, bound) in wanted {
+			let default = &defaults[key.name()];
+			let bound = if !taken.contains(&bound) {
+				bound
+			} else if bound != *default && !taken.contains(default) {
+				problems.push(format!("'{}' is bound to more than one game key, using '{}' for {}", bound, default, key.name()));
+				default.clone()
+			} else {
+				problems.push(format!("'{}' is bound to more than one game key, leaving {} unbound", bound, key.name()));
+				continue;
+			};
+
+			taken.insert(bound.clone());
+			keybinds.insert(key.name().to_string(), bound);
+		}
+
+		self.keybinds = keybinds;
+		problems
+	}
+
+	// Export every setting to a file at the given path, for importing elsewhere.
+	pub fn export_to(&self, path: &Path) -> Result<(), String> {
+		let export = SettingsExport {
+			version: SETTINGS_VERSION,
+			settings: self.clone()
+		};
+		let text = toml::to_string_pretty(&export).map_err(|err| format!("Failed to write settings: {}", err))?;
+
+		if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+			fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+		}
+		fs::write(path, text).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+	}
+
+	// Import the settings from a file exported with export_to.
+	// The version is checked before anything else, and the keybinds are checked and fixed the same way as the config file's.
+	pub fn import_from(path: &Path) -> Result<Self, String> {
+		let text = fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+		let value: toml::Value = toml::from_str(&text).map_err(|err| format!("Invalid settings file {}: {}", path.display(), err))?;
+
+		let version = value.get("version")
+			.and_then(toml::Value::as_integer)
+			.ok_or_else(|| format!("{} isn't a settings export, it has no version", path.display()))?;
+		if version < 1 || version > SETTINGS_VERSION as i64 {
+			return Err(format!("{} is from settings version {}, this game only understands up to version {}", path.display(), version, SETTINGS_VERSION));
+		}
+
+		let mut export: SettingsExport = value.try_into().map_err(|err| format!("Invalid settings file {}: {}", path.display(), err))?;
+		for problem in export.settings.validate_keybinds() {
+			tracing::warn!("{} in settings file {}", problem, path.display());
+		}
+		Ok(export.settings)
+	}
+
+	// Find the game key bound to a keyboard key, given its name.
+	pub fn key_for(&self, bound: &str) -> Option<Key> {
+		self.keybinds.iter()
+			.find(|(_, value)| value.as_str() == bound)
+			.and_then(|(name, _)| Key::from_name(name))
+	}
+}
+
+// Implement a watcher that picks up changes to the config file while the game is running.
+// The file's directory is watched rather than the file itself, since many editors save by replacing the file.
+
+pub struct ConfigWatcher {
+	path: PathBuf,
+	// Stops watching when dropped.
+	_watcher: RecommendedWatcher,
+	changes: Receiver<()>
+}
+
+impl ConfigWatcher {
+	// Start watching the config file at a given path.
+	pub fn new(path: &Path) -> notify::Result<Self> {
+		let (sender, changes) = mpsc::channel();
+		let name = path.file_name().map(|name| name.to_owned());
+
+		let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+			let Ok(event) = result else { return };
+
+			// Only count writes to the config file, not reads or other files in the directory.
+			let is_write = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+			if is_write && event.paths.iter().any(|changed| changed.file_name() == name.as_deref()) {
+				sender.send(()).ok();
+			}
+		})?;
+		watcher.watch(path.parent().unwrap_or(Path::new(".")), RecursiveMode::NonRecursive)?;
+
+		Ok(Self {
+			path: path.to_path_buf(),
+			_watcher: watcher,
+			changes
+		})
+	}
+
+	// Check if the config file has changed since the last call, returning its new settings if so.
+	// A file that's been broken mid-edit is reported and skipped, keeping the settings already in use.
+	pub fn poll(&self) -> Option<Config> {
+		// Saving a file often fires several events, only reload once for all of them.
+		if self.changes.try_iter().count() == 0 {
+			return None;
+		}
+
+		match Config::read_from(&self.path) {
+			Ok(config) => {
+				tracing::info!("Reloaded config from {}", self.path.display());
+				Some(config)
+			},
+			Err(err) => {
+				tracing::warn!("{}, keeping current settings", err);
+				None
+			}
+		}
+	}
+}
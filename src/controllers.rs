@@ -0,0 +1,102 @@
+// Dependencies
+
+use gilrs::{Gilrs, Axis, EventType};
+use gilrs::ff::{EffectBuilder, Effect, BaseEffect, BaseEffectType, Replay, Repeat, Ticks};
+
+use crate::gamepad::{Gamepad, Button};
+use crate::input::Input;
+use crate::rumble::Pulse;
+
+// Get the game's name for a gilrs button, if it's one the game responds to.
+pub fn button(button: gilrs::Button) -> Option<Button> {
+	match button {
+		gilrs::Button::South => Some(Button::South),
+		gilrs::Button::East => Some(Button::East),
+		gilrs::Button::West => Some(Button::West),
+		gilrs::Button::North => Some(Button::North),
+		gilrs::Button::DPadUp => Some(Button::DPadUp),
+		gilrs::Button::DPadDown => Some(Button::DPadDown),
+		gilrs::Button::DPadLeft => Some(Button::DPadLeft),
+		gilrs::Button::DPadRight => Some(Button::DPadRight),
+		gilrs::Button::Start => Some(Button::Start),
+		gilrs::Button::Select => Some(Button::Select),
+		_ => None
+	}
+}
+
+// Implement reading the game controllers plugged in, with gilrs.
+// Every controller drives the same gamepad, so any of them can steer the paddle and get around the menus.
+
+pub struct Controllers {
+	gilrs: Gilrs,
+	gamepad: Gamepad,
+	// Where the left stick last was, since gilrs reports each axis separately.
+	stick: (f32, f32),
+	// The rumble pulse playing, kept until the next one since it stops if it's dropped.
+	effect: Option<Effect>
+}
+
+impl Controllers {
+	// Start reading controllers, or warn and carry on without them if the platform can't.
+	pub fn start() -> Option<Self> {
+		match Gilrs::new() {
+			Ok(gilrs) => Some(Self { gilrs, gamepad: Gamepad::new(), stick: (0.0, 0.0), effect: None }),
+			Err(err) => {
+				tracing::warn!("Failed to read game controllers: {}", err);
+				None
+			}
+		}
+	}
+
+	// Get the inputs from everything the controllers have done since the last poll.
+	pub fn poll(&mut self) -> Vec<Input> {
+		let mut inputs = Vec::new();
+
+		while let Some(event) = self.gilrs.next_event() {
+			match event.event {
+				EventType::ButtonPressed(pressed, _) => {
+					inputs.extend(button(pressed).and_then(|button| self.gamepad.press(button)));
+				},
+				// Up is positive for gilrs, and negative for the game.
+				EventType::AxisChanged(Axis::LeftStickX, x, _) => {
+					self.stick.0 = x;
+					inputs.extend(self.gamepad.stick(self.stick.0, -self.stick.1));
+				},
+				EventType::AxisChanged(Axis::LeftStickY, y, _) => {
+					self.stick.1 = y;
+					inputs.extend(self.gamepad.stick(self.stick.0, -self.stick.1));
+				},
+				_ => {}
+			}
+		}
+		inputs
+	}
+
+	// Shake every controller plugged in that can, replacing whatever pulse was playing.
+	pub fn rumble(&mut self, pulse: Pulse) {
+		let ids: Vec<_> = self.gilrs.gamepads()
+			.filter(|(_, gamepad)| gamepad.is_ff_supported())
+			.map(|(id, _)| id)
+			.collect();
+		if ids.is_empty() {
+			return;
+		}
+
+		let time = Ticks::from_ms(pulse.duration as u32);
+		let effect = EffectBuilder::new()
+			.add_effect(BaseEffect {
+				kind: BaseEffectType::Strong { magnitude: (pulse.strength.clamp(0.0, 1.0) * u16::MAX as f32) as u16 },
+				scheduling: Replay { play_for: time, ..Replay::default() },
+				..BaseEffect::default()
+			})
+			.repeat(Repeat::For(time))
+			.gamepads(&ids)
+			.finish(&mut self.gilrs)
+			.and_then(|effect| effect.play().map(|_| effect));
+
+		match effect {
+			Ok(effect) => self.effect = Some(effect),
+			Err(err) => tracing::warn!("Failed to rumble game controllers: {}", err)
+		}
+	}
+}
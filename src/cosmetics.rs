@@ -0,0 +1,165 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+use crate::profiles::{Profile, LifetimeStats};
+
+// How many copies of a moving object its trail is made of, and how far apart they are, in milliseconds of its movement.
+
+pub const TRAIL_LENGTH: usize = 4;
+pub const TRAIL_SPACING: f32 = 16.0;
+
+// How dark the darker parts of a pattern are, as the opacity of the shade drawn over them.
+
+const PATTERN_SHADE: f32 = 0.35;
+
+// How many bands a striped object is split into along its length.
+
+const STRIPES: usize = 5;
+
+// Implement the patterns a skin is drawn with, over its colour.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Pattern {
+	Solid,
+	// Bands across the object, alternately light and dark.
+	Striped,
+	// Four squares, light and dark like a chessboard.
+	Checked
+}
+
+// Implement the trails a skin leaves behind it while it's moving.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Trail {
+	None,
+	// Fading copies of the whole object.
+	Fade,
+	// Fading dots where its middle was.
+	Dotted
+}
+
+// Implement a cosmetic skin for a paddle or ball, unlocked by playing for long enough.
+// Skins only change how an object's drawn, never its size or how it plays.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Skin {
+	// Name the skin is saved under in a profile, and the key of its name in the language strings, under skins.
+	pub id: &'static str,
+	pub fill: [f32; 4],
+	pub pattern: Pattern,
+	pub trail: Trail,
+	// How long a profile has to have played, in minutes, to use it.
+	pub unlock_minutes: u64
+}
+
+// The skins for the player's paddle, in the order they're unlocked. The first is the one everyone starts with.
+
+pub const PADDLE_SKINS: &[Skin] = &[
+	Skin { id: "classic", fill: [1.0, 1.0, 1.0, 1.0], pattern: Pattern::Solid, trail: Trail::None, unlock_minutes: 0 },
+	Skin { id: "ember", fill: [1.0, 0.55, 0.2, 1.0], pattern: Pattern::Solid, trail: Trail::None, unlock_minutes: 10 },
+	Skin { id: "zebra", fill: [1.0, 1.0, 1.0, 1.0], pattern: Pattern::Striped, trail: Trail::None, unlock_minutes: 30 },
+	Skin { id: "neon", fill: [0.3, 1.0, 0.9, 1.0], pattern: Pattern::Striped, trail: Trail::None, unlock_minutes: 60 },
+	Skin { id: "gold", fill: [1.0, 0.84, 0.3, 1.0], pattern: Pattern::Solid, trail: Trail::None, unlock_minutes: 180 }
+];
+
+// The skins for the ball, in the order they're unlocked. The first is the one everyone starts with.
+
+pub const BALL_SKINS: &[Skin] = &[
+	Skin { id: "classic", fill: [1.0, 1.0, 1.0, 1.0], pattern: Pattern::Solid, trail: Trail::None, unlock_minutes: 0 },
+	Skin { id: "comet", fill: [1.0, 1.0, 1.0, 1.0], pattern: Pattern::Solid, trail: Trail::Fade, unlock_minutes: 15 },
+	Skin { id: "checker", fill: [1.0, 1.0, 1.0, 1.0], pattern: Pattern::Checked, trail: Trail::None, unlock_minutes: 45 },
+	Skin { id: "plasma", fill: [1.0, 0.4, 1.0, 1.0], pattern: Pattern::Solid, trail: Trail::Dotted, unlock_minutes: 90 },
+	Skin { id: "gold", fill: [1.0, 0.84, 0.3, 1.0], pattern: Pattern::Solid, trail: Trail::Fade, unlock_minutes: 180 }
+];
+
+impl Skin {
+	// Check whether a profile with the given stats has played long enough to use this skin.
+	pub fn unlocked(&self, stats: &LifetimeStats) -> bool {
+		stats.play_time >= self.unlock_minutes * 60
+	}
+
+	// Key of this skin's name in the language strings.
+	pub fn text_key(&self) -> String {
+		format!("skins.{}", self.id)
+	}
+
+	// Draw an object in this skin, at the given position and size.
+	// Its trail is drawn first, behind it, going back the way it came, but only while it's moving.
+	pub fn draw(&self, renderer: &mut dyn Renderer, position: Vec2, size: Vec2, velocity: Vec2, moving: bool) {
+		if moving && self.trail != Trail::None && velocity != Vec2::new(0.0, 0.0) {
+			self.draw_trail(renderer, position, size, velocity);
+		}
+
+		renderer.fill(position, size, self.fill);
+		match self.pattern {
+			Pattern::Solid => (),
+			Pattern::Striped => {
+				// Stripes go across the object's length, so a paddle's are horizontal.
+				let along = if size.y >= size.x { Vec2::new(0.0, size.y / STRIPES as f32) } else { Vec2::new(size.x / STRIPES as f32, 0.0) };
+				let band = if size.y >= size.x { Vec2::new(size.x, along.y) } else { Vec2::new(along.x, size.y) };
+				for stripe in (1..STRIPES).step_by(2) {
+					renderer.shade(position + along * stripe as f32, band, PATTERN_SHADE);
+				}
+			},
+			Pattern::Checked => {
+				let half = size / 2.0;
+				renderer.shade(position, half, PATTERN_SHADE);
+				renderer.shade(position + half, half, PATTERN_SHADE);
+			}
+		}
+	}
+
+	// Draw the copies an object leaves behind, each further back more faded than the last.
+	fn draw_trail(&self, renderer: &mut dyn Renderer, position: Vec2, size: Vec2, velocity: Vec2) {
+		for copy in (1..=TRAIL_LENGTH).rev() {
+			let behind = position - velocity * TRAIL_SPACING * copy as f32;
+			let [r, g, b, a] = self.fill;
+			let fill = [r, g, b, a * 0.5 * (1.0 - copy as f32 / (TRAIL_LENGTH + 1) as f32)];
+			match self.trail {
+				Trail::None => (),
+				Trail::Fade => renderer.fill(behind, size, fill),
+				Trail::Dotted => {
+					let dot = size / 3.0;
+					renderer.fill(behind + dot, dot, fill);
+				}
+			}
+		}
+	}
+}
+
+// Find the skin with the given id, if the stats have unlocked it, or the one everyone starts with otherwise.
+// A profile's file could name a skin that doesn't exist or that it hasn't earned yet, e.g. if it was edited by hand.
+
+pub fn chosen(skins: &'static [Skin], id: &str, stats: &LifetimeStats) -> &'static Skin {
+	skins.iter().find(|skin| skin.id == id && skin.unlocked(stats)).unwrap_or(&skins[0])
+}
+
+// Get the skins the player's paddle and the ball are drawn in for the given profile.
+// Guests haven't saved any play time, so they play with the skins everyone starts with.
+
+pub fn for_profile(profile: Option<&Profile>) -> (&'static Skin, &'static Skin) {
+	match profile {
+		Some(profile) => (
+			chosen(PADDLE_SKINS, &profile.paddle_skin, &profile.stats),
+			chosen(BALL_SKINS, &profile.ball_skin, &profile.stats)
+		),
+		None => (&PADDLE_SKINS[0], &BALL_SKINS[0])
+	}
+}
+
+// Get the next unlocked skin after the one with the given id, stepping forwards or backwards and wrapping around at either end.
+
+pub fn cycle(skins: &'static [Skin], id: &str, stats: &LifetimeStats, step: i32) -> &'static Skin {
+	let unlocked: Vec<&'static Skin> = skins.iter().filter(|skin| skin.unlocked(stats)).collect();
+	let index = unlocked.iter().position(|skin| skin.id == id).unwrap_or(0) as i32;
+	unlocked[(index + step).rem_euclid(unlocked.len() as i32) as usize]
+}
+
+// Get the skins a profile unlocked between having the first stats and the second, e.g. over a match it just finished.
+
+pub fn newly_unlocked(skins: &'static [Skin], before: &LifetimeStats, after: &LifetimeStats) -> Vec<&'static Skin> {
+	skins.iter()
+		.filter(|skin| skin.unlocked(after) && !skin.unlocked(before))
+		.collect()
+}
@@ -0,0 +1,133 @@
+// Dependencies
+
+use std::collections::VecDeque;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::{self, Write};
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+use pong::config::Config;
+use pong::paths;
+
+// How many of the most recent log lines go into a crash report.
+
+const LOG_LINES: usize = 200;
+
+// The most recent log lines, the settings in use, and whether a crash report has been written yet.
+// These are global since the panic hook can't be given any state of its own.
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static CONFIG_SUMMARY: Mutex<String> = Mutex::new(String::new());
+static CRASHED: AtomicBool = AtomicBool::new(false);
+
+// Implement a log writer that keeps the most recent lines in memory for crash reports.
+
+#[derive(Clone, Copy, Default)]
+pub struct RecentLogs;
+
+impl Write for RecentLogs {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let mut logs = RECENT_LOGS.lock().unwrap_or_else(|err| err.into_inner());
+		for line in String::from_utf8_lossy(buf).lines() {
+			if logs.len() == LOG_LINES {
+				logs.pop_front();
+			}
+			logs.push_back(line.to_string());
+		}
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+	type Writer = Self;
+
+	fn make_writer(&'a self) -> Self::Writer {
+		*self
+	}
+}
+
+// Note the settings in use, to include in any crash report.
+
+pub fn set_config(config: &Config) {
+	let summary = toml::to_string_pretty(config).unwrap_or_else(|err| format!("(couldn't write settings: {})", err));
+	*CONFIG_SUMMARY.lock().unwrap_or_else(|err| err.into_inner()) = summary;
+}
+
+// Get the directory crash reports are saved to in the platform's data directory.
+
+pub fn dir() -> Option<PathBuf> {
+	paths::data_dir().map(|dir| dir.join("crashes"))
+}
+
+// Replace the default panic output with a crash report written to a file, telling the player where to find it.
+// Only the first panic is reported, since one thread panicking usually makes the others panic too.
+
+pub fn install() {
+	panic::set_hook(Box::new(|info| {
+		if CRASHED.swap(true, Ordering::SeqCst) {
+			return;
+		}
+
+		let report = report(info);
+		match write_report(&report) {
+			Ok(path) => eprintln!("Pong has crashed.\n\n{}\n\nA crash report was saved to {}", message(info), path.display()),
+			Err(err) => eprintln!("Pong has crashed, and the crash report couldn't be saved ({}).\n\n{}", err, report)
+		}
+	}));
+}
+
+// Get the message a panic was raised with.
+
+fn message(info: &PanicHookInfo) -> String {
+	let payload = info.payload();
+	let message = payload.downcast_ref::<&str>().copied()
+		.or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+		.unwrap_or("(no message)");
+
+	match info.location() {
+		Some(location) => format!("{} (at {})", message, location),
+		None => message.to_string()
+	}
+}
+
+// Build the crash report for a panic: what went wrong and where, the backtrace, the settings in use and what was logged leading up to it.
+
+fn report(info: &PanicHookInfo) -> String {
+	let thread = std::thread::current();
+	let config = CONFIG_SUMMARY.lock().unwrap_or_else(|err| err.into_inner());
+	let logs = RECENT_LOGS.lock().unwrap_or_else(|err| err.into_inner());
+
+	let mut report = String::new();
+	report += &format!("Pong {} crashed on {} ({})\n", env!("CARGO_PKG_VERSION"), std::env::consts::OS, std::env::consts::ARCH);
+	report += &format!("Thread '{}' panicked: {}\n\n", thread.name().unwrap_or("unnamed"), message(info));
+	report += &format!("Backtrace:\n{}\n\n", Backtrace::force_capture());
+	report += &format!("Settings:\n{}\n\n", config);
+	report += "Recent log lines:\n";
+	for line in logs.iter() {
+		report += line;
+		report += "\n";
+	}
+	report
+}
+
+// Write a crash report to the crashes directory, named after the current time.
+
+fn write_report(report: &str) -> io::Result<PathBuf> {
+	let dir = dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory on this platform"))?;
+	fs::create_dir_all(&dir)?;
+
+	let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+	let path = dir.join(format!("crash-{}.txt", secs));
+	fs::write(&path, report)?;
+	Ok(path)
+}
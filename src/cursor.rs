@@ -0,0 +1,116 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::input::Input;
+
+// Implement the kinds of windowing system that differ in how the cursor can be held inside the window.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Platform {
+	// The cursor can be locked in place, but not kept inside the window while it moves.
+	MacOs,
+	// The cursor can be kept inside the window, but programs aren't allowed to move it.
+	Wayland,
+	// The cursor can be kept inside the window, and moved by the program, e.g. Windows and X11.
+	Other
+}
+
+// Implement the ways of holding the cursor in the window while the paddle follows it, from most to least reliable.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GrabMode {
+	// The cursor stays where it is, and the paddle follows the mouse's own movement instead.
+	Locked,
+	// The cursor moves as usual, but can't leave the window.
+	Confined,
+	// The cursor is moved back to the middle of the window after every movement, and the paddle follows how far it went.
+	Recentering,
+	// Nothing could hold the cursor, so it's only hidden.
+	Free
+}
+
+impl GrabMode {
+	// Get the modes to try on a platform, in order, until one works.
+	// Each platform only offers some of them, and the last always works.
+	pub fn fallbacks(platform: Platform) -> &'static [GrabMode] {
+		match platform {
+			Platform::MacOs => &[GrabMode::Locked, GrabMode::Recentering, GrabMode::Free],
+			Platform::Wayland => &[GrabMode::Confined, GrabMode::Free],
+			Platform::Other => &[GrabMode::Confined, GrabMode::Recentering, GrabMode::Free]
+		}
+	}
+}
+
+// How tall a court, in logical points, the mouse's movement is measured against.
+// On a taller or shorter court the virtual cursor goes proportionally further, so moving the mouse the same distance always takes the paddle the same part of the way across.
+
+pub const REFERENCE_HEIGHT: f32 = 768.0;
+
+// Implement the cursor the scenes see while it's captured.
+// When the real cursor can't move freely inside the window, a virtual one is moved instead, by how far the mouse went, and kept inside the window.
+// How far it goes doesn't depend on the screen's scale or the size of the window, only on how far the mouse moved.
+
+#[derive(Clone, Debug)]
+pub struct CursorGrab {
+	// How the cursor is being held, or None while it's free.
+	pub mode: Option<GrabMode>,
+	// Where the scenes last saw the cursor.
+	pub position: Vec2,
+	// How many physical pixels make up a logical point on the window's screen, which the mouse's own movement is measured in.
+	pub scale_factor: f32
+}
+
+impl Default for CursorGrab {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl CursorGrab {
+	pub fn new() -> Self {
+		Self {
+			mode: None,
+			position: Vec2::new(0.0, 0.0),
+			scale_factor: 1.0
+		}
+	}
+
+	// Check if the real cursor needs moving back to the middle of the window after it moves.
+	pub fn recentering(&self) -> bool {
+		self.mode == Some(GrabMode::Recentering)
+	}
+
+	// Translate the real cursor moving to the given position in a window of the given size.
+	// While recentering, the cursor coming back to the middle isn't a movement, so there's nothing to pass on.
+	pub fn cursor_moved(&mut self, x: f32, y: f32, width: f32, height: f32) -> Option<Input> {
+		match self.mode {
+			Some(GrabMode::Locked) => None,
+			Some(GrabMode::Recentering) => self.move_by(x - width / 2.0, y - height / 2.0, width, height),
+			_ => {
+				self.position = Vec2::new(x, y);
+				Some(Input::MouseMoved { x, y })
+			}
+		}
+	}
+
+	// Translate the mouse's own movement, in physical pixels, which is only followed while the cursor is locked in place.
+	// Some platforms report raw counts from the mouse instead, which don't follow the screen's scale, so this is only roughly in logical points there.
+	pub fn mouse_motion(&mut self, dx: f32, dy: f32, width: f32, height: f32) -> Option<Input> {
+		match self.mode {
+			Some(GrabMode::Locked) => self.move_by(dx / self.scale_factor, dy / self.scale_factor, width, height),
+			_ => None
+		}
+	}
+
+	// Move the virtual cursor by a distance in logical points, scaled to the size of the court, keeping it inside the window.
+	fn move_by(&mut self, dx: f32, dy: f32, width: f32, height: f32) -> Option<Input> {
+		if dx == 0.0 && dy == 0.0 {
+			return None;
+		}
+
+		let travel = height / REFERENCE_HEIGHT;
+		let (dx, dy) = (dx * travel, dy * travel);
+		self.position = Vec2::new((self.position.x + dx).clamp(0.0, width), (self.position.y + dy).clamp(0.0, height));
+		Some(Input::MouseMoved { x: self.position.x, y: self.position.y })
+	}
+}
@@ -0,0 +1,400 @@
+// Dependencies
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+// Imports from the Glium library:
+use glium::{
+	implement_vertex,
+	VertexBuffer, IndexBuffer,
+	Display, Frame, Program, Surface, DrawParameters, Blend, BlendingFunction, LinearBlendingFactor,
+	index::PrimitiveType,
+	program::ProgramCreationInput,
+	texture::{Texture2d, RawImage2d, MipmapsOption},
+	uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction},
+	uniform
+};
+
+use glium::glutin::event::{
+	WindowEvent,
+	VirtualKeyCode,
+	MouseButton,
+	MouseScrollDelta,
+	ElementState as KeyState
+};
+
+// egui is the immediate-mode UI library used to build the panel.
+// https://crates.io/crates/egui
+
+use egui::{RawInput, Event, Pos2, Vec2, Rect, Modifiers, PointerButton, TextureId, ImageData, ClippedMesh};
+
+use pong::scene::Context;
+use pong::game::Command;
+
+use crate::error::Error;
+
+// The key that shows and hides the panel.
+// It's a development tool rather than part of the game, so it isn't one of the rebindable keys.
+
+const TOGGLE_KEY: VirtualKeyCode = VirtualKeyCode::F3;
+
+// How far a notch of the mouse wheel scrolls, in points.
+
+const SCROLL_LINE: f32 = 50.0;
+
+// Implement a vertex of an egui mesh, as the debug shaders take it.
+
+#[derive(Copy, Clone, Debug)]
+struct DebugVertex {
+	a_pos: [f32; 2],
+	a_tc: [f32; 2],
+	a_srgba: [u8; 4]
+}
+implement_vertex!(DebugVertex, a_pos, a_tc, a_srgba);
+
+// Implement the debug panel.
+// This collects window events for egui, builds the panel each frame and draws it over the game.
+// Changes made in the panel are applied to the simulation as commands, so they end up in replays like any other.
+
+pub struct DebugUi {
+	// Whether the panel is showing.
+	pub open: bool,
+	// Whether objects are drawn between ticks, smoothing their movement. Turning this off shows exactly what the simulation has.
+	pub interpolate: bool,
+	egui: egui::Context,
+	painter: Painter,
+	// Events received since the panel was last built.
+	events: Vec<Event>,
+	modifiers: Modifiers,
+	// Where the pointer is, in points.
+	pointer: Pos2,
+	start: Instant
+}
+
+impl DebugUi {
+	pub fn new(display: &Display) -> Result<Self, Error> {
+		Ok(Self {
+			open: false,
+			interpolate: true,
+			egui: egui::Context::default(),
+			painter: Painter::new(display)?,
+			events: Vec::new(),
+			modifiers: Modifiers::default(),
+			pointer: Pos2::ZERO,
+			start: Instant::now()
+		})
+	}
+
+	// Pass a window event to the panel, toggling it if its key was pressed.
+	// Returns whether the panel used the event, in which case the game shouldn't see it.
+	pub fn handle_event(&mut self, event: &WindowEvent, scale_factor: f64) -> bool {
+		let scale_factor = scale_factor as f32;
+
+		if let WindowEvent::KeyboardInput { input, .. } = event {
+			if input.state == KeyState::Pressed && input.virtual_keycode == Some(TOGGLE_KEY) {
+				self.open = !self.open;
+				return true;
+			}
+		}
+
+		// Modifiers are kept track of even while the panel is hidden, so they're right when it opens.
+		if let WindowEvent::ModifiersChanged(state) = event {
+			self.modifiers = Modifiers {
+				alt: state.alt(),
+				ctrl: state.ctrl(),
+				shift: state.shift(),
+				mac_cmd: cfg!(target_os = "macos") && state.logo(),
+				command: if cfg!(target_os = "macos") { state.logo() } else { state.ctrl() }
+			};
+			return false;
+		}
+
+		if !self.open {
+			return false;
+		}
+
+		let wants_pointer = self.egui.wants_pointer_input();
+		let wants_keyboard = self.egui.wants_keyboard_input();
+
+		match event {
+			WindowEvent::CursorMoved { position, .. } => {
+				self.pointer = Pos2::new(position.x as f32 / scale_factor, position.y as f32 / scale_factor);
+				self.events.push(Event::PointerMoved(self.pointer));
+				wants_pointer
+			},
+			WindowEvent::CursorLeft { .. } => {
+				self.events.push(Event::PointerGone);
+				false
+			},
+			WindowEvent::MouseInput { state, button, .. } => {
+				let button = match button {
+					MouseButton::Left => PointerButton::Primary,
+					MouseButton::Right => PointerButton::Secondary,
+					MouseButton::Middle => PointerButton::Middle,
+					MouseButton::Other(_) => return false
+				};
+				self.events.push(Event::PointerButton {
+					pos: self.pointer,
+					button,
+					pressed: *state == KeyState::Pressed,
+					modifiers: self.modifiers
+				});
+				wants_pointer
+			},
+			WindowEvent::MouseWheel { delta, .. } => {
+				let delta = match delta {
+					MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x, *y) * SCROLL_LINE,
+					MouseScrollDelta::PixelDelta(delta) => Vec2::new(delta.x as f32, delta.y as f32) / scale_factor
+				};
+				self.events.push(Event::Scroll(delta));
+				wants_pointer
+			},
+			WindowEvent::ReceivedCharacter(character) if !character.is_control() => {
+				self.events.push(Event::Text(character.to_string()));
+				wants_keyboard
+			},
+			WindowEvent::KeyboardInput { input, .. } => {
+				if let Some(key) = input.virtual_keycode.and_then(egui_key) {
+					self.events.push(Event::Key {
+						key,
+						pressed: input.state == KeyState::Pressed,
+						modifiers: self.modifiers
+					});
+				}
+				wants_keyboard
+			},
+			_ => false
+		}
+	}
+
+	// Build the panel from the current state of the game, applying any changes made in it, then draw it over the frame.
+	pub fn show(&mut self, display: &Display, frame: &mut Frame, ctx: &mut Context, delta_time: f32) {
+		if !self.open {
+			self.events.clear();
+			return;
+		}
+
+		let (width, height) = frame.get_dimensions();
+		let scale_factor = display.gl_window().window().scale_factor() as f32;
+		let input = RawInput {
+			screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(width as f32, height as f32) / scale_factor)),
+			pixels_per_point: Some(scale_factor),
+			time: Some(self.start.elapsed().as_secs_f64()),
+			modifiers: self.modifiers,
+			events: std::mem::take(&mut self.events),
+			..RawInput::default()
+		};
+
+		let interpolate = &mut self.interpolate;
+		let output = self.egui.run(input, |egui| {
+			egui::Window::new("Debug").show(egui, |ui| {
+				ui.label(format!("Frame time: {:.1} ms", delta_time));
+				ui.label(format!("Tick: {}", ctx.game.ticks));
+
+				// Edit a copy of the rules, sending the whole set back if anything changed.
+				ui.collapsing("Physics", |ui| {
+					let mut rules = ctx.game.rules;
+					let mut max_bounce_angle = rules.max_bounce_angle.to_degrees();
+					let changed = [
+						ui.add(egui::Slider::new(&mut rules.ball_speed, 0.1..=5.0).text("Ball speed")).changed(),
+						ui.add(egui::Slider::new(&mut rules.paddle_size, 0.1..=5.0).text("Paddle size")).changed(),
+						ui.add(egui::Slider::new(&mut max_bounce_angle, 0.0..=89.0).text("Max bounce angle")).changed(),
+						ui.add(egui::Slider::new(&mut rules.paddle_momentum, 0.0..=2.0).text("Paddle momentum")).changed(),
+						ui.add(egui::Slider::new(&mut rules.spin_friction, 0.0..=1.0).text("Spin friction")).changed(),
+						ui.add(egui::Slider::new(&mut rules.spin_curve, 0.0..=0.01).text("Spin curve")).changed(),
+						ui.add(egui::Slider::new(&mut rules.paddle_restitution, 0.5..=2.0).text("Paddle restitution")).changed(),
+						ui.add(egui::Slider::new(&mut rules.wall_restitution, 0.5..=2.0).text("Wall restitution")).changed(),
+						ui.add(egui::Slider::new(&mut rules.gravity.x, -0.001..=0.001).text("Gravity x")).changed(),
+						ui.add(egui::Slider::new(&mut rules.gravity.y, -0.001..=0.001).text("Gravity y")).changed(),
+						ui.add(egui::Slider::new(&mut rules.wind.x, -0.001..=0.001).text("Wind x")).changed(),
+						ui.add(egui::Slider::new(&mut rules.wind.y, -0.001..=0.001).text("Wind y")).changed()
+					];
+					if ui.button("Reset").clicked() {
+						rules = Default::default();
+						max_bounce_angle = rules.max_bounce_angle.to_degrees();
+					} else if !changed.contains(&true) {
+						return;
+					}
+					rules.max_bounce_angle = max_bounce_angle.to_radians();
+					ctx.sim.apply(Command::SetRules(rules));
+				});
+
+				ui.collapsing("AI", |ui| {
+					let mut accuracy = ctx.game.ai_accuracy;
+					if ui.add(egui::Slider::new(&mut accuracy, 0.0..=1.0).text("Accuracy")).changed() {
+						ctx.sim.apply(Command::SetAiAccuracy(accuracy));
+					}
+				});
+
+				ui.collapsing("Rendering", |ui| {
+					ui.checkbox(interpolate, "Interpolate between ticks");
+				});
+			});
+		});
+
+		let meshes = self.egui.tessellate(output.shapes);
+		self.painter.paint(display, frame, scale_factor, meshes, output.textures_delta);
+	}
+}
+
+// Translate a key from the window into the key egui uses for it, for the keys its widgets respond to.
+
+fn egui_key(key: VirtualKeyCode) -> Option<egui::Key> {
+	Some(match key {
+		VirtualKeyCode::Down => egui::Key::ArrowDown,
+		VirtualKeyCode::Left => egui::Key::ArrowLeft,
+		VirtualKeyCode::Right => egui::Key::ArrowRight,
+		VirtualKeyCode::Up => egui::Key::ArrowUp,
+		VirtualKeyCode::Escape => egui::Key::Escape,
+		VirtualKeyCode::Tab => egui::Key::Tab,
+		VirtualKeyCode::Back => egui::Key::Backspace,
+		VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => egui::Key::Enter,
+		VirtualKeyCode::Space => egui::Key::Space,
+		VirtualKeyCode::Delete => egui::Key::Delete,
+		VirtualKeyCode::Home => egui::Key::Home,
+		VirtualKeyCode::End => egui::Key::End,
+		VirtualKeyCode::A => egui::Key::A,
+		VirtualKeyCode::C => egui::Key::C,
+		VirtualKeyCode::V => egui::Key::V,
+		VirtualKeyCode::X => egui::Key::X,
+		VirtualKeyCode::Z => egui::Key::Z,
+		_ => return None
+	})
+}
+
+// Implement the painter that draws egui's meshes with Glium.
+// Colours and textures stay in gamma space from start to finish, which is close enough for a debug panel.
+
+struct Painter {
+	program: Program,
+	textures: HashMap<TextureId, Texture2d>
+}
+
+impl Painter {
+	fn new(display: &Display) -> Result<Self, Error> {
+		// The shader's output is already in sRGB, so Glium mustn't convert it again.
+		let program = Program::new(display, ProgramCreationInput::SourceCode {
+			vertex_shader: include_str!("./shaders/debug_ui.vsh"),
+			tessellation_control_shader: None,
+			tessellation_evaluation_shader: None,
+			geometry_shader: None,
+			fragment_shader: include_str!("./shaders/debug_ui.frag"),
+			transform_feedback_varyings: None,
+			outputs_srgb: true,
+			uses_point_size: false
+		})?;
+
+		Ok(Self {
+			program,
+			textures: HashMap::new()
+		})
+	}
+
+	// Upload new and changed textures, draw the meshes, then free textures egui is done with.
+	fn paint(&mut self, display: &Display, frame: &mut Frame, scale_factor: f32, meshes: Vec<ClippedMesh>, textures: egui::TexturesDelta) {
+		for (id, delta) in textures.set {
+			self.set_texture(display, id, delta);
+		}
+
+		let (width, height) = frame.get_dimensions();
+		let screen_size = [width as f32 / scale_factor, height as f32 / scale_factor];
+
+		for ClippedMesh(clip, mesh) in meshes {
+			let texture = match self.textures.get(&mesh.texture_id) {
+				Some(texture) => texture,
+				None => continue
+			};
+
+			let vertices: Vec<DebugVertex> = mesh.vertices.iter().map(|vertex| DebugVertex {
+				a_pos: [vertex.pos.x, vertex.pos.y],
+				a_tc: [vertex.uv.x, vertex.uv.y],
+				a_srgba: vertex.color.to_array()
+			}).collect();
+
+			// Buffers only fail to be made when the OpenGL context is lost, which the next frame will report.
+			// Skip the mesh rather than crashing the game.
+			let buffers = VertexBuffer::new(display, &vertices)
+				.map_err(|err| err.to_string())
+				.and_then(|vx_buf| IndexBuffer::new(display, PrimitiveType::TrianglesList, &mesh.indices)
+					.map(|ix_buf| (vx_buf, ix_buf))
+					.map_err(|err| err.to_string()));
+			let (vx_buf, ix_buf) = match buffers {
+				Ok(buffers) => buffers,
+				Err(err) => {
+					tracing::warn!("Failed to build debug panel mesh: {}", err);
+					continue;
+				}
+			};
+
+			// Clip to the mesh's rect, converted to pixels counted up from the bottom of the window.
+			let left = (clip.min.x * scale_factor).clamp(0.0, width as f32);
+			let right = (clip.max.x * scale_factor).clamp(left, width as f32);
+			let top = (clip.min.y * scale_factor).clamp(0.0, height as f32);
+			let bottom = (clip.max.y * scale_factor).clamp(top, height as f32);
+			let scissor = glium::Rect {
+				left: left.round() as u32,
+				bottom: (height as f32 - bottom).round() as u32,
+				width: (right - left).round() as u32,
+				height: (bottom - top).round() as u32
+			};
+
+			// egui's colours have their alpha already multiplied in.
+			let params = DrawParameters {
+				blend: Blend {
+					color: BlendingFunction::Addition {
+						source: LinearBlendingFactor::One,
+						destination: LinearBlendingFactor::OneMinusSourceAlpha
+					},
+					alpha: BlendingFunction::Addition {
+						source: LinearBlendingFactor::OneMinusDestinationAlpha,
+						destination: LinearBlendingFactor::One
+					},
+					constant_value: (0.0, 0.0, 0.0, 0.0)
+				},
+				scissor: Some(scissor),
+				..Default::default()
+			};
+
+			let uniforms = uniform!{
+				u_screen_size: screen_size,
+				u_sampler: texture.sampled()
+					.magnify_filter(MagnifySamplerFilter::Linear)
+					.minify_filter(MinifySamplerFilter::Linear)
+					.wrap_function(SamplerWrapFunction::Clamp)
+			};
+
+			if let Err(err) = frame.draw(&vx_buf, &ix_buf, &self.program, &uniforms, &params) {
+				tracing::warn!("Failed to draw debug panel: {}", err);
+			}
+		}
+
+		for id in textures.free {
+			self.textures.remove(&id);
+		}
+	}
+
+	// Create a texture, or write over part of one.
+	fn set_texture(&mut self, display: &Display, id: TextureId, delta: egui::epaint::ImageDelta) {
+		let [width, height] = delta.image.size();
+		let pixels: Vec<u8> = match &delta.image {
+			ImageData::Color(image) => image.pixels.iter().flat_map(|pixel| pixel.to_array()).collect(),
+			ImageData::Alpha(image) => image.srgba_pixels(1.0).flat_map(|pixel| pixel.to_array()).collect()
+		};
+		let image = RawImage2d::from_raw_rgba(pixels, (width as u32, height as u32));
+
+		match (delta.pos, self.textures.get(&id)) {
+			(Some([x, y]), Some(texture)) => texture.write(glium::Rect {
+				left: x as u32,
+				bottom: y as u32,
+				width: width as u32,
+				height: height as u32
+			}, image),
+			_ => match Texture2d::with_mipmaps(display, image, MipmapsOption::NoMipmap) {
+				Ok(texture) => {
+					self.textures.insert(id, texture);
+				},
+				Err(err) => tracing::warn!("Failed to create debug panel texture: {}", err)
+			}
+		}
+	}
+}
@@ -0,0 +1,118 @@
+// Dependencies
+
+use std::fmt;
+
+use glium::backend::glutin::DisplayCreationError;
+use glium::program::ProgramCreationError;
+use glium::{vertex, index, buffer, SwapBuffersError};
+
+use pong::locale::Locale;
+
+// Implement an error type for everything that can go wrong setting up or drawing to the window.
+// Each error is shown to the player as a readable message instead of a panic.
+
+#[derive(Debug)]
+pub enum Error {
+	// The window or OpenGL context couldn't be created.
+	Display(DisplayCreationError),
+	// The shaders failed to compile or link.
+	Shader(ProgramCreationError),
+	// A vertex, index or uniform buffer couldn't be created.
+	VertexBuffer(vertex::BufferCreationError),
+	IndexBuffer(index::BufferCreationError),
+	UniformBuffer(buffer::BufferCreationError),
+	// An asset couldn't be found or read.
+	MissingAsset(&'static str),
+	// A finished frame couldn't be shown, usually because the OpenGL context was lost.
+	SwapBuffers(SwapBuffersError),
+	// A replay given on the command line couldn't be loaded.
+	Replay(String),
+	// Telemetry couldn't be exported to the file given on the command line.
+	Telemetry(String),
+	// Settings couldn't be imported from or exported to the file given on the command line.
+	Settings(String),
+	// The metrics server couldn't listen on the port given on the command line.
+	Metrics(std::io::Error),
+	// The remote control server couldn't listen on the port given on the command line.
+	#[cfg(feature = "remote")]
+	Remote(std::io::Error),
+	// The terminal couldn't be set up or drawn to while playing in it.
+	#[cfg(feature = "terminal")]
+	Terminal(std::io::Error)
+}
+
+impl Error {
+	// Get the message to show the player, in the given language.
+	pub fn message(&self, locale: &Locale) -> String {
+		match self {
+			Error::Display(err) => locale.format("error.display", &[("details", err)]),
+			Error::Shader(err) => locale.format("error.shader", &[("details", err)]),
+			Error::VertexBuffer(err) => locale.format("error.vertex-buffer", &[("details", err)]),
+			Error::IndexBuffer(err) => locale.format("error.index-buffer", &[("details", err)]),
+			Error::UniformBuffer(err) => locale.format("error.uniform-buffer", &[("details", err)]),
+			Error::MissingAsset(key) => locale.format("error.missing-asset", &[("asset", key)]),
+			Error::SwapBuffers(err) => locale.format("error.swap-buffers", &[("details", err)]),
+			Error::Replay(err) => locale.format("error.replay", &[("details", err)]),
+			Error::Telemetry(err) => locale.format("error.telemetry", &[("details", err)]),
+			Error::Settings(err) => locale.format("error.settings", &[("details", err)]),
+			Error::Metrics(err) => locale.format("error.metrics", &[("details", err)]),
+			#[cfg(feature = "remote")]
+			Error::Remote(err) => locale.format("error.remote", &[("details", err)]),
+			#[cfg(feature = "terminal")]
+			Error::Terminal(err) => locale.format("error.terminal", &[("details", err)])
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.message(&Locale::default()))
+	}
+}
+
+impl std::error::Error for Error {}
+
+// Implement conversions so errors can be returned with the ? operator.
+
+impl From<DisplayCreationError> for Error {
+	fn from(err: DisplayCreationError) -> Self {
+		Error::Display(err)
+	}
+}
+
+impl From<ProgramCreationError> for Error {
+	fn from(err: ProgramCreationError) -> Self {
+		Error::Shader(err)
+	}
+}
+
+impl From<vertex::BufferCreationError> for Error {
+	fn from(err: vertex::BufferCreationError) -> Self {
+		Error::VertexBuffer(err)
+	}
+}
+
+impl From<index::BufferCreationError> for Error {
+	fn from(err: index::BufferCreationError) -> Self {
+		Error::IndexBuffer(err)
+	}
+}
+
+impl From<buffer::BufferCreationError> for Error {
+	fn from(err: buffer::BufferCreationError) -> Self {
+		Error::UniformBuffer(err)
+	}
+}
+
+impl From<SwapBuffersError> for Error {
+	fn from(err: SwapBuffersError) -> Self {
+		Error::SwapBuffers(err)
+	}
+}
+
+// Report an error to the player in the given language and exit.
+
+pub fn exit_with(err: Error, locale: &Locale) -> ! {
+	eprintln!("{}\n\n{}", locale.get("error.stopped"), err.message(locale));
+	std::process::exit(1);
+}
@@ -0,0 +1,82 @@
+// Dependencies
+
+use std::vec::Drain;
+
+use serde::{Serialize, Deserialize};
+
+use crate::structs::Vec2;
+use crate::stats::MatchStats;
+
+// Implement a Side enum identifying which half of the court something belongs to.
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Side {
+	Left,
+	Right
+}
+
+impl Side {
+	// Get the side opposite to this one.
+	pub fn opponent(self) -> Self {
+		match self {
+			Side::Left => Side::Right,
+			Side::Right => Side::Left
+		}
+	}
+
+	// Index of this side, for per-player arrays such as scores.
+	pub fn index(self) -> usize {
+		match self {
+			Side::Left => 0,
+			Side::Right => 1
+		}
+	}
+}
+
+// Events emitted by the simulation, or the game around it, when something notable happens.
+// Other systems (audio, particles, HUD, networking) react to these instead of poking at the game state directly.
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GameEvent {
+	// The ball bounced off a paddle belonging to the given side, touching it at the given point.
+	BallHitPaddle { side: Side, point: Vec2 },
+	// The ball bounced off the top or bottom edge of the court at the given point.
+	BallHitWall { point: Vec2 },
+	// A new match started from nothing, e.g. a rematch or a change of mode.
+	MatchStarted,
+	// The given side scored a point, leaving the scores as given.
+	PointScored { side: Side, scores: [u32; 2] },
+	// A side won the match, with the given final scores and the statistics for the whole match.
+	MatchEnded { winner: Side, scores: [u32; 2], stats: MatchStats },
+	// A power-up was collected by the given side.
+	PowerUpCollected { side: Side },
+	// The config file was changed while the game was running, and its settings have been applied.
+	ConfigChanged
+}
+
+// Implement a queue of events waiting to be consumed.
+
+#[derive(Clone, Default, Debug)]
+pub struct EventQueue {
+	events: Vec<GameEvent>
+}
+
+impl EventQueue {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Add an event to the end of the queue.
+	pub fn push(&mut self, event: GameEvent) {
+		self.events.push(event);
+	}
+
+	// Remove and iterate over all queued events, in the order they were emitted.
+	pub fn drain(&mut self) -> Drain<'_, GameEvent> {
+		self.events.drain(..)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+}
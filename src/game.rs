@@ -1,57 +1,524 @@
 // Dependencies
 
-use crate::structs::{Vec2, Object, ObjectType};
+use std::str::FromStr;
 
-use glium::Display;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::{Vec2, Object, ObjectType, Body};
+use crate::collision::{self, ObjectCollider, Colliders};
+use crate::broadphase::{self, Grid};
+use crate::events::{Side, GameEvent, EventQueue};
+use crate::stats::MatchStats;
+use crate::modes::{GameMode, Classic};
+use crate::rng::Rng;
+use crate::math;
+use crate::prediction::Trajectory;
+
+// The simulation runs at a fixed rate, independent of the framerate.
+// Delta time is measured in milliseconds, so this is the length of a single tick in milliseconds.
+
+pub const TICK_RATE: f32 = 120.0;
+pub const TICK_TIME: f32 = 1000.0 / TICK_RATE;
+
+// How quickly a ball's spin wears off in flight, per millisecond.
+
+pub const SPIN_DECAY: f32 = 0.0005;
+
+// The most time a single frame can advance the simulation by, in milliseconds.
+// After a long stall, e.g. the machine sleeping or the process being suspended, the game carries on from where it was instead of fast-forwarding through the missed time.
+
+pub const MAX_FRAME_TIME: f32 = 250.0;
+
+// The furthest a ball can move in a single physics step, as a fraction of the narrowest paddle's width.
+// Faster balls have each tick split into several steps, up to a limit.
+
+pub const SUBSTEP_FRACTION: f32 = 0.5;
+pub const MAX_SUBSTEPS: u32 = 16;
+
+// The size of the cells the court is split into for finding which objects might collide, in pixels.
+// Each ball only needs checking against the objects that share a cell with it.
+// With only a handful of objects it's quicker to check everything, so the grid isn't used.
+
+pub const BROADPHASE_CELL_SIZE: f32 = 64.0;
+pub const BROADPHASE_MIN_OBJECTS: usize = 16;
+
+// The furthest from horizontal the ball can be served, in radians.
+
+pub const MAX_SERVE_ANGLE: f32 = 0.35;
+
+// How quickly AI paddles close in on where they're heading, per millisecond at full accuracy.
+
+pub const AI_TRACKING_RATE: f32 = 0.00375;
+
+// Create a struct representing the tunable rules for a match.
+// Game modes and scripts can adjust these to change how the game plays.
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+	// Multiplier for the ball's serve speed and speed cap.
+	pub ball_speed: f32,
+	// Multiplier for the height of the paddles.
+	pub paddle_size: f32,
+	// The steepest angle the ball can bounce off a paddle at, in radians from horizontal.
+	pub max_bounce_angle: f32,
+	// How much of a paddle's vertical speed is passed on to the ball when it hits.
+	pub paddle_momentum: f32,
+	// How much grip the paddles and walls have on the ball, from 0.0 (none) to 1.0 (no sliding), which builds up and uses spin.
+	pub spin_friction: f32,
+	// How strongly spin curves the ball's flight.
+	pub spin_curve: f32,
+	// How much speed the ball bounces off the paddles and walls with, as a multiple of the speed it hit at.
+	pub paddle_restitution: f32,
+	pub wall_restitution: f32,
+	// Steady pushes on the ball from the arena, as accelerations in pixels per millisecond squared.
+	// Gravity pulls it towards one side of the court, wind blows it along. Both are off by default.
+	pub gravity: Vec2,
+	pub wind: Vec2
+}
+
+impl Default for Rules {
+	fn default() -> Self {
+		Self {
+			ball_speed: 1.0,
+			paddle_size: 1.0,
+			max_bounce_angle: 60f32.to_radians(),
+			paddle_momentum: 0.25,
+			spin_friction: 0.3,
+			spin_curve: 0.001,
+			paddle_restitution: 1.15,
+			wall_restitution: 1.0,
+			gravity: Vec2::new(0.0, 0.0),
+			wind: Vec2::new(0.0, 0.0)
+		}
+	}
+}
+
+impl Rules {
+	// Get the height of a paddle for a play area of the given height.
+	pub fn paddle_height(&self, height: f32) -> f32 {
+		height * 0.25 * self.paddle_size
+	}
+}
+
+// Implement the difficulty levels for the AI paddle.
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+	Easy,
+	Normal,
+	Hard
+}
+
+impl FromStr for Difficulty {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name {
+			"easy" => Ok(Difficulty::Easy),
+			"normal" => Ok(Difficulty::Normal),
+			"hard" => Ok(Difficulty::Hard),
+			_ => Err(format!("unknown difficulty '{}', expected easy, normal or hard", name))
+		}
+	}
+}
+
+impl Difficulty {
+	// Every difficulty, from easiest to hardest.
+	pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+	// Name of this difficulty as used in the config file and on the command line.
+	pub fn name(self) -> &'static str {
+		match self {
+			Difficulty::Easy => "easy",
+			Difficulty::Normal => "normal",
+			Difficulty::Hard => "hard"
+		}
+	}
+
+	// How accurately the AI tracks the ball at this difficulty.
+	pub fn ai_accuracy(self) -> f32 {
+		match self {
+			Difficulty::Easy => 0.3,
+			Difficulty::Normal => 0.5,
+			Difficulty::Hard => 0.8
+		}
+	}
+}
+
+// Changes made to a match from outside the simulation, such as the player moving their paddle.
+// Anything that affects how a match plays out goes through one of these, so they can be recorded and played back in a replay.
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Command {
+	// Move the player's paddle so its center is at the given height, as far as the court allows.
+	MovePaddle { y: f32 },
+	// Resize the play area, resetting every object.
+	Resize { width: f32, height: f32 },
+	// Change the rules of the match.
+	SetRules(Rules),
+	// Change how accurately the AI tracks the ball.
+	SetAiAccuracy(f32),
+	// Throw away the match in progress and start a new one.
+	NewMatch,
+	// Change the score needed to win a match.
+	SetWinScore(u32),
+	// Change how long the ball waits before each serve, in milliseconds.
+	SetServeDelay(f32),
+	// Throw away the match in progress and start a new one in another game mode.
+	SetMode(#[serde(with = "crate::modes::by_name")] &'static dyn GameMode)
+}
+
+// Serve a ball that's just been reset from the middle of the court, heading away from the serving side at the given angle.
+fn serve(ball: &mut Object, server: Side, angle: f32) {
+	if server == Side::Right {
+		ball.velocity.x = -ball.velocity.x;
+	}
+	ball.serve(angle);
+}
+
+// Bounce a ball off whatever it hit, depending on whether that can be pushed around or not.
+// Returns the change in the other object's velocity, which is nothing for a paddle.
+fn bounce_off(ball: &mut Object, other: &ObjectCollider, velocity: Vec2, restitution: f32, inverse_mass: f32, normal: Vec2, rules: &Rules) -> Vec2 {
+	if inverse_mass > 0.0 {
+		bounce_off_body(ball, velocity, inverse_mass, restitution, normal)
+	} else {
+		bounce_off_paddle(ball, other, velocity, restitution, normal, rules);
+		Vec2::new(0.0, 0.0)
+	}
+}
+
+// Bounce a ball off the face of a paddle with the given normal.
+// Off the front, the ball is angled by where it hit, sped up or slowed down by the paddle's restitution, and some of the paddle's movement is carried over, so moving into the ball sends it off faster and at a sharper angle.
+// Hitting the top or bottom of the paddle deflects it vertically, and hitting a corner square on sends it back the way it came.
+// Either way, the paddle's grip on the ball changes its spin.
+fn bounce_off_paddle(ball: &mut Object, paddle: &ObjectCollider, paddle_velocity: Vec2, paddle_restitution: f32, normal: Vec2, rules: &Rules) {
+	ball.velocity = if normal.x != 0.0 && normal.y != 0.0 {
+		Vec2::new(-ball.velocity.x, -ball.velocity.y)
+	} else if normal.x != 0.0 {
+		let restitution = ball.restitution * paddle_restitution;
+		let mut velocity = collision::paddle_bounce(ball, paddle, rules.max_bounce_angle, restitution);
+		velocity.y += paddle_velocity.y * rules.paddle_momentum;
+		velocity
+	} else {
+		// Bounce off the paddle as it's moving, so a paddle catching up with the ball knocks it away rather than carrying it along.
+		collision::reflect(ball.velocity - paddle_velocity, normal) + paddle_velocity
+	};
+
+	grip(ball, normal, paddle_velocity, rules);
+	ball.clamp_velocity();
+}
+
+// Bounce a ball off another dynamic body, such as another ball, trading momentum between them by their masses.
+// The two get equal and opposite changes in momentum, so the lighter one changes speed more. Returns the change in the other body's velocity.
+fn bounce_off_body(ball: &mut Object, other_velocity: Vec2, other_inverse_mass: f32, other_restitution: f32, normal: Vec2) -> Vec2 {
+	let inverse_mass = ball.inverse_mass();
+	let total = inverse_mass + other_inverse_mass;
+	if total <= 0.0 {
+		return Vec2::new(0.0, 0.0);
+	}
+
+	let closing = (ball.velocity - other_velocity).dot(normal);
+	let restitution = ball.restitution * other_restitution;
+	let impulse = -(1.0 + restitution) * closing / total;
+
+	ball.velocity += normal * (impulse * inverse_mass);
+	ball.clamp_velocity();
+	normal * (-impulse * other_inverse_mass)
+}
+
+// Apply a surface's grip to a ball bouncing off it, trading some of its sliding for spin.
+// The grip can turn the ball but never slows it down, or turns it any steeper than the steepest bounce off a paddle.
+// Otherwise a ball grinding along the walls would slow down or end up bouncing straight up and down.
+fn grip(ball: &mut Object, normal: Vec2, surface_velocity: Vec2, rules: &Rules) {
+	if rules.spin_friction <= 0.0 {
+		return;
+	}
+
+	let speed = ball.velocity.length();
+	let direction = if ball.velocity.x < 0.0 { -1.0 } else { 1.0 };
+	let limit = math::atan2(ball.velocity.y, ball.velocity.x.abs()).abs().max(rules.max_bounce_angle);
+
+	let (velocity, spin) = collision::apply_friction(ball.velocity, ball.spin, normal, surface_velocity, rules.spin_friction);
+	let angle = math::atan2(velocity.y, velocity.x * direction).clamp(-limit, limit);
+	let (sin, cos) = math::sin_cos(angle);
+
+	ball.velocity = Vec2::new(direction * speed * cos, speed * sin);
+	ball.spin = spin;
+}
 
 // Create a struct representing our game state.
 // This will store object states, scores, etc, and be responsible for simulating each frame update.
 
+// Game states can be serialized to save a match, send it over the network, or compare it against a known state in tests.
+// Events waiting to be consumed aren't part of the saved state.
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameState {
+	// Rules for the match being played, saved by name.
+	#[serde(with = "crate::modes::by_name")]
+	pub mode: &'static dyn GameMode,
 	pub objects: Vec<Object>,
-	pub control_id: usize,
+	// Dimensions of the play area.
+	pub width: f32,
+	pub height: f32,
+	// Index of the object controlled by the player, if any. Every other paddle is controlled by the AI.
+	pub control_id: Option<usize>,
 	pub ai_accuracy: f32,
-	pub paused: bool
+	pub paused: bool,
+	// Points scored by each side, and the score needed to win a match.
+	pub scores: [u32; 2],
+	pub win_score: u32,
+	pub rules: Rules,
+	// Random number generator for everything random in the simulation. It starts from seed 0 so the simulation never depends on the wall clock, use set_seed to vary it.
+	pub rng: Rng,
+	// Time elapsed in the current match, in milliseconds.
+	pub elapsed: f32,
+	// Ticks simulated since the game started, used to time recorded commands.
+	#[serde(default)]
+	pub ticks: u64,
+	// Statistics for the current match.
+	#[serde(default)]
+	pub stats: MatchStats,
+	// How long the ball waits in the middle before each serve, in milliseconds, and how much of that wait is left.
+	// Only the paddles move while it's waiting.
+	#[serde(default)]
+	pub serve_delay: f32,
+	#[serde(default)]
+	pub serve_timer: f32,
+	// The side the ball is served from next, which is whoever conceded the last point. The left side serves first.
+	#[serde(default = "first_server")]
+	pub server: Side,
+	// Events emitted by the simulation, waiting to be consumed.
+	#[serde(skip)]
+	pub events: EventQueue,
+	// Time elapsed that hasn't been simulated yet, carried over to the next frame.
+	pub accumulator: f32,
+	// Every object's collider, and the rest of what a step needs to work out collisions, refilled in place each step rather than allocated afresh.
+	#[serde(skip)]
+	buffers: StepBuffers
+}
+
+// Implement the working space for a step's collisions, kept between steps so once it's grown to fit every object, a tick doesn't allocate.
+
+#[derive(Clone, Debug, Default)]
+struct StepBuffers {
+	bodies: Colliders,
+	grid: Grid,
+	// The objects near the one being moved, found in the grid.
+	nearby: Vec<usize>,
+	// The push and bounce each body is owed from collisions settled on the other body's turn, and which pairs of bodies they were.
+	pushes: Vec<Vec2>,
+	impulses: Vec<Vec2>,
+	settled: Vec<(usize, usize)>
+}
+
+// The side that serves at the start of a match, for game states saved before serves alternated.
+fn first_server() -> Side {
+	Side::Left
+}
+
+impl Default for GameState {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl GameState {
 	pub fn new() -> Self {
 		Self {
+			mode: &Classic,
 			objects: vec![],
-			control_id: 0,
+			width: 0.0,
+			height: 0.0,
+			control_id: None,
 			ai_accuracy: 0.5,
-			paused: true
+			paused: true,
+			scores: [0, 0],
+			win_score: 11,
+			rules: Rules::default(),
+			rng: Rng::new(0),
+			elapsed: 0.0,
+			ticks: 0,
+			stats: MatchStats::default(),
+			serve_delay: 0.0,
+			serve_timer: 0.0,
+			server: first_server(),
+			events: EventQueue::new(),
+			accumulator: 0.0,
+			buffers: StepBuffers::default()
 		}
 	}
 
-	// Event loop for game physics and simulation.
-	pub fn update(&mut self, delta_time: f32, width: f32, height: f32) {
+	// Build a game state set up for a match in the given game mode.
+	// The play area still needs to be sized with resize before simulating.
+	pub fn with_mode(mode: &'static dyn GameMode) -> Self {
+		let mut game = Self::new();
+		game.mode = mode;
+		mode.setup(&mut game);
+		game
+	}
+
+	// Advance the simulation by the time elapsed since the last frame.
+	// This runs as many fixed ticks as fit into the elapsed time, carrying the remainder over to the next frame.
+	pub fn advance(&mut self, delta_time: f32) {
+		// Time doesn't build up while the game is paused, so nothing carries over from before a pause when it resumes.
+		if self.paused {
+			self.accumulator = 0.0;
+			return;
+		}
+
+		// Drop any time beyond the maximum rather than simulating it all at once.
+		if delta_time > MAX_FRAME_TIME {
+			tracing::debug!("Frame took {:.0}ms, only simulating {:.0}ms of it", delta_time, MAX_FRAME_TIME);
+		}
+
+		self.accumulator += delta_time.clamp(0.0, MAX_FRAME_TIME);
+		while self.accumulator >= TICK_TIME {
+			self.update(TICK_TIME);
+			self.accumulator -= TICK_TIME;
+		}
+	}
+
+	// Run a number of fixed ticks immediately.
+	// This lets the simulation be stepped without a window or a clock, e.g. in tests or on a server.
+	pub fn step(&mut self, ticks: u32) {
+		for _ in 0..ticks {
+			self.update(TICK_TIME);
+		}
+	}
+
+	// How far the simulation is between the last tick and the next one, from 0.0 to 1.0.
+	// This is used to interpolate object positions when rendering.
+	pub fn alpha(&self) -> f32 {
+		self.accumulator / TICK_TIME
+	}
+
+	// Simulate a single tick of game physics.
+	pub fn update(&mut self, delta_time: f32) {
 		// Do not simulate if game is paused.
 		if self.paused {return};
 
-		// Build a list of colliders and track ball movement.
+		let _span = tracing::trace_span!("tick").entered();
+
+		// Paddles are moved directly by the AI or the player, so work out how fast each one moved since the last tick.
+		// Store where each object was at the start of this tick for render interpolation.
+		for obj in &mut self.objects {
+			if obj.obj_type != ObjectType::Ball && delta_time > 0.0 {
+				obj.velocity = (obj.position - obj.prev_position) * (1.0 / delta_time);
+			}
+			obj.prev_position = obj.position;
+		}
+
+		// Move everything in smaller steps if the ball is going fast enough to skip over a large part of a paddle in one go.
+		let substeps = self.substeps(delta_time);
+		for _ in 0..substeps {
+			self.integrate(delta_time / substeps as f32);
+		}
+
+		self.serve_timer = (self.serve_timer - delta_time).max(0.0);
+		self.elapsed += delta_time;
+		self.ticks += 1;
 
-		let mut colliders = vec![];
-		let mut ball_track: Option<(Vec2, Vec2,)> = None;
 		for obj in &self.objects {
 			if obj.obj_type == ObjectType::Ball {
-				ball_track = Some((obj.position, obj.velocity,));
+				self.stats.ball_speed(obj.velocity.length());
 			}
+		}
+
+		// Run the game mode's per-tick rules.
+		let mode = self.mode;
+		mode.tick(self);
+	}
+
+	// Work out how many steps to split a tick into, so no ball moves further than a fraction of a paddle's width in a single step.
+	fn substeps(&self, delta_time: f32) -> u32 {
+		let paddle_width = self.objects.iter()
+			.filter(|obj| obj.obj_type != ObjectType::Ball)
+			.map(|obj| obj.size.x)
+			.fold(f32::INFINITY, f32::min);
+
+		let distance = self.objects.iter()
+			.filter(|obj| obj.obj_type == ObjectType::Ball)
+			.map(|obj| obj.velocity.length() * delta_time)
+			.fold(0.0, f32::max);
+
+		let step_distance = paddle_width * SUBSTEP_FRACTION;
+		if distance > step_distance {
+			((distance / step_distance).ceil() as u32).min(MAX_SUBSTEPS)
+		} else {
+			1
+		}
+	}
+
+	// Move every object and resolve collisions over a single step.
+	fn integrate(&mut self, delta_time: f32) {
+		let (width, height, rules) = (self.width, self.height, self.rules);
+		let waiting_to_serve = self.serve_timer > 0.0;
+
+		// Update every object's collider from where it is now, and track ball movement.
+		// The buffers are taken out of the game state while it's being changed, and put back for the next step at the end.
+
+		let mut buffers = std::mem::take(&mut self.buffers);
+		buffers.bodies.update(&self.objects);
+		let StepBuffers { bodies, grid, nearby, pushes, impulses, settled } = &mut buffers;
+		let Colliders { colliders, velocities, restitutions, inverse_masses, sides } = &*bodies;
+		let ball_track = self.objects.iter()
+			.rev()
+			.find(|obj| obj.obj_type == ObjectType::Ball)
+			.map(|obj| (obj.position, obj.velocity));
 
-			colliders.push(obj.get_collider());
+		// File the colliders into a grid, so each ball is only checked against the objects near it.
+		let use_grid = colliders.len() >= BROADPHASE_MIN_OBJECTS;
+		if use_grid {
+			grid.rebuild(width, height, BROADPHASE_CELL_SIZE, colliders);
 		}
+		let find_nearby = |area: &ObjectCollider, found: &mut Vec<usize>| if use_grid {
+			grid.query_into(area, found);
+		} else {
+			found.clear();
+			found.extend(0..colliders.len());
+		};
+
+		// Track which side scored this tick, if any.
+		let mut scored: Option<Side> = None;
+
+		// When two dynamic bodies meet, whichever's turn comes first settles the collision for both of them.
+		// The other body's share of the push and bounce is saved here and applied once everything has moved, and the pair isn't checked again this step.
+		pushes.clear();
+		pushes.resize(colliders.len(), Vec2::new(0.0, 0.0));
+		impulses.clear();
+		impulses.resize(colliders.len(), Vec2::new(0.0, 0.0));
+		settled.clear();
 
 		// Behaviour & Logic Loop
 		for i in 0..self.objects.len() {
 			let obj = &mut self.objects[i];
-			let mut obj_collider = colliders[i];
+			let obj_collider = colliders[i];
+
+			// Balls stay where they are until they're served.
+			if waiting_to_serve && obj.obj_type == ObjectType::Ball {
+				continue;
+			}
 
 			// Handle simulation and physics for this object.
 
+			// Apply the forces on the ball before moving it.
+			// Gravity and wind accelerate it, and spin curves its flight and slowly wears off.
+			let acceleration = rules.gravity + rules.wind;
+			if obj.obj_type == ObjectType::Ball && (obj.spin != 0.0 || acceleration != Vec2::new(0.0, 0.0)) {
+				obj.velocity += acceleration * delta_time;
+				obj.velocity = collision::curve(obj.velocity, obj.spin, rules.spin_curve, delta_time);
+				obj.spin *= math::exp(-SPIN_DECAY * delta_time);
+				obj.clamp_velocity();
+			}
+
 			// How much to move the object by this frame.
-			let mut delta = Vec2 {
-				x: obj.velocity.x * delta_time,
-				y: obj.velocity.y * delta_time
+			// Kinematic bodies like paddles have already been moved by the AI or the player, their velocity is only a record of how fast they're going.
+			let mut delta = match obj.obj_type.body() {
+				Body::Dynamic => obj.velocity * delta_time,
+				Body::Kinematic => Vec2::new(0.0, 0.0)
 			};
 
 			match obj.obj_type {
@@ -60,54 +527,104 @@ impl GameState {
 					let center = obj.get_center();
 					// Check if ball is out of bounds.
 					if center.x < 0.0 || center.x > width {
-						// If it is, reset to its original position.
-						obj.reset(width, height);
+						// If it is, the side opposite the goal it went into scores, and the side that conceded serves next.
+						let side = if center.x < 0.0 { Side::Right } else { Side::Left };
+						scored = Some(side);
+						self.server = side.opponent();
+
+						// Reset to its original position.
+						obj.reset(width, height, &rules);
+						serve(obj, self.server, self.rng.range(-MAX_SERVE_ANGLE, MAX_SERVE_ANGLE));
 					} else {
-						// Check if next position update will cause a collision.
+						// If the ball has ended up inside a paddle, e.g. because the paddle moved into it, push it back out first.
+						// Pushing it out of one paddle can push it into another, so anything within a ball's width is checked too.
+						let around = ObjectCollider::from_bounds(obj_collider.min - obj.size, obj_collider.max + obj.size);
+						find_nearby(&around, nearby);
+						for &o in nearby.iter() {
+							let other = &colliders[o];
+							if o == i || !obj_collider.can_collide(other) || settled.contains(&(o, i)) {
+								continue;
+							}
 
-						if delta.x < 0.0 {
-							obj_collider.min += delta;
-						} else {
-							obj_collider.max += delta;
+							let collider = obj.get_collider();
+							if let Some(contact) = collider.contact(other) {
+								let normal = contact.normal;
+
+								// A paddle can't be pushed, so the ball has to move all the way out of it.
+								// Two dynamic bodies share the push by their masses.
+								let inverse_mass = obj.inverse_mass();
+								let share = if inverse_masses[o] > 0.0 { inverse_mass / (inverse_mass + inverse_masses[o]) } else { 1.0 };
+								obj.position += contact.push() * share;
+								if inverse_masses[o] > 0.0 {
+									pushes[o] += contact.push() * (share - 1.0);
+									settled.push((i, o));
+								}
+
+								// Only bounce if the ball and paddle are still closing in on each other.
+								// If the ball's already getting away, bouncing again would turn it back inside and leave it stuck.
+								if (obj.velocity - velocities[o]).dot(normal) < 0.0 {
+									impulses[o] += bounce_off(obj, other, velocities[o], restitutions[o], inverse_masses[o], normal, &rules);
+									delta = obj.velocity * delta_time;
+
+									if let Some(side) = sides[o] {
+										self.stats.ball_hit(side);
+										self.events.push(GameEvent::BallHitPaddle { side, point: contact.point });
+									}
+								}
+							}
 						}
 
-						// Check if ball will hit the horizontal edges of the screen.
-						if center.y < obj.size.y / 2.0 || center.y > height - obj.size.y / 2.0 {
-							// Flip y velocity.
-							obj.velocity.y = -obj.velocity.y;
-							delta.y = -(delta.y * 1.2);
+						// Check if ball will hit the horizontal edges of the screen, while still heading into them.
+						// A ball that's already bouncing away is left alone, or it would be flipped back into the wall on every step it overlaps it.
+						let hit_top = center.y < obj.size.y / 2.0 && obj.velocity.y < 0.0;
+						let hit_bottom = center.y > height - obj.size.y / 2.0 && obj.velocity.y > 0.0;
+						if hit_top || hit_bottom {
+							// Flip y velocity, losing some of it to the bounce, with the wall's grip on the ball changing its spin.
+							// Only the speed into the wall is lost, so the ball keeps its pace across the court.
+							obj.velocity.y = -obj.velocity.y * obj.restitution * rules.wall_restitution;
+
+							let normal = Vec2::new(0.0, if hit_top { 1.0 } else { -1.0 });
+							grip(obj, normal, Vec2::new(0.0, 0.0), &rules);
+							obj.clamp_velocity();
+							delta = obj.velocity * delta_time;
+
+							self.events.push(GameEvent::BallHitWall {
+								point: Vec2::new(center.x, if hit_top { 0.0 } else { height })
+							});
 						} else {
-							// Otherwise, iterate through each collider to check for a collision.
-							for o in 0..colliders.len() {
-								if o == i {
-									// Don't collide with self
-									continue;
+							// Otherwise, sweep the ball along its movement this tick to find the first paddle it hits.
+							// Checking the whole path rather than just where the ball ends up stops a fast ball from passing straight through a paddle.
+							let collider = obj.get_collider();
+							find_nearby(&broadphase::swept_bounds(&collider, delta), nearby);
+							let hit = nearby.iter()
+								.copied()
+								.filter(|&o| o != i && collider.can_collide(&colliders[o]) && !settled.contains(&(o, i)))
+								.filter_map(|o| collider.sweep(delta, &colliders[o]).map(|sweep| (o, sweep)))
+								.min_by(|(_, a), (_, b)| a.time.total_cmp(&b.time));
+
+							if let Some((o, sweep)) = hit {
+								// Move the ball up to the point of contact.
+								obj.position += delta * sweep.time;
+
+								// Bounce the ball off whichever face of the paddle it hit.
+								impulses[o] += bounce_off(obj, &colliders[o], velocities[o], restitutions[o], inverse_masses[o], sweep.normal, &rules);
+								if inverse_masses[o] > 0.0 {
+									settled.push((i, o));
 								}
 
-								// Check if this object is colliding with the ball.
-								let other = &colliders[o];
-								
-								if obj_collider.is_colliding(other) {
-									// Increase x velocity of the ball and flip it in the other direction.
-									obj.velocity.x = -(obj.velocity.x * 1.15).clamp(-obj.max_velocity.x, obj.max_velocity.x);
-
-									// Increase and flip y velocity based on where the ball hit the paddle.
-									// Ball travels upwards if it hit the upper half, and downwards if it hit the lower half.
-									// Velocity increases the further away from the center it was hit.
-									let angle = center.y - other.center.y;
-									let traj = ((angle.abs() * 2.0) / center.y).clamp(0.0, obj.max_velocity.y);
-									obj.velocity.y = if angle >= 0.0 { traj } else { -traj };
-
-									// Update position delta.
-									delta.x = -delta.x;
-									delta.y = -delta.y;
+								// Spend the rest of the tick moving away from the paddle.
+								delta = obj.velocity * (delta_time * (1.0 - sweep.time));
+
+								if let Some(side) = sides[o] {
+									self.stats.ball_hit(side);
+									self.events.push(GameEvent::BallHitPaddle { side, point: sweep.point });
 								}
 							}
 						}
 					}
 				},
-				// AI behaviour for non-controlled paddle.
-				ObjectType::PaddleLeft => if let Some(track) = ball_track {
+				// AI behaviour for non-controlled paddles.
+				ObjectType::PaddleLeft | ObjectType::PaddleRight if self.control_id != Some(i) => if let Some(track) = ball_track {
 					let (pos, vel) = track;
 
 					// Check if ball is moving towards this paddle.
@@ -120,26 +637,28 @@ impl GameState {
 					// Y co-ordinate to move towards, center of screen by default.
 					let mut y_tar = height / 2.0;
 
-					// Calculate y co-ordinate the ball will intercept at
+					// Calculate y co-ordinate the ball will intercept at.
+					// The AI doesn't work out the bounces, it just heads for the top or bottom quarter if the ball's going to hit a wall first.
 					if is_incoming {
-						let x_diff = obj_collider.center.x - pos.x;
-						let time = x_diff / vel.x;
-						let y_move = vel.y * time;
-
-						let mut y_pos = pos.y + y_move;
+						let mut y_pos = Trajectory::new(pos, vel, obj_collider.center.x).last().map_or(f32::NAN, |point| point.y);
 						if y_pos < 0.0 {
 							y_pos = height * 0.25;
 						} else if y_pos > height {
 							y_pos = height * 0.75;
 						}
-						y_tar = y_pos;
+
+						// A ball crawling along with no vertical speed predicts 0 * infinity, stay in the center instead.
+						if !y_pos.is_nan() {
+							y_tar = y_pos;
+						}
 					}
 
-					// Interpolate position towards target co-ordinate.
+					// Ease position towards target co-ordinate, closing the same share of the distance over the same time at any delta time.
 					// Accuracy affects the speed of this movement.
+					let ease = 1.0 - math::exp(-AI_TRACKING_RATE * self.ai_accuracy * delta_time);
 					obj.position.y = (obj.position.y + (
 						y_tar - (obj.size.y / 2.0) - obj.position.y
-					) * (delta_time * 0.00375 * self.ai_accuracy))
+					) * ease)
 					.clamp(0.0, height - obj.size.y);
 				},
 				_ => ()
@@ -147,26 +666,128 @@ impl GameState {
 
 			obj.position += delta;
 		}
+
+		// Finish off collisions settled by the other body's turn.
+		for (obj, (push, impulse)) in self.objects.iter_mut().zip(pushes.iter().zip(impulses.iter())) {
+			obj.position += *push;
+			if *impulse != Vec2::new(0.0, 0.0) {
+				obj.velocity += *impulse;
+				obj.clamp_velocity();
+			}
+		}
+		self.buffers = buffers;
+
+		if let Some(side) = scored {
+			self.serve_timer = self.serve_delay;
+			self.score(side);
+		}
+	}
+
+	// Award a point to the given side, ending the match if the game mode decides someone has won.
+	pub fn score(&mut self, side: Side) {
+		let mode = self.mode;
+		mode.point_scored(self, side);
+		self.stats.point_scored(side);
+		self.events.push(GameEvent::PointScored { side, scores: self.scores });
+		tracing::debug!(?side, scores = ?self.scores, "Point scored");
+
+		if let Some(winner) = mode.winner(self) {
+			let stats = MatchStats { duration: self.elapsed, ..self.stats.clone() };
+			self.events.push(GameEvent::MatchEnded { winner, scores: self.scores, stats });
+			tracing::info!(?winner, scores = ?self.scores, mode = self.mode.name(), "Match ended");
+
+			self.reset_match();
+		}
+	}
+
+	// Start a new match, clearing the scores and statistics.
+	pub fn reset_match(&mut self) {
+		self.scores = [0, 0];
+		self.server = first_server();
+		self.elapsed = 0.0;
+		self.stats = MatchStats::default();
+	}
+
+	// Apply a change from outside the simulation.
+	pub fn apply(&mut self, command: Command) {
+		match command {
+			Command::MovePaddle { y } => {
+				let height = self.height;
+				if let Some(control_obj) = self.get_control() {
+					control_obj.position.y = (y - (control_obj.size.y / 2.0)).clamp(0.0, (height - control_obj.size.y).max(0.0));
+				}
+			},
+			Command::Resize { width, height } => self.resize(width, height),
+			Command::SetRules(rules) => self.set_rules(rules),
+			Command::SetAiAccuracy(accuracy) => self.ai_accuracy = accuracy,
+			Command::NewMatch => {
+				self.reset_match();
+				self.reset_objects();
+				self.events.push(GameEvent::MatchStarted);
+			},
+			Command::SetWinScore(score) => self.win_score = score,
+			Command::SetServeDelay(delay) => self.serve_delay = delay,
+			Command::SetMode(mode) => {
+				self.mode = mode;
+				mode.setup(self);
+				self.reset_match();
+				self.reset_objects();
+				self.events.push(GameEvent::MatchStarted);
+			}
+		}
+	}
+
+	// Resize the play area.
+	// All objects are reset to their initial positions, which prevents unintended behaviour when the window resizes.
+	pub fn resize(&mut self, width: f32, height: f32) {
+		self.width = width;
+		self.height = height;
+		self.reset_objects();
 	}
 
-	// Reset all objects to their starting state.
-	pub fn reset_objects(&mut self, width: f32, height: f32) {
+	// Reset all objects to their starting state, waiting to serve the ball again.
+	pub fn reset_objects(&mut self) {
+		self.serve_timer = self.serve_delay;
 		for obj in &mut self.objects {
-			obj.reset(width, height);
+			obj.reset(self.width, self.height, &self.rules);
+			if obj.obj_type == ObjectType::Ball {
+				serve(obj, self.server, self.rng.range(-MAX_SERVE_ANGLE, MAX_SERVE_ANGLE));
+			}
 		}
 	}
 
-	// Get player-controlled object.
-	pub fn get_control(&mut self) -> &mut Object {
-		&mut self.objects[self.control_id]
+	// Reseed the random number generator, making the rest of the match reproducible.
+	pub fn set_seed(&mut self, seed: u64) {
+		self.rng = Rng::new(seed);
+	}
+
+	// Change the rules of the match.
+	// Paddles are resized straight away, the ball's new speed applies from its next serve.
+	pub fn set_rules(&mut self, rules: Rules) {
+		self.rules = rules;
+
+		for obj in &mut self.objects {
+			if obj.obj_type != ObjectType::Ball {
+				let center = obj.get_center();
+				obj.size.y = rules.paddle_height(self.height);
+				obj.restitution = rules.paddle_restitution;
+				obj.position.y = center.y - (obj.size.y / 2.0);
+			}
+		}
 	}
 
-	// Pause or unpause the game.
-	pub fn pause(&mut self, display: &Display, pause: bool) {
-		let gl_window = display.gl_window();
-		let window = gl_window.window();
-		window.set_cursor_grab(!pause).ok();
-		window.set_cursor_visible(pause);
-		self.paused = pause;
+	// Get the sides one point away from winning the match.
+	pub fn match_point(&self) -> Vec<Side> {
+		[Side::Left, Side::Right].into_iter().filter(|side| self.mode.match_point(self, *side)).collect()
+	}
+
+	// Get which side the player is playing on, if there's a player at all.
+	pub fn control_side(&self) -> Option<Side> {
+		self.objects.get(self.control_id?)?.obj_type.side()
+	}
+
+	// Get player-controlled object.
+	pub fn get_control(&mut self) -> Option<&mut Object> {
+		self.objects.get_mut(self.control_id?)
 	}
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+// Dependencies
+
+use crate::input::{Input, Key};
+
+// How far the stick has to be pushed to move through a menu, and how far back towards the middle it has to come before it can move again.
+
+pub const STICK_PUSH: f32 = 0.6;
+pub const STICK_RELEASE: f32 = 0.3;
+
+// Implement the gamepad buttons the game responds to, named by where they sit rather than what's printed on them, since that differs between brands.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Button {
+	// The face buttons, by compass direction.
+	South,
+	East,
+	West,
+	North,
+	DPadUp,
+	DPadDown,
+	DPadLeft,
+	DPadRight,
+	Start,
+	Select
+}
+
+impl Button {
+	// Get the game key a button stands for.
+	// The bottom face button confirms and the right one goes back, the usual layout on most controllers.
+	pub fn key(self) -> Option<Key> {
+		match self {
+			Button::South => Some(Key::Enter),
+			Button::East | Button::Start => Some(Key::Escape),
+			Button::DPadUp => Some(Key::Up),
+			Button::DPadDown => Some(Key::Down),
+			Button::DPadLeft => Some(Key::Left),
+			Button::DPadRight => Some(Key::Right),
+			Button::Select => Some(Key::Scores),
+			Button::West | Button::North => None
+		}
+	}
+}
+
+// Implement the translation from a gamepad to the inputs scenes respond to, so every menu works with a controller the same as with the keyboard.
+// Whichever library reads the controller passes on button presses and stick positions here.
+
+#[derive(Clone, Default, Debug)]
+pub struct Gamepad {
+	// The direction the stick was last pushed in for the menus, until it comes back towards the middle.
+	held: Option<Key>
+}
+
+impl Gamepad {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Translate a button press.
+	pub fn press(&mut self, button: Button) -> Option<Input> {
+		button.key().map(Input::KeyPressed)
+	}
+
+	// Translate the left stick moving, from -1.0 to 1.0 on each axis with up being negative, the same way round as the window.
+	// The stick always steers the paddle, and pushing it far enough in a direction moves through menus once, like a d-pad press.
+	pub fn stick(&mut self, x: f32, y: f32) -> Vec<Input> {
+		let mut inputs = vec![Input::StickMoved { y: y.clamp(-1.0, 1.0) }];
+
+		// Only the main direction counts, so a diagonal push doesn't move both ways at once.
+		let (distance, key) = if x.abs() > y.abs() {
+			(x.abs(), if x < 0.0 { Key::Left } else { Key::Right })
+		} else {
+			(y.abs(), if y < 0.0 { Key::Up } else { Key::Down })
+		};
+
+		if distance < STICK_RELEASE {
+			self.held = None;
+		} else if distance >= STICK_PUSH && self.held != Some(key) {
+			self.held = Some(key);
+			inputs.push(Input::KeyPressed(key));
+		}
+		inputs
+	}
+}
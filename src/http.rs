@@ -0,0 +1,125 @@
+// Dependencies
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+// How long a client has to send its whole request, and how long the longest line of it can be, in bytes.
+// The servers answer one request at a time, so a slow or endless request would hold up everyone else.
+
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+pub const MAX_LINE_LENGTH: usize = 8 * 1024;
+pub const MAX_HEADERS: usize = 64;
+
+// Implement a request as far as the servers need it. Bodies are never read.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Request {
+	pub method: String,
+	pub path: String,
+	pub headers: Vec<(String, String)>
+}
+
+impl Request {
+	// Get the value of the first header with the given name, ignoring case.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+	}
+}
+
+// Implement the answer to a request.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Response {
+	// The status line, without the HTTP version.
+	pub status: &'static str,
+	pub content_type: &'static str,
+	pub body: String
+}
+
+impl Response {
+	pub fn new(status: &'static str, content_type: &'static str, body: String) -> Self {
+		Self { status, content_type, body }
+	}
+
+	// Make a plain text response.
+	pub fn text(status: &'static str, body: impl Into<String>) -> Self {
+		Self::new(status, "text/plain", body.into())
+	}
+}
+
+// Implement a reader that gives up once a deadline's passed, however slowly the bytes trickle in.
+// A read timeout alone only limits the gap between bytes.
+
+struct Deadline {
+	stream: TcpStream,
+	deadline: Instant
+}
+
+impl Read for Deadline {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let remaining = self.deadline.saturating_duration_since(Instant::now());
+		if remaining.is_zero() {
+			return Err(io::Error::new(io::ErrorKind::TimedOut, "request took too long"));
+		}
+		self.stream.set_read_timeout(Some(remaining))?;
+		self.stream.read(buf)
+	}
+}
+
+// Read a line without its line ending, or None if it's longer than MAX_LINE_LENGTH.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+	let mut line = Vec::new();
+	reader.take(MAX_LINE_LENGTH as u64 + 1).read_until(b'\n', &mut line)?;
+	if line.len() > MAX_LINE_LENGTH {
+		return Ok(None);
+	}
+	Ok(Some(String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']).to_string()))
+}
+
+// Read a request's line and headers, or the error to answer with if they're malformed or too big.
+pub fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+	let too_large = || Response::text("431 Request Header Fields Too Large", "Request too large\n");
+
+	let Some(line) = read_line(reader)? else {
+		return Ok(Err(too_large()));
+	};
+	let mut request = match line.split_whitespace().take(2).collect::<Vec<_>>()[..] {
+		[method, path] => Request { method: method.to_string(), path: path.to_string(), headers: vec![] },
+		_ => return Ok(Err(Response::text("400 Bad Request", "Bad request\n")))
+	};
+
+	// The headers end at the first blank line, or when the client stops sending.
+	loop {
+		let Some(line) = read_line(reader)? else {
+			return Ok(Err(too_large()));
+		};
+		if line.trim().is_empty() {
+			break;
+		}
+		if request.headers.len() == MAX_HEADERS {
+			return Ok(Err(too_large()));
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			request.headers.push((name.trim().to_string(), value.trim().to_string()));
+		}
+	}
+	Ok(Ok(request))
+}
+
+// Answer a single request on a connection with the given handler, then close it.
+// Malformed and oversized requests are answered without reaching the handler.
+pub fn respond(stream: TcpStream, handler: impl FnOnce(&Request) -> Response) -> io::Result<()> {
+	let deadline = Instant::now() + REQUEST_TIMEOUT;
+	stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+	let mut reader = BufReader::new(Deadline { stream, deadline });
+
+	let response = match read_request(&mut reader)? {
+		Ok(request) => handler(&request),
+		Err(response) => response
+	};
+
+	let mut stream = reader.into_inner().stream;
+	write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", response.status, response.content_type, response.body.len(), response.body)?;
+	stream.flush()
+}
@@ -0,0 +1,97 @@
+// Implement the input events that scenes respond to.
+// The window layer translates its own events into these, so scenes don't depend on any particular windowing library.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Key {
+	Escape,
+	Enter,
+	Space,
+	Up,
+	Down,
+	Left,
+	Right,
+	// Show the high scores.
+	Scores,
+	// Save a screenshot of the window.
+	Screenshot,
+	// Save the last few seconds of the game as a GIF.
+	Clip,
+	// Start or stop recording a video of the game.
+	Record
+}
+
+impl Key {
+	// Every key the game responds to.
+	pub const ALL: [Key; 11] = [Key::Escape, Key::Enter, Key::Space, Key::Up, Key::Down, Key::Left, Key::Right, Key::Scores, Key::Screenshot, Key::Clip, Key::Record];
+
+	// Name of this key as used in the config file.
+	pub fn name(self) -> &'static str {
+		match self {
+			Key::Escape => "escape",
+			Key::Enter => "enter",
+			Key::Space => "space",
+			Key::Up => "up",
+			Key::Down => "down",
+			Key::Left => "left",
+			Key::Right => "right",
+			Key::Scores => "scores",
+			Key::Screenshot => "screenshot",
+			Key::Clip => "clip",
+			Key::Record => "record"
+		}
+	}
+
+	// Look up a key by its config file name.
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|key| key.name() == name)
+	}
+}
+
+// Names of the keyboard keys that can be bound to game keys, the same as winit's VirtualKeyCode names.
+
+pub const KEYBOARD_KEYS: [&str; 163] = [
+	"Key1", "Key2", "Key3", "Key4", "Key5", "Key6", "Key7", "Key8", "Key9", "Key0", "A", "B",
+	"C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N",
+	"O", "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z",
+	"Escape", "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11",
+	"F12", "F13", "F14", "F15", "F16", "F17", "F18", "F19", "F20", "F21", "F22", "F23",
+	"F24", "Snapshot", "Scroll", "Pause", "Insert", "Home", "Delete", "End", "PageDown", "PageUp", "Left", "Up",
+	"Right", "Down", "Back", "Return", "Space", "Compose", "Caret", "Numlock", "Numpad0", "Numpad1", "Numpad2", "Numpad3",
+	"Numpad4", "Numpad5", "Numpad6", "Numpad7", "Numpad8", "Numpad9", "NumpadAdd", "NumpadDivide", "NumpadDecimal", "NumpadComma", "NumpadEnter", "NumpadEquals",
+	"NumpadMultiply", "NumpadSubtract", "AbntC1", "AbntC2", "Apostrophe", "Apps", "Asterisk", "At", "Ax", "Backslash", "Calculator", "Capital",
+	"Colon", "Comma", "Convert", "Equals", "Grave", "Kana", "Kanji", "LAlt", "LBracket", "LControl", "LShift", "LWin",
+	"Mail", "MediaSelect", "MediaStop", "Minus", "Mute", "MyComputer", "NavigateForward", "NavigateBackward", "NextTrack", "NoConvert", "OEM102", "Period",
+	"PlayPause", "Plus", "Power", "PrevTrack", "RAlt", "RBracket", "RControl", "RShift", "RWin", "Semicolon", "Slash", "Sleep",
+	"Stop", "Sysrq", "Tab", "Underline", "Unlabeled", "VolumeDown", "VolumeUp", "Wake", "WebBack", "WebFavorites", "WebForward", "WebHome",
+	"WebRefresh", "WebSearch", "WebStop", "Yen", "Copy", "Paste", "Cut"
+];
+
+// Check if a name is one of the keyboard keys that can be bound.
+pub fn is_keyboard_key(name: &str) -> bool {
+	KEYBOARD_KEYS.contains(&name)
+}
+
+// Look up a keyboard key by its name, if it's one that can be bound.
+pub fn keyboard_key(name: &str) -> Option<&'static str> {
+	KEYBOARD_KEYS.iter().copied().find(|key| *key == name)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Input {
+	// A key was pressed.
+	KeyPressed(Key),
+	// A key on the keyboard was pressed, by its name in KEYBOARD_KEYS, whether or not it's bound to anything.
+	// This comes just before the KeyPressed for the game key it's bound to, and is only needed to rebind keys.
+	KeyboardKey(&'static str),
+	// A mouse button was pressed, or let go of.
+	MousePressed,
+	MouseReleased,
+	// The cursor moved to the given position in the window.
+	MouseMoved { x: f32, y: f32 },
+	// A gamepad's stick moved up or down, from -1.0 at the top to 1.0 at the bottom.
+	StickMoved { y: f32 },
+	// Hardware like a fader at an installation set the paddle's position, from 0.0 at the top to 1.0 at the bottom.
+	PaddleMoved { y: f32 },
+	// The window gained or lost focus.
+	Focused(bool)
+}
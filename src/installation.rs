@@ -0,0 +1,195 @@
+// Dependencies
+
+use std::fs::File;
+use std::io::Read;
+use std::net::{UdpSocket, Ipv4Addr};
+use std::sync::mpsc::{self, Sender, Receiver};
+use std::thread;
+
+use crate::config::InstallationConfig;
+use crate::input::Input;
+
+// Read an OSC string from the start of some bytes, returning it and the bytes after it.
+// Strings end with a zero byte and are padded with more to a multiple of 4 bytes.
+fn osc_string(bytes: &[u8]) -> Option<(&str, &[u8])> {
+	let end = bytes.iter().position(|byte| *byte == 0)?;
+	let text = std::str::from_utf8(&bytes[..end]).ok()?;
+	let padded = (end / 4 + 1) * 4;
+	Some((text, bytes.get(padded..)?))
+}
+
+// Read a big-endian number of the given size from the start of some bytes, returning the bytes after it.
+fn osc_bytes<const N: usize>(bytes: &[u8]) -> Option<([u8; N], &[u8])> {
+	let value = bytes.get(..N)?.try_into().ok()?;
+	Some((value, &bytes[N..]))
+}
+
+// Get the value sent to the given address in an OSC packet, if there is one, from 0.0 to 1.0.
+// The value is the message's first argument, which can be a float, a double or an integer.
+// Bundles are searched for the address, and the last value sent to it wins.
+pub fn parse_osc(packet: &[u8], address: &str) -> Option<f32> {
+	let (name, rest) = osc_string(packet)?;
+
+	if name == "#bundle" {
+		// Skip the time tag, then go through the elements, each with its size first.
+		let (_, mut rest) = osc_bytes::<8>(rest)?;
+		let mut value = None;
+		while !rest.is_empty() {
+			let (size, after) = osc_bytes::<4>(rest)?;
+			let size = u32::from_be_bytes(size) as usize;
+			value = parse_osc(after.get(..size)?, address).or(value);
+			rest = &after[size..];
+		}
+		return value;
+	}
+
+	if name != address {
+		return None;
+	}
+
+	let (tags, rest) = osc_string(rest)?;
+	let value = match tags.strip_prefix(',')?.chars().next()? {
+		'f' => f32::from_be_bytes(osc_bytes::<4>(rest)?.0),
+		'd' => f64::from_be_bytes(osc_bytes::<8>(rest)?.0) as f32,
+		'i' => i32::from_be_bytes(osc_bytes::<4>(rest)?.0) as f32,
+		_ => return None
+	};
+	value.is_finite().then_some(value.clamp(0.0, 1.0))
+}
+
+// Implement a reader for the bytes coming from a MIDI device, picking out control change messages, e.g. from a knob or fader.
+// Devices can leave out the status byte when it's the same as the last message's, so it's remembered between messages.
+
+#[derive(Clone, Default, Debug)]
+pub struct MidiParser {
+	// The status byte of the message being read, if it's a control change.
+	status: Option<u8>,
+	// The controller number, once it's been read.
+	controller: Option<u8>
+}
+
+impl MidiParser {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Read the next byte, returning the channel, controller number and value once a whole control change has been read.
+	pub fn feed(&mut self, byte: u8) -> Option<(u8, u8, u8)> {
+		match byte {
+			// Timing and other real-time messages can turn up in the middle of any other message, and don't affect it.
+			0xF8..=0xFF => None,
+			// Any other status byte starts a new message, which is only followed if it's a control change.
+			0x80..=0xF7 => {
+				self.status = (byte & 0xF0 == 0xB0).then_some(byte);
+				self.controller = None;
+				None
+			},
+			_ => {
+				let status = self.status?;
+				match self.controller.take() {
+					None => {
+						self.controller = Some(byte);
+						None
+					},
+					Some(controller) => Some((status & 0x0F, controller, byte))
+				}
+			}
+		}
+	}
+}
+
+// Implement inputs from hardware at installations and events, like knobs and faders, which set the paddle's position.
+// Positions are sent over the network as OSC messages, or read from a MIDI device as control changes, each on its own thread.
+// A value of 0.0, or 0 over MIDI, puts the paddle at the bottom, so pushing a fader up moves the paddle up.
+
+pub struct Installation {
+	receiver: Receiver<f32>
+}
+
+impl Installation {
+	// Start listening for whatever the config asks for, or nothing if it doesn't ask for anything.
+	// Anything that can't be opened is reported and left out, so a missing device doesn't stop the game.
+	pub fn start(config: &InstallationConfig) -> Option<Self> {
+		if config.osc_port == 0 && config.midi_device.is_empty() {
+			return None;
+		}
+
+		let (sender, receiver) = mpsc::channel();
+
+		if config.osc_port != 0 {
+			match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, config.osc_port)) {
+				Ok(socket) => {
+					tracing::info!("Listening for OSC messages to {} on port {}", config.osc_address, config.osc_port);
+					listen_osc(socket, config.osc_address.clone(), sender.clone());
+				},
+				Err(err) => tracing::warn!("Failed to listen for OSC messages on port {}: {}", config.osc_port, err)
+			}
+		}
+
+		if !config.midi_device.is_empty() {
+			match File::open(&config.midi_device) {
+				Ok(device) => {
+					tracing::info!("Reading MIDI controller {} from {}", config.midi_controller, config.midi_device);
+					read_midi(device, config.midi_controller, sender);
+				},
+				Err(err) => tracing::warn!("Failed to open MIDI device {}: {}", config.midi_device, err)
+			}
+		}
+
+		Some(Self { receiver })
+	}
+
+	// Get where the paddle was last sent since this was last asked, if anywhere.
+	pub fn poll(&self) -> Option<Input> {
+		self.receiver.try_iter().last().map(|value| Input::PaddleMoved { y: 1.0 - value })
+	}
+}
+
+// Pass on every value sent to the given address, until the game exits.
+fn listen_osc(socket: UdpSocket, address: String, sender: Sender<f32>) {
+	thread::spawn(move || {
+		let mut packet = [0; 1536];
+		loop {
+			match socket.recv(&mut packet) {
+				Ok(size) => {
+					if let Some(value) = parse_osc(&packet[..size], &address) {
+						if sender.send(value).is_err() {
+							return;
+						}
+					}
+				},
+				Err(err) => {
+					tracing::warn!("Stopped listening for OSC messages: {}", err);
+					return;
+				}
+			}
+		}
+	});
+}
+
+// Pass on every change to the given controller, on any channel, until the device is unplugged or the game exits.
+fn read_midi(mut device: File, controller: u8, sender: Sender<f32>) {
+	thread::spawn(move || {
+		let mut parser = MidiParser::new();
+		let mut bytes = [0; 64];
+		loop {
+			let size = match device.read(&mut bytes) {
+				Ok(0) => return,
+				Ok(size) => size,
+				Err(err) => {
+					tracing::warn!("Stopped reading the MIDI device: {}", err);
+					return;
+				}
+			};
+
+			for byte in &bytes[..size] {
+				if let Some((_, number, value)) = parser.feed(*byte).filter(|(_, number, _)| *number == controller) {
+					tracing::trace!(number, value, "MIDI control change");
+					if sender.send(value as f32 / 127.0).is_err() {
+						return;
+					}
+				}
+			}
+		}
+	});
+}
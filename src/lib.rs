@@ -0,0 +1,60 @@
+// Pong library crate.
+// This holds the game simulation (game state, physics and object structs) independently of the window, so it can be tested, benchmarked and reused by other binaries.
+// Nothing here needs a display or GPU, so the simulation can be stepped headlessly with the default features disabled.
+
+pub mod structs;
+pub mod game;
+pub mod prediction;
+pub mod collision;
+pub mod broadphase;
+pub mod events;
+pub mod stats;
+pub mod modes;
+pub mod rng;
+pub mod math;
+pub mod clock;
+pub mod paths;
+pub mod config;
+pub mod scores;
+pub mod profiles;
+pub mod cosmetics;
+pub mod assets;
+pub mod locale;
+pub mod sim;
+pub mod replay;
+pub mod telemetry;
+pub mod http;
+pub mod metrics;
+pub mod clip;
+pub mod video;
+pub mod pacing;
+pub mod save;
+pub mod input;
+pub mod gamepad;
+pub mod rumble;
+pub mod cursor;
+pub mod touch;
+pub mod installation;
+pub mod viewport;
+pub mod scene;
+pub mod text;
+pub mod theme;
+pub mod particles;
+pub mod toast;
+pub mod callout;
+pub mod scoreboard;
+pub mod tutorial;
+pub mod widget;
+pub mod scenes;
+
+#[cfg(feature = "scripting")]
+pub mod scripting;
+
+#[cfg(feature = "terminal")]
+pub mod terminal;
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+#[cfg(feature = "gamepad")]
+pub mod controllers;
@@ -0,0 +1,115 @@
+// Dependencies
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::assets::Assets;
+
+// The language built into the game, which every other language falls back to.
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+// Implement the text shown to the player in a given language, looked up by key.
+// Each language is a TOML file of strings in the locales folder of the assets, so languages can be added or changed by overriding them.
+// Tables in the file group the keys, e.g. `title` in the `[profiles]` table is looked up as `profiles.title`.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Locale {
+	language: String,
+	strings: HashMap<String, String>,
+	// The strings in the default language, for anything the language is missing.
+	fallback: HashMap<String, String>
+}
+
+impl Default for Locale {
+	// The default language, using the strings built into the game.
+	fn default() -> Self {
+		let fallback = parse(include_str!("./locales/en.toml")).unwrap_or_default();
+		Self {
+			language: DEFAULT_LANGUAGE.to_string(),
+			strings: HashMap::new(),
+			fallback
+		}
+	}
+}
+
+impl Locale {
+	// Load a language from the assets, falling back to the default language for anything it doesn't have.
+	// A language that can't be found or read is reported, and the default language is used instead.
+	pub fn load(assets: &mut Assets, language: &str) -> Self {
+		let mut locale = Self::default();
+		if let Some(strings) = load_strings(assets, DEFAULT_LANGUAGE) {
+			locale.fallback = strings;
+		}
+
+		if language != DEFAULT_LANGUAGE {
+			match load_strings(assets, language) {
+				Some(strings) => {
+					locale.language = language.to_string();
+					locale.strings = strings;
+				},
+				None => tracing::warn!("Language '{}' isn't available, using '{}'", language, DEFAULT_LANGUAGE)
+			}
+		}
+		locale
+	}
+
+	// Build a language from the text of its file, falling back to the built-in default language.
+	pub fn from_text(language: &str, text: &str) -> Result<Self, String> {
+		Ok(Self {
+			language: language.to_string(),
+			strings: parse(text)?,
+			..Self::default()
+		})
+	}
+
+	// The name of the language in use, e.g. "en".
+	pub fn language(&self) -> &str {
+		&self.language
+	}
+
+	// Get the string with the given key.
+	// A key missing from every language is shown as it is, so it's obvious something needs adding.
+	pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+		self.strings.get(key)
+			.or_else(|| self.fallback.get(key))
+			.map(String::as_str)
+			.unwrap_or(key)
+	}
+
+	// Get the string with the given key, filling in each `{name}` with the value given for it.
+	pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+		args.iter().fold(self.get(key).to_string(), |text, (name, value)| {
+			text.replace(&format!("{{{}}}", name), &value.to_string())
+		})
+	}
+}
+
+// Load the strings for a language from the assets, reporting a file that can't be parsed.
+fn load_strings(assets: &mut Assets, language: &str) -> Option<HashMap<String, String>> {
+	let key = format!("locales/{}.toml", language);
+	let text = assets.text(&key)?;
+	parse(&text).map_err(|err| tracing::warn!("Invalid language file {}: {}", key, err)).ok()
+}
+
+// Parse a language file into strings by key, flattening tables into dotted keys.
+fn parse(text: &str) -> Result<HashMap<String, String>, String> {
+	let table: toml::value::Table = toml::from_str(text).map_err(|err| err.to_string())?;
+	let mut strings = HashMap::new();
+	flatten(&mut strings, "", &table);
+	Ok(strings)
+}
+
+fn flatten(strings: &mut HashMap<String, String>, prefix: &str, table: &toml::value::Table) {
+	for (name, value) in table {
+		let key = if prefix.is_empty() { name.clone() } else { format!("{}.{}", prefix, name) };
+		match value {
+			toml::Value::String(text) => {
+				strings.insert(key, text.clone());
+			},
+			toml::Value::Table(table) => flatten(strings, &key, table),
+			// Anything else isn't text, so there's nothing to show.
+			_ => tracing::warn!("Language string {} isn't text", key)
+		}
+	}
+}
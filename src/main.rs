@@ -3,6 +3,10 @@
 // Imports from standard rustc libraries.
 // https://docs.rs/rustc-std-workspace-std/1.0.1/std/index.html
 
+use std::io;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
 use std::time::{Instant, Duration};
 
 // Glium is the library being used as an OpenGL wrapper.
@@ -10,8 +14,7 @@ use std::time::{Instant, Duration};
 
 use glium::{
 	Program,
-	Display, Surface,
-	uniform
+	Display, Surface
 };
 
 // Glutin is the library used by Glium for OpenGL context creation.
@@ -20,205 +23,1207 @@ use glium::{
 use glium::glutin::{
 	event,
 	event_loop::{EventLoop, ControlFlow},
-	window::WindowBuilder,
+	window::{Window, WindowBuilder},
+	dpi::{LogicalSize, PhysicalPosition},
 	ContextBuilder
 };
 
+// Tracing subscriber, used to print logs filtered by the RUST_LOG environment variable.
+// https://crates.io/crates/tracing-subscriber
+
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
 use glium::glutin::event::{
-	VirtualKeyCode as Key,
+	VirtualKeyCode,
 	ElementState as KeyState
 };
 
-// Import structs.rs from codebase
+// Import the game simulation from the pong library crate.
+
+use pong::game::{GameState, TICK_RATE, TICK_TIME};
+use pong::events::Side;
+use pong::sim::Simulation;
+use pong::events::GameEvent;
+use pong::input::{self, Input, Key};
+use pong::cursor::{CursorGrab, GrabMode, Platform};
+use pong::viewport::{Viewport, Insets};
+use pong::touch::Touches;
+use pong::installation::Installation;
+use pong::config::{Config, ConfigWatcher};
+use pong::scores::HighScores;
+use pong::profiles::Profiles;
+use pong::cosmetics::{self, PADDLE_SKINS, BALL_SKINS};
+use pong::assets::Assets;
+use pong::locale::Locale;
+use pong::theme::{self, Date};
+use pong::rng::Rng;
+use pong::scene::{Context, SceneManager};
+use pong::scenes::{MainMenuScene, ReplayScene, ResumeScene, ProfileScene};
+use pong::save::SavedMatch;
+use pong::replay::Replay;
+use pong::telemetry::Telemetry;
+use pong::metrics::{Metrics, MetricsServer};
+use pong::clip::ClipRecorder;
+use pong::video::VideoRecorder;
+use pong::pacing::{FramePacer, FRAME_RATE, BACKGROUND_FRAME_RATE};
+use pong::paths;
+use pong::clock::SystemClock;
+
+#[cfg(feature = "remote")]
+use std::net::Ipv4Addr;
+#[cfg(feature = "remote")]
+use pong::remote::RemoteServer;
+
+#[cfg(feature = "gamepad")]
+use pong::controllers::Controllers;
+
+#[cfg(feature = "scripting")]
+use pong::scripting::Scripts;
+#[cfg(feature = "scripting")]
+use pong::game::Command;
+
+// Import render.rs from codebase
+
+mod render;
+use render::{Batch, GliumRenderer, FrameUniforms};
+
+// Import cli.rs from codebase
+
+mod cli;
+use cli::Args;
+use clap::Parser;
+
+// Import scoreboard_window.rs from codebase
+
+mod scoreboard_window;
+use scoreboard_window::ScoreboardWindow;
+
+// Import screenshot.rs from codebase
+
+mod screenshot;
+
+// Import crash.rs from codebase
+
+mod crash;
+use crash::RecentLogs;
+
+// Import error.rs from codebase
+
+mod error;
+use error::{Error, exit_with};
+
+// Import debug_ui.rs from codebase, if the debug panel is enabled.
+
+#[cfg(feature = "debug-ui")]
+mod debug_ui;
+#[cfg(feature = "debug-ui")]
+use debug_ui::DebugUi;
+
+// How many frames in a row need to have nothing moving before the game stops drawing every frame, which gives scenes a frame to react to being paused.
+// While idle, anything feeding the game from outside the window is still checked this often.
+
+const IDLE_AFTER_FRAMES: u32 = 2;
+const IDLE_POLL_TIME: Duration = Duration::from_millis(250);
+
+// Translate a key from the window into a key the game responds to, using the keybinds from the config.
+// Keybinds refer to keys by their VirtualKeyCode name.
+
+fn map_key(config: &Config, key: VirtualKeyCode) -> Option<Key> {
+	config.key_for(&format!("{:?}", key))
+}
+
+// Work out which kind of windowing system the window is on, which decides how the cursor can be held inside it.
+
+#[cfg(target_os = "macos")]
+fn cursor_platform(_window: &Window) -> Platform {
+	Platform::MacOs
+}
+
+#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+fn cursor_platform(window: &Window) -> Platform {
+	use glium::glutin::platform::unix::WindowExtUnix;
+
+	if window.wayland_surface().is_some() {
+		Platform::Wayland
+	} else {
+		Platform::Other
+	}
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+fn cursor_platform(_window: &Window) -> Platform {
+	Platform::Other
+}
+
+// Get the middle of the play area, where the cursor is moved back to while recentering.
+
+fn window_centre(viewport: &Viewport) -> PhysicalPosition<f64> {
+	let centre = viewport.to_physical(viewport.size() / 2.0);
+	PhysicalPosition::new(centre.x as f64, centre.y as f64)
+}
+
+// Hold the cursor inside the window, trying each way the platform offers until one works.
+// The window's grab locks the cursor in place on macOS and keeps it inside the window everywhere else.
+
+fn grab_cursor(window: &Window, platform: Platform, viewport: &Viewport) -> GrabMode {
+	for &mode in GrabMode::fallbacks(platform) {
+		let result = match mode {
+			GrabMode::Locked | GrabMode::Confined => window.set_cursor_grab(true),
+			GrabMode::Recentering => window.set_cursor_position(window_centre(viewport)),
+			GrabMode::Free => Ok(())
+		};
+
+		match result {
+			Ok(()) => {
+				tracing::debug!("Holding the cursor with {:?}", mode);
+				return mode;
+			},
+			Err(err) => tracing::warn!("Failed to hold the cursor with {:?}: {}", mode, err)
+		}
+	}
+	GrabMode::Free
+}
+
+// Apply settings given as command-line arguments, which take priority over the config file.
+
+fn apply_args(args: &Args, config: &mut Config) {
+	if let Some(difficulty) = args.ai_difficulty {
+		config.gameplay.ai_difficulty = difficulty;
+	}
+	if args.fullscreen {
+		config.window.fullscreen = true;
+	}
+	if args.scoreboard {
+		config.window.scoreboard = true;
+	}
+	if args.gles {
+		config.window.gles = true;
+	}
+}
+
+// Get the fullscreen mode for the window, if it should be fullscreen.
+
+#[cfg(not(target_os = "macos"))]
+fn fullscreen_mode(fullscreen: bool) -> Option<glium::glutin::window::Fullscreen> {
+	fullscreen.then_some(glium::glutin::window::Fullscreen::Borderless(None))
+}
+
+// Go in or out of fullscreen.
+// On macOS the window covers the screen in place instead of moving to its own space, which skips the slide animation and the resizes that come with it.
+
+#[cfg(target_os = "macos")]
+fn set_fullscreen(window: &Window, fullscreen: bool) {
+	use glium::glutin::platform::macos::WindowExtMacOS;
+
+	if window.simple_fullscreen() != fullscreen && !window.set_simple_fullscreen(fullscreen) {
+		tracing::warn!("Failed to {} fullscreen", if fullscreen { "enter" } else { "leave" });
+	}
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_fullscreen(window: &Window, fullscreen: bool) {
+	window.set_fullscreen(fullscreen_mode(fullscreen));
+}
+
+// Get the edges of the screen the window can't draw over, in logical points.
+// In fullscreen on a Mac the window reaches the top of the screen, where the menu bar drops down and the camera notch sits.
+
+#[cfg(target_os = "macos")]
+fn safe_area(window: &Window, fullscreen: bool) -> Insets {
+	use glium::glutin::platform::macos::MonitorHandleExtMacOS;
+	use objc::{msg_send, sel, sel_impl};
+	use objc::runtime::{Object, BOOL, NO};
+
+	#[repr(C)]
+	struct NSEdgeInsets {
+		top: f64,
+		left: f64,
+		bottom: f64,
+		right: f64
+	}
+
+	let screen = match window.current_monitor().and_then(|monitor| monitor.ns_screen()) {
+		Some(screen) if fullscreen => screen as *mut Object,
+		_ => return Insets::default()
+	};
+
+	// Screens only know their safe area from macOS 12, before which nothing covers the window.
+	// SAFETY: the screen is a live NSScreen owned by the window's monitor, and safeAreaInsets is only sent if it's understood.
+	unsafe {
+		let supported: BOOL = msg_send![screen, respondsToSelector: sel!(safeAreaInsets)];
+		if supported == NO {
+			return Insets::default();
+		}
+
+		let insets: NSEdgeInsets = msg_send![screen, safeAreaInsets];
+		Insets {
+			top: insets.top as f32,
+			right: insets.right as f32,
+			bottom: insets.bottom as f32,
+			left: insets.left as f32
+		}
+	}
+}
+
+#[cfg(not(target_os = "macos"))]
+fn safe_area(_window: &Window, _fullscreen: bool) -> Insets {
+	Insets::default()
+}
+
+// Create the window and everything needed to draw to it.
+
+fn init_display(event_loop: &EventLoop<()>, config: &Config, assets: &mut Assets, locale: &Locale) -> Result<(Display, Program, Batch, FrameUniforms), Error> {
+	// Initialise the display window.
 
-mod structs;
-use structs::{Rect, Object, ObjectType};
+	// On macOS the window can only go fullscreen once it's been created.
 
-// Import game.rs from codebase
+	let win_build = WindowBuilder::new()
+		.with_title(locale.get("window.title"))
+		.with_inner_size(LogicalSize::new(config.window.width, config.window.height));
+	#[cfg(not(target_os = "macos"))]
+	let win_build = win_build.with_fullscreen(fullscreen_mode(config.window.fullscreen));
+	let ctx_build = |gles| ContextBuilder::new()
+		.with_vsync(config.window.vsync)
+		.with_gl(render::gl_request(gles));
 
-mod game;
-use game::GameState;
+	// Ask for OpenGL ES if the config says to, or on phones where it's all there is.
+	// Otherwise desktop OpenGL is tried first, falling back to OpenGL ES if there's none or it's too old for the game's shaders, e.g. on a Raspberry Pi.
 
-// Import basic shaders from file.
+	let gles = config.window.gles || cfg!(target_os = "android");
+	let display = match Display::new(win_build.clone(), ctx_build(gles), event_loop) {
+		Ok(display) if gles || render::supports_desktop_shaders(&display) => display,
+		Ok(display) => {
+			let version = display.get_opengl_version_string();
+			tracing::info!("OpenGL {} is too old, falling back to OpenGL ES", version);
+			drop(display);
+			Display::new(win_build, ctx_build(true), event_loop)?
+		},
+		Err(err) if !gles => {
+			tracing::info!("Desktop OpenGL isn't available, falling back to OpenGL ES: {}", err);
+			Display::new(win_build, ctx_build(true), event_loop).map_err(|_| err)?
+		},
+		Err(err) => return Err(err.into())
+	};
 
-const VERTEX_SHADER_SRC: &'static str = include_str!("./shaders/vertex_shader.vsh");
-const FRAGMENT_SHADER_SRC: &'static str = include_str!("./shaders/fragment_shader.frag");
+	#[cfg(target_os = "macos")]
+	set_fullscreen(display.gl_window().window(), config.window.fullscreen);
+
+	// Build a program from GLSL source code.
+	// This compiles the shaders and links them together for rendering.
+
+	let program = render::load_program(&display, assets)?;
+
+	// Create the objects to be rendered in the game.
+	// Since the game only uses basic rect shapes, they're gathered into one vertex buffer, built here, and drawn together rather than one at a time.
+
+	let batch = Batch::new(&display)?;
+
+	// Create the buffer for the uniforms every rect in a frame shares.
+
+	let uniforms = FrameUniforms::new(&display)?;
+
+	Ok((display, program, batch, uniforms))
+}
+
+// Simulate a match between two AI paddles with no window, printing each point and the result.
+// The match gives up after 10 minutes of game time in case neither side can win.
+// If a telemetry file is given, every tick of the match is exported to it at the end.
+
+fn run_headless(mut game: GameState, telemetry_path: Option<&Path>, locale: &Locale) {
+	game.control_id = None;
+	game.resize(1024.0, 768.0);
+	game.paused = false;
+
+	let side_name = |side: Side| match side {
+		Side::Left => locale.get("cli.left"),
+		Side::Right => locale.get("cli.right")
+	};
+
+	let mut telemetry = Telemetry::new();
+	let mut winner = None;
+
+	let ticks = TICK_RATE as u32 * 600;
+	for tick in 1..=ticks {
+		game.step(1);
+
+		let events: Vec<_> = game.events.drain().collect();
+		telemetry.record(&game, &events);
+
+		for event in events {
+			match event {
+				GameEvent::PointScored { side, scores } => println!("{}", locale.format("cli.point", &[("side", &side_name(side)), ("left", &scores[0]), ("right", &scores[1])])),
+				GameEvent::MatchEnded { winner: side, .. } => winner = Some((side, tick)),
+				// Ignore anything else.
+				_ => ()
+			}
+		}
+
+		if winner.is_some() {
+			break;
+		}
+	}
+
+	match winner {
+		Some((side, tick)) => println!("{}", locale.format("cli.wins", &[("side", &side_name(side)), ("seconds", &format!("{:.1}", tick as f32 / TICK_RATE))])),
+		None => println!("{}", locale.format("cli.no-winner", &[("left", &game.scores[0]), ("right", &game.scores[1])]))
+	}
+
+	if let Some(path) = telemetry_path {
+		telemetry.save_to(path).unwrap_or_else(|err| exit_with(Error::Telemetry(err), locale));
+	}
+}
+
+// Run AI matches back to back in real time, like a dedicated server, serving metrics for operators on the given address and port.
+// This runs until the process is stopped.
+
+fn run_server(mut game: GameState, address: IpAddr, port: u16, locale: &Locale) -> ! {
+	game.control_id = None;
+	game.resize(1024.0, 768.0);
+	game.paused = false;
+
+	let metrics = Arc::new(Mutex::new(Metrics::new()));
+	MetricsServer::spawn(address, port, Arc::clone(&metrics)).unwrap_or_else(|err| exit_with(Error::Metrics(err), locale));
+
+	let tick = Duration::from_secs_f32(TICK_TIME / 1000.0);
+	let mut next_tick = Instant::now();
+	loop {
+		let start = Instant::now();
+		game.step(1);
+		let duration = start.elapsed();
+
+		let events: Vec<_> = game.events.drain().collect();
+		metrics.lock().unwrap().record_tick(&game, start, duration, &events);
+
+		// Sleep until the next tick is due, without trying to catch up on any missed while the machine was busy.
+		next_tick = (next_tick + tick).max(Instant::now());
+		std::thread::sleep(next_tick.saturating_duration_since(Instant::now()));
+	}
+}
+
+// Import settings exported from another machine, replacing the config file.
+
+fn import_settings(path: &Path, locale: &Locale) {
+	let config = Config::import_from(path).unwrap_or_else(|err| exit_with(Error::Settings(err), locale));
+	let config_path = Config::path().unwrap_or_else(|| exit_with(Error::Settings(locale.get("error.no-config-dir").to_string()), locale));
+
+	config.save_to(&config_path).unwrap_or_else(|err| {
+		exit_with(Error::Settings(locale.format("error.write-failed", &[("path", &config_path.display()), ("details", &err)])), locale)
+	});
+	println!("{}", locale.format("cli.imported-settings", &[("from", &path.display()), ("to", &config_path.display())]));
+}
+
+// Export the telemetry for a recorded match, reporting but otherwise ignoring any failure so it never interrupts play.
+
+fn export_telemetry(replay: &Replay, path: &Path) {
+	match Telemetry::from_replay(replay.clone()).save_to(path) {
+		Ok(()) => tracing::info!("Exported match telemetry to {}", path.display()),
+		Err(err) => tracing::warn!("{}", err)
+	}
+}
+
+// Save a replay of any match that ended this frame and the records set in it, and start recording the next match.
+// Whether any records were waiting for a name last frame is tracked, so the high scores are saved once they've been named.
+
+fn record_frame(ctx: &mut Context, live: bool, telemetry: Option<&Path>, unnamed_records: &mut bool) {
+	if live && ctx.events.iter().any(|event| matches!(event, GameEvent::MatchEnded { .. })) {
+		if let Some(replay) = ctx.sim.finish_recording() {
+			replay.save();
+			if let Some(path) = telemetry {
+				export_telemetry(&replay, path);
+			}
+		}
+		ctx.sim.start_recording();
+	}
+
+	// Save the high scores again once a guest has named the records they set, or chosen not to.
+
+	if *unnamed_records && ctx.high_scores.unnamed.is_empty() {
+		ctx.high_scores.save();
+	}
+
+	// Update the high scores from this frame's events, saving them whenever a record changes.
+	// Records are named after the active profile straight away. Records set by the AI playing itself go unnamed, and a guest is asked for their name when the match ends.
+
+	if ctx.high_scores.record(ctx.game.mode, ctx.game.control_side(), &ctx.events) {
+		match (ctx.game.control_side(), &ctx.profiles.active) {
+			(Some(_), Some(name)) => ctx.high_scores.name_records(name),
+			(None, _) => ctx.high_scores.unnamed.clear(),
+			_ => ()
+		}
+		ctx.high_scores.save();
+	}
+	*unnamed_records = !ctx.high_scores.unnamed.is_empty();
+
+	// Add finished matches to the active profile's lifetime stats, letting the player know about any skins the time they've played has unlocked.
+
+	let before = ctx.profiles.active().map(|profile| profile.stats);
+	if ctx.profiles.record(ctx.game.control_side(), &ctx.events) {
+		if let (Some(before), Some(after)) = (before, ctx.profiles.active().map(|profile| profile.stats)) {
+			let unlocked = cosmetics::newly_unlocked(PADDLE_SKINS, &before, &after).into_iter().map(|skin| ("toast.paddle-unlocked", skin))
+				.chain(cosmetics::newly_unlocked(BALL_SKINS, &before, &after).into_iter().map(|skin| ("toast.ball-unlocked", skin)));
+			for (key, skin) in unlocked {
+				let message = ctx.locale.format(key, &[("skin", &ctx.locale.get(&skin.text_key()))]);
+				ctx.show_toast(message);
+			}
+		}
+		ctx.profiles.save();
+	}
+}
+
+// When the game exits, save a replay of the match in progress along with everything else that should outlast it.
+
+fn end_session(ctx: &Context, live: bool, telemetry: Option<&Path>) {
+	if let Some(replay) = ctx.sim.finish_recording().filter(|replay| replay.length > 0) {
+		replay.save();
+		if let Some(path) = telemetry {
+			export_telemetry(&replay, path);
+		}
+	}
+	save_session(ctx, live);
+}
+
+// Save everything that should outlast the game closing: the match itself so it can be continued next time, any settings changed in-game, and who was playing.
+
+fn save_session(ctx: &Context, live: bool) {
+	let game = ctx.sim.with(|game| game.clone());
+	if live && game.elapsed > 0.0 {
+		SavedMatch::new(game).save();
+	}
+
+	if ctx.config_changed {
+		ctx.saved_config.save();
+	}
+	if !ctx.profiles.profiles.is_empty() {
+		ctx.profiles.save();
+	}
+}
+
+// Start recording a video of the window with ffmpeg, to the given path or a new one in the videos directory.
+// Failing to record isn't fatal, the player's just told with a toast.
+
+fn start_video(ctx: &mut Context, display: &Display, path: Option<PathBuf>) -> Option<VideoRecorder> {
+	let (width, height) = display.get_framebuffer_dimensions();
+	let result = path.or_else(VideoRecorder::new_path)
+		.ok_or_else(|| io::Error::other("no data directory on this platform"))
+		.and_then(|path| VideoRecorder::start(&path, width, height));
+
+	match result {
+		Ok(video) => {
+			ctx.show_toast(ctx.locale.get("toast.recording-started").to_string());
+			Some(video)
+		},
+		Err(err) => {
+			tracing::warn!("Failed to start recording video: {}", err);
+			ctx.show_toast(ctx.locale.get("toast.recording-failed").to_string());
+			None
+		}
+	}
+}
+
+// Stop recording a video, waiting for ffmpeg to finish writing it.
+
+fn stop_video(ctx: &mut Context, video: VideoRecorder) {
+	match video.finish() {
+		Ok(path) => {
+			tracing::info!("Saved video to {}", path.display());
+			ctx.show_toast(ctx.locale.get("toast.recording-saved").to_string());
+		},
+		Err(err) => {
+			tracing::warn!("Failed to record video: {}", err);
+			ctx.show_toast(ctx.locale.get("toast.recording-failed").to_string());
+		}
+	}
+}
 
 // Main function.
 // This will create the window, declare game variables, then run the event loop.
+// On Android the game is a library loaded by the app, which calls this once its activity starts.
 
+#[cfg_attr(target_os = "android", ndk_glue::main(backtrace = "on"))]
 pub fn main() {
+	// Print logs to the terminal. By default only warnings and errors are shown, set RUST_LOG (e.g. RUST_LOG=pong=debug) for more.
+	// The most recent logs, including information the terminal doesn't show, are also kept for crash reports.
+	// With profiling on, every span is sent to a Tracy profiler too, e.g. each frame, tick and scene update, for finding out where a stutter's coming from.
+
+	let registry = tracing_subscriber::registry()
+		.with(fmt::layer().with_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"))))
+		.with(fmt::layer().with_writer(RecentLogs).with_ansi(false).with_filter(LevelFilter::INFO));
+	#[cfg(feature = "profiling")]
+	let registry = registry.with(tracing_tracy::TracyLayer::default());
+	registry.init();
+
+	// If the game panics, write a crash report instead of silently dying.
+
+	crash::install();
+
+	// Parse command-line arguments, make sure the game's directories exist, then load settings from the config file and the player profiles.
+	// Arguments take priority over the profile named on the command line, which takes priority over the config file.
+
+	let args = Args::parse();
+	paths::create_dirs();
+	let saved_config = Config::load();
+	let mut profiles = Profiles::load();
+	let mut config = saved_config.clone();
+	if let Some(name) = &args.profile {
+		profiles.select_or_create(name, &saved_config);
+		if let Some(profile) = profiles.active() {
+			profile.apply(&mut config);
+		}
+	}
+	apply_args(&args, &mut config);
+	crash::set_config(&config);
+
+	// Create the asset manager, which loads shaders and other assets with optional overrides from disk.
+	// Then load the text for the chosen language, so even errors from here on are shown in it.
+
+	let mut assets = Assets::with_default_overrides();
+	let locale = Locale::load(&mut assets, &config.language);
+
+	// Export or import the settings if asked to, then exit.
+	// Exports leave out the command-line arguments and profile, so they hold only what's in the config file.
+
+	if let Some(path) = &args.export_settings {
+		saved_config.export_to(path).unwrap_or_else(|err| exit_with(Error::Settings(err), &locale));
+		println!("{}", locale.format("cli.exported-settings", &[("path", &path.display())]));
+		return;
+	}
+
+	if let Some(path) = &args.import_settings {
+		import_settings(path, &locale);
+		return;
+	}
+
+	// Create the game state handler, set up for the selected game mode.
+
+	let mut game = GameState::with_mode(args.mode);
+	game.ai_accuracy = config.gameplay.ai_difficulty.ai_accuracy();
+	game.win_score = config.gameplay.win_score;
+	game.serve_delay = config.gameplay.serve_delay();
+
+	// Matches are random unless a seed is given.
+	match args.seed {
+		Some(seed) => game.set_seed(seed),
+		None => game.rng = Rng::from_time()
+	}
+
+	// In headless mode, simulate a match and exit without ever opening a window.
+	// Given a metrics port, it keeps playing matches as a server instead.
+
+	if args.headless {
+		if let Some(port) = args.metrics_port {
+			run_server(game, args.metrics_address, port, &locale);
+		}
+		run_headless(game, args.telemetry.as_deref(), &locale);
+		return;
+	}
+
+	// Load the replay to play back, if one was given, before going to the trouble of opening a window.
+	// If telemetry was asked for, it's exported straight from the replay without opening a window at all.
+
+	let replay = args.replay.as_ref().map(|path| Replay::load_from(path).unwrap_or_else(|err| exit_with(Error::Replay(err), &locale)));
+
+	if let (Some(replay), Some(path)) = (&replay, &args.telemetry) {
+		Telemetry::from_replay(replay.clone()).save_to(path).unwrap_or_else(|err| exit_with(Error::Telemetry(err), &locale));
+		return;
+	}
+
+	// Hand the game state over to the simulation thread.
+	// From here on it ticks at a fixed rate, independently of rendering and window events.
+
+	let sim = Simulation::spawn(game);
+
+	// Create the scene stack, starting on the main menu.
+	// The game asks first if there's a match to continue from last time.
+	// Before that, it asks who's playing, unless there are no profiles or one was named on the command line.
+	// When playing back a replay, the live match is never started and the replay scene is shown instead.
+
+	let mut language = config.language.clone();
+	let mut fullscreen = config.window.fullscreen;
+	let mut ctx = Context::new(sim, config);
+	ctx.saved_config = saved_config;
+	ctx.high_scores = HighScores::load();
+	ctx.profiles = profiles;
+	ctx.locale = locale;
+	ctx.season = theme::seasonal(Date::today());
+	let mut scenes = SceneManager::new();
+	let live = replay.is_none();
+	match replay {
+		Some(replay) => scenes.push(&mut ctx, Box::new(ReplayScene::new(replay, Arc::new(SystemClock::new())))),
+		None => {
+			scenes.push(&mut ctx, Box::new(MainMenuScene::new()));
+
+			if let Some((saved, path)) = SavedMatch::load().zip(SavedMatch::path()) {
+				scenes.push(&mut ctx, Box::new(ResumeScene::new(saved, path)));
+			}
+
+			if args.profile.is_none() && !ctx.profiles.profiles.is_empty() {
+				let scene = ProfileScene::new(&ctx);
+				scenes.push(&mut ctx, Box::new(scene));
+			}
+		}
+	}
+
+	// Record every match, saving a replay each time one ends.
+	// If telemetry was asked for, each recording is also exported to the telemetry file, which ends up holding the last match played.
+
+	if live {
+		ctx.sim.start_recording();
+	}
+
+	// When playing in the terminal, draw the game there until it exits instead of opening a window.
+
+	#[cfg(feature = "terminal")]
+	if args.terminal {
+		let mut unnamed_records = false;
+		let result = pong::terminal::run(&mut ctx, &mut scenes, |ctx| record_frame(ctx, live, args.telemetry.as_deref(), &mut unnamed_records));
+		end_session(&ctx, live, args.telemetry.as_deref());
+		if let Err(err) = result {
+			exit_with(Error::Terminal(err), &ctx.locale);
+		}
+		return;
+	}
+
 	// Create a handler for the event loop.
 
 	let event_loop = EventLoop::new();
 
-	// Initialise the display window.
+	// Create the window, showing a readable error and exiting if the graphics drivers aren't up to it.
 
-	let win_build = WindowBuilder::new();
-	let ctx_build = ContextBuilder::new();
-	let display = Display::new(win_build, ctx_build, &event_loop).expect("Failed to create Display");
+	let (display, program, mut batch, mut uniforms) = init_display(&event_loop, &ctx.config, &mut assets, &ctx.locale).unwrap_or_else(|err| exit_with(err, &ctx.locale));
+	let gl_version = display.get_opengl_version_string();
+	tracing::info!("Created window with OpenGL {}", gl_version);
 
-	let mut is_focused = false;
+	// Open the scoreboard on a second screen if it's been asked for. The game carries on without it if it can't be opened.
 
-	{
-		let gl_window = display.gl_window();
-		let window = gl_window.window();
-		window.set_title("Pong");
-	}
+	let mut scoreboard = ctx.config.window.scoreboard.then(|| {
+		ScoreboardWindow::new(&event_loop, display.gl_window().window(), render::gl_request(render::is_gles(&display)), &mut assets, &ctx.locale)
+			.map_err(|err| tracing::warn!("Failed to open the scoreboard window: {}", err.message(&ctx.locale)))
+			.ok()
+	}).flatten();
 
-	// Build a program from GLSL source code.
-	// This compiles the shaders and links them together for rendering.
+	// Set up the debug panel, hidden until its key is pressed.
 
-	let program = Program::from_source(&display, VERTEX_SHADER_SRC, FRAGMENT_SHADER_SRC, None).unwrap();
+	#[cfg(feature = "debug-ui")]
+	let mut debug_ui = DebugUi::new(&display).unwrap_or_else(|err| exit_with(err, &ctx.locale));
 
-	// Create the game state handler.
-	let mut game = GameState::new();
+	// Load custom rule scripts, if scripting is enabled.
 
-	// Create the objects to be rendered in the game.
-	// Since the game only uses basic rect shapes, it's more performant to build a single VBO here and manipulate it to fit each rendered object.
+	#[cfg(feature = "scripting")]
+	let scripts = Scripts::load_installed();
+
+	// Watch the config file, so changes made while the game is running apply without a restart.
+
+	let config_watcher = Config::path().and_then(|path| ConfigWatcher::new(&path)
+		.map_err(|err| tracing::warn!("Failed to watch config file {}: {}", path.display(), err))
+		.ok());
+
+	// Keep the last few seconds of the game, so they can be saved as a GIF.
+
+	let mut clip = ClipRecorder::new();
+
+	// Track the video being recorded, if any, and whether one should start as soon as the window's drawn, because the command line asked for it.
 
-	let rect = Rect::new(&display, 1.0, 1.0);
+	let mut video: Option<VideoRecorder> = None;
+	let mut record_on_start = args.record.clone();
 
-	// Initialise objects
-	game.objects = vec![
-		Object::new(ObjectType::Ball).set_size(25.0, 25.0),
-		Object::new(ObjectType::PaddleLeft).set_size(25.0, 100.0),
-		Object::new(ObjectType::PaddleRight).set_size(25.0, 100.0)
-	];
+	// Track whether the cursor is currently captured by the window, and how.
 
-	// Control inputs will affect the PaddleRight object.
-	game.control_id = 2;
+	let platform = cursor_platform(display.gl_window().window());
+	let mut cursor = CursorGrab::new();
 
-	// Store the window dimensions and perspective matrix here so that it doesn't have to be recalculated every frame.
-	// Only recalculate on the initial frame or on a window resize, otherwise it isn't necessary.
+	// Track the fingers on a touch screen, and whether the app is in the background, where it has no window to draw to.
 
-	let (mut width, mut height) = (0.0, 0.0);
+	let mut touches = Touches::new();
+	let mut suspended = false;
 
-	let mut perspective: Option<[[f32; 4]; 4]> = None;
+	// Listen for knobs and faders driving the paddle, if the config sets any up.
+
+	let installation = Installation::start(&ctx.config.installation);
+
+	// Listen for an operator managing the game remotely, if the command line asks for it.
+	// Only this machine can reach it, unless it's asked to listen on every network interface, which needs a token.
+
+	#[cfg(feature = "remote")]
+	let remote = args.remote_port.map(|port| {
+		let address = if args.remote_listen_all { Ipv4Addr::UNSPECIFIED } else { Ipv4Addr::LOCALHOST };
+		RemoteServer::spawn(address.into(), port, args.remote_token.clone())
+			.unwrap_or_else(|err| exit_with(Error::Remote(err), &ctx.locale))
+	});
+
+	// Read any game controllers plugged in.
+
+	#[cfg(feature = "gamepad")]
+	let mut controllers = Controllers::start();
+
+	// Store the viewport here, with its perspective matrix in the uniform buffer, so that it doesn't have to be recalculated every frame.
+	// Only recalculate on the initial frame, or when the window is resized, moved to a screen with a different scale or goes in or out of fullscreen, otherwise it isn't necessary.
+
+	let mut viewport = Viewport::new(0.0, 0.0, 1.0);
 	let mut perspective_update = true;
 
-	// Initialise a variable used to implement delta time. This is a representation of the amount of time elapsed since the last frame.
-	// This is done to update the scene independently of framerate, instead fixed by time.
+	// Track when the last frame was drawn, to time the game by, and when the next one's due.
+	// Vsync can only be turned on or off when the window's created, so changing it in-game waits for a restart.
 
+	let vsync = ctx.config.window.vsync;
 	let mut last_frame = Instant::now();
+	let mut pacer = FramePacer::new(last_frame);
 
-	// Start running the event loop.
-	// This will keep the display window open until the event loop exits.
+	// Track whether the window is minimized, and how many frames in a row have had nothing moving in them.
+	// Once nothing's moved for a couple of frames, e.g. on a menu or paused, frames are only drawn when something happens to the window, so the game doesn't spend a core redrawing the same picture.
 
-	event_loop.run(move |event, _, control_flow| {
-		// Set a timer for the next frame to be drawn, then calculate the delta time.
-		// Delta time will be used later when calculating movements for on-screen objects.
+	let mut minimized = false;
+	let mut still_frames = 0;
+	let mut woken = false;
 
-		let next_frame_time = last_frame + Duration::from_nanos(16_666_667);
-		*control_flow = ControlFlow::WaitUntil(next_frame_time);
+	// Track whether the window has focus, which it's assumed to have until told otherwise.
 
-		let now = Instant::now();
-		let delta_time = (now - last_frame).as_nanos() as f32 / 1_000_000.0;
+	let mut focused = true;
 
-		last_frame = now;
+	// Track whether any records were waiting for a name last frame, so the high scores are saved once they've been named.
 
-		// Start drawing this frame.
+	let mut unnamed_records = false;
 
-		let mut frame = display.draw();
-		frame.clear_color(0.0, 0.0, 0.0, 1.0);
+	// Start running the event loop.
+	// This will keep the display window open until the event loop exits.
 
-		// Calculate the perspective matrix. This achieves 3 things:
-		// It stops the content of the window from stretching to match the screen.
-		// It moves the origin point (0,0) to the top-left of the window instead of the center.
-		// It allows co-ordinates to be calculated by pixel, giving a screen space equivalent to the dimensions of the window rather than a range of -1 to 1.
+	event_loop.run(move |event, _, control_flow| {
+		// When the game exits, save a replay of the match in progress, the match itself so it can be continued next time, and any settings changed in-game.
 
-		if perspective_update {
-			// Get the width and height dimensions of the display window.
-			let (_width, _height) = frame.get_dimensions();
-			(width, height) = (_width as f32, _height as f32);
+		if let event::Event::LoopDestroyed = event {
+			if let Some(video) = video.take() {
+				stop_video(&mut ctx, video);
+			}
+			end_session(&ctx, live, args.telemetry.as_deref());
+			return;
+		}
 
-			// Reset all objects to their initial positions.
-			// This first happens when the game starts, and also prevents unintended behaviour if the window resizes.
+		// On Android the app can be sent to the background at any time, taking the window away and possibly never coming back.
+		// The game pauses and saves, then draws nothing until it's brought back.
 
-			game.reset_objects(width, height);
-			
-			// Build the perspective matrix.
-			perspective = Some({
-				[
-					[2.0 / width, 0.0, 0.0, 0.0],
-					[0.0, -2.0 / height, 0.0, 0.0],
-					[0.0, 0.0, 1.0, 0.0],
-					[-1.0, 1.0, 0.0, 1.0]
-				]
-			});
-			perspective_update = false;
+		match event {
+			event::Event::Suspended => {
+				scenes.handle_input(&mut ctx, Input::Focused(false));
+				save_session(&ctx, live);
+				suspended = true;
+			},
+			event::Event::Resumed if suspended => {
+				suspended = false;
+				perspective_update = true;
+			},
+			// Ignore anything else.
+			_ => ()
 		}
 
-		// Execute an update tick for the game state.
+		if suspended {
+			*control_flow = ControlFlow::Wait;
+			return;
+		}
 
-		game.update(delta_time, width, height);
+		// While idle, a frame is only drawn after the window's own events, like input, focus and resizing, or when the slow timer runs out.
 
-		// Iterate through each object and render them.
+		if matches!(event, event::Event::WindowEvent { .. } | event::Event::NewEvents(event::StartCause::ResumeTimeReached { .. })) {
+			woken = true;
+		}
 
-		for obj in &game.objects {
-			// Render this object.
+		let events_cleared = matches!(event, event::Event::MainEventsCleared);
+		let redraw = matches!(event, event::Event::RedrawRequested(window_id) if window_id == display.gl_window().window().id());
 
-			let uniforms = uniform!{
-				perspective: perspective.unwrap(),
-				matrix: [
-					[obj.size.x, 0.0, 0.0, 0.0],
-					[0.0, obj.size.y, 0.0, 0.0],
-					[0.0, 0.0, 1.0, 0.0],
-					[obj.position.x, obj.position.y, 1.0, 1.0]
-				]
-			};
+		// Handle input events from the system, such as keypresses or mouse movements.
+		// These are translated into game inputs and passed to the active scene.
 
-			frame.draw(&rect.vx_buf, &rect.ix_buf, &program, &uniforms, &Default::default()).unwrap();
-		}
+		// The mouse itself moved, which the paddle only follows while the cursor is locked in place.
 
-		frame.finish().unwrap();
+		if let event::Event::DeviceEvent { event: event::DeviceEvent::MouseMotion { delta: (dx, dy) }, .. } = &event {
+			if let Some(input) = cursor.mouse_motion(*dx as f32, *dy as f32, ctx.width, ctx.height) {
+				scenes.handle_input(&mut ctx, input);
+			}
+		}
 
-		// Handle input events from the system, such as keypresses or mouse movements.
+		// Closing the scoreboard window only closes the scoreboard, and nothing else it's sent matters to the game.
 
-		let is_paused = game.paused;
-		let control_obj = game.get_control();
+		if let event::Event::WindowEvent { window_id, event } = &event {
+			if scoreboard.as_ref().is_some_and(|window| window.id() == *window_id) {
+				if let event::WindowEvent::CloseRequested = event {
+					scoreboard = None;
+				}
+				return;
+			}
+		}
 
-		match event {
-			// A window event has been received, check its type and handle it.
-			event::Event::WindowEvent { event, .. } => match event {
+		if let event::Event::WindowEvent { event, .. } = event {
+			let input = match event {
+				// The debug panel is using the event, so the game doesn't see it.
+				#[cfg(feature = "debug-ui")]
+				_ if debug_ui.handle_event(&event, display.gl_window().window().scale_factor()) => None,
 				// The close button has been pressed, exit the program.
 				event::WindowEvent::CloseRequested => {
 					*control_flow = ControlFlow::Exit;
-					return;
+					None
 				},
 				// The window was resized, recalculate the perspective on the next frame.
-				event::WindowEvent::Resized(_size) => {
+				// Minimizing shrinks it to nothing on some platforms, which the play area isn't resized to.
+				event::WindowEvent::Resized(size) => {
+					minimized = size.width == 0 || size.height == 0;
+					perspective_update = true;
+					None
+				},
+				// The window moved to a screen with a different scale, like from a Retina display to an external monitor.
+				event::WindowEvent::ScaleFactorChanged { .. } => {
 					perspective_update = true;
+					None
 				},
-				// Only simulate when the window is focused.
 				event::WindowEvent::Focused(focus) => {
-					is_focused = focus;
-					game.pause(&display, !focus);
+					focused = focus;
+					Some(Input::Focused(focus))
 				},
 				// The player pressed a key.
-				event::WindowEvent::KeyboardInput {device_id: _, input, is_synthetic: _} => match input.virtual_keycode.unwrap() {
-					// Pause if the player presses the escape key.
-					Key::Escape => if is_focused && input.state == KeyState::Pressed {
-						game.pause(&display, !game.paused);
+				// The scene hears which keyboard key it was first, in case it's rebinding keys, then which game key it's bound to.
+				event::WindowEvent::KeyboardInput { input, .. } if input.state == KeyState::Pressed => {
+					if let Some(name) = input.virtual_keycode.and_then(|key| input::keyboard_key(&format!("{:?}", key))) {
+						scenes.handle_input(&mut ctx, Input::KeyboardKey(name));
 					}
-					// Ignore anything else.
-					_ => ()
+					input.virtual_keycode.and_then(|key| map_key(&ctx.config, key)).map(Input::KeyPressed)
 				},
-				// The player pressed a button.
-				event::WindowEvent::MouseInput { device_id: _, state, button: _, .. } => if is_focused {
-					if is_paused && state == KeyState::Pressed {
-						game.pause(&display, false);
+				// The player pressed or let go of a button.
+				event::WindowEvent::MouseInput { state: KeyState::Pressed, .. } => Some(Input::MousePressed),
+				event::WindowEvent::MouseInput { state: KeyState::Released, .. } => Some(Input::MouseReleased),
+				// The player moved their mouse.
+				// While recentering, the cursor is put back in the middle each time it moves away from it.
+				// The cursor's position is in pixels, so it's translated to a point in the play area first.
+				event::WindowEvent::CursorMoved { position, .. } => {
+					let position = viewport.to_logical(position.x as f32, position.y as f32);
+					let input = cursor.cursor_moved(position.x, position.y, ctx.width, ctx.height);
+					if input.is_some() && cursor.recentering() {
+						display.gl_window().window().set_cursor_position(window_centre(&viewport)).ok();
 					}
+					input
 				},
-				// The player moved their mouse.
-				event::WindowEvent::CursorMoved { device_id: _, position, .. } => if !is_paused {
-					control_obj.position.y = position.y as f32 - (control_obj.size.y / 2.0);
+				// The player touched the screen, e.g. to drag the paddle on a phone.
+				// Like the cursor, the touch is translated to a point in the play area first.
+				event::WindowEvent::Touch(touch) => {
+					let position = viewport.to_logical(touch.location.x as f32, touch.location.y as f32);
+					match touch.phase {
+						event::TouchPhase::Started => {
+							for input in touches.start(touch.id, position.x, position.y) {
+								scenes.handle_input(&mut ctx, input);
+							}
+							None
+						},
+						event::TouchPhase::Moved => touches.moved(touch.id, position.x, position.y),
+						event::TouchPhase::Ended | event::TouchPhase::Cancelled => touches.end(touch.id)
+					}
 				},
 				// Ignore anything else.
-				_ => ()
-			},
-			// Ignore events that aren't being listened for.
-			_ => ()
+				_ => None
+			};
+
+			// Screenshots and clips can be taken from any scene, so the window layer handles them itself.
+			match input {
+				Some(Input::KeyPressed(Key::Screenshot)) => match screenshot::capture(&display) {
+					Ok(path) => {
+						tracing::info!("Saved screenshot to {}", path.display());
+						ctx.show_toast(ctx.locale.get("toast.screenshot-saved").to_string());
+					},
+					Err(err) => {
+						tracing::warn!("Failed to save screenshot: {}", err);
+						ctx.show_toast(ctx.locale.get("toast.screenshot-failed").to_string());
+					}
+				},
+				Some(Input::KeyPressed(Key::Clip)) => match clip.save() {
+					Ok(path) => {
+						tracing::info!("Saved clip to {}", path.display());
+						ctx.show_toast(ctx.locale.get("toast.clip-saved").to_string());
+					},
+					Err(err) => {
+						tracing::warn!("Failed to save clip: {}", err);
+						ctx.show_toast(ctx.locale.get("toast.clip-failed").to_string());
+					}
+				},
+				Some(Input::KeyPressed(Key::Record)) => match video.take() {
+					Some(video) => stop_video(&mut ctx, video),
+					None => video = start_video(&mut ctx, &display, None)
+				},
+				Some(input) => scenes.handle_input(&mut ctx, input),
+				None => ()
+			}
+		}
+
+		// Exit once there are no scenes left.
+
+		if scenes.is_empty() {
+			*control_flow = ControlFlow::Exit;
+		}
+
+		// Capture or release the cursor if the active scene asked for it.
+		// The debug panel needs the cursor free while it's open.
+
+		#[cfg(feature = "debug-ui")]
+		let grab = ctx.cursor_grabbed && !debug_ui.open;
+		#[cfg(not(feature = "debug-ui"))]
+		let grab = ctx.cursor_grabbed;
+
+		if grab != cursor.mode.is_some() {
+			let gl_window = display.gl_window();
+			let window = gl_window.window();
+
+			if grab {
+				cursor.mode = Some(grab_cursor(window, platform, &viewport));
+			} else {
+				if matches!(cursor.mode, Some(GrabMode::Locked | GrabMode::Confined)) {
+					window.set_cursor_grab(false).ok();
+				}
+				cursor.mode = None;
+			}
+			window.set_cursor_visible(!grab);
+		}
+
+		// Once the events that arrived have been handled, decide when the next frame is drawn.
+		// While idle, the event loop waits for the window's events instead of drawing every frame.
+		// Anything else feeding the game, like a remote operator or a fader on a mixing desk, is still checked on a slow timer.
+		// Otherwise, with vsync on, a frame is drawn as soon as the last one's been shown, so the screen paces the game.
+		// With vsync off, the pacer keeps frames to FRAME_RATE.
+		// A match carrying on in the background is drawn and simulated much less often, vsync or not.
+
+		let idle = minimized || still_frames >= IDLE_AFTER_FRAMES;
+		let background = !focused && !ctx.config.window.pause_in_background;
+
+		if events_cleared && *control_flow != ControlFlow::Exit {
+			if idle && !woken {
+				let polled = installation.is_some();
+				#[cfg(feature = "remote")]
+				let polled = polled || remote.is_some();
+				#[cfg(feature = "gamepad")]
+				let polled = polled || controllers.is_some();
+
+				*control_flow = if polled {
+					ControlFlow::WaitUntil(Instant::now() + IDLE_POLL_TIME)
+				} else {
+					ControlFlow::Wait
+				};
+				return;
+			}
+
+			ctx.sim.set_background(background);
+			pacer.set_frame_rate(if background { BACKGROUND_FRAME_RATE } else { FRAME_RATE });
+
+			if !vsync || background {
+				if let Some(wake) = pacer.wake_time(Instant::now()) {
+					*control_flow = ControlFlow::WaitUntil(wake);
+					return;
+				}
+				pacer.spin();
+			}
+			*control_flow = ControlFlow::Poll;
+			display.gl_window().window().request_redraw();
+			return;
 		}
+
+		// Draw a frame when the game window asks for one.
+
+		if !redraw {
+			return;
+		}
+
+		// Time spent idle doesn't count towards this frame, so nothing jumps ahead when it wakes.
+
+		let now = Instant::now();
+		let delta_time = if idle { 1000.0 / FRAME_RATE as f32 } else { (now - last_frame).as_secs_f32() * 1000.0 };
+		last_frame = now;
+		pacer.frame_drawn(now);
+		woken = false;
+
+		// Start drawing this frame.
+
+		let _span = tracing::trace_span!("frame").entered();
+
+		// The theme sets the colour behind everything.
+
+		let mut frame = display.draw();
+		let [red, green, blue] = ctx.theme().background;
+		frame.clear_color(red, green, blue, 1.0);
+
+		// Calculate the perspective matrix. This achieves 3 things:
+		// It stops the content of the window from stretching to match the screen.
+		// It moves the origin point (0,0) to the top-left of the play area instead of the center, keeping clear of anything covering the window.
+		// It allows co-ordinates to be calculated by logical point, giving a screen space equivalent to the dimensions of the window rather than a range of -1 to 1, drawn at the display's full resolution.
+
+		if perspective_update && !minimized {
+			// Get the width and height dimensions of the display window in pixels, and how many pixels make up a point.
+			let (width, height) = frame.get_dimensions();
+			let gl_window = display.gl_window();
+			let window = gl_window.window();
+			// A phone held upright turns the court on its side, so it still fills the screen.
+			viewport = Viewport::new(width as f32, height as f32, window.scale_factor() as f32)
+				.with_insets(safe_area(window, fullscreen))
+				.with_rotation(cfg!(target_os = "android") && height > width);
+
+			// Resize the play area, resetting all objects to their initial positions.
+			// This first happens when the game starts, and also prevents unintended behaviour if the window resizes.
+
+			let size = viewport.size();
+			ctx.resize(size.x, size.y);
+			cursor.scale_factor = viewport.scale;
+
+			// Build the perspective matrix, and write it to the uniform buffer for every draw call to use.
+			uniforms.set_perspective(viewport.perspective());
+			perspective_update = false;
+		}
+
+		// Take a snapshot of the game state from the simulation thread for this frame, along with any events it emitted.
+
+		ctx.refresh();
+
+		// Draw objects exactly where the simulation has them if the debug panel turned interpolation off.
+
+		#[cfg(feature = "debug-ui")]
+		if !debug_ui.interpolate {
+			ctx.alpha = 1.0;
+		}
+
+		// Save replays, high scores and profile stats from this frame's events.
+
+		record_frame(&mut ctx, live, args.telemetry.as_deref(), &mut unnamed_records);
+
+		// Move the paddle to wherever a knob or fader last set it.
+
+		if let Some(input) = installation.as_ref().and_then(Installation::poll) {
+			scenes.handle_input(&mut ctx, input);
+		}
+
+		// Steer the paddle and get around the menus with any controllers, the same as with the keyboard.
+
+		#[cfg(feature = "gamepad")]
+		if let Some(controllers) = &mut controllers {
+			for input in controllers.poll() {
+				scenes.handle_input(&mut ctx, input);
+			}
+		}
+
+		// Shake the controllers for anything the player should feel from this frame.
+
+		#[cfg(feature = "gamepad")]
+		if let (Some(controllers), Some(pulse)) = (&mut controllers, ctx.rumble.take()) {
+			controllers.rumble(pulse);
+		}
+
+		// Apply anything an operator asked for remotely, and tell them how the game's going.
+
+		#[cfg(feature = "remote")]
+		if let Some(remote) = &remote {
+			remote.update(&mut ctx, &mut scenes);
+		}
+
+		// Apply any changes to the config file, keeping the command-line overrides.
+
+		if let Some(mut config) = config_watcher.as_ref().and_then(ConfigWatcher::poll) {
+			ctx.saved_config = config.clone();
+			if let Some(profile) = ctx.profiles.active() {
+				profile.apply(&mut config);
+			}
+			apply_args(&args, &mut config);
+			crash::set_config(&config);
+			ctx.apply_config(config);
+		}
+
+		// Switch to another language if it's been changed, whether in the config file or in-game.
+
+		if ctx.config.language != language {
+			language = ctx.config.language.clone();
+			ctx.locale = Locale::load(&mut assets, &language);
+		}
+
+		// Go in or out of fullscreen if it's been changed.
+
+		if ctx.config.window.fullscreen != fullscreen {
+			fullscreen = ctx.config.window.fullscreen;
+			set_fullscreen(display.gl_window().window(), fullscreen);
+			perspective_update = true;
+		}
+
+		// Run script hooks for the events, applying any rule changes they make.
+
+		#[cfg(feature = "scripting")]
+		if let Some(rules) = scripts.handle_events(&ctx.game, &ctx.events) {
+			ctx.sim.apply(Command::SetRules(rules));
+		}
+
+		// Show the score in the window title whenever it changes.
+
+		let score_changed = ctx.events.iter().any(|event| matches!(event, GameEvent::PointScored { .. } | GameEvent::MatchEnded { .. }));
+		if score_changed {
+			let gl_window = display.gl_window();
+			gl_window.window().set_title(&ctx.locale.format("window.title-score", &[("left", &ctx.game.scores[0]), ("right", &ctx.game.scores[1])]));
+		}
+
+		// Update the active scene, any toasts being shown and the theme's particles, then draw every scene in the stack.
+
+		scenes.update(&mut ctx);
+		ctx.update_toasts(delta_time);
+		ctx.update_particles(delta_time);
+
+		scenes.render(&ctx, &mut GliumRenderer {
+			frame: &mut frame,
+			batch: &mut batch,
+			program: &program,
+			uniforms: &uniforms
+		});
+
+		// Draw the debug panel over everything else.
+
+		#[cfg(feature = "debug-ui")]
+		debug_ui.show(&display, &mut frame, &mut ctx, delta_time);
+
+		if let Err(err) = frame.finish() {
+			exit_with(err.into(), &ctx.locale);
+		}
+
+		// Mark the end of the frame for the profiler, which lines up everything else against frames.
+
+		#[cfg(feature = "profiling")]
+		tracing_tracy::client::frame_mark();
+
+		// Draw the frame again, smaller, into the clip buffer when one is due.
+
+		clip.update(delta_time, ctx.width, ctx.height, |renderer| scenes.render(&ctx, renderer));
+
+		// Add the frame to the video being recorded, stopping if it can't be, e.g. because the window was resized.
+
+		if let Some(path) = record_on_start.take() {
+			video = start_video(&mut ctx, &display, path);
+		}
+		if let Some(recorder) = &mut video {
+			let result = recorder.push(delta_time, || {
+				let image: glium::texture::RawImage2d<u8> = display.read_front_buffer().map_err(|err| io::Error::other(format!("{:?}", err)))?;
+				Ok((image.width, image.height, image.data.into_owned()))
+			});
+			if let Err(err) = result {
+				tracing::warn!("Stopped recording video: {}", err);
+				if let Some(video) = video.take() {
+					stop_video(&mut ctx, video);
+				}
+			}
+		}
+
+		// Draw the scoreboard in its own window, closing it if it can't be drawn to any more.
+
+		if let Some(window) = &mut scoreboard {
+			if let Err(err) = window.draw(&ctx.game, &ctx.events) {
+				tracing::warn!("Closed the scoreboard window: {}", err.message(&ctx.locale));
+				scoreboard = None;
+			}
+		}
+
+		// Count the frames with nothing moving: a paused game with no toasts fading out, no particles from the theme, no video being recorded and no replay playing.
+
+		#[cfg(feature = "debug-ui")]
+		let debug_open = debug_ui.open;
+		#[cfg(not(feature = "debug-ui"))]
+		let debug_open = false;
+
+		let still = live && ctx.game.paused && ctx.toasts.is_empty() && ctx.particles.is_empty() && video.is_none() && !debug_open;
+		still_frames = if still { still_frames + 1 } else { 0 };
 	});
 }
\ No newline at end of file
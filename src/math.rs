@@ -0,0 +1,37 @@
+// Math functions used by the simulation.
+// Basic arithmetic and square roots on f32 are exactly specified, so they give the same result on every machine.
+// Trigonometry and exponentials aren't: the standard library hands them to the platform's maths library, which can round differently between operating systems and CPUs.
+// With the `deterministic` feature, they use portable software versions instead, so lockstep networking and replays stay bit-identical across platforms.
+
+// Sine and cosine of an angle in radians.
+#[cfg(not(feature = "deterministic"))]
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+	angle.sin_cos()
+}
+
+#[cfg(feature = "deterministic")]
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+	libm::sincosf(angle)
+}
+
+// Angle in radians of the point (x, y) from the positive x axis.
+#[cfg(not(feature = "deterministic"))]
+pub fn atan2(y: f32, x: f32) -> f32 {
+	y.atan2(x)
+}
+
+#[cfg(feature = "deterministic")]
+pub fn atan2(y: f32, x: f32) -> f32 {
+	libm::atan2f(y, x)
+}
+
+// e raised to the given power.
+#[cfg(not(feature = "deterministic"))]
+pub fn exp(value: f32) -> f32 {
+	value.exp()
+}
+
+#[cfg(feature = "deterministic")]
+pub fn exp(value: f32) -> f32 {
+	libm::expf(value)
+}
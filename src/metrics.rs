@@ -0,0 +1,147 @@
+// Dependencies
+
+use std::fmt::Write as _;
+use std::io;
+use std::net::{TcpListener, SocketAddr, IpAddr};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::events::GameEvent;
+use crate::game::GameState;
+use crate::http::{self, Response};
+
+// Upper bounds of the buckets ticks are sorted into by how long they took to simulate, in seconds.
+// A tick has to take well under its own length to keep up, which is around 8ms.
+
+pub const TICK_BUCKETS: [f64; 8] = [0.00001, 0.000025, 0.00005, 0.0001, 0.00025, 0.0005, 0.001, 0.0025];
+
+// How often the measured tick rate is worked out again, in seconds.
+
+pub const TICK_RATE_WINDOW: f64 = 1.0;
+
+// Implement the numbers a server running matches reports to its operators, in the Prometheus text format.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Metrics {
+	// Matches finished, and points scored, since the server started.
+	pub matches: u64,
+	pub points: u64,
+	// Ticks simulated since the server started, and how long they took altogether, in seconds.
+	pub ticks: u64,
+	pub tick_seconds: f64,
+	// How many ticks took at most each of TICK_BUCKETS to simulate.
+	pub tick_buckets: [u64; TICK_BUCKETS.len()],
+	// How many ticks a second were simulated over the last TICK_RATE_WINDOW, once one's gone by.
+	pub tick_rate: f64,
+	// When the current window started, and the ticks simulated since then.
+	window_start: Option<Instant>,
+	window_ticks: u64,
+	// Paddles controlled by a player rather than the AI.
+	pub players: u32
+}
+
+impl Metrics {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Count a tick that started at the given time and took the given time to simulate, along with anything that happened in it.
+	pub fn record_tick(&mut self, game: &GameState, started: Instant, duration: Duration, events: &[GameEvent]) {
+		let seconds = duration.as_secs_f64();
+		self.ticks += 1;
+		self.tick_seconds += seconds;
+		for (bucket, count) in TICK_BUCKETS.iter().zip(&mut self.tick_buckets) {
+			if seconds <= *bucket {
+				*count += 1;
+			}
+		}
+
+		match self.window_start {
+			Some(start) => {
+				self.window_ticks += 1;
+				let elapsed = started.saturating_duration_since(start).as_secs_f64();
+				if elapsed >= TICK_RATE_WINDOW {
+					self.tick_rate = self.window_ticks as f64 / elapsed;
+					self.window_start = Some(started);
+					self.window_ticks = 0;
+				}
+			},
+			None => self.window_start = Some(started)
+		}
+
+		for event in events {
+			match event {
+				GameEvent::PointScored { .. } => self.points += 1,
+				GameEvent::MatchEnded { .. } => self.matches += 1,
+				// Ignore anything else.
+				_ => ()
+			}
+		}
+		self.players = game.control_side().is_some() as u32;
+	}
+
+	// Write the metrics out in the Prometheus text format.
+	pub fn render(&self) -> String {
+		let mut out = String::new();
+		let mut metric = |name: &str, kind: &str, help: &str, value: &dyn std::fmt::Display| {
+			writeln!(out, "# HELP {} {}\n# TYPE {} {}\n{} {}", name, help, name, kind, name, value).ok();
+		};
+
+		metric("pong_matches_total", "counter", "Matches finished.", &self.matches);
+		metric("pong_points_total", "counter", "Points scored.", &self.points);
+		metric("pong_ticks_total", "counter", "Ticks simulated.", &self.ticks);
+		metric("pong_tick_rate_hertz", "gauge", "Ticks simulated every second, measured over the last second.", &self.tick_rate);
+		metric("pong_connected_players", "gauge", "Paddles controlled by a player rather than the AI.", &self.players);
+
+		writeln!(out, "# HELP pong_tick_duration_seconds Time taken to simulate each tick.").ok();
+		writeln!(out, "# TYPE pong_tick_duration_seconds histogram").ok();
+		for (bucket, count) in TICK_BUCKETS.iter().zip(&self.tick_buckets) {
+			writeln!(out, "pong_tick_duration_seconds_bucket{{le=\"{}\"}} {}", bucket, count).ok();
+		}
+		writeln!(out, "pong_tick_duration_seconds_bucket{{le=\"+Inf\"}} {}", self.ticks).ok();
+		writeln!(out, "pong_tick_duration_seconds_sum {}", self.tick_seconds).ok();
+		writeln!(out, "pong_tick_duration_seconds_count {}", self.ticks).ok();
+		out
+	}
+}
+
+// Implement a small HTTP server that answers Prometheus scraping the metrics, on its own thread.
+// Only GET /metrics is answered, anything else is not found.
+
+pub struct MetricsServer {
+	addr: SocketAddr
+}
+
+impl MetricsServer {
+	// Start serving the metrics on the given address and port, or any free port if it's 0.
+	pub fn spawn(address: IpAddr, port: u16, metrics: Arc<Mutex<Metrics>>) -> io::Result<Self> {
+		let listener = TcpListener::bind((address, port))?;
+		let addr = listener.local_addr()?;
+
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let result = stream.and_then(|stream| http::respond(stream, |request| respond(request, &metrics)));
+				if let Err(err) = result {
+					tracing::warn!("Failed to serve metrics: {}", err);
+				}
+			}
+		});
+
+		tracing::info!("Serving metrics on http://{}/metrics", addr);
+		Ok(Self { addr })
+	}
+
+	// Get the address the server's listening on.
+	pub fn addr(&self) -> SocketAddr {
+		self.addr
+	}
+}
+
+// Answer a request for the metrics.
+fn respond(request: &http::Request, metrics: &Mutex<Metrics>) -> Response {
+	match (request.method.as_str(), request.path.as_str()) {
+		("GET", "/metrics") => Response::new("200 OK", "text/plain; version=0.0.4", metrics.lock().unwrap().render()),
+		_ => Response::text("404 Not Found", "Not found\n")
+	}
+}
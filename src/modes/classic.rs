@@ -0,0 +1,27 @@
+// Dependencies
+
+use crate::game::GameState;
+use crate::structs::{Object, ObjectType};
+use crate::modes::GameMode;
+
+// Implement the classic game mode.
+// A ball and two paddles, the first side to reach the winning score wins.
+
+pub struct Classic;
+
+impl GameMode for Classic {
+	fn name(&self) -> &'static str {
+		"classic"
+	}
+
+	fn setup(&self, game: &mut GameState) {
+		game.objects = vec![
+			Object::new(ObjectType::Ball).set_size(25.0, 25.0),
+			Object::new(ObjectType::PaddleLeft).set_size(25.0, 100.0),
+			Object::new(ObjectType::PaddleRight).set_size(25.0, 100.0)
+		];
+
+		// Control inputs will affect the PaddleRight object.
+		game.control_id = Some(2);
+	}
+}
@@ -0,0 +1,97 @@
+// Dependencies
+
+use std::fmt;
+
+use crate::game::GameState;
+use crate::events::Side;
+
+// Implement a trait representing a game mode.
+// A mode sets up the objects for a match, can hook into every tick, and decides how points are counted and when the match is won.
+// Modes hold no state of their own, anything they need to track lives in the GameState.
+
+pub trait GameMode: Sync {
+	// Unique name used to look this mode up in the registry.
+	fn name(&self) -> &'static str;
+
+	// Set up the objects and rules for a new match.
+	fn setup(&self, game: &mut GameState);
+
+	// Called at the end of every simulation tick.
+	fn tick(&self, _game: &mut GameState) {}
+
+	// Count a point scored by the given side.
+	fn point_scored(&self, game: &mut GameState, side: Side) {
+		game.scores[side.index()] += 1;
+	}
+
+	// Check if a side has won the match.
+	fn winner(&self, game: &GameState) -> Option<Side> {
+		[Side::Left, Side::Right].into_iter().find(|side| game.scores[side.index()] >= game.win_score)
+	}
+
+	// Check if a side would win the match by scoring the next point.
+	fn match_point(&self, game: &GameState, side: Side) -> bool {
+		game.scores[side.index()] + 1 >= game.win_score
+	}
+
+	// Check if the player is shown the path the ball's predicted to take towards their paddle.
+	fn shows_trajectory(&self) -> bool {
+		false
+	}
+}
+
+// Modes are told apart by their names, so commands that switch modes can be compared and printed.
+
+impl PartialEq for dyn GameMode {
+	fn eq(&self, other: &Self) -> bool {
+		self.name() == other.name()
+	}
+}
+
+impl fmt::Debug for dyn GameMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+// Import each mode from codebase.
+
+mod classic;
+pub use classic::Classic;
+
+mod survival;
+pub use survival::Survival;
+
+mod training;
+pub use training::Training;
+
+// Registry of every available game mode.
+// New modes only need to be added here to become selectable.
+
+pub static MODES: &[&dyn GameMode] = &[
+	&Classic,
+	&Survival,
+	&Training
+];
+
+// Look up a game mode by its name.
+pub fn find(name: &str) -> Option<&'static dyn GameMode> {
+	MODES.iter().copied().find(|mode| mode.name() == name)
+}
+
+// Serialize a game mode as its registry name, for use with `#[serde(with = "crate::modes::by_name")]`.
+
+pub mod by_name {
+	use serde::{Serializer, Deserializer, Deserialize, de::Error};
+
+	use super::{GameMode, find};
+
+	pub fn serialize<S: Serializer>(mode: &&'static dyn GameMode, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(mode.name())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static dyn GameMode, D::Error> {
+		let name = String::deserialize(deserializer)?;
+		find(&name).ok_or_else(|| D::Error::custom(format!("unknown game mode '{}'", name)))
+	}
+}
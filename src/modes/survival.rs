@@ -0,0 +1,47 @@
+// Dependencies
+
+use crate::game::GameState;
+use crate::events::Side;
+use crate::modes::{GameMode, Classic};
+
+// Implement the survival game mode.
+// The player faces a perfectly accurate AI and the match ends the first time they concede.
+// The player's score is the number of seconds they survived.
+
+pub struct Survival;
+
+impl GameMode for Survival {
+	fn name(&self) -> &'static str {
+		"survival"
+	}
+
+	fn setup(&self, game: &mut GameState) {
+		Classic.setup(game);
+		game.ai_accuracy = 1.0;
+	}
+
+	fn tick(&self, game: &mut GameState) {
+		game.scores[Side::Right.index()] = (game.elapsed / 1000.0) as u32;
+	}
+
+	// Only the AI's points count, scoring against it just serves again.
+	fn point_scored(&self, game: &mut GameState, side: Side) {
+		if side == Side::Left {
+			game.scores[side.index()] += 1;
+		}
+	}
+
+	// The AI wins as soon as it scores once, there's no way for the player to win.
+	fn winner(&self, game: &GameState) -> Option<Side> {
+		if game.scores[Side::Left.index()] > 0 {
+			Some(Side::Left)
+		} else {
+			None
+		}
+	}
+
+	// The AI is always a point away from winning, so there's never a match point worth pointing out.
+	fn match_point(&self, _game: &GameState, _side: Side) -> bool {
+		false
+	}
+}
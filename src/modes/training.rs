@@ -0,0 +1,33 @@
+// Dependencies
+
+use crate::game::GameState;
+use crate::events::Side;
+use crate::modes::{GameMode, Classic};
+
+// Implement the training game mode, for practising against the AI.
+// The path the ball's predicted to take is drawn for the player, bounces and all, so they can learn where to be.
+// Points are still counted, but nobody ever wins, so the player can keep practising for as long as they like.
+
+pub struct Training;
+
+impl GameMode for Training {
+	fn name(&self) -> &'static str {
+		"training"
+	}
+
+	fn setup(&self, game: &mut GameState) {
+		Classic.setup(game);
+	}
+
+	fn winner(&self, _game: &GameState) -> Option<Side> {
+		None
+	}
+
+	fn match_point(&self, _game: &GameState, _side: Side) -> bool {
+		false
+	}
+
+	fn shows_trajectory(&self) -> bool {
+		true
+	}
+}
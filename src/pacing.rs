@@ -0,0 +1,66 @@
+// Dependencies
+
+use std::time::{Duration, Instant};
+
+// How many frames a second the window draws when vsync isn't pacing it.
+
+pub const FRAME_RATE: u32 = 60;
+
+// How many frames a second the window draws while the game carries on in the background, unfocused.
+
+pub const BACKGROUND_FRAME_RATE: u32 = 10;
+
+// How long before a frame is due the event loop wakes up, spinning for the rest of the time.
+// The OS's timers can wake a thread a millisecond or more late, which would make frames late too.
+
+pub const SPIN_TIME: Duration = Duration::from_millis(2);
+
+// Implement the timing of frames when vsync isn't pacing them.
+// Frames are due on a fixed grid, one every 1/FRAME_RATE seconds from the first, so a frame drawn a little late doesn't push every frame after it later too.
+// A frame missed entirely, e.g. while the window's being dragged, is skipped rather than rushed to catch up.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FramePacer {
+	interval: Duration,
+	// When the next frame is due.
+	deadline: Instant
+}
+
+impl FramePacer {
+	pub fn new(now: Instant) -> Self {
+		Self {
+			interval: Duration::from_secs(1) / FRAME_RATE,
+			deadline: now
+		}
+	}
+
+	// Change how many frames a second are drawn, starting from the next frame.
+	pub fn set_frame_rate(&mut self, frame_rate: u32) {
+		self.interval = Duration::from_secs(1) / frame_rate;
+	}
+
+	pub fn deadline(&self) -> Instant {
+		self.deadline
+	}
+
+	// Get when the event loop should wake up to draw the next frame, ahead of it being due, or None if it's time to draw it now.
+	pub fn wake_time(&self, now: Instant) -> Option<Instant> {
+		self.deadline.checked_sub(SPIN_TIME).filter(|wake| *wake > now)
+	}
+
+	// Wait out what's left until the next frame is due, which is too short to trust to the OS's timers.
+	pub fn spin(&self) {
+		while Instant::now() < self.deadline {
+			std::hint::spin_loop();
+		}
+	}
+
+	// Move on to the next frame once one's been drawn at the given time.
+	pub fn frame_drawn(&mut self, now: Instant) {
+		self.deadline += self.interval;
+		if self.deadline <= now {
+			let missed = ((now - self.deadline).as_nanos() / self.interval.as_nanos()) as u32 + 1;
+			self.deadline += self.interval * missed;
+		}
+	}
+}
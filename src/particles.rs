@@ -0,0 +1,176 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+use crate::rng::Rng;
+use crate::math;
+
+// Most particles on screen at once, so a long frame can't spawn an unbounded number of them.
+
+pub const MAX_PARTICLES: usize = 512;
+
+// How often a snowflake falls from the top of the window, in milliseconds.
+
+const SNOW_INTERVAL: f32 = 30.0;
+
+// How often a firework goes off, in milliseconds, how many sparks it throws out, and how fast they fall, in pixels per millisecond per millisecond.
+
+const FIREWORK_INTERVAL: f32 = 700.0;
+const FIREWORK_SPARKS: usize = 32;
+const FIREWORK_GRAVITY: f32 = 0.0001;
+
+// The colours fireworks can be, one picked for each.
+
+const FIREWORK_COLOURS: [[f32; 4]; 4] = [
+	[1.0, 0.35, 0.35, 1.0],
+	[1.0, 0.85, 0.3, 1.0],
+	[0.4, 0.8, 1.0, 1.0],
+	[0.6, 1.0, 0.5, 1.0]
+];
+
+// Implement the kinds of particle a theme can fill the background with.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Emitter {
+	// Snowflakes drifting down from the top of the window.
+	Snow,
+	// Bursts of sparks that fall and fade, dotted around the top half of the window.
+	Fireworks
+}
+
+// Implement a single particle, drawn as a small square.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Particle {
+	position: Vec2,
+	// Speed in pixels per millisecond.
+	velocity: Vec2,
+	// How fast it falls faster, in pixels per millisecond per millisecond.
+	gravity: f32,
+	size: f32,
+	colour: [f32; 4],
+	// How long it's been around and how long it lasts, in milliseconds. It fades out over its life if fade is set.
+	age: f32,
+	life: f32,
+	fade: bool
+}
+
+// Implement the particles drawn behind every scene, purely for decoration.
+// They have their own random number generator, so they never touch the simulation's.
+
+#[derive(Clone, Debug)]
+pub struct Particles {
+	particles: Vec<Particle>,
+	rng: Rng,
+	// Time since the last particle or burst was spawned, in milliseconds.
+	timer: f32
+}
+
+impl Default for Particles {
+	fn default() -> Self {
+		Self::new(Rng::from_time())
+	}
+}
+
+impl Particles {
+	pub fn new(rng: Rng) -> Self {
+		Self {
+			particles: Vec::with_capacity(MAX_PARTICLES),
+			rng,
+			timer: 0.0
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.particles.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.particles.is_empty()
+	}
+
+	// Move every particle on by the time since the last frame, in milliseconds, dropping any that have run their course or left the window.
+	// While emitting, the emitter spawns new ones too. Otherwise those already on screen are left to finish.
+	pub fn update(&mut self, emitter: Option<Emitter>, emitting: bool, delta_time: f32, width: f32, height: f32) {
+		for particle in &mut self.particles {
+			particle.velocity.y += particle.gravity * delta_time;
+			particle.position += particle.velocity * delta_time;
+			particle.age += delta_time;
+		}
+		self.particles.retain(|particle| particle.age < particle.life && particle.position.y < height);
+
+		let Some(emitter) = emitter.filter(|_| emitting) else {
+			self.timer = 0.0;
+			return;
+		};
+
+		let interval = match emitter {
+			Emitter::Snow => SNOW_INTERVAL,
+			Emitter::Fireworks => FIREWORK_INTERVAL
+		};
+		self.timer += delta_time;
+		while self.timer >= interval {
+			self.timer -= interval;
+			match emitter {
+				Emitter::Snow => self.snowflake(width),
+				Emitter::Fireworks => self.firework(width, height)
+			}
+		}
+	}
+
+	fn spawn(&mut self, particle: Particle) {
+		if self.particles.len() < MAX_PARTICLES {
+			self.particles.push(particle);
+		}
+	}
+
+	// Drop a snowflake in from above the top of the window, drifting a little to one side.
+	fn snowflake(&mut self, width: f32) {
+		let size = self.rng.range(2.0, 5.0);
+		let particle = Particle {
+			position: Vec2::new(self.rng.range(0.0, width), -size),
+			velocity: Vec2::new(self.rng.range(-0.01, 0.01), self.rng.range(0.03, 0.08)),
+			gravity: 0.0,
+			size,
+			colour: [1.0, 1.0, 1.0, 0.8],
+			age: 0.0,
+			life: f32::INFINITY,
+			fade: false
+		};
+		self.spawn(particle);
+	}
+
+	// Set off a firework somewhere in the top half of the window, throwing sparks out evenly all around.
+	fn firework(&mut self, width: f32, height: f32) {
+		let centre = Vec2::new(self.rng.range(width * 0.2, width * 0.8), self.rng.range(height * 0.15, height * 0.5));
+		let colour = FIREWORK_COLOURS[(self.rng.next_u64() % FIREWORK_COLOURS.len() as u64) as usize];
+		let speed = self.rng.range(0.08, 0.15);
+		let life = self.rng.range(900.0, 1400.0);
+
+		for spark in 0..FIREWORK_SPARKS {
+			let angle = spark as f32 / FIREWORK_SPARKS as f32 * std::f32::consts::TAU;
+			let (sin, cos) = math::sin_cos(angle);
+			let particle = Particle {
+				position: centre,
+				velocity: Vec2::new(cos, sin) * speed * self.rng.range(0.7, 1.0),
+				gravity: FIREWORK_GRAVITY,
+				size: 3.0,
+				colour,
+				age: 0.0,
+				life,
+				fade: true
+			};
+			self.spawn(particle);
+		}
+	}
+
+	// Draw every particle, centred on its position.
+	pub fn render(&self, renderer: &mut dyn Renderer) {
+		for particle in &self.particles {
+			let [r, g, b, a] = particle.colour;
+			let opacity = if particle.fade { a * (1.0 - particle.age / particle.life) } else { a };
+			let size = Vec2::new(particle.size, particle.size);
+			renderer.fill(particle.position - size / 2.0, size, [r, g, b, opacity]);
+		}
+	}
+}
@@ -0,0 +1,33 @@
+// Dependencies
+
+use std::fs;
+use std::path::PathBuf;
+
+// Implement the directories the game keeps its files in, following each platform's conventions.
+// On Linux these follow the XDG base directories (~/.config/pong and ~/.local/share/pong by default),
+// on Windows they're in AppData\Roaming, and on macOS they're in ~/Library.
+// Each one is None on a platform without that kind of directory, in which case whatever would be kept there isn't saved.
+
+// Name of the game's folder inside each directory.
+
+pub const APP_DIR: &str = "pong";
+
+// Get the directory for settings, such as the config file.
+pub fn config_dir() -> Option<PathBuf> {
+	dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+// Get the directory for the player's own data, such as saves, replays, high scores, screenshots and scripts.
+pub fn data_dir() -> Option<PathBuf> {
+	dirs::data_dir().map(|dir| dir.join(APP_DIR))
+}
+
+// Create every directory that doesn't exist yet, so the game's files have somewhere to go from the first run.
+// Failures are only reported, since the game can still be played without saving anything.
+pub fn create_dirs() {
+	for dir in [config_dir(), data_dir()].into_iter().flatten() {
+		if let Err(err) = fs::create_dir_all(&dir) {
+			tracing::warn!("Failed to create directory {}: {}", dir.display(), err);
+		}
+	}
+}
@@ -0,0 +1,97 @@
+// Dependencies
+
+use crate::structs::{Vec2, ObjectType};
+use crate::game::GameState;
+use crate::events::Side;
+
+// Most times the predicted path is followed off the walls, so a ball going almost straight up and down doesn't bounce forever.
+
+pub const MAX_BOUNCES: u32 = 8;
+
+// Implement the path a ball is predicted to take towards a paddle, as the points it bounces off the walls and the point it reaches the paddle.
+// It goes in straight lines between bounces. Spin curving the ball and anything in its way, like power-ups, are left out.
+// The AI's paddles use this to decide where to go, without following the bounces, and training mode draws it for the player.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Trajectory {
+	position: Vec2,
+	velocity: Vec2,
+	// The x co-ordinate the path ends at.
+	target_x: f32,
+	// The lowest and highest y co-ordinates the path can reach before bouncing, and how many more bounces it follows.
+	top: f32,
+	bottom: f32,
+	bounces: u32,
+	done: bool
+}
+
+impl Trajectory {
+	// Predict the path of a point moving at the given velocity until it reaches the given x, with no walls to bounce off.
+	pub fn new(position: Vec2, velocity: Vec2, target_x: f32) -> Self {
+		Self {
+			position,
+			velocity,
+			target_x,
+			top: f32::NEG_INFINITY,
+			bottom: f32::INFINITY,
+			bounces: 0,
+			done: false
+		}
+	}
+
+	// Bounce the path off walls at the given y co-ordinates, up to the given number of times.
+	pub fn with_walls(mut self, top: f32, bottom: f32, bounces: u32) -> Self {
+		self.top = top;
+		self.bottom = bottom;
+		self.bounces = bounces;
+		self
+	}
+}
+
+impl Iterator for Trajectory {
+	type Item = Vec2;
+
+	// Get the next point the path turns at: where it bounces off a wall, or finally where it reaches the target x.
+	// A point moving away from the target, or not across at all, never reaches it, so its y is infinite or NaN.
+	fn next(&mut self) -> Option<Vec2> {
+		if self.done {
+			return None;
+		}
+
+		let time = (self.target_x - self.position.x) / self.velocity.x;
+
+		let wall = if self.velocity.y < 0.0 { self.top } else { self.bottom };
+		let wall_time = (wall - self.position.y) / self.velocity.y;
+		if self.bounces > 0 && wall_time >= 0.0 && wall_time < time {
+			self.position = Vec2::new(self.position.x + self.velocity.x * wall_time, wall);
+			self.velocity.y = -self.velocity.y;
+			self.bounces -= 1;
+			return Some(self.position);
+		}
+
+		self.done = true;
+		Some(Vec2::new(self.target_x, self.position.y + self.velocity.y * time))
+	}
+}
+
+// Predict the path of the ball coming towards the given side's paddle, from the middle of the ball, bouncing off the top and bottom of the court.
+// Returns where the path starts along with it, or None if the ball isn't heading that way.
+// The ball followed is the same one the AI follows, the last one in play.
+
+pub fn incoming(game: &GameState, side: Side) -> Option<(Vec2, Trajectory)> {
+	let ball = game.objects.iter().rev().find(|obj| obj.obj_type == ObjectType::Ball)?;
+	let paddle = game.objects.iter().find(|obj| obj.obj_type.side() == Some(side))?;
+
+	// The path ends where the middle of the ball would be when it touches the paddle's face.
+	let (target_x, incoming) = match side {
+		Side::Left => (paddle.position.x + paddle.size.x + ball.size.x / 2.0, ball.velocity.x < 0.0),
+		Side::Right => (paddle.position.x - ball.size.x / 2.0, ball.velocity.x > 0.0)
+	};
+	if !incoming {
+		return None;
+	}
+
+	let start = ball.get_center();
+	let half = ball.size.y / 2.0;
+	Some((start, Trajectory::new(start, ball.velocity, target_x).with_walls(half, game.height - half, MAX_BOUNCES)))
+}
@@ -0,0 +1,189 @@
+// Dependencies
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::config::Config;
+use crate::events::{Side, GameEvent};
+use crate::stats::MatchStats;
+use crate::paths;
+
+// Implement named local player profiles, kept in a file in the platform's data directory.
+// Each profile has the player's own preferences, which take priority over the config file while they're playing, and their stats across every match.
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+	// Name of the profile playing now, if any.
+	pub active: Option<String>,
+	pub profiles: Vec<Profile>
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+	pub name: String,
+	// How far the paddle moves for a given mouse movement.
+	pub sensitivity: f32,
+	// Name of the colour theme.
+	pub theme: String,
+	// Names of the skins the player's paddle and the ball are drawn in, once they're unlocked.
+	pub paddle_skin: String,
+	pub ball_skin: String,
+	// Keybinds that differ from the config file, in the same format.
+	pub keybinds: BTreeMap<String, String>,
+	pub stats: LifetimeStats
+}
+
+// Implement statistics added up over every match a profile has played.
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+	pub matches_played: u32,
+	pub matches_won: u32,
+	// Times the player hit the ball, and points they won straight from the serve.
+	pub hits: u32,
+	pub aces: u32,
+	// Most hits made in a single point, by both sides.
+	pub longest_rally: u32,
+	// Fastest the ball has gone, in pixels per millisecond.
+	pub max_ball_speed: f32,
+	// Time spent playing finished matches, in seconds, which unlocks skins.
+	pub play_time: u64
+}
+
+impl Default for Profile {
+	fn default() -> Self {
+		Self::new("Player", &Config::default())
+	}
+}
+
+impl Profile {
+	// Create a profile with the given name, starting with the preferences from the given settings.
+	pub fn new(name: &str, config: &Config) -> Self {
+		Self {
+			name: name.to_string(),
+			sensitivity: config.controls.sensitivity,
+			theme: config.theme.clone(),
+			paddle_skin: "classic".into(),
+			ball_skin: "classic".into(),
+			keybinds: BTreeMap::new(),
+			stats: LifetimeStats::default()
+		}
+	}
+
+	// Apply this profile's preferences over the given settings.
+	// The profile's keybinds are checked the same way as the config file's, since together they could clash.
+	pub fn apply(&self, config: &mut Config) {
+		config.controls.sensitivity = self.sensitivity;
+		config.theme = self.theme.clone();
+		config.keybinds.extend(self.keybinds.clone());
+
+		for problem in config.validate_keybinds() {
+			tracing::warn!("{} in profile '{}'", problem, self.name);
+		}
+	}
+}
+
+impl LifetimeStats {
+	// Add a finished match, played on the given side, to the totals.
+	pub fn record(&mut self, side: Side, won: bool, stats: &MatchStats) {
+		self.matches_played += 1;
+		if won {
+			self.matches_won += 1;
+		}
+
+		self.hits += stats.hits[side.index()];
+		self.aces += stats.aces[side.index()];
+		self.longest_rally = self.longest_rally.max(stats.longest_rally);
+		self.max_ball_speed = self.max_ball_speed.max(stats.max_ball_speed);
+		self.play_time += (stats.duration / 1000.0).round() as u64;
+	}
+}
+
+impl Profiles {
+	// Get the path of the profiles file in the platform's data directory.
+	pub fn path() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("profiles.toml"))
+	}
+
+	// Load the profiles, starting with none if there aren't any saved yet.
+	pub fn load() -> Self {
+		Self::path().map(|path| Self::load_from(&path)).unwrap_or_default()
+	}
+
+	// Load the profiles from a given path.
+	// If the file can't be read or parsed, it's reported and left alone rather than overwritten, so a broken file doesn't lose anyone's profile.
+	pub fn load_from(path: &Path) -> Self {
+		if !path.exists() {
+			return Self::default();
+		}
+
+		let loaded = fs::read_to_string(path)
+			.map_err(|err| format!("Failed to read profiles {}: {}", path.display(), err))
+			.and_then(|text| toml::from_str(&text).map_err(|err| format!("Invalid profiles {}: {}", path.display(), err)));
+
+		loaded.unwrap_or_else(|err| {
+			tracing::warn!("{}, playing without profiles", err);
+			Self::default()
+		})
+	}
+
+	// Save the profiles to the platform's data directory, reporting any failure.
+	pub fn save(&self) {
+		let Some(path) = Self::path() else { return };
+		if let Err(err) = self.save_to(&path) {
+			tracing::warn!("Failed to save profiles to {}: {}", path.display(), err);
+		}
+	}
+
+	// Write the profiles to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let text = toml::to_string_pretty(self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		fs::write(path, text)
+	}
+
+	// Get the profile playing now, if any.
+	pub fn active(&self) -> Option<&Profile> {
+		let name = self.active.as_ref()?;
+		self.profiles.iter().find(|profile| &profile.name == name)
+	}
+
+	pub fn active_mut(&mut self) -> Option<&mut Profile> {
+		let name = self.active.as_ref()?;
+		self.profiles.iter_mut().find(|profile| &profile.name == name)
+	}
+
+	// Make the profile with the given name the active one, creating it from the given settings if there isn't one yet.
+	pub fn select_or_create(&mut self, name: &str, config: &Config) {
+		if !self.profiles.iter().any(|profile| profile.name == name) {
+			self.profiles.push(Profile::new(name, config));
+		}
+		self.active = Some(name.to_string());
+	}
+
+	// Add any finished matches to the active profile's stats, played by the player on the given side.
+	// Returns whether anything changed, meaning the profiles should be saved.
+	pub fn record(&mut self, player: Option<Side>, events: &[GameEvent]) -> bool {
+		let (Some(player), Some(profile)) = (player, self.active_mut()) else {
+			return false;
+		};
+
+		let mut changed = false;
+		for event in events {
+			if let GameEvent::MatchEnded { winner, stats, .. } = event {
+				profile.stats.record(player, *winner == player, stats);
+				changed = true;
+			}
+		}
+		changed
+	}
+}
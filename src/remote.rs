@@ -0,0 +1,188 @@
+// Dependencies
+
+use std::io;
+use std::net::{TcpListener, SocketAddr, IpAddr};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Sender, Receiver};
+use std::thread;
+
+use serde::Serialize;
+
+use crate::game::{Command, Difficulty};
+use crate::scene::{Context, SceneManager, Transition};
+use crate::scenes::PlayingScene;
+use crate::http::{self, Request, Response};
+
+// Implement the changes an operator can make to the game remotely, e.g. from a kiosk's management dashboard.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RemoteCommand {
+	// Throw away whatever's going on and start a new match.
+	StartMatch,
+	// Go back to the main menu.
+	StopMatch,
+	// Change the AI's difficulty, the same as in the settings.
+	SetDifficulty(Difficulty)
+}
+
+impl RemoteCommand {
+	// Make the change to the game, from whichever scene it's on.
+	pub fn apply(self, ctx: &mut Context, scenes: &mut SceneManager) {
+		match self {
+			RemoteCommand::StartMatch => {
+				scenes.apply(ctx, Transition::PopToRoot);
+				ctx.sim.apply(Command::NewMatch);
+				scenes.apply(ctx, Transition::Push(Box::new(PlayingScene)));
+			},
+			RemoteCommand::StopMatch => scenes.apply(ctx, Transition::PopToRoot),
+			RemoteCommand::SetDifficulty(difficulty) => ctx.change_config(|config| config.gameplay.ai_difficulty = difficulty)
+		}
+	}
+}
+
+// Implement what the server reports about the game, as JSON.
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Status {
+	pub mode: &'static str,
+	pub scores: [u32; 2],
+	// Whether the game is stopped, e.g. on a menu, rather than a match being played.
+	pub paused: bool,
+	pub difficulty: Difficulty
+}
+
+impl Status {
+	pub fn new(ctx: &Context) -> Self {
+		Self {
+			mode: ctx.game.mode.name(),
+			scores: ctx.game.scores,
+			paused: ctx.game.paused,
+			difficulty: ctx.config.gameplay.ai_difficulty
+		}
+	}
+}
+
+// Implement what the server does with a request.
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Route {
+	// Report the status.
+	Status,
+	// Pass a command on to the game.
+	Command(RemoteCommand),
+	// Answer with an error, with its HTTP status line and a message.
+	Error(&'static str, String)
+}
+
+// Work out what to do with a request by its method and path.
+// GET /status reports the score, POST /match/start and /match/stop start and stop matches, and POST /difficulty/<name> changes the difficulty.
+
+pub fn route(method: &str, path: &str) -> Route {
+	let command = match path {
+		"/status" => return match method {
+			"GET" => Route::Status,
+			_ => Route::Error("405 Method Not Allowed", "Use GET\n".to_string())
+		},
+		"/match/start" => RemoteCommand::StartMatch,
+		"/match/stop" => RemoteCommand::StopMatch,
+		_ => match path.strip_prefix("/difficulty/").map(str::parse) {
+			Some(Ok(difficulty)) => RemoteCommand::SetDifficulty(difficulty),
+			Some(Err(err)) => return Route::Error("400 Bad Request", format!("{}\n", err)),
+			None => return Route::Error("404 Not Found", "Not found\n".to_string())
+		}
+	};
+
+	match method {
+		"POST" => Route::Command(command),
+		_ => Route::Error("405 Method Not Allowed", "Use POST\n".to_string())
+	}
+}
+
+// Implement a small HTTP server for managing the game remotely, on its own thread, for kiosks and museum installations.
+// Commands are queued for the game to pick up each frame, and the status is updated by the game each frame.
+// If there's a token, every request has to give it in an `Authorization: Bearer` header.
+// Without one, the server only listens on a loopback address, so nothing else on the network can reach it, and turns away requests from web pages.
+
+pub struct RemoteServer {
+	addr: SocketAddr,
+	status: Arc<Mutex<Option<Status>>>,
+	receiver: Receiver<RemoteCommand>
+}
+
+impl RemoteServer {
+	// Start the server on the given address and port, or any free port if it's 0.
+	// Without a token, a web page open in a browser on this machine could still send it a form, so requests a browser would send cross-site are refused.
+	// Those carry an Origin header, or a Content-Type for the form's body, which a script like curl has no need for.
+	pub fn spawn(address: IpAddr, port: u16, token: Option<String>) -> io::Result<Self> {
+		if token.is_none() && !address.is_loopback() {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("a token is needed to listen on {}", address)));
+		}
+
+		let listener = TcpListener::bind((address, port))?;
+		let addr = listener.local_addr()?;
+		let status = Arc::new(Mutex::new(None));
+		let (sender, receiver) = mpsc::channel();
+
+		let shared = status.clone();
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let result = stream.and_then(|stream| http::respond(stream, |request| respond(request, token.as_deref(), &shared, &sender)));
+				if let Err(err) = result {
+					tracing::warn!("Failed to answer a remote control request: {}", err);
+				}
+			}
+		});
+
+		tracing::info!("Listening for remote control on http://{}", addr);
+		Ok(Self { addr, status, receiver })
+	}
+
+	// Get the address the server's listening on.
+	pub fn addr(&self) -> SocketAddr {
+		self.addr
+	}
+
+	// Report the game's latest status, then apply any commands that have come in since the last frame.
+	pub fn update(&self, ctx: &mut Context, scenes: &mut SceneManager) {
+		for command in self.receiver.try_iter() {
+			tracing::info!("Remote control: {:?}", command);
+			command.apply(ctx, scenes);
+		}
+		*self.status.lock().unwrap() = Some(Status::new(ctx));
+	}
+}
+
+// Check a token against the one expected, taking the same time however much of it matches, so it can't be guessed a byte at a time.
+pub fn token_matches(given: &str, expected: &str) -> bool {
+	given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// Answer a request, once the token's been checked.
+fn respond(request: &Request, token: Option<&str>, status: &Mutex<Option<Status>>, sender: &Sender<RemoteCommand>) -> Response {
+	let given = request.header("authorization").and_then(|value| value.strip_prefix("Bearer "));
+	let authorized = token.is_none_or(|token| given.is_some_and(|given| token_matches(given, token)));
+
+	let from_browser = request.header("origin").is_some() || request.header("content-type").is_some_and(|value| !value.is_empty());
+
+	let route = if !authorized {
+		Route::Error("401 Unauthorized", "Unauthorized\n".to_string())
+	} else if token.is_none() && from_browser {
+		Route::Error("403 Forbidden", "Requests from web pages need a token\n".to_string())
+	} else {
+		route(&request.method, &request.path)
+	};
+
+	match route {
+		Route::Status => match status.lock().unwrap().as_ref().map(serde_json::to_string) {
+			Some(Ok(body)) => Response::new("200 OK", "application/json", body),
+			Some(Err(err)) => Response::text("500 Internal Server Error", format!("{}\n", err)),
+			None => Response::text("503 Service Unavailable", "The game hasn't started yet\n")
+		},
+		Route::Command(command) => {
+			// The game only stops listening when it exits.
+			sender.send(command).ok();
+			Response::text("202 Accepted", "Accepted\n")
+		},
+		Route::Error(status_line, message) => Response::text(status_line, message)
+	}
+}
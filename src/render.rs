@@ -0,0 +1,259 @@
+// Dependencies
+
+// Imports from the Glium library:
+use glium::{
+	// Struct macros.
+	implement_vertex, implement_uniform_block,
+	// Imports for VBOs and VBO Indexing.
+	VertexBuffer, IndexBuffer,
+	Display, Frame, Program, Surface, DrawParameters, Blend,
+	index::PrimitiveType,
+	uniform,
+	uniforms::UniformBuffer,
+	Version, Api
+};
+
+// Imports from Glutin, for choosing the version of OpenGL.
+use glium::glutin::{GlRequest, Api as GlApi};
+
+use pong::structs::Vec2;
+use pong::scene::Renderer;
+use pong::assets::Assets;
+
+use crate::error::Error;
+
+// Get the version of OpenGL to ask for when creating a window.
+// Desktop OpenGL 3.2 by default, or OpenGL ES 2.0 for boards like the Raspberry Pi and phones, which don't have desktop OpenGL.
+
+pub fn gl_request(gles: bool) -> GlRequest {
+	if gles {
+		GlRequest::Specific(GlApi::OpenGlEs, (2, 0))
+	} else {
+		GlRequest::Latest
+	}
+}
+
+// Check whether a window is drawing with OpenGL ES rather than desktop OpenGL.
+
+pub fn is_gles(display: &Display) -> bool {
+	matches!(display.get_opengl_version(), Version(Api::GlEs, ..))
+}
+
+// Check whether a window's desktop OpenGL is new enough for the desktop shaders, which need OpenGL 3.2.
+
+pub fn supports_desktop_shaders(display: &Display) -> bool {
+	matches!(*display.get_opengl_version(), Version(Api::Gl, major, minor) if (major, minor) >= (3, 2))
+}
+
+// Build a program from GLSL source code for a window.
+// This compiles the shaders and links them together for rendering.
+// OpenGL ES only understands GLSL ES, so it has its own copies of the shaders.
+
+pub fn load_program(display: &Display, assets: &mut Assets) -> Result<Program, Error> {
+	let (vertex_key, fragment_key) = if is_gles(display) {
+		("shaders/gles/vertex_shader.vsh", "shaders/gles/fragment_shader.frag")
+	} else {
+		("shaders/vertex_shader.vsh", "shaders/fragment_shader.frag")
+	};
+
+	let vertex_shader = assets.text(vertex_key).ok_or(Error::MissingAsset(vertex_key))?;
+	let fragment_shader = assets.text(fragment_key).ok_or(Error::MissingAsset(fragment_key))?;
+	Ok(Program::from_source(display, &vertex_shader, &fragment_shader, None)?)
+}
+
+// How many rects are drawn together in one draw call at most.
+// Each rect has 4 vertices, which have to fit in 16-bit indices.
+
+pub const BATCH_RECTS: usize = 4096;
+
+// How many batches the vertex buffer has room for, each written to its own part of it in turn.
+// The graphics card can still be drawing the last few batches while the next one's written, without either waiting for the other.
+
+pub const BATCH_SLOTS: usize = 3;
+
+// Implement a Vertex struct used to represent vertices.
+// Each vertex carries the colour of the rect it's a corner of, so rects of different colours can be drawn together.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+	pub position: [f32; 2],
+	pub fill: [f32; 4]
+}
+implement_vertex!(Vertex, position, fill);
+
+// Implement a batch of rects, gathered up over a frame and drawn a few thousand at a time instead of one draw call each.
+// The vertex buffer is made once and kept mapped into the game's memory, so each batch is copied straight into it rather than handed to the driver.
+// The indices never change, so they're built once too.
+
+pub struct Batch {
+	vertices: Vec<Vertex>,
+	vx_buf: VertexBuffer<Vertex>,
+	ix_buf: IndexBuffer<u16>,
+	// Which part of the vertex buffer the next batch is written to.
+	slot: usize
+}
+
+impl Batch {
+	pub fn new(display: &Display) -> Result<Self, Error> {
+		// Persistent mapping needs OpenGL 4.4 or ARB_buffer_storage. Without it, the buffer is written through the driver instead.
+		let size = BATCH_RECTS * 4 * BATCH_SLOTS;
+		let vx_buf = match VertexBuffer::empty_persistent(display, size) {
+			Ok(vx_buf) => vx_buf,
+			Err(_) => VertexBuffer::empty_dynamic(display, size)?
+		};
+		if !vx_buf.is_persistent() {
+			tracing::debug!("Persistent mapping isn't supported, writing vertices through the driver");
+		}
+
+		// Two triangles for each rect, going round its corners.
+		let indices: Vec<u16> = (0..BATCH_RECTS as u16)
+			.flat_map(|rect| [0, 1, 2, 2, 3, 0].map(|corner| rect * 4 + corner))
+			.collect();
+		let ix_buf = IndexBuffer::new(display, PrimitiveType::TrianglesList, &indices)?;
+
+		Ok(Self {
+			vertices: Vec::with_capacity(BATCH_RECTS * 4),
+			vx_buf,
+			ix_buf,
+			slot: 0
+		})
+	}
+
+	pub fn is_full(&self) -> bool {
+		self.vertices.len() >= BATCH_RECTS * 4
+	}
+
+	// Add a rect at the given position and size, in the given colour.
+	pub fn push(&mut self, position: Vec2, size: Vec2, fill: [f32; 4]) {
+		let (min, max) = (position, position + size);
+		self.vertices.extend_from_slice(&[
+			Vertex { position: [min.x, min.y], fill },
+			Vertex { position: [max.x, min.y], fill },
+			Vertex { position: [max.x, max.y], fill },
+			Vertex { position: [min.x, max.y], fill }
+		]);
+	}
+}
+
+// Implement the uniform block the desktop vertex shader shares between draw calls.
+
+#[derive(Copy, Clone, Debug)]
+pub struct FrameBlock {
+	pub perspective: [[f32; 4]; 4]
+}
+implement_uniform_block!(FrameBlock, perspective);
+
+// Implement the uniforms that are the same for every draw call in a frame.
+// On desktop OpenGL they're kept in a uniform buffer on the graphics card, written only when they change, instead of being uploaded again for every batch.
+// OpenGL ES 2.0 has no uniform buffers, so there they're uploaded with each draw call.
+
+pub struct FrameUniforms {
+	buffer: Option<UniformBuffer<FrameBlock>>,
+	perspective: [[f32; 4]; 4]
+}
+
+impl FrameUniforms {
+	pub fn new(display: &Display) -> Result<Self, Error> {
+		let perspective = [[0.0; 4]; 4];
+		let buffer = if is_gles(display) {
+			None
+		} else {
+			Some(UniformBuffer::dynamic(display, FrameBlock { perspective })?)
+		};
+
+		Ok(Self {
+			buffer,
+			perspective
+		})
+	}
+
+	// Change the perspective matrix, e.g. after the window's been resized.
+	pub fn set_perspective(&mut self, perspective: [[f32; 4]; 4]) {
+		self.perspective = perspective;
+		if let Some(buffer) = &self.buffer {
+			buffer.write(&FrameBlock { perspective });
+		}
+	}
+}
+
+// Implement the scene renderer for Glium.
+// Rects are gathered into a batch and drawn together, all blended over what's already there, which draws solid rects the same as not blending them.
+// The batch is drawn whenever it fills up and when the renderer's finished with, so nothing drawn later ends up underneath.
+
+pub struct GliumRenderer<'a> {
+	pub frame: &'a mut Frame,
+	pub batch: &'a mut Batch,
+	pub program: &'a Program,
+	pub uniforms: &'a FrameUniforms
+}
+
+impl GliumRenderer<'_> {
+	fn push(&mut self, position: Vec2, size: Vec2, fill: [f32; 4]) {
+		if self.batch.is_full() {
+			self.flush();
+		}
+		self.batch.push(position, size, fill);
+	}
+
+	// Draw every rect in the batch, then empty it.
+	pub fn flush(&mut self) {
+		let rects = self.batch.vertices.len() / 4;
+		if rects == 0 {
+			return;
+		}
+
+		// Write the batch to the next part of the buffer, leaving the parts the last batches were drawn from alone.
+		let start = self.batch.slot * BATCH_RECTS * 4;
+		self.batch.slot = (self.batch.slot + 1) % BATCH_SLOTS;
+		let vertices = self.batch.vx_buf.slice(start..start + rects * 4).expect("a batch never holds more than BATCH_RECTS rects");
+		vertices.write(&self.batch.vertices);
+		let indices = self.batch.ix_buf.slice(0..rects * 6).expect("a batch never holds more than BATCH_RECTS rects");
+		self.batch.vertices.clear();
+
+		let params = DrawParameters {
+			blend: Blend::alpha_blending(),
+			..Default::default()
+		};
+
+		let result = match &self.uniforms.buffer {
+			Some(buffer) => self.frame.draw(vertices, indices, self.program, &uniform!{
+				Frame: buffer
+			}, &params),
+			None => self.frame.draw(vertices, indices, self.program, &uniform!{
+				perspective: self.uniforms.perspective
+			}, &params)
+		};
+
+		// Draw calls only fail on invalid parameters, which would be a bug rather than something the player can fix.
+		// Skip the batch rather than crashing the game.
+		if let Err(err) = result {
+			tracing::warn!("Failed to draw {} rects: {}", rects, err);
+		}
+	}
+}
+
+impl Drop for GliumRenderer<'_> {
+	fn drop(&mut self) {
+		self.flush();
+	}
+}
+
+impl Renderer for GliumRenderer<'_> {
+	fn rect(&mut self, position: Vec2, size: Vec2) {
+		self.push(position, size, [1.0, 1.0, 1.0, 1.0]);
+	}
+
+	// Draw a see-through black rect over what's already there.
+	fn shade(&mut self, position: Vec2, size: Vec2, opacity: f32) {
+		self.push(position, size, [0.0, 0.0, 0.0, opacity.clamp(0.0, 1.0)]);
+	}
+
+	// Draw a see-through white rect over what's already there.
+	fn fade(&mut self, position: Vec2, size: Vec2, opacity: f32) {
+		self.push(position, size, [1.0, 1.0, 1.0, opacity.clamp(0.0, 1.0)]);
+	}
+
+	fn fill(&mut self, position: Vec2, size: Vec2, colour: [f32; 4]) {
+		self.push(position, size, colour.map(|channel| channel.clamp(0.0, 1.0)));
+	}
+}
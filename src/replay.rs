@@ -0,0 +1,271 @@
+// Dependencies
+
+use std::fs;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use crate::game::{GameState, Command, TICK_RATE};
+use crate::paths;
+
+// The version of the replay format. Replays saved in a different version are refused rather than played back wrong.
+
+pub const REPLAY_VERSION: u32 = 1;
+
+// How often playback keeps a copy of the match, in ticks, so seeking backwards doesn't have to simulate from the start.
+
+pub const KEYFRAME_INTERVAL: u64 = 600;
+
+// How far back an instant replay goes, in ticks, and how often the replay buffer keeps a copy of the match to start one from.
+
+pub const INSTANT_REPLAY_LENGTH: u64 = 3 * TICK_RATE as u64;
+pub const INSTANT_REPLAY_INTERVAL: u64 = TICK_RATE as u64 / 2;
+
+// Implement a recording of a match.
+// The simulation is deterministic, so a replay only needs the match as it was when recording started, including the random number generator's seed,
+// and every command applied to it along with the tick it came before. Playing it back simulates the match again from there.
+// Without the `deterministic` feature, replays are only guaranteed to play back the same on the platform they were recorded on.
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+	pub version: u32,
+	// The match when recording started.
+	pub start: GameState,
+	// Every command applied, with how many ticks into the recording it was applied.
+	pub commands: Vec<(u64, Command)>,
+	// How many ticks the recording lasts.
+	pub length: u64
+}
+
+impl Replay {
+	// Start a recording of a match from its current state.
+	pub fn new(start: GameState) -> Self {
+		Self {
+			version: REPLAY_VERSION,
+			start,
+			commands: vec![],
+			length: 0
+		}
+	}
+
+	// Record a command applied the given number of ticks into the recording.
+	// The player's paddle can be moved many times between ticks, but only where it ends up matters, so only the last move before each tick is kept.
+	pub fn record(&mut self, tick: u64, command: Command) {
+		if let (Some((last_tick, Command::MovePaddle { .. })), Command::MovePaddle { .. }) = (self.commands.last(), &command) {
+			if *last_tick == tick {
+				self.commands.pop();
+			}
+		}
+
+		self.commands.push((tick, command));
+		self.length = self.length.max(tick);
+	}
+
+	// Get the directory replays are saved to in the platform's data directory.
+	pub fn dir() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("replays"))
+	}
+
+	// Save the replay to the replays directory, named after the current time.
+	pub fn save(&self) {
+		let Some(dir) = Self::dir() else {
+			tracing::warn!("No data directory on this platform, replay not saved");
+			return;
+		};
+
+		let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+		let path = dir.join(format!("match-{}.replay", secs));
+		match self.save_to(&path) {
+			Ok(()) => tracing::info!("Saved replay to {}", path.display()),
+			Err(err) => tracing::warn!("Failed to save replay to {}: {}", path.display(), err)
+		}
+	}
+
+	// Write the replay to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let data = bincode::serialize(self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		fs::write(path, data)
+	}
+
+	// Read a replay from a given path.
+	pub fn load_from(path: &Path) -> Result<Self, String> {
+		let data = fs::read(path).map_err(|err| format!("Failed to read replay {}: {}", path.display(), err))?;
+
+		// Check the version before anything else, since the rest of the format can change between versions.
+		let version: u32 = bincode::deserialize(&data).map_err(|err| format!("Invalid replay {}: {}", path.display(), err))?;
+		if version != REPLAY_VERSION {
+			return Err(format!("Replay {} is version {}, expected version {}", path.display(), version, REPLAY_VERSION));
+		}
+
+		bincode::deserialize(&data).map_err(|err| format!("Invalid replay {}: {}", path.display(), err))
+	}
+}
+
+// Implement playback of a replay, simulating the match again tick by tick.
+
+pub struct Playback {
+	replay: Replay,
+	// The match as of the current tick.
+	game: GameState,
+	// Ticks played back so far, and the index of the next command to apply.
+	tick: u64,
+	next: usize,
+	// Copies of the match taken every KEYFRAME_INTERVAL ticks, along with the index of the next command at that point.
+	keyframes: Vec<(GameState, usize)>
+}
+
+impl Playback {
+	// Start playing back a replay from its first tick.
+	pub fn new(replay: Replay) -> Self {
+		let mut game = replay.start.clone();
+		game.paused = false;
+
+		Self {
+			keyframes: vec![(game.clone(), 0)],
+			replay,
+			game,
+			tick: 0,
+			next: 0
+		}
+	}
+
+	// The match as of the current tick.
+	pub fn game(&self) -> &GameState {
+		&self.game
+	}
+
+	// Get the mutable match, e.g. to consume its events.
+	pub fn game_mut(&mut self) -> &mut GameState {
+		&mut self.game
+	}
+
+	// How many ticks into the replay playback is.
+	pub fn tick(&self) -> u64 {
+		self.tick
+	}
+
+	// How many ticks the replay lasts.
+	pub fn length(&self) -> u64 {
+		self.replay.length
+	}
+
+	// Check if playback has reached the end of the replay.
+	pub fn is_finished(&self) -> bool {
+		self.tick >= self.replay.length
+	}
+
+	// Play back a number of ticks, stopping at the end of the replay.
+	pub fn step(&mut self, ticks: u64) {
+		for _ in 0..ticks {
+			if self.is_finished() {
+				return;
+			}
+
+			// Apply every command that came before this tick.
+			while let Some((tick, command)) = self.replay.commands.get(self.next) {
+				if *tick > self.tick {
+					break;
+				}
+				self.game.apply(command.clone());
+				self.next += 1;
+			}
+
+			self.game.step(1);
+			self.tick += 1;
+
+			if self.tick.is_multiple_of(KEYFRAME_INTERVAL) && self.keyframes.len() as u64 == self.tick / KEYFRAME_INTERVAL {
+				self.keyframes.push((self.game.clone(), self.next));
+			}
+		}
+	}
+
+	// Jump to the given tick, clamped to the length of the replay.
+	// Seeking backwards starts again from the last keyframe before it.
+	pub fn seek(&mut self, tick: u64) {
+		let tick = tick.min(self.replay.length);
+
+		if tick < self.tick {
+			let index = ((tick / KEYFRAME_INTERVAL) as usize).min(self.keyframes.len() - 1);
+			let (game, next) = &self.keyframes[index];
+			self.game = game.clone();
+			self.next = *next;
+			self.tick = index as u64 * KEYFRAME_INTERVAL;
+		}
+
+		// Anything that happened on the way there has already been skipped over.
+		self.step(tick - self.tick);
+		self.game.events.drain();
+	}
+}
+
+// Implement a rolling buffer of the last few seconds of a match, which an instant replay is made from after each point.
+// Like a recording, it keeps every command applied, but only back as far as the copy of the match an instant replay would start from.
+
+#[derive(Default)]
+pub struct ReplayBuffer {
+	// Copies of the match, oldest first, each with how many commands had been recorded when it was taken.
+	keyframes: VecDeque<(GameState, u64)>,
+	// Commands applied since the oldest copy, with the tick they came before, and how many older ones have been dropped.
+	commands: VecDeque<(u64, Command)>,
+	dropped: u64
+}
+
+impl ReplayBuffer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Record a command applied to the match before the given tick.
+	pub fn record(&mut self, tick: u64, command: Command) {
+		self.commands.push_back((tick, command));
+	}
+
+	// Throw away everything kept, e.g. when the match is replaced with one the commands so far don't lead to.
+	pub fn clear(&mut self) {
+		self.keyframes.clear();
+		self.dropped += self.commands.len() as u64;
+		self.commands.clear();
+	}
+
+	// Keep a copy of the match if it's been long enough since the last one, then drop anything older than an instant replay needs.
+	pub fn update(&mut self, game: &GameState) {
+		let last = self.keyframes.back().map(|(keyframe, _)| keyframe.ticks);
+		if last.is_some_and(|last| game.ticks < last) {
+			self.clear();
+		} else if last.is_some_and(|last| game.ticks < last + INSTANT_REPLAY_INTERVAL) {
+			return;
+		}
+
+		let mut keyframe = game.clone();
+		keyframe.events.drain();
+		self.keyframes.push_back((keyframe, self.dropped + self.commands.len() as u64));
+
+		// Only the latest copy from at least an instant replay's length ago is needed to start one from.
+		while self.keyframes.get(1).is_some_and(|(keyframe, _)| keyframe.ticks + INSTANT_REPLAY_LENGTH <= game.ticks) {
+			self.keyframes.pop_front();
+		}
+		let first = self.keyframes[0].1;
+		while self.dropped < first {
+			self.commands.pop_front();
+			self.dropped += 1;
+		}
+	}
+
+	// Make a replay from the oldest copy kept up to the given tick, or None if nothing's been kept yet.
+	// It can start a little further back than an instant replay goes, so playback should seek to INSTANT_REPLAY_LENGTH before the end.
+	pub fn replay(&self, tick: u64) -> Option<Replay> {
+		let (start, first) = self.keyframes.front()?;
+		let mut replay = Replay::new(start.clone());
+		for (command_tick, command) in self.commands.iter().skip((first - self.dropped) as usize) {
+			replay.record(command_tick - start.ticks, command.clone());
+		}
+		replay.length = tick.saturating_sub(start.ticks);
+		Some(replay)
+	}
+}
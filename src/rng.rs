@@ -0,0 +1,69 @@
+// Dependencies
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+// Implement a small seedable random number generator (xorshift64*).
+// The same seed always produces the same sequence on every platform, so anything random in the simulation
+// (serve angles, power-up spawns, arena generation) can be reproduced from the seed alone.
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Rng {
+	seed: u64,
+	state: u64
+}
+
+impl Rng {
+	// Build a new generator from a seed.
+	pub fn new(seed: u64) -> Self {
+		// Scramble the seed with SplitMix64 so that similar seeds give unrelated sequences, and so the state is never zero.
+		let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		state ^= state >> 31;
+
+		Self {
+			seed,
+			state: if state == 0 { 1 } else { state }
+		}
+	}
+
+	// Build a new generator seeded from the current time.
+	pub fn from_time() -> Self {
+		let seed = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|time| time.as_nanos() as u64)
+			.unwrap_or(0);
+		Self::new(seed)
+	}
+
+	// Get the seed this generator was built from.
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	// Generate the next random 64-bit number.
+	pub fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+		self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+	}
+
+	// Generate a random number from 0.0 (inclusive) to 1.0 (exclusive).
+	pub fn next_f32(&mut self) -> f32 {
+		// Use the top 24 bits, which is all the precision an f32 has.
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	// Generate a random number between min (inclusive) and max (exclusive).
+	pub fn range(&mut self, min: f32, max: f32) -> f32 {
+		min + (max - min) * self.next_f32()
+	}
+
+	// Return true with the given probability, from 0.0 to 1.0.
+	pub fn chance(&mut self, probability: f32) -> bool {
+		self.next_f32() < probability
+	}
+}
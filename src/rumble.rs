@@ -0,0 +1,85 @@
+// Dependencies
+
+use crate::structs::ObjectType;
+use crate::events::GameEvent;
+use crate::game::GameState;
+
+// How long each kind of pulse lasts, in milliseconds.
+
+pub const HIT_PULSE_TIME: f32 = 80.0;
+pub const GOAL_PULSE_TIME: f32 = 250.0;
+
+// How strong a hit feels with the ball barely moving, so even the slowest hit can be felt. Hits get stronger from here up to full strength at the ball's top speed.
+
+pub const MIN_HIT_STRENGTH: f32 = 0.25;
+
+// How strong a goal feels, from 0.0 to 1.0. Letting one in is felt more than scoring one.
+
+pub const SCORED_STRENGTH: f32 = 0.5;
+pub const CONCEDED_STRENGTH: f32 = 1.0;
+
+// Implement a single pulse of a controller's rumble motors.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pulse {
+	// How hard the motors shake, from 0.0 to 1.0.
+	pub strength: f32,
+	// How long it lasts, in milliseconds.
+	pub duration: f32
+}
+
+// Implement rumble feedback for a player on a gamepad, shaking the controller when they hit the ball and when a point is scored.
+// Whichever library drives the controller takes the pulses from here, so only the strongest one since it last looked is kept.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Rumble {
+	// Whether the player is steering with a gamepad, rather than the mouse.
+	pub gamepad: bool,
+	pending: Option<Pulse>
+}
+
+impl Rumble {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Queue a pulse for anything in this frame's events the player should feel, if rumble is turned on and they're on a gamepad.
+	// Hits are stronger the faster the ball comes off the paddle.
+	pub fn record(&mut self, game: &GameState, events: &[GameEvent], enabled: bool) {
+		let Some(player) = game.control_side().filter(|_| enabled && self.gamepad) else {
+			return;
+		};
+
+		for event in events {
+			let pulse = match event {
+				GameEvent::BallHitPaddle { side, .. } if *side == player => Pulse {
+					strength: MIN_HIT_STRENGTH + (1.0 - MIN_HIT_STRENGTH) * ball_speed(game),
+					duration: HIT_PULSE_TIME
+				},
+				GameEvent::PointScored { side, .. } => Pulse {
+					strength: if *side == player { SCORED_STRENGTH } else { CONCEDED_STRENGTH },
+					duration: GOAL_PULSE_TIME
+				},
+				// Ignore anything else.
+				_ => continue
+			};
+
+			if self.pending.is_none_or(|pending| pulse.strength > pending.strength) {
+				self.pending = Some(pulse);
+			}
+		}
+	}
+
+	// Take the pulse waiting to be played, if there is one.
+	pub fn take(&mut self) -> Option<Pulse> {
+		self.pending.take()
+	}
+}
+
+// Get how fast the fastest ball is going, from 0.0 when it's still to 1.0 at its top speed.
+fn ball_speed(game: &GameState) -> f32 {
+	game.objects.iter()
+		.filter(|obj| obj.obj_type == ObjectType::Ball && obj.max_velocity.length() > 0.0)
+		.map(|obj| (obj.velocity.length() / obj.max_velocity.length()).clamp(0.0, 1.0))
+		.fold(0.0, f32::max)
+}
@@ -0,0 +1,88 @@
+// Dependencies
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::game::GameState;
+use crate::paths;
+
+// The version of the saved match format. Matches saved in a different version are thrown away rather than loaded wrong.
+
+pub const SAVE_VERSION: u32 = 1;
+
+// Implement a match saved when the game quits, so it can be continued on the next launch.
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedMatch {
+	pub version: u32,
+	pub game: GameState
+}
+
+impl SavedMatch {
+	pub fn new(game: GameState) -> Self {
+		Self {
+			version: SAVE_VERSION,
+			game
+		}
+	}
+
+	// Get the path of the saved match in the platform's data directory.
+	pub fn path() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("match.save"))
+	}
+
+	// Load the saved match, if there is one.
+	// A match that can't be loaded is reported and treated as if there wasn't one.
+	pub fn load() -> Option<Self> {
+		let path = Self::path()?;
+		if !path.exists() {
+			return None;
+		}
+
+		Self::load_from(&path)
+			.map_err(|err| tracing::warn!("{}, starting a new match", err))
+			.ok()
+	}
+
+	// Read a saved match from a given path.
+	pub fn load_from(path: &Path) -> Result<Self, String> {
+		let data = fs::read(path).map_err(|err| format!("Failed to read saved match {}: {}", path.display(), err))?;
+
+		// Check the version before anything else, since the rest of the format can change between versions.
+		let version: u32 = bincode::deserialize(&data).map_err(|err| format!("Invalid saved match {}: {}", path.display(), err))?;
+		if version != SAVE_VERSION {
+			return Err(format!("Saved match {} is version {}, expected version {}", path.display(), version, SAVE_VERSION));
+		}
+
+		bincode::deserialize(&data).map_err(|err| format!("Invalid saved match {}: {}", path.display(), err))
+	}
+
+	// Save the match to the platform's data directory, reporting any failure.
+	pub fn save(&self) {
+		let Some(path) = Self::path() else { return };
+		if let Err(err) = self.save_to(&path) {
+			tracing::warn!("Failed to save match to {}: {}", path.display(), err);
+		}
+	}
+
+	// Write the match to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let data = bincode::serialize(self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		fs::write(path, data)
+	}
+
+	// Remove the saved match at a given path once it's been continued or thrown away, so it's only offered once.
+	pub fn clear_from(path: &Path) {
+		if let Err(err) = fs::remove_file(path) {
+			if err.kind() != std::io::ErrorKind::NotFound {
+				tracing::warn!("Failed to remove saved match {}: {}", path.display(), err);
+			}
+		}
+	}
+}
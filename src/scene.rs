@@ -0,0 +1,307 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::game::{GameState, Command};
+use crate::events::GameEvent;
+use crate::sim::Simulation;
+use crate::input::Input;
+use crate::config::{Config, WindowConfig};
+use crate::scores::HighScores;
+use crate::profiles::Profiles;
+use crate::toast::Toasts;
+use crate::callout::Callouts;
+use crate::rumble::Rumble;
+use crate::tutorial::Tutorial;
+use crate::locale::Locale;
+use crate::theme::{self, Theme};
+use crate::particles::Particles;
+
+// Implement a trait for anything that can draw a scene.
+// Scenes only describe what to draw, the window layer decides how to draw it.
+
+pub trait Renderer {
+	// Draw a filled rectangle, with its position at the top-left corner.
+	fn rect(&mut self, position: Vec2, size: Vec2);
+
+	// Darken a rectangle of whatever's already been drawn, with an opacity from 0.0 (no change) to 1.0 (black).
+	// Renderers that can only draw in one colour leave it out.
+	fn shade(&mut self, _position: Vec2, _size: Vec2, _opacity: f32) {}
+
+	// Draw a filled rectangle that's only partly there, with an opacity from 0.0 (invisible) to 1.0 (the same as rect).
+	// Renderers that can only draw in one colour draw it solid until it's less than half there.
+	fn fade(&mut self, position: Vec2, size: Vec2, opacity: f32) {
+		if opacity >= 0.5 {
+			self.rect(position, size);
+		}
+	}
+
+	// Draw a filled rectangle in the given colour, as red, green, blue and opacity from 0.0 to 1.0.
+	// Renderers that can only draw in one colour draw it like a fade, going by its opacity alone.
+	fn fill(&mut self, position: Vec2, size: Vec2, colour: [f32; 4]) {
+		self.fade(position, size, colour[3]);
+	}
+}
+
+// Context shared between all scenes.
+// This holds the running simulation, a snapshot of its state for the current frame, and requests for the window layer.
+
+pub struct Context {
+	pub sim: Simulation,
+	// Settings loaded from the config file, with any command-line overrides.
+	pub config: Config,
+	// Settings to write back to the config file, without the command-line overrides, and whether they've been changed in-game.
+	pub saved_config: Config,
+	pub config_changed: bool,
+	// The player's best results and the local player profiles, loaded by the window layer.
+	pub high_scores: HighScores,
+	pub profiles: Profiles,
+	// Text to show the player, in the language chosen in the settings.
+	pub locale: Locale,
+	// Snapshot of the game state for this frame, and how far it is between ticks.
+	pub game: GameState,
+	pub alpha: f32,
+	// Events emitted by the simulation since the last frame.
+	pub events: Vec<GameEvent>,
+	// Dimensions of the window.
+	pub width: f32,
+	pub height: f32,
+	pub focused: bool,
+	// Whether the window should capture and hide the cursor.
+	pub cursor_grabbed: bool,
+	// Messages shown over every scene for a moment, in the order they were sent.
+	pub toasts: Toasts,
+	// The announcer's callouts shown mid-court during a match.
+	pub callouts: Callouts,
+	// Pulses waiting to shake the player's controller.
+	pub rumble: Rumble,
+	// The tutorial being shown over the match, while the player's working through it.
+	pub tutorial: Option<Tutorial>,
+	// The holiday theme for today's date, if it's a holiday. The window layer checks the date, so nothing else depends on it.
+	pub season: Option<&'static Theme>,
+	// Particles from the theme's emitter, drawn behind every scene.
+	pub particles: Particles
+}
+
+impl Context {
+	pub fn new(sim: Simulation, config: Config) -> Self {
+		let (game, alpha) = sim.snapshot();
+		Self {
+			sim,
+			saved_config: config.clone(),
+			config_changed: false,
+			config,
+			high_scores: HighScores::default(),
+			profiles: Profiles::default(),
+			locale: Locale::default(),
+			game,
+			alpha,
+			events: vec![],
+			width: 0.0,
+			height: 0.0,
+			focused: false,
+			cursor_grabbed: false,
+			toasts: Toasts::default(),
+			callouts: Callouts::default(),
+			rumble: Rumble::default(),
+			tutorial: None,
+			season: None,
+			particles: Particles::default()
+		}
+	}
+
+	// Resize the window and the play area with it.
+	pub fn resize(&mut self, width: f32, height: f32) {
+		self.width = width;
+		self.height = height;
+		self.sim.apply(Command::Resize { width, height });
+	}
+
+	// Apply settings from a reloaded config file.
+	// Window settings other than fullscreen only take effect the next time the game starts, everything else applies straight away.
+	pub fn apply_config(&mut self, mut config: Config) {
+		let window = WindowConfig { fullscreen: self.config.window.fullscreen, ..config.window.clone() };
+		if window != self.config.window {
+			tracing::info!("Window settings changed, restart the game to apply them");
+			config.window = WindowConfig { fullscreen: config.window.fullscreen, ..self.config.window.clone() };
+		}
+
+		if config == self.config {
+			return;
+		}
+
+		self.sim.apply(Command::SetAiAccuracy(config.gameplay.ai_difficulty.ai_accuracy()));
+		self.sim.apply(Command::SetWinScore(config.gameplay.win_score));
+		self.sim.apply(Command::SetServeDelay(config.gameplay.serve_delay()));
+
+		self.config = config;
+		self.events.push(GameEvent::ConfigChanged);
+	}
+
+	// Change settings from inside the game, e.g. from a settings menu.
+	// The change applies straight away, and is made to the saved settings too so the window layer can write them back to the config file on exit.
+	pub fn change_config(&mut self, change: impl Fn(&mut Config)) {
+		change(&mut self.saved_config);
+		self.config_changed = true;
+
+		let mut config = self.config.clone();
+		change(&mut config);
+		self.apply_config(config);
+	}
+
+	// Show a message over every scene for a moment, after any already waiting.
+	pub fn show_toast(&mut self, message: impl Into<String>) {
+		self.toasts.push(message);
+	}
+
+	// Queue toasts for this frame's events, then count down the toasts being shown by the time since the last frame, in milliseconds.
+	pub fn update_toasts(&mut self, delta_time: f32) {
+		self.toasts.record(&self.events, &self.locale);
+		self.toasts.update(delta_time);
+	}
+
+	// Get the theme to draw with: today's holiday theme if there is one and they're turned on, otherwise the one chosen in the config file.
+	pub fn theme(&self) -> &'static Theme {
+		match self.season {
+			Some(season) if self.config.seasonal_themes => season,
+			_ => theme::find(&self.config.theme)
+		}
+	}
+
+	// Move the theme's particles on by the time since the last frame, in milliseconds.
+	// New ones are only spawned while the window has focus, so the game can go idle in the background once they've cleared.
+	pub fn update_particles(&mut self, delta_time: f32) {
+		let emitter = self.theme().emitter;
+		self.particles.update(emitter, self.focused, delta_time, self.width, self.height);
+	}
+
+	// Collect the simulation's events and take a new snapshot of its state for this frame.
+	pub fn refresh(&mut self) {
+		self.events = self.sim.drain_events();
+		(self.game, self.alpha) = self.sim.snapshot();
+	}
+}
+
+// What the scene manager should do after a scene handles an update or input.
+
+pub enum Transition {
+	// Stay on the current scene.
+	None,
+	// Push a new scene on top of this one.
+	Push(Box<dyn Scene>),
+	// Remove this scene, returning to the one below it.
+	Pop,
+	// Remove every scene but the one at the bottom of the stack, e.g. to go back to the main menu.
+	PopToRoot,
+	// Replace this scene with another.
+	Replace(Box<dyn Scene>),
+	// Exit the game.
+	Quit
+}
+
+// Implement a trait representing a scene, such as a menu or gameplay.
+
+pub trait Scene {
+	// Called whenever this scene becomes the active scene, either by being pushed or by the scene above it being popped.
+	fn enter(&mut self, _ctx: &mut Context) {}
+
+	// Called once per frame while this scene is active.
+	fn update(&mut self, _ctx: &mut Context) -> Transition {
+		Transition::None
+	}
+
+	// Draw this scene. Every scene in the stack is drawn from the bottom up, so overlays are drawn over the scenes below them.
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer);
+
+	// Whether this scene covers the whole window, so the scenes below it aren't drawn at all.
+	fn opaque(&self) -> bool {
+		false
+	}
+
+	// Respond to an input event while this scene is active.
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition;
+}
+
+// Implement a stack-based scene manager.
+// Only the scene at the top of the stack is updated and receives input.
+
+#[derive(Default)]
+pub struct SceneManager {
+	stack: Vec<Box<dyn Scene>>
+}
+
+impl SceneManager {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Push a scene on top of the stack, making it active.
+	pub fn push(&mut self, ctx: &mut Context, mut scene: Box<dyn Scene>) {
+		scene.enter(ctx);
+		self.stack.push(scene);
+	}
+
+	// Check if there are no scenes left, meaning the game should exit.
+	pub fn is_empty(&self) -> bool {
+		self.stack.is_empty()
+	}
+
+	// Update the active scene.
+	pub fn update(&mut self, ctx: &mut Context) {
+		let _span = tracing::trace_span!("scene_update").entered();
+		if let Some(scene) = self.stack.last_mut() {
+			let transition = scene.update(ctx);
+			self.apply(ctx, transition);
+		}
+	}
+
+	// Draw the theme's particles, then every scene in the stack from the bottom up, starting at the highest one covering the whole window, then any toasts over the top of them.
+	pub fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let _span = tracing::trace_span!("scene_render").entered();
+		ctx.particles.render(renderer);
+
+		let bottom = self.stack.iter().rposition(|scene| scene.opaque()).unwrap_or(0);
+		for scene in &self.stack[bottom..] {
+			scene.render(ctx, renderer);
+		}
+
+		ctx.toasts.render(renderer, ctx.width, ctx.height);
+	}
+
+	// Pass an input event to the active scene.
+	pub fn handle_input(&mut self, ctx: &mut Context, input: Input) {
+		let _span = tracing::trace_span!("input", ?input).entered();
+		if let Input::Focused(focus) = input {
+			ctx.focused = focus;
+		}
+
+		if let Some(scene) = self.stack.last_mut() {
+			let transition = scene.handle_input(ctx, input);
+			self.apply(ctx, transition);
+		}
+	}
+
+	// Apply a transition returned by the active scene, or asked for from outside the scenes, e.g. by remote control.
+	pub fn apply(&mut self, ctx: &mut Context, transition: Transition) {
+		match transition {
+			Transition::None => (),
+			Transition::Push(scene) => self.push(ctx, scene),
+			Transition::Pop => {
+				self.stack.pop();
+				if let Some(scene) = self.stack.last_mut() {
+					scene.enter(ctx);
+				}
+			},
+			Transition::PopToRoot => {
+				self.stack.truncate(1);
+				if let Some(scene) = self.stack.last_mut() {
+					scene.enter(ctx);
+				}
+			},
+			Transition::Replace(scene) => {
+				self.stack.pop();
+				self.push(ctx, scene);
+			},
+			Transition::Quit => self.stack.clear()
+		}
+	}
+}
@@ -0,0 +1,246 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::game::Command;
+use crate::events::Side;
+use crate::stats::MatchStats;
+use crate::modes::{self, GameMode};
+use crate::widget::{Widget, Bounds, ListMenu, MenuAction};
+use crate::scenes::{NameEntryScene, NameFor};
+use crate::text;
+
+// How much the results darken the court behind them, from 0.0 to 1.0.
+
+pub const GAME_OVER_SHADE: f32 = 0.8;
+
+// Thickness of the score timeline's middle line, in multiples of the text's pixel size.
+
+const TIMELINE_LINE: f32 = 0.5;
+
+// Implement the entries on the game over screen, from top to bottom.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameOverEntry {
+	// Play again in the same mode.
+	Rematch,
+	// Play again in another mode, picked with left and right.
+	ChangeMode,
+	// Leave for the main menu.
+	BackToMenu
+}
+
+impl GameOverEntry {
+	pub const ALL: [GameOverEntry; 3] = [GameOverEntry::Rematch, GameOverEntry::ChangeMode, GameOverEntry::BackToMenu];
+}
+
+// Implement the results screen shown when a match ends.
+// This sits on top of the gameplay scene with the simulation stopped, showing the final score and how the match went until the player picks what's next.
+// Under the stats, a timeline shows who was ahead after each point, with a bar above the line for the left side leading and below it for the right.
+// Entries are chosen the same way as on the main menu.
+
+pub struct GameOverScene {
+	pub winner: Side,
+	pub scores: [u32; 2],
+	pub stats: MatchStats,
+	// The mode Change Mode switches to.
+	pub mode: &'static dyn GameMode,
+	pub menu: ListMenu
+}
+
+impl GameOverScene {
+	// Show the results of a match, offering the next mode in the registry after the one just played.
+	pub fn new(ctx: &Context, winner: Side, scores: [u32; 2], stats: MatchStats) -> Self {
+		let mut scene = Self {
+			winner,
+			scores,
+			stats,
+			mode: ctx.game.mode,
+			menu: ListMenu::new(0)
+		};
+		scene.cycle_mode(ctx, 1);
+		scene
+	}
+
+	// Size of the text's pixels and the space each entry takes up, sized to fit the window's height.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 120.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 5.0) * scale)
+	}
+
+	// Get the top of the given entry.
+	fn entry_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.74 + index as f32 * line_height
+	}
+
+	// Get the area of each entry that can be pointed at.
+	pub fn widgets(ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		(0..GameOverEntry::ALL.len()).map(|index| Widget::button(ListMenu::row(ctx, Self::entry_top(ctx, index), line_height))).collect()
+	}
+
+	// Pick the next or previous mode for Change Mode, skipping the one just played if there's any other.
+	fn cycle_mode(&mut self, ctx: &Context, step: isize) {
+		let count = modes::MODES.len() as isize;
+		let mut index = modes::MODES.iter().position(|mode| *mode == self.mode).unwrap_or(0) as isize;
+		for _ in 0..count {
+			index = (index + step).rem_euclid(count);
+			if *modes::MODES[index as usize] != *ctx.game.mode {
+				break;
+			}
+		}
+		self.mode = modes::MODES[index as usize];
+	}
+
+	// Get the heading, from the point of view of the player if they played, by name if they're playing as a profile.
+	pub fn heading(&self, ctx: &Context) -> String {
+		let locale = &ctx.locale;
+		let name = ctx.profiles.active.as_ref();
+		match (ctx.game.control_side(), name) {
+			(Some(player), Some(name)) if player == self.winner => locale.format("game-over.name-wins", &[("name", name)]),
+			(Some(_), Some(name)) => locale.format("game-over.name-loses", &[("name", name)]),
+			(Some(player), None) if player == self.winner => locale.get("game-over.win").to_string(),
+			(Some(_), None) => locale.get("game-over.lose").to_string(),
+			(None, _) => {
+				let side = locale.get(match self.winner {
+					Side::Left => "game-over.left",
+					Side::Right => "game-over.right"
+				});
+				locale.format("game-over.side-wins", &[("side", &side)])
+			}
+		}
+	}
+
+	// Get the lines of stats shown under the score, with the left side's numbers first.
+	pub fn stats_lines(&self, ctx: &Context) -> Vec<String> {
+		let locale = &ctx.locale;
+		let stats = &self.stats;
+		vec![
+			locale.format("game-over.hits", &[("left", &stats.hits[0]), ("right", &stats.hits[1])]),
+			locale.format("game-over.aces", &[("left", &stats.aces[0]), ("right", &stats.aces[1])]),
+			locale.format("game-over.longest-rally", &[("hits", &stats.longest_rally), ("average", &format!("{:.1}", stats.average_rally()))]),
+			// Speeds are kept in pixels per millisecond, but shown per second.
+			locale.format("game-over.top-speed", &[("speed", &(stats.max_ball_speed * 1000.0).round())])
+		]
+	}
+
+	// Get the area the score timeline is drawn in, between the stats and the entries.
+	fn timeline_area(ctx: &Context) -> Bounds {
+		Bounds::new(Vec2::new(ctx.width * 0.2, ctx.height * 0.58), Vec2::new(ctx.width * 0.6, ctx.height * 0.12))
+	}
+
+	// Get the bars of the score timeline, one for each point in order, as their top-left corners and sizes.
+	// Each bar's height is how far ahead the leading side was after that point, against the biggest lead of the match.
+	pub fn timeline_bars(&self, ctx: &Context) -> Vec<(Vec2, Vec2)> {
+		let area = Self::timeline_area(ctx);
+		let (left, width, height) = (area.position.x, area.size.x, area.size.y);
+		let middle = area.position.y + height / 2.0;
+
+		let leads: Vec<i64> = self.stats.score_history().iter().skip(1).map(|[left, right]| *left as i64 - *right as i64).collect();
+		let biggest = leads.iter().map(|lead| lead.abs()).max().unwrap_or(0).max(1) as f32;
+		let bar_width = width / leads.len().max(1) as f32;
+
+		leads.iter().enumerate().filter(|(_, lead)| **lead != 0).map(|(index, lead)| {
+			let bar_height = lead.abs() as f32 / biggest * height / 2.0;
+			let top = if *lead > 0 { middle - bar_height } else { middle };
+			(Vec2::new(left + index as f32 * bar_width, top), Vec2::new(bar_width, bar_height))
+		}).collect()
+	}
+
+	// Get the text of each entry, marking the highlighted one.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		self.menu.mark(GameOverEntry::ALL.iter().map(|entry| match entry {
+			GameOverEntry::Rematch => ctx.locale.get("game-over.rematch").to_string(),
+			GameOverEntry::ChangeMode => ctx.locale.format("game-over.change-mode", &[("mode", &self.mode.name())]),
+			GameOverEntry::BackToMenu => ctx.locale.get("game-over.menu").to_string()
+		}))
+	}
+
+	// Do whatever the highlighted entry does.
+	fn choose(&self, ctx: &mut Context) -> Transition {
+		match GameOverEntry::ALL[self.menu.selected] {
+			GameOverEntry::Rematch => {
+				ctx.sim.apply(Command::NewMatch);
+				Transition::Pop
+			},
+			GameOverEntry::ChangeMode => {
+				// Modes can set their own AI accuracy, but the difficulty setting wins, the same as when the game starts.
+				ctx.sim.apply(Command::SetMode(self.mode));
+				ctx.sim.apply(Command::SetAiAccuracy(ctx.config.gameplay.ai_difficulty.ai_accuracy()));
+				Transition::Pop
+			},
+			GameOverEntry::BackToMenu => Transition::PopToRoot
+		}
+	}
+}
+
+impl Scene for GameOverScene {
+	// Stop the simulation and release the cursor.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.menu.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	// Ask a guest for their name if they just set a record, so it can be shown with the high scores.
+	fn update(&mut self, ctx: &mut Context) -> Transition {
+		let guest = ctx.game.control_side().is_some() && ctx.profiles.active.is_none();
+		if guest && !ctx.high_scores.unnamed.is_empty() {
+			return Transition::Push(Box::new(NameEntryScene::new(NameFor::Records)));
+		}
+		Transition::None
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		renderer.shade(Vec2::new(0.0, 0.0), Vec2::new(ctx.width, ctx.height), GAME_OVER_SHADE);
+
+		let (scale, _) = Self::layout(ctx);
+		let middle = ctx.width / 2.0;
+		text::draw_centered(renderer, &self.heading(ctx), middle, ctx.height * 0.08, scale * 2.0);
+
+		let score = ctx.locale.format("game-over.score", &[("left", &self.scores[0]), ("right", &self.scores[1])]);
+		text::draw_centered(renderer, &score, middle, ctx.height * 0.2, scale * 3.0);
+
+		let line_height = (text::GLYPH_HEIGHT + 3.0) * scale;
+		for (index, line) in self.stats_lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, middle, ctx.height * 0.3 + index as f32 * line_height, scale);
+		}
+
+		// Draw the timeline's bars either side of a line through its middle, with a gap between bars when there's room for one.
+		let line = scale * TIMELINE_LINE;
+		let area = Self::timeline_area(ctx);
+		renderer.rect(Vec2::new(area.position.x, area.position.y + (area.size.y - line) / 2.0), Vec2::new(area.size.x, line));
+		for (position, size) in self.timeline_bars(ctx) {
+			let gap = if size.x > scale * 2.0 { scale / 2.0 } else { 0.0 };
+			renderer.rect(position, Vec2::new(size.x - gap, size.y));
+		}
+
+		self.menu.render(renderer, &Self::widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, middle, Self::entry_top(ctx, index), scale);
+		}
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		let changing_mode = GameOverEntry::ALL[self.menu.selected] == GameOverEntry::ChangeMode;
+
+		match input {
+			// Pick another mode while Change Mode is highlighted.
+			Input::KeyPressed(Key::Left) if changing_mode => {
+				self.cycle_mode(ctx, -1);
+				Transition::None
+			},
+			Input::KeyPressed(Key::Right) if changing_mode => {
+				self.cycle_mode(ctx, 1);
+				Transition::None
+			},
+			Input::KeyPressed(Key::Escape) => Transition::PopToRoot,
+			_ => match self.menu.handle(&Self::widgets(ctx), input) {
+				MenuAction::Choose => self.choose(ctx),
+				_ => Transition::None
+			}
+		}
+	}
+}
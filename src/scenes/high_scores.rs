@@ -0,0 +1,60 @@
+// Dependencies
+
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::text;
+
+// Implement the high scores screen.
+// This sits on top of the gameplay or paused scene, stopping the simulation and listing the player's best results until closed.
+
+pub struct HighScoresScene;
+
+impl HighScoresScene {
+	// Get the lines of text to show, from the title down.
+	pub fn lines(ctx: &Context) -> Vec<String> {
+		let (scores, locale) = (&ctx.high_scores, &ctx.locale);
+		// Records show who set them, once they've been given a name.
+		let best_survival = match &scores.best_survival_by {
+			Some(name) => locale.format("high-scores.best-survival-by", &[("seconds", &scores.best_survival), ("name", name)]),
+			None => locale.format("high-scores.best-survival", &[("seconds", &scores.best_survival)])
+		};
+		let longest_rally = match &scores.longest_rally_by {
+			Some(name) => locale.format("high-scores.longest-rally-by", &[("hits", &scores.longest_rally), ("name", name)]),
+			None => locale.format("high-scores.longest-rally", &[("hits", &scores.longest_rally)])
+		};
+
+		let mut lines = vec![locale.get("high-scores.title").to_string(), String::new(), best_survival, longest_rally];
+
+		for (mode, record) in &scores.matches {
+			lines.push(locale.format("high-scores.mode-record", &[("mode", mode), ("won", &record.won), ("played", &record.played)]));
+		}
+		lines
+	}
+}
+
+impl Scene for HighScoresScene {
+	// Stop the simulation and release the cursor.
+	fn enter(&mut self, ctx: &mut Context) {
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	// Draw each line centered across the window, sized to fit the window's height.
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		let line_height = (text::GLYPH_HEIGHT + 3.0) * scale;
+
+		for (index, line) in Self::lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, ctx.height * 0.2 + index as f32 * line_height, scale);
+		}
+	}
+
+	fn handle_input(&mut self, _ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Close the high scores, going back to whatever was open before.
+			Input::KeyPressed(Key::Escape | Key::Scores) => Transition::Pop,
+			// Ignore anything else.
+			_ => Transition::None
+		}
+	}
+}
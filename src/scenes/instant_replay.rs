@@ -0,0 +1,114 @@
+// Dependencies
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::Input;
+use crate::game::{TICK_TIME, MAX_FRAME_TIME};
+use crate::events::GameEvent;
+use crate::replay::{Replay, Playback, INSTANT_REPLAY_LENGTH};
+use crate::scenes::{ReplayScene, PausedScene};
+use crate::clock::Clock;
+use crate::text;
+
+// How fast instant replays play back, as a fraction of real time.
+
+pub const INSTANT_REPLAY_SPEED: f32 = 0.5;
+
+// Implement the instant replay scene.
+// This sits on top of the gameplay scene after a point, holding up the next serve while the last few seconds before it are played back in slow motion.
+// It goes back to the match once the point's been scored again, or straight away if the player presses any key or clicks.
+
+pub struct InstantReplayScene {
+	playback: Playback,
+	clock: Arc<dyn Clock>,
+	// The tick playback started from, for the progress bar.
+	start: u64,
+	// When the scene last updated, and how much slowed-down time since then hasn't been played back yet, in milliseconds.
+	last_update: Duration,
+	accumulator: f32,
+	// Whether the point's been scored again, so there's nothing left to show.
+	finished: bool
+}
+
+impl InstantReplayScene {
+	// Play back the last few seconds of a replay from the instant replay buffer, in time with the given clock.
+	pub fn new(replay: Replay, clock: Arc<dyn Clock>) -> Self {
+		let mut playback = Playback::new(replay);
+		playback.seek(playback.length().saturating_sub(INSTANT_REPLAY_LENGTH));
+
+		Self {
+			start: playback.tick(),
+			playback,
+			last_update: clock.now(),
+			clock,
+			accumulator: 0.0,
+			finished: false
+		}
+	}
+
+	pub fn playback(&self) -> &Playback {
+		&self.playback
+	}
+}
+
+impl Scene for InstantReplayScene {
+	// Hold the match underneath until the replay's over. The gameplay scene sets it going again when it's back on top.
+	fn enter(&mut self, ctx: &mut Context) {
+		ctx.sim.with(|game| game.paused = true);
+		self.last_update = self.clock.now();
+	}
+
+	// Play back as many ticks as fit into the time since the last update, slowed down, one at a time so it stops on the tick the point's scored.
+	fn update(&mut self, _ctx: &mut Context) -> Transition {
+		let now = self.clock.now();
+		let delta_time = now.saturating_sub(self.last_update).as_nanos() as f32 / 1_000_000.0;
+		self.last_update = now;
+
+		self.accumulator += delta_time.min(MAX_FRAME_TIME) * INSTANT_REPLAY_SPEED;
+		while !self.finished && self.accumulator >= TICK_TIME {
+			self.playback.step(1);
+			self.accumulator -= TICK_TIME;
+
+			// Nothing reacts to the replay's events, they already happened.
+			let scored = self.playback.game_mut().events.drain().any(|event| matches!(event, GameEvent::PointScored { .. }));
+			self.finished = scored || self.playback.is_finished();
+		}
+
+		if self.finished {
+			Transition::Pop
+		} else {
+			Transition::None
+		}
+	}
+
+	// Draw the replay like any other, labelled so it isn't mistaken for the match carrying on, with a bar along the bottom showing how much is left.
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		ReplayScene::render_match(ctx, self.playback.game(), self.accumulator / TICK_TIME, renderer);
+
+		let total = self.playback.length().saturating_sub(self.start).max(1);
+		let progress = self.playback.tick().saturating_sub(self.start) as f32 / total as f32;
+		renderer.rect(Vec2::new(0.0, ctx.height - 4.0), Vec2::new(ctx.width * progress, 4.0));
+
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		text::draw_centered(renderer, ctx.locale.get("replay.instant"), ctx.width / 2.0, ctx.height * 0.1, scale);
+		text::draw_centered(renderer, ctx.locale.get("replay.skip"), ctx.width / 2.0, ctx.height * 0.85, (scale / 2.0).floor());
+	}
+
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Skip the rest of the replay.
+			Input::KeyPressed(_) | Input::MousePressed => Transition::Pop,
+			// Pause instead if the window loses focus, the same as during the match.
+			Input::Focused(false) if ctx.config.window.pause_in_background => Transition::Replace(Box::new(PausedScene::new())),
+			// Ignore anything else.
+			_ => Transition::None
+		}
+	}
+}
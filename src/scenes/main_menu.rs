@@ -0,0 +1,132 @@
+// Dependencies
+
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::Input;
+use crate::game::Command;
+use crate::modes::{GameMode, Classic, Training};
+use crate::scenes::{PlayingScene, SettingsScene, ProfileScene};
+use crate::widget::{Widget, ListMenu, MenuAction};
+use crate::text;
+
+// Implement the entries on the main menu, from top to bottom.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuEntry {
+	// Start a new match against the AI.
+	Play,
+	// Start a training match, showing where the ball's going.
+	Training,
+	// Change the settings.
+	Settings,
+	// Choose who's playing, or make a new profile.
+	Profiles,
+	// Exit the game.
+	Quit
+}
+
+impl MenuEntry {
+	pub const ALL: [MenuEntry; 5] = [MenuEntry::Play, MenuEntry::Training, MenuEntry::Settings, MenuEntry::Profiles, MenuEntry::Quit];
+
+	// Key of this entry's text in the language strings.
+	fn text_key(self) -> &'static str {
+		match self {
+			MenuEntry::Play => "menu.play",
+			MenuEntry::Training => "menu.training",
+			MenuEntry::Settings => "menu.settings",
+			MenuEntry::Profiles => "menu.profiles",
+			MenuEntry::Quit => "menu.quit"
+		}
+	}
+}
+
+// Implement the title screen the game starts on.
+// Entries are chosen with the up and down keys and enter, or by pointing at them with the mouse and clicking.
+
+pub struct MainMenuScene {
+	pub menu: ListMenu
+}
+
+impl Default for MainMenuScene {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl MainMenuScene {
+	pub fn new() -> Self {
+		Self {
+			menu: ListMenu::new(0)
+		}
+	}
+
+	// Size of the text's pixels and the space each entry takes up, sized to fit the window's height.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 5.0) * scale)
+	}
+
+	// Get the top of the given entry.
+	fn entry_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.45 + index as f32 * line_height
+	}
+
+	// Get the area of each entry that can be pointed at.
+	pub fn widgets(ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		(0..MenuEntry::ALL.len()).map(|index| Widget::button(ListMenu::row(ctx, Self::entry_top(ctx, index), line_height))).collect()
+	}
+
+	// Get the text of each entry, marking the highlighted one.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		self.menu.mark(MenuEntry::ALL.iter().map(|entry| ctx.locale.get(entry.text_key()).to_string()))
+	}
+
+	// Do whatever the highlighted entry does.
+	fn choose(&self, ctx: &mut Context) -> Transition {
+		match MenuEntry::ALL[self.menu.selected] {
+			// Play starts a match in whichever mode was chosen, e.g. on the command line, unless the last match was training.
+			MenuEntry::Play => {
+				ctx.sim.apply(if ctx.game.mode.name() == Training.name() { Command::SetMode(&Classic) } else { Command::NewMatch });
+				Transition::Push(Box::new(PlayingScene))
+			},
+			MenuEntry::Training => {
+				ctx.sim.apply(Command::SetMode(&Training));
+				Transition::Push(Box::new(PlayingScene))
+			},
+			MenuEntry::Settings => Transition::Push(Box::new(SettingsScene::new())),
+			MenuEntry::Profiles => Transition::Push(Box::new(ProfileScene::new(ctx))),
+			MenuEntry::Quit => Transition::Quit
+		}
+	}
+}
+
+impl Scene for MainMenuScene {
+	// Keep the simulation stopped and the cursor free while on the menu.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.menu.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let (scale, _) = Self::layout(ctx);
+		text::draw_centered(renderer, ctx.locale.get("menu.title"), ctx.width / 2.0, ctx.height * 0.2, scale * 3.0);
+
+		self.menu.render(renderer, &Self::widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, Self::entry_top(ctx, index), scale);
+		}
+	}
+
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match self.menu.handle(&Self::widgets(ctx), input) {
+			MenuAction::Choose => self.choose(ctx),
+			_ => Transition::None
+		}
+	}
+}
@@ -0,0 +1,34 @@
+// Scenes making up the game.
+
+mod main_menu;
+pub use main_menu::{MainMenuScene, MenuEntry};
+
+mod playing;
+pub use playing::{PlayingScene, MATCH_POINT_FLASH};
+
+mod paused;
+pub use paused::{PausedScene, PauseEntry, PAUSE_SHADE};
+
+mod game_over;
+pub use game_over::{GameOverScene, GameOverEntry, GAME_OVER_SHADE};
+
+mod high_scores;
+pub use high_scores::HighScoresScene;
+
+mod replay;
+pub use replay::{ReplayScene, REPLAY_SKIP};
+
+mod instant_replay;
+pub use instant_replay::{InstantReplayScene, INSTANT_REPLAY_SPEED};
+
+mod resume;
+pub use resume::ResumeScene;
+
+mod profiles;
+pub use profiles::ProfileScene;
+
+mod name_entry;
+pub use name_entry::{NameEntryScene, NameFor, NameKey, NAME_CHARACTERS, MAX_NAME_LENGTH};
+
+mod settings;
+pub use settings::{SettingsScene, SettingsPage, SettingsRow, SENSITIVITY_STEP, MIN_SENSITIVITY, MAX_SENSITIVITY, MAX_WIN_SCORE, MAX_SERVE_COUNTDOWN};
@@ -0,0 +1,296 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::scenes::ProfileScene;
+use crate::widget::{Widget, Bounds, Pointer, PointerAction};
+use crate::text;
+
+use super::profiles::play_as_active;
+
+// Longest name that can be entered, in characters.
+
+pub const MAX_NAME_LENGTH: usize = 12;
+
+// Characters on the on-screen keyboard, row by row, all of which the font can draw.
+// A last row under them holds the space, delete and done keys.
+
+pub const NAME_CHARACTERS: [&str; 4] = ["ABCDEFGHIJ", "KLMNOPQRST", "UVWXYZ-_.!", "0123456789"];
+
+// Implement what a name is being entered for.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NameFor {
+	// A new player profile, which is made the active one.
+	NewProfile,
+	// The high score records a guest just beat.
+	Records
+}
+
+// Implement the keys on the on-screen keyboard.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NameKey {
+	Character(char),
+	Space,
+	Delete,
+	Done
+}
+
+impl NameKey {
+	// Get the keys on the on-screen keyboard, row by row.
+	pub fn rows() -> Vec<Vec<NameKey>> {
+		let mut rows: Vec<Vec<NameKey>> = NAME_CHARACTERS.iter().map(|row| row.chars().map(NameKey::Character).collect()).collect();
+		rows.push(vec![NameKey::Space, NameKey::Delete, NameKey::Done]);
+		rows
+	}
+}
+
+// Implement the screen for entering a name.
+// Names can be typed on the keyboard, or picked a character at a time from an on-screen keyboard, using the arrow keys and enter, a gamepad, or the mouse.
+// Typing moves the highlight to done, so enter finishes the name straight after.
+
+pub struct NameEntryScene {
+	pub purpose: NameFor,
+	pub name: String,
+	// Row and column of the highlighted key on the on-screen keyboard.
+	pub row: usize,
+	pub column: usize,
+	// What the mouse is pointing at and holding down.
+	pointer: Pointer,
+	// Whether to ignore the next game key, since it came from a keyboard key that was just typed.
+	swallow: bool
+}
+
+impl NameEntryScene {
+	pub fn new(purpose: NameFor) -> Self {
+		Self {
+			purpose,
+			name: String::new(),
+			row: 0,
+			column: 0,
+			pointer: Pointer::new(),
+			swallow: false
+		}
+	}
+
+	// Size of the text's pixels, and the size of each key on the on-screen keyboard.
+	fn layout(ctx: &Context) -> (f32, Vec2) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		(scale, Vec2::new((text::GLYPH_ADVANCE + 3.0) * scale, (text::GLYPH_HEIGHT + 4.0) * scale))
+	}
+
+	// Get the area of each key on the on-screen keyboard, row by row in the same order as NameKey::rows.
+	// Rows are centered, and the keys on the last row share its width between them.
+	pub fn widgets(ctx: &Context) -> Vec<Widget> {
+		let (_, key_size) = Self::layout(ctx);
+		let columns = NAME_CHARACTERS[0].len() as f32;
+		let left = (ctx.width - key_size.x * columns) / 2.0;
+
+		NameKey::rows().iter().enumerate().flat_map(|(row, keys)| {
+			let width = key_size.x * columns / keys.len() as f32;
+			let top = ctx.height * 0.4 + row as f32 * key_size.y;
+			(0..keys.len()).map(move |column| {
+				Widget::button(Bounds::new(Vec2::new(left + column as f32 * width, top), Vec2::new(width, key_size.y)))
+			})
+		}).collect()
+	}
+
+	// Get the highlighted key.
+	pub fn selected(&self) -> NameKey {
+		NameKey::rows()[self.row][self.column]
+	}
+
+	// Get the title, which says what the name is for.
+	pub fn title(&self, ctx: &Context) -> String {
+		ctx.locale.get(match self.purpose {
+			NameFor::NewProfile => "name-entry.new-profile",
+			NameFor::Records => "name-entry.records"
+		}).to_string()
+	}
+
+	// Add a character to the end of the name, if there's room.
+	fn push(&mut self, c: char) {
+		if self.name.chars().count() < MAX_NAME_LENGTH {
+			self.name.push(c);
+		}
+	}
+
+	// Move the highlight to the given key.
+	fn highlight(&mut self, key: NameKey) {
+		for (row, keys) in NameKey::rows().iter().enumerate() {
+			if let Some(column) = keys.iter().position(|other| *other == key) {
+				(self.row, self.column) = (row, column);
+			}
+		}
+	}
+
+	// Move the highlight up or down a row, wrapping around at either end and keeping to the nearest column the row has.
+	fn move_row(&mut self, step: isize) {
+		let rows = NameKey::rows();
+		self.row = (self.row as isize + step).rem_euclid(rows.len() as isize) as usize;
+		self.column = self.column.min(rows[self.row].len() - 1);
+	}
+
+	// Move the highlight along the row, wrapping around at either end.
+	fn move_column(&mut self, step: isize) {
+		let keys = NameKey::rows()[self.row].len() as isize;
+		self.column = (self.column as isize + step).rem_euclid(keys) as usize;
+	}
+
+	// Do whatever the highlighted key does.
+	fn choose(&mut self, ctx: &mut Context) -> Transition {
+		match self.selected() {
+			NameKey::Character(c) => self.push(c),
+			NameKey::Space => self.push(' '),
+			NameKey::Delete => {
+				self.name.pop();
+			},
+			NameKey::Done => return self.finish(ctx)
+		}
+		Transition::None
+	}
+
+	// Use the name for what it was asked for, as long as there's something to it.
+	fn finish(&mut self, ctx: &mut Context) -> Transition {
+		let name = self.name.trim();
+		if name.is_empty() {
+			return Transition::None;
+		}
+
+		match self.purpose {
+			NameFor::NewProfile => {
+				let config = ctx.saved_config.clone();
+				ctx.profiles.select_or_create(name, &config);
+				play_as_active(ctx);
+			},
+			NameFor::Records => ctx.high_scores.name_records(name)
+		}
+		Transition::Pop
+	}
+
+	// Go back without a name. A new profile goes back to the list of profiles, and records are left without a name.
+	fn cancel(&mut self, ctx: &mut Context) -> Transition {
+		match self.purpose {
+			NameFor::NewProfile => Transition::Replace(Box::new(ProfileScene::new(ctx))),
+			NameFor::Records => {
+				ctx.high_scores.unnamed.clear();
+				Transition::Pop
+			}
+		}
+	}
+}
+
+// Get the character a keyboard key types, by its name in KEYBOARD_KEYS.
+// Letters are typed in capitals, the same as the font draws them.
+
+fn typed(name: &str) -> Option<char> {
+	let digit = name.strip_prefix("Key").or_else(|| name.strip_prefix("Numpad")).filter(|digit| digit.len() == 1);
+	match name {
+		"Space" => Some(' '),
+		"Minus" | "NumpadSubtract" => Some('-'),
+		"Period" | "NumpadDecimal" => Some('.'),
+		"Underline" => Some('_'),
+		_ if name.len() == 1 => name.chars().next().filter(char::is_ascii_uppercase),
+		_ => digit.and_then(|digit| digit.chars().next()).filter(char::is_ascii_digit)
+	}
+}
+
+impl Scene for NameEntryScene {
+	// Keep the simulation stopped and the cursor free while entering a name.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let (scale, key_size) = Self::layout(ctx);
+		let middle = ctx.width / 2.0;
+		text::draw_centered(renderer, &self.title(ctx), middle, ctx.height * 0.1, scale);
+
+		// Show where the next character goes while there's room for one.
+		let mut name = self.name.clone();
+		if name.chars().count() < MAX_NAME_LENGTH {
+			name.push('_');
+		}
+		text::draw_centered(renderer, &name, middle, ctx.height * 0.22, scale * 2.0);
+
+		let widgets = Self::widgets(ctx);
+		self.pointer.render(renderer, &widgets);
+
+		let keys = NameKey::rows().concat();
+		for (key, widget) in keys.iter().zip(&widgets) {
+			let label = match key {
+				NameKey::Character(c) => c.to_string(),
+				NameKey::Space => ctx.locale.get("name-entry.space").to_string(),
+				NameKey::Delete => ctx.locale.get("name-entry.delete").to_string(),
+				NameKey::Done => ctx.locale.get("name-entry.done").to_string()
+			};
+			let bounds = widget.bounds;
+			let top = bounds.position.y + (key_size.y - text::GLYPH_HEIGHT * scale) / 2.0;
+			text::draw_centered(renderer, &label, bounds.position.x + bounds.size.x / 2.0, top, scale);
+
+			// Underline the highlighted key.
+			if *key == self.selected() {
+				let width = text::width(&label, scale);
+				renderer.rect(Vec2::new(bounds.position.x + (bounds.size.x - width) / 2.0, top + (text::GLYPH_HEIGHT + 1.0) * scale), Vec2::new(width, scale));
+			}
+		}
+	}
+
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		// Every key press comes as the keyboard key first, then the game key it's bound to.
+		// Keys that type something are typed, and the game key that follows, if they're bound to one, is ignored.
+		if let Input::KeyboardKey(name) = input {
+			let used = match (name, typed(name)) {
+				("Back", _) => {
+					self.name.pop();
+					true
+				},
+				(_, Some(c)) => {
+					self.push(c);
+					self.highlight(NameKey::Done);
+					true
+				},
+				_ => false
+			};
+			self.swallow = used && ctx.config.key_for(name).is_some();
+			return Transition::None;
+		}
+
+		if self.swallow && matches!(input, Input::KeyPressed(_)) {
+			self.swallow = false;
+			return Transition::None;
+		}
+
+		match input {
+			Input::KeyPressed(Key::Up) => self.move_row(-1),
+			Input::KeyPressed(Key::Down) => self.move_row(1),
+			Input::KeyPressed(Key::Left) => self.move_column(-1),
+			Input::KeyPressed(Key::Right) => self.move_column(1),
+			Input::KeyPressed(Key::Enter | Key::Space) => return self.choose(ctx),
+			Input::KeyPressed(Key::Escape) => return self.cancel(ctx),
+			// Highlight whatever key the mouse is pointing at, and press it when clicked.
+			Input::MouseMoved { .. } | Input::MousePressed | Input::MouseReleased => {
+				let keys = NameKey::rows().concat();
+				match self.pointer.handle(&Self::widgets(ctx), input) {
+					PointerAction::Hover(index) => self.highlight(keys[index]),
+					PointerAction::Click(index) => {
+						self.highlight(keys[index]);
+						return self.choose(ctx);
+					},
+					_ => ()
+				}
+			},
+			// Ignore anything else.
+			_ => ()
+		}
+		Transition::None
+	}
+}
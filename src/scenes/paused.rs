@@ -0,0 +1,136 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::game::Command;
+use crate::scenes::{HighScoresScene, SettingsScene};
+use crate::widget::{Widget, ListMenu, MenuAction};
+use crate::text;
+
+// How much the pause menu darkens the playfield behind it, from 0.0 to 1.0.
+
+pub const PAUSE_SHADE: f32 = 0.6;
+
+// Implement the entries on the pause menu, from top to bottom.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PauseEntry {
+	// Carry on with the match.
+	Resume,
+	// Start the match again from 0-0.
+	Restart,
+	// Change the settings without leaving the match.
+	Settings,
+	// Leave the match and go back to the main menu.
+	QuitToMenu
+}
+
+impl PauseEntry {
+	pub const ALL: [PauseEntry; 4] = [PauseEntry::Resume, PauseEntry::Restart, PauseEntry::Settings, PauseEntry::QuitToMenu];
+
+	// Key of this entry's text in the language strings.
+	fn text_key(self) -> &'static str {
+		match self {
+			PauseEntry::Resume => "pause.resume",
+			PauseEntry::Restart => "pause.restart",
+			PauseEntry::Settings => "pause.settings",
+			PauseEntry::QuitToMenu => "pause.quit-to-menu"
+		}
+	}
+}
+
+// Implement the paused scene.
+// This sits on top of the gameplay scene and stops the simulation until the player resumes, darkening the playfield under a menu.
+// Entries are chosen the same way as on the main menu.
+
+pub struct PausedScene {
+	pub menu: ListMenu
+}
+
+impl Default for PausedScene {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PausedScene {
+	pub fn new() -> Self {
+		Self {
+			menu: ListMenu::new(0)
+		}
+	}
+
+	// Size of the text's pixels and the space each entry takes up, sized to fit the window's height.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 5.0) * scale)
+	}
+
+	// Get the top of the given entry.
+	fn entry_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.45 + index as f32 * line_height
+	}
+
+	// Get the area of each entry that can be pointed at.
+	pub fn widgets(ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		(0..PauseEntry::ALL.len()).map(|index| Widget::button(ListMenu::row(ctx, Self::entry_top(ctx, index), line_height))).collect()
+	}
+
+	// Get the text of each entry, marking the highlighted one.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		self.menu.mark(PauseEntry::ALL.iter().map(|entry| ctx.locale.get(entry.text_key()).to_string()))
+	}
+
+	// Do whatever the highlighted entry does.
+	fn choose(&self, ctx: &mut Context) -> Transition {
+		match PauseEntry::ALL[self.menu.selected] {
+			PauseEntry::Resume => Transition::Pop,
+			PauseEntry::Restart => {
+				ctx.sim.apply(Command::NewMatch);
+				Transition::Pop
+			},
+			PauseEntry::Settings => Transition::Push(Box::new(SettingsScene::new())),
+			PauseEntry::QuitToMenu => Transition::PopToRoot
+		}
+	}
+}
+
+impl Scene for PausedScene {
+	// Stop the simulation and release the cursor.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.menu.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		renderer.shade(Vec2::new(0.0, 0.0), Vec2::new(ctx.width, ctx.height), PAUSE_SHADE);
+
+		let (scale, _) = Self::layout(ctx);
+		text::draw_centered(renderer, ctx.locale.get("pause.title"), ctx.width / 2.0, ctx.height * 0.2, scale * 2.0);
+
+		self.menu.render(renderer, &Self::widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, Self::entry_top(ctx, index), scale);
+		}
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Resume when the window regains focus.
+			Input::Focused(true) => Transition::Pop,
+			// Resume straight away if the player presses escape again.
+			Input::KeyPressed(Key::Escape) if ctx.focused => Transition::Pop,
+			Input::KeyPressed(Key::Scores) => Transition::Push(Box::new(HighScoresScene)),
+			// A click that only brings the window back into focus doesn't choose anything.
+			Input::MousePressed if !ctx.focused => Transition::None,
+			_ => match self.menu.handle(&Self::widgets(ctx), input) {
+				MenuAction::Choose => self.choose(ctx),
+				_ => Transition::None
+			}
+		}
+	}
+}
@@ -0,0 +1,246 @@
+// Dependencies
+
+use crate::structs::{Vec2, ObjectType};
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::game::Command;
+use crate::events::{Side, GameEvent};
+use crate::scenes::{PausedScene, HighScoresScene, GameOverScene, InstantReplayScene};
+use crate::tutorial::Tutorial;
+use crate::cosmetics::{self, PADDLE_SKINS};
+use crate::prediction;
+use crate::text;
+
+// How long the score of a side at match point takes to flash off and back on, in milliseconds of match time.
+
+pub const MATCH_POINT_FLASH: f32 = 500.0;
+
+// How far apart the dots along the ball's predicted path are in training mode, how big they are, and how faint the path and the ghost ball at its end are drawn.
+
+pub const TRAJECTORY_SPACING: f32 = 12.0;
+pub const TRAJECTORY_DOT: f32 = 3.0;
+const TRAJECTORY_FILL: [f32; 4] = [1.0, 1.0, 1.0, 0.35];
+
+// Implement the gameplay scene.
+// This draws the court with the score over it, and moves the player's paddle with the mouse.
+// Until the player has finished the tutorial, its prompts are shown over the court too, as are the announcer's callouts unless they're turned off.
+// The player's paddle and the ball are drawn in the skins the active profile has chosen. The other paddle is always plain.
+// In training mode, the path the ball's predicted to take towards the player is drawn under everything else.
+// After each point, unless they're turned off, the last few seconds before it are replayed in slow motion before the next serve.
+
+pub struct PlayingScene;
+
+impl PlayingScene {
+	// Size of the score's pixels, sized to fit the window's height.
+	pub fn score_scale(ctx: &Context) -> f32 {
+		(ctx.height / 60.0).floor().max(3.0)
+	}
+
+	// Check if sides at match point have their score flashed off right now.
+	pub fn match_point_hidden(ctx: &Context) -> bool {
+		(ctx.game.elapsed / (MATCH_POINT_FLASH / 2.0)) as u32 % 2 == 1
+	}
+
+	// Draw the scoreboard: each side's score either side of the middle of the court, with a dot on the outside of the side serving next.
+	// The score of a side one point away from winning flashes, with a label under the scoreboard flashing along with it.
+	fn render_scoreboard(ctx: &Context, renderer: &mut dyn Renderer) {
+		let scale = Self::score_scale(ctx);
+		let middle = ctx.width / 2.0;
+		let gap = scale * 4.0;
+		let match_point = ctx.game.match_point();
+		let hidden = Self::match_point_hidden(ctx);
+
+		for side in [Side::Left, Side::Right] {
+			let score = ctx.game.scores[side.index()].to_string();
+			let width = text::width(&score, scale);
+			let (x, dot) = match side {
+				Side::Left => (middle - gap - width, middle - gap - width - scale * 2.0),
+				Side::Right => (middle + gap, middle + gap + width + scale)
+			};
+
+			if !(hidden && match_point.contains(&side)) {
+				text::draw(renderer, &score, Vec2::new(x, scale * 2.0), scale);
+			}
+			if ctx.game.server == side {
+				renderer.rect(Vec2::new(dot, scale * 2.0 + (text::GLYPH_HEIGHT - 1.0) * scale / 2.0), Vec2::new(scale, scale));
+			}
+		}
+
+		// Name the player under their score when they're playing as a profile.
+		let name_scale = (scale / 2.0).floor();
+		let name_top = scale * (text::GLYPH_HEIGHT + 3.0);
+		if let Some((side, name)) = ctx.game.control_side().zip(ctx.profiles.active.as_ref()) {
+			let x = match side {
+				Side::Left => middle - gap - text::width(name, name_scale),
+				Side::Right => middle + gap
+			};
+			text::draw(renderer, name, Vec2::new(x, name_top), name_scale);
+		}
+
+		if !match_point.is_empty() && !hidden {
+			let top = name_top + name_scale * (text::GLYPH_HEIGHT + 3.0);
+			text::draw_centered(renderer, ctx.locale.get("hud.match-point"), middle, top, name_scale);
+		}
+	}
+
+	// Draw the path the ball's predicted to take to the player's paddle as a dotted line, with a ghost of the ball where it'll meet the paddle.
+	fn render_trajectory(ctx: &Context, renderer: &mut dyn Renderer) {
+		let Some((start, trajectory)) = ctx.game.control_side().and_then(|side| prediction::incoming(&ctx.game, side)) else { return };
+		let Some(ball) = ctx.game.objects.iter().rev().find(|obj| obj.obj_type == ObjectType::Ball) else { return };
+
+		let dot = Vec2::new(TRAJECTORY_DOT, TRAJECTORY_DOT);
+		let mut from = start;
+		for to in trajectory {
+			// A ball that isn't moving across the court never gets there.
+			if !to.y.is_finite() {
+				return;
+			}
+
+			let length = (to - from).length();
+			let dots = (length / TRAJECTORY_SPACING) as usize;
+			for i in 0..dots {
+				let point = from.lerp(to, i as f32 * TRAJECTORY_SPACING / length);
+				renderer.fill(point - dot / 2.0, dot, TRAJECTORY_FILL);
+			}
+			from = to;
+		}
+
+		renderer.fill(from - ball.size / 2.0, ball.size, TRAJECTORY_FILL);
+	}
+
+	// Draw the scoreboard, the countdown to the next serve while there is one, the latest callout, and the tutorial if it's being shown.
+	fn render_overlay(ctx: &Context, renderer: &mut dyn Renderer) {
+		let scale = Self::score_scale(ctx);
+		let middle = ctx.width / 2.0;
+		Self::render_scoreboard(ctx, renderer);
+
+		// Count down the whole seconds left, so the last second shows 1 rather than 0.
+		if ctx.game.serve_timer > 0.0 {
+			let seconds = (ctx.game.serve_timer / 1000.0).ceil() as u32;
+			text::draw_centered(renderer, &seconds.to_string(), middle, ctx.height * 0.3, scale);
+		}
+
+		if ctx.config.gameplay.callouts {
+			ctx.callouts.render(renderer, &ctx.game, ctx.width, ctx.height);
+		}
+
+		if let Some(tutorial) = &ctx.tutorial {
+			tutorial.render(renderer, &ctx.locale, ctx.width, ctx.height);
+		}
+	}
+
+	// Follow the player through the tutorial, turning it off for good once they've done every step.
+	// It's turned off without going through apply_config, since nothing the simulation uses has changed.
+	fn update_tutorial(ctx: &mut Context) {
+		let Some(tutorial) = &mut ctx.tutorial else { return };
+		tutorial.update(&ctx.game, &ctx.events);
+
+		if tutorial.is_finished() {
+			ctx.tutorial = None;
+			ctx.config.gameplay.tutorial = false;
+			ctx.saved_config.gameplay.tutorial = false;
+			ctx.config_changed = true;
+			ctx.show_toast(ctx.locale.get("tutorial.complete").to_string());
+		}
+	}
+}
+
+impl Scene for PlayingScene {
+	// Resume the simulation and capture the cursor for paddle control.
+	// The tutorial starts if it's turned on and there's a player to follow, and stops if it's been turned off in the settings.
+	fn enter(&mut self, ctx: &mut Context) {
+		ctx.sim.with(|game| game.paused = false);
+		ctx.cursor_grabbed = true;
+
+		if !ctx.config.gameplay.tutorial {
+			ctx.tutorial = None;
+		} else if ctx.tutorial.is_none() && ctx.game.control_side().is_some() {
+			ctx.tutorial = Some(Tutorial::new());
+		}
+	}
+
+	// Show the results as soon as a match has been won, or an instant replay of any other point.
+	// The announcer follows the match even while its callouts are turned off, so it knows how long the rally is if they're turned back on.
+	fn update(&mut self, ctx: &mut Context) -> Transition {
+		Self::update_tutorial(ctx);
+		ctx.callouts.update(&ctx.game);
+		ctx.callouts.record(&ctx.game, &ctx.events, &ctx.locale);
+		ctx.rumble.record(&ctx.game, &ctx.events, ctx.config.controls.rumble);
+
+		let ended = ctx.events.iter().find_map(|event| match event {
+			GameEvent::MatchEnded { winner, scores, stats } => Some((*winner, *scores, stats.clone())),
+			_ => None
+		});
+
+		let scored = ctx.events.iter().any(|event| matches!(event, GameEvent::PointScored { .. }));
+
+		match ended {
+			Some((winner, scores, stats)) => Transition::Push(Box::new(GameOverScene::new(ctx, winner, scores, stats))),
+			None if scored && ctx.config.gameplay.instant_replays => match ctx.sim.instant_replay() {
+				Some(replay) => Transition::Push(Box::new(InstantReplayScene::new(replay, ctx.sim.clock()))),
+				None => Transition::None
+			},
+			None => Transition::None
+		}
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		if ctx.game.mode.shows_trajectory() {
+			Self::render_trajectory(ctx, renderer);
+		}
+
+		let (paddle_skin, ball_skin) = cosmetics::for_profile(ctx.profiles.active());
+		for obj in &ctx.game.objects {
+			let skin = match obj.obj_type.side() {
+				None => ball_skin,
+				Some(side) if Some(side) == ctx.game.control_side() => paddle_skin,
+				Some(_) => &PADDLE_SKINS[0]
+			};
+
+			// Render this object, interpolated between the last two ticks so movement stays smooth at any framerate.
+			skin.draw(renderer, obj.interpolated_position(ctx.alpha), obj.size, obj.velocity, !ctx.game.paused);
+		}
+
+		Self::render_overlay(ctx, renderer);
+	}
+
+	// Nothing below the match should show through it, e.g. the main menu it was started from.
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Pause if the player presses the escape key or the window loses focus, unless the match is set to carry on in the background.
+			Input::KeyPressed(Key::Escape) => Transition::Push(Box::new(PausedScene::new())),
+			Input::Focused(false) if ctx.config.window.pause_in_background => Transition::Push(Box::new(PausedScene::new())),
+			// Show the high scores, which pauses the game while they're open.
+			Input::KeyPressed(Key::Scores) => Transition::Push(Box::new(HighScoresScene)),
+			// The player moved their mouse.
+			// The paddle follows the cursor, with its distance from the middle of the screen scaled by the sensitivity.
+			Input::MouseMoved { y, .. } => {
+				ctx.rumble.gamepad = false;
+				let middle = ctx.height / 2.0;
+				let target = middle + (y - middle) * ctx.config.controls.sensitivity;
+				ctx.sim.apply(Command::MovePaddle { y: target });
+				Transition::None
+			},
+			// The player moved a gamepad's stick.
+			// The paddle goes to the same place on the screen as the stick is held, as far up or down, scaled by the sensitivity.
+			Input::StickMoved { y } => {
+				ctx.rumble.gamepad = true;
+				let middle = ctx.height / 2.0;
+				let target = middle + y * middle * ctx.config.controls.sensitivity;
+				ctx.sim.apply(Command::MovePaddle { y: target });
+				Transition::None
+			},
+			// A knob or fader set the paddle's position. It goes exactly where it's set, without any sensitivity.
+			Input::PaddleMoved { y } => {
+				ctx.sim.apply(Command::MovePaddle { y: y * ctx.height });
+				Transition::None
+			},
+			// Ignore anything else.
+			_ => Transition::None
+		}
+	}
+}
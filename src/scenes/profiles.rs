@@ -0,0 +1,112 @@
+// Dependencies
+
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::widget::{Widget, ListMenu, MenuAction};
+use crate::scenes::{NameEntryScene, NameFor};
+use crate::text;
+
+// Implement the screen for choosing who's playing at the start of a match.
+// Every saved profile is listed, followed by an entry for playing as a guest without a profile, and one for making a new profile.
+
+pub struct ProfileScene {
+	// The entry after the last profile is the guest, and the one after that makes a new profile.
+	pub menu: ListMenu
+}
+
+impl ProfileScene {
+	// Start with the active profile highlighted, or the guest if there isn't one.
+	pub fn new(ctx: &Context) -> Self {
+		let profiles = &ctx.profiles;
+		let selected = profiles.active.as_ref()
+			.and_then(|name| profiles.profiles.iter().position(|profile| &profile.name == name))
+			.unwrap_or(profiles.profiles.len());
+
+		Self {
+			menu: ListMenu::new(selected)
+		}
+	}
+
+	// Size of the text's pixels and the space each line takes up, sized to fit the window's height.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 3.0) * scale)
+	}
+
+	// Get the top of the given line, counting the title and the gap under it.
+	fn line_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.2 + index as f32 * line_height
+	}
+
+	// Get the area of each entry that can be pointed at, one for each profile, then the guest and the new profile.
+	pub fn widgets(ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		(0..ctx.profiles.profiles.len() + 2).map(|index| Widget::button(ListMenu::row(ctx, Self::line_top(ctx, index + 2), line_height))).collect()
+	}
+
+	// Get the lines of text to show, from the title down, marking the highlighted entry.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		let names = ctx.profiles.profiles.iter().map(|profile| profile.name.as_str()).chain([ctx.locale.get("profiles.guest"), ctx.locale.get("profiles.new")]);
+		let entries = self.menu.mark(names.map(str::to_string));
+
+		[ctx.locale.get("profiles.title").to_string(), String::new()].into_iter().chain(entries).collect()
+	}
+
+	// Play as the highlighted profile or as a guest, or ask for the new profile's name.
+	fn choose(&self, ctx: &mut Context) -> Transition {
+		if self.menu.selected == ctx.profiles.profiles.len() + 1 {
+			return Transition::Replace(Box::new(NameEntryScene::new(NameFor::NewProfile)));
+		}
+
+		ctx.profiles.active = ctx.profiles.profiles.get(self.menu.selected).map(|profile| profile.name.clone());
+		play_as_active(ctx);
+		Transition::Pop
+	}
+}
+
+// Apply the active profile's preferences over the current settings, if there is one.
+pub(super) fn play_as_active(ctx: &mut Context) {
+	if let Some(profile) = ctx.profiles.active() {
+		let mut config = ctx.config.clone();
+		profile.apply(&mut config);
+		ctx.apply_config(config);
+	}
+}
+
+impl Scene for ProfileScene {
+	// Keep the simulation stopped and the cursor free while the player chooses.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.menu.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let (scale, _) = Self::layout(ctx);
+
+		self.menu.render(renderer, &Self::widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, Self::line_top(ctx, index), scale);
+		}
+	}
+
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Carry on with whoever was playing last time.
+			Input::KeyPressed(Key::Escape) => {
+				play_as_active(ctx);
+				Transition::Pop
+			},
+			// Play as the highlighted profile.
+			_ => match self.menu.handle(&Self::widgets(ctx), input) {
+				MenuAction::Choose => self.choose(ctx),
+				_ => Transition::None
+			}
+		}
+	}
+}
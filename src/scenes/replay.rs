@@ -0,0 +1,133 @@
+// Dependencies
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::game::{GameState, TICK_RATE, TICK_TIME, MAX_FRAME_TIME};
+use crate::replay::{Replay, Playback};
+use crate::clock::Clock;
+use crate::text;
+
+// How far the left and right keys skip through a replay, in ticks.
+
+pub const REPLAY_SKIP: u64 = 5 * TICK_RATE as u64;
+
+// Implement the replay scene.
+// This plays back a recorded match in real time instead of the live simulation, with keys to pause and skip through it.
+
+pub struct ReplayScene {
+	playback: Playback,
+	clock: Arc<dyn Clock>,
+	// When the scene last updated, and how much time since then hasn't been played back yet, in milliseconds.
+	last_update: Duration,
+	accumulator: f32,
+	pub paused: bool
+}
+
+impl ReplayScene {
+	// Play back a replay in time with the given clock.
+	pub fn new(replay: Replay, clock: Arc<dyn Clock>) -> Self {
+		Self {
+			playback: Playback::new(replay),
+			last_update: clock.now(),
+			clock,
+			accumulator: 0.0,
+			paused: false
+		}
+	}
+
+	pub fn playback(&self) -> &Playback {
+		&self.playback
+	}
+
+	// Draw a match being played back, scaled to fit the window, with its objects interpolated the given fraction of the way to the next tick.
+	pub fn render_match(ctx: &Context, game: &GameState, alpha: f32, renderer: &mut dyn Renderer) {
+		if game.width > 0.0 && game.height > 0.0 {
+			let scale = (ctx.width / game.width).min(ctx.height / game.height);
+			let offset = Vec2::new(ctx.width - game.width * scale, ctx.height - game.height * scale) * 0.5;
+
+			for obj in &game.objects {
+				renderer.rect(offset + obj.interpolated_position(alpha) * scale, obj.size * scale);
+			}
+		}
+	}
+}
+
+impl Scene for ReplayScene {
+	// Show the cursor, since there's no paddle to control.
+	fn enter(&mut self, ctx: &mut Context) {
+		ctx.cursor_grabbed = false;
+		self.last_update = self.clock.now();
+	}
+
+	// Play back as many ticks as fit into the time since the last update, the same way the live simulation does.
+	fn update(&mut self, _ctx: &mut Context) -> Transition {
+		let now = self.clock.now();
+		let delta_time = now.saturating_sub(self.last_update).as_nanos() as f32 / 1_000_000.0;
+		self.last_update = now;
+
+		if !self.paused {
+			self.accumulator += delta_time.min(MAX_FRAME_TIME);
+			let ticks = (self.accumulator / TICK_TIME) as u64;
+			self.playback.step(ticks);
+			self.accumulator -= ticks as f32 * TICK_TIME;
+		}
+
+		// Nothing reacts to the replay's events, they already happened.
+		self.playback.game_mut().events.drain();
+
+		// Stop at the end, so pressing play again starts from the beginning.
+		if self.playback.is_finished() {
+			self.paused = true;
+		}
+		Transition::None
+	}
+
+	// Draw the match scaled to fit the window, with a bar along the bottom showing how far through the replay it is.
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let alpha = if self.paused { 1.0 } else { self.accumulator / TICK_TIME };
+		Self::render_match(ctx, self.playback.game(), alpha, renderer);
+
+		let progress = self.playback.tick() as f32 / self.playback.length().max(1) as f32;
+		renderer.rect(Vec2::new(0.0, ctx.height - 4.0), Vec2::new(ctx.width * progress, 4.0));
+
+		if self.paused {
+			let scale = (ctx.height / 100.0).floor().max(2.0);
+			text::draw_centered(renderer, ctx.locale.get("replay.paused"), ctx.width / 2.0, ctx.height * 0.1, scale);
+		}
+	}
+
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, _ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			// Pause or resume, starting again from the beginning once the replay's over.
+			Input::KeyPressed(Key::Space | Key::Enter) => {
+				if self.paused && self.playback.is_finished() {
+					self.playback.seek(0);
+				}
+				self.paused = !self.paused;
+				self.accumulator = 0.0;
+				Transition::None
+			},
+			// Skip backwards or forwards.
+			Input::KeyPressed(Key::Left) => {
+				self.playback.seek(self.playback.tick().saturating_sub(REPLAY_SKIP));
+				Transition::None
+			},
+			Input::KeyPressed(Key::Right) => {
+				self.playback.seek(self.playback.tick() + REPLAY_SKIP);
+				Transition::None
+			},
+			// Exit the game, there's no match to go back to.
+			Input::KeyPressed(Key::Escape) => Transition::Quit,
+			// Ignore anything else.
+			_ => Transition::None
+		}
+	}
+}
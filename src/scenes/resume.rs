@@ -0,0 +1,124 @@
+// Dependencies
+
+use std::path::PathBuf;
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::save::SavedMatch;
+use crate::scenes::PlayingScene;
+use crate::widget::{Widget, Bounds, Pointer, PointerAction};
+use crate::text;
+
+// Implement the prompt to continue a match saved when the game last quit.
+// This sits on top of the main menu at launch. Continuing swaps the saved match into the simulation and carries on playing it, otherwise it's back to the menu.
+
+pub struct ResumeScene {
+	saved: Option<SavedMatch>,
+	// Where the match was saved, removed once the player has decided so it's only offered once.
+	path: PathBuf,
+	// What the mouse is pointing at and holding down.
+	pointer: Pointer
+}
+
+impl ResumeScene {
+	pub fn new(saved: SavedMatch, path: PathBuf) -> Self {
+		Self {
+			saved: Some(saved),
+			path,
+			pointer: Pointer::new()
+		}
+	}
+
+	// Size of the text's pixels and the space each line takes up, sized to fit the window's height.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 100.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 3.0) * scale)
+	}
+
+	// Get the top of the given line.
+	fn line_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.3 + index as f32 * line_height
+	}
+
+	// Get the area of the two answers that can be pointed at, continuing first, each a band across the whole window.
+	// They're the last two lines.
+	pub fn widgets(&self, ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		let lines = self.lines(ctx).len();
+		(lines - 2..lines).map(|index| {
+			Widget::button(Bounds::new(Vec2::new(0.0, Self::line_top(ctx, index)), Vec2::new(ctx.width, line_height)))
+		}).collect()
+	}
+
+	// Continue playing the saved match, resizing it if the window's changed size since it was saved.
+	fn resume(&mut self, ctx: &mut Context) -> Transition {
+		if let Some(saved) = self.saved.take() {
+			let resized = saved.game.width != ctx.width || saved.game.height != ctx.height;
+			ctx.sim.restore(saved.game);
+			if resized {
+				ctx.resize(ctx.width, ctx.height);
+			}
+		}
+		SavedMatch::clear_from(&self.path);
+		Transition::Replace(Box::new(PlayingScene))
+	}
+
+	// Go to the menu instead, where a new match can be started.
+	fn decline(&self) -> Transition {
+		SavedMatch::clear_from(&self.path);
+		Transition::Pop
+	}
+
+	// Get the lines of text to show, from the question down.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		let locale = &ctx.locale;
+		let mut lines = vec![locale.get("resume.title").to_string()];
+		if let Some(saved) = &self.saved {
+			lines.push(format!("{}  {} - {}", saved.game.mode.name(), saved.game.scores[0], saved.game.scores[1]));
+		}
+		lines.push(String::new());
+		lines.push(locale.get("resume.continue").to_string());
+		lines.push(locale.get("resume.new-match").to_string());
+		lines
+	}
+}
+
+impl Scene for ResumeScene {
+	// Keep the simulation stopped and the cursor free while the player decides.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let (scale, _) = Self::layout(ctx);
+
+		self.pointer.render(renderer, &self.widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, Self::line_top(ctx, index), scale);
+		}
+	}
+
+	// The prompt replaces the menu below it until the player decides.
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		match input {
+			Input::KeyPressed(Key::Enter) => self.resume(ctx),
+			Input::KeyPressed(Key::Escape) => self.decline(),
+			// Either answer can be clicked too.
+			Input::MouseMoved { .. } | Input::MousePressed | Input::MouseReleased => match self.pointer.handle(&self.widgets(ctx), input) {
+				PointerAction::Click(0) => self.resume(ctx),
+				PointerAction::Click(_) => self.decline(),
+				_ => Transition::None
+			},
+			// Ignore anything else.
+			_ => Transition::None
+		}
+	}
+}
@@ -0,0 +1,455 @@
+// Dependencies
+
+use std::collections::BTreeMap;
+
+use crate::structs::Vec2;
+use crate::scene::{Scene, Context, Renderer, Transition};
+use crate::input::{Input, Key};
+use crate::game::Difficulty;
+use crate::cosmetics::{self, PADDLE_SKINS, BALL_SKINS};
+use crate::widget::{Widget, Bounds, ListMenu, MenuAction};
+use crate::text;
+
+// How much the mouse sensitivity changes with each press of left or right, and how low and high it can go.
+
+pub const SENSITIVITY_STEP: f32 = 0.1;
+pub const MIN_SENSITIVITY: f32 = 0.1;
+pub const MAX_SENSITIVITY: f32 = 5.0;
+
+// Most points a match can be played to, and the longest countdown before a serve, in seconds.
+
+pub const MAX_WIN_SCORE: u32 = 99;
+pub const MAX_SERVE_COUNTDOWN: u32 = 5;
+
+// Implement the pages of the settings menu. It opens on a list of the other pages.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SettingsPage {
+	Categories,
+	Video,
+	Controls,
+	Gameplay,
+	Skins
+}
+
+// Implement the rows shown on the settings pages.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SettingsRow {
+	// Open another page.
+	Page(SettingsPage),
+	Fullscreen,
+	Vsync,
+	// Whether a match pauses when the window loses focus.
+	PauseInBackground,
+	// Whether holiday themes are put on by the date.
+	SeasonalThemes,
+	Sensitivity,
+	// Whether a gamepad rumbles during a match.
+	Rumble,
+	// Which keyboard key triggers a game key.
+	Keybind(Key),
+	Difficulty,
+	WinScore,
+	ServeCountdown,
+	// Whether the tutorial is shown over the next match.
+	Tutorial,
+	// Whether notable moments are called out mid-court.
+	Callouts,
+	// Whether each point is replayed in slow motion.
+	InstantReplays,
+	// Which unlocked skin the active profile's paddle and ball are drawn in.
+	PaddleSkin,
+	BallSkin,
+	// Go back to the list of pages, or leave the settings from there.
+	Back
+}
+
+impl SettingsPage {
+	// Key of this page's title in the language strings.
+	fn text_key(self) -> &'static str {
+		match self {
+			SettingsPage::Categories => "settings.title",
+			SettingsPage::Video => "settings.video",
+			SettingsPage::Controls => "settings.controls",
+			SettingsPage::Gameplay => "settings.gameplay",
+			SettingsPage::Skins => "settings.skins"
+		}
+	}
+
+	// Get the rows on this page, from top to bottom.
+	pub fn rows(self) -> Vec<SettingsRow> {
+		let mut rows = match self {
+			SettingsPage::Categories => vec![
+				SettingsRow::Page(SettingsPage::Video),
+				SettingsRow::Page(SettingsPage::Controls),
+				SettingsRow::Page(SettingsPage::Gameplay),
+				SettingsRow::Page(SettingsPage::Skins)
+			],
+			SettingsPage::Video => vec![SettingsRow::Fullscreen, SettingsRow::Vsync, SettingsRow::PauseInBackground, SettingsRow::SeasonalThemes],
+			SettingsPage::Controls => {
+				let mut rows = vec![SettingsRow::Sensitivity, SettingsRow::Rumble];
+				rows.extend(Key::ALL.into_iter().map(SettingsRow::Keybind));
+				rows
+			},
+			SettingsPage::Gameplay => vec![SettingsRow::Difficulty, SettingsRow::WinScore, SettingsRow::ServeCountdown, SettingsRow::Tutorial, SettingsRow::Callouts, SettingsRow::InstantReplays],
+			SettingsPage::Skins => vec![SettingsRow::PaddleSkin, SettingsRow::BallSkin]
+		};
+		rows.push(SettingsRow::Back);
+		rows
+	}
+}
+
+// Implement the settings menu, reachable from the main menu and the pause menu.
+// Rows are chosen the same way as on the other menus, and left and right change the highlighted setting.
+// Changes apply straight away where they can and are saved to the config file on exit. Vsync only changes the next time the game starts.
+
+pub struct SettingsScene {
+	pub page: SettingsPage,
+	pub menu: ListMenu,
+	// The game key waiting for a keyboard key to be bound to it, if any.
+	pub rebinding: Option<Key>,
+	// Whether to ignore the next game key, since it came from the keyboard key that was just bound.
+	swallow: bool
+}
+
+impl Default for SettingsScene {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SettingsScene {
+	pub fn new() -> Self {
+		Self {
+			page: SettingsPage::Categories,
+			menu: ListMenu::new(0),
+			rebinding: None,
+			swallow: false
+		}
+	}
+
+	// Size of the text's pixels and the space each row takes up.
+	// The text is smaller than on the other menus, so every keybind fits on one page.
+	fn layout(ctx: &Context) -> (f32, f32) {
+		let scale = (ctx.height / 150.0).floor().max(2.0);
+		(scale, (text::GLYPH_HEIGHT + 3.0) * scale)
+	}
+
+	// Get the top of the given row.
+	fn row_top(ctx: &Context, index: usize) -> f32 {
+		let (_, line_height) = Self::layout(ctx);
+		ctx.height * 0.25 + index as f32 * line_height
+	}
+
+	// Get the area of the slider track on the given row, off to the right of the text.
+	// The track is as tall as the row, so it's easy to grab, but only drawn as a thin line.
+	fn track_bounds(ctx: &Context, index: usize) -> Bounds {
+		let (_, line_height) = Self::layout(ctx);
+		Bounds::new(Vec2::new(ctx.width * 0.8, Self::row_top(ctx, index)), Vec2::new(ctx.width * 0.15, line_height))
+	}
+
+	// Get the area of each row that can be pointed at.
+	// Rows with a number setting are sliders, which can also be dragged along their track.
+	pub fn widgets(&self, ctx: &Context) -> Vec<Widget> {
+		let (_, line_height) = Self::layout(ctx);
+		self.page.rows().into_iter().enumerate().map(|(index, row)| {
+			let bounds = ListMenu::row(ctx, Self::row_top(ctx, index), line_height);
+			match Self::slider(ctx, row) {
+				Some(_) => Widget::slider(bounds, Self::track_bounds(ctx, index)),
+				None => Widget::button(bounds)
+			}
+		}).collect()
+	}
+
+	// Get the lowest and highest values of a number setting and its current value, or nothing for rows that aren't numbers.
+	pub fn slider(ctx: &Context, row: SettingsRow) -> Option<(f32, f32, f32)> {
+		let gameplay = &ctx.config.gameplay;
+		match row {
+			SettingsRow::Sensitivity => Some((MIN_SENSITIVITY, MAX_SENSITIVITY, ctx.config.controls.sensitivity)),
+			SettingsRow::WinScore => Some((1.0, MAX_WIN_SCORE as f32, gameplay.win_score as f32)),
+			SettingsRow::ServeCountdown => Some((0.0, MAX_SERVE_COUNTDOWN as f32, gameplay.serve_countdown as f32)),
+			_ => None
+		}
+	}
+
+	// Get the text of a row, with the setting's current value.
+	fn row_text(&self, ctx: &Context, row: SettingsRow) -> String {
+		let locale = &ctx.locale;
+		let on_off = |on: bool| locale.get(if on { "settings.on" } else { "settings.off" });
+
+		let (name, value) = match row {
+			SettingsRow::Page(page) => return locale.get(page.text_key()).to_string(),
+			SettingsRow::Back => return locale.get("settings.back").to_string(),
+			SettingsRow::Fullscreen => ("settings.fullscreen", on_off(ctx.config.window.fullscreen).to_string()),
+			// Vsync shows what it'll be after a restart, since it can't change before then.
+			SettingsRow::Vsync => ("settings.vsync", on_off(ctx.saved_config.window.vsync).to_string()),
+			SettingsRow::PauseInBackground => ("settings.pause-in-background", on_off(ctx.config.window.pause_in_background).to_string()),
+			SettingsRow::SeasonalThemes => ("settings.seasonal-themes", on_off(ctx.config.seasonal_themes).to_string()),
+			SettingsRow::Sensitivity => ("settings.sensitivity", format!("{:.1}", ctx.config.controls.sensitivity)),
+			SettingsRow::Rumble => ("settings.rumble", on_off(ctx.config.controls.rumble).to_string()),
+			SettingsRow::Keybind(key) => {
+				let bound = if self.rebinding == Some(key) {
+					locale.get("settings.press-key")
+				} else {
+					ctx.config.keybinds.get(key.name()).map(String::as_str).unwrap_or_else(|| locale.get("settings.unbound"))
+				};
+				return format!("{}: {}", locale.get(&format!("keys.{}", key.name())), bound);
+			},
+			SettingsRow::Difficulty => {
+				let difficulty = ctx.config.gameplay.ai_difficulty;
+				("settings.difficulty", locale.get(&format!("settings.difficulty-{}", difficulty.name())).to_string())
+			},
+			SettingsRow::WinScore => ("settings.win-score", ctx.config.gameplay.win_score.to_string()),
+			SettingsRow::ServeCountdown => match ctx.config.gameplay.serve_countdown {
+				0 => ("settings.serve-countdown", locale.get("settings.off").to_string()),
+				seconds => ("settings.serve-countdown", locale.format("settings.seconds", &[("seconds", &seconds)]))
+			},
+			SettingsRow::Tutorial => ("settings.tutorial", on_off(ctx.config.gameplay.tutorial).to_string()),
+			SettingsRow::Callouts => ("settings.callouts", on_off(ctx.config.gameplay.callouts).to_string()),
+			SettingsRow::InstantReplays => ("settings.instant-replays", on_off(ctx.config.gameplay.instant_replays).to_string()),
+			// Skins are kept in the active profile, so guests can't choose one.
+			SettingsRow::PaddleSkin | SettingsRow::BallSkin => {
+				let (paddle, ball) = cosmetics::for_profile(ctx.profiles.active());
+				let (name, skin) = if row == SettingsRow::PaddleSkin { ("settings.paddle-skin", paddle) } else { ("settings.ball-skin", ball) };
+				match ctx.profiles.active() {
+					Some(_) => (name, locale.get(&skin.text_key()).to_string()),
+					None => (name, locale.get("settings.skin-needs-profile").to_string())
+				}
+			}
+		};
+		format!("{}: {}", locale.get(name), value)
+	}
+
+	// Get the text of each row on the page, marking the highlighted one.
+	pub fn lines(&self, ctx: &Context) -> Vec<String> {
+		self.menu.mark(self.page.rows().into_iter().map(|row| self.row_text(ctx, row)))
+	}
+
+	// Switch to another page, highlighting its first row.
+	fn open(&mut self, page: SettingsPage) {
+		self.page = page;
+		self.menu.selected = 0;
+		self.menu.pointer.reset();
+	}
+
+	// Go back to the list of pages, highlighting the one just left, or leave the settings if already there.
+	fn back(&mut self) -> Transition {
+		if self.page == SettingsPage::Categories {
+			return Transition::Pop;
+		}
+
+		let left = SettingsRow::Page(self.page);
+		self.open(SettingsPage::Categories);
+		self.menu.selected = self.page.rows().iter().position(|row| *row == left).unwrap_or(0);
+		Transition::None
+	}
+
+	// Change the highlighted setting one step up or down.
+	// Settings that are on or off are switched either way, and the difficulty and skins wrap around at either end.
+	// Skins are only changed in the active profile, stepping through the ones it's unlocked.
+	fn adjust(&mut self, ctx: &mut Context, step: i32) {
+		match self.page.rows()[self.menu.selected] {
+			SettingsRow::Fullscreen => {
+				let fullscreen = !ctx.config.window.fullscreen;
+				ctx.change_config(|config| config.window.fullscreen = fullscreen);
+			},
+			SettingsRow::Vsync => {
+				let vsync = !ctx.saved_config.window.vsync;
+				ctx.change_config(|config| config.window.vsync = vsync);
+			},
+			SettingsRow::PauseInBackground => {
+				let pause = !ctx.config.window.pause_in_background;
+				ctx.change_config(|config| config.window.pause_in_background = pause);
+			},
+			SettingsRow::SeasonalThemes => {
+				let seasonal = !ctx.config.seasonal_themes;
+				ctx.change_config(|config| config.seasonal_themes = seasonal);
+			},
+			SettingsRow::Sensitivity => {
+				let sensitivity = ctx.config.controls.sensitivity + step as f32 * SENSITIVITY_STEP;
+				Self::set_number(ctx, SettingsRow::Sensitivity, sensitivity);
+			},
+			SettingsRow::Rumble => {
+				let rumble = !ctx.config.controls.rumble;
+				ctx.change_config(|config| config.controls.rumble = rumble);
+			},
+			SettingsRow::Difficulty => {
+				let count = Difficulty::ALL.len() as i32;
+				let index = Difficulty::ALL.iter().position(|difficulty| *difficulty == ctx.config.gameplay.ai_difficulty).unwrap_or(0) as i32;
+				let difficulty = Difficulty::ALL[(index + step).rem_euclid(count) as usize];
+				ctx.change_config(|config| config.gameplay.ai_difficulty = difficulty);
+			},
+			SettingsRow::WinScore => Self::set_number(ctx, SettingsRow::WinScore, (ctx.config.gameplay.win_score as i32 + step) as f32),
+			SettingsRow::ServeCountdown => Self::set_number(ctx, SettingsRow::ServeCountdown, (ctx.config.gameplay.serve_countdown as i32 + step) as f32),
+			SettingsRow::Tutorial => {
+				let tutorial = !ctx.config.gameplay.tutorial;
+				ctx.change_config(|config| config.gameplay.tutorial = tutorial);
+			},
+			SettingsRow::Callouts => {
+				let callouts = !ctx.config.gameplay.callouts;
+				ctx.change_config(|config| config.gameplay.callouts = callouts);
+			},
+			SettingsRow::InstantReplays => {
+				let instant_replays = !ctx.config.gameplay.instant_replays;
+				ctx.change_config(|config| config.gameplay.instant_replays = instant_replays);
+			},
+			SettingsRow::PaddleSkin => {
+				if let Some(profile) = ctx.profiles.active_mut() {
+					profile.paddle_skin = cosmetics::cycle(PADDLE_SKINS, &profile.paddle_skin, &profile.stats, step).id.to_string();
+				}
+			},
+			SettingsRow::BallSkin => {
+				if let Some(profile) = ctx.profiles.active_mut() {
+					profile.ball_skin = cosmetics::cycle(BALL_SKINS, &profile.ball_skin, &profile.stats, step).id.to_string();
+				}
+			},
+			// The other rows don't have a value to change.
+			SettingsRow::Page(_) | SettingsRow::Keybind(_) | SettingsRow::Back => ()
+		}
+	}
+
+	// Set a number setting, rounded to the nearest value it can take and kept between its lowest and highest.
+	fn set_number(ctx: &mut Context, row: SettingsRow, value: f32) {
+		match row {
+			SettingsRow::Sensitivity => {
+				// Work in whole steps, so repeated changes don't drift away from round numbers.
+				let steps = (value / SENSITIVITY_STEP).round();
+				let sensitivity = (steps * SENSITIVITY_STEP).clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+				ctx.change_config(|config| config.controls.sensitivity = sensitivity);
+
+				// The active profile's sensitivity is applied over the config file's, so it needs changing too.
+				if let Some(profile) = ctx.profiles.active_mut() {
+					profile.sensitivity = sensitivity;
+				}
+			},
+			SettingsRow::WinScore => {
+				let win_score = value.round().clamp(1.0, MAX_WIN_SCORE as f32) as u32;
+				ctx.change_config(|config| config.gameplay.win_score = win_score);
+			},
+			SettingsRow::ServeCountdown => {
+				let countdown = value.round().clamp(0.0, MAX_SERVE_COUNTDOWN as f32) as u32;
+				ctx.change_config(|config| config.gameplay.serve_countdown = countdown);
+			},
+			_ => ()
+		}
+	}
+
+	// Do whatever the highlighted row does when chosen.
+	fn choose(&mut self, ctx: &mut Context) -> Transition {
+		match self.page.rows()[self.menu.selected] {
+			SettingsRow::Page(page) => self.open(page),
+			SettingsRow::Keybind(key) => self.rebinding = Some(key),
+			SettingsRow::Back => return self.back(),
+			SettingsRow::Fullscreen | SettingsRow::Vsync | SettingsRow::PauseInBackground | SettingsRow::SeasonalThemes | SettingsRow::Rumble | SettingsRow::Difficulty | SettingsRow::Tutorial | SettingsRow::Callouts | SettingsRow::InstantReplays | SettingsRow::PaddleSkin | SettingsRow::BallSkin => self.adjust(ctx, 1),
+			// Numbers are only changed with left and right.
+			SettingsRow::Sensitivity | SettingsRow::WinScore | SettingsRow::ServeCountdown => ()
+		}
+		Transition::None
+	}
+
+	// Bind a keyboard key to a game key.
+	// Whatever game key it was bound to before takes over this one's old keyboard key, so the two swap rather than clash.
+	fn bind(ctx: &mut Context, key: Key, name: &'static str) {
+		let old = ctx.config.keybinds.get(key.name()).cloned();
+		let other = ctx.config.key_for(name).filter(|other| *other != key);
+
+		let change = |keybinds: &mut BTreeMap<String, String>| {
+			keybinds.insert(key.name().to_string(), name.to_string());
+			if let Some(other) = other {
+				match &old {
+					Some(old) => keybinds.insert(other.name().to_string(), old.clone()),
+					None => keybinds.remove(other.name())
+				};
+			}
+		};
+		ctx.change_config(|config| change(&mut config.keybinds));
+
+		// The active profile's keybinds are applied over the config file's, so they need changing too.
+		if let Some(profile) = ctx.profiles.active_mut() {
+			change(&mut profile.keybinds);
+		}
+	}
+}
+
+impl Scene for SettingsScene {
+	// Keep the simulation stopped and the cursor free while changing settings.
+	fn enter(&mut self, ctx: &mut Context) {
+		self.menu.pointer.release();
+		ctx.sim.with(|game| game.paused = true);
+		ctx.cursor_grabbed = false;
+	}
+
+	fn render(&self, ctx: &Context, renderer: &mut dyn Renderer) {
+		let (scale, _) = Self::layout(ctx);
+		text::draw_centered(renderer, ctx.locale.get(self.page.text_key()), ctx.width / 2.0, ctx.height * 0.1, scale * 2.0);
+
+		self.menu.render(renderer, &self.widgets(ctx));
+		for (index, line) in self.lines(ctx).iter().enumerate() {
+			text::draw_centered(renderer, line, ctx.width / 2.0, Self::row_top(ctx, index), scale);
+		}
+
+		// Draw each slider as a line along its track, with a mark where its value is.
+		for (index, row) in self.page.rows().into_iter().enumerate() {
+			if let Some((min, max, value)) = Self::slider(ctx, row) {
+				let track = Self::track_bounds(ctx, index);
+				let middle = track.position.y + (text::GLYPH_HEIGHT * scale - scale) / 2.0;
+				let along = track.size.x * ((value - min) / (max - min)).clamp(0.0, 1.0);
+				renderer.rect(Vec2::new(track.position.x, middle), Vec2::new(track.size.x, scale));
+				renderer.rect(Vec2::new(track.position.x + along - scale / 2.0, track.position.y), Vec2::new(scale, text::GLYPH_HEIGHT * scale));
+			}
+		}
+	}
+
+	// The settings cover whatever menu they were opened from.
+	fn opaque(&self) -> bool {
+		true
+	}
+
+	fn handle_input(&mut self, ctx: &mut Context, input: Input) -> Transition {
+		// Every key press comes as the keyboard key first, then the game key it's bound to.
+		// While rebinding, the keyboard key is bound, unless it's the one bound to escape, which cancels. Either way the game key that follows is ignored.
+		if let Input::KeyboardKey(name) = input {
+			self.swallow = false;
+			if let Some(key) = self.rebinding.take() {
+				if ctx.config.key_for(name) != Some(Key::Escape) {
+					Self::bind(ctx, key, name);
+				}
+				self.swallow = true;
+			}
+			return Transition::None;
+		}
+
+		if self.rebinding.is_some() {
+			return Transition::None;
+		}
+		if self.swallow && matches!(input, Input::KeyPressed(_)) {
+			self.swallow = false;
+			return Transition::None;
+		}
+
+		match input {
+			Input::KeyPressed(Key::Left) => {
+				self.adjust(ctx, -1);
+				Transition::None
+			},
+			Input::KeyPressed(Key::Right) => {
+				self.adjust(ctx, 1);
+				Transition::None
+			},
+			Input::KeyPressed(Key::Escape) => self.back(),
+			// Set sliders to wherever they're dragged.
+			_ => match self.menu.handle(&self.widgets(ctx), input) {
+				MenuAction::Choose => self.choose(ctx),
+				MenuAction::Drag(fraction) => {
+					let row = self.page.rows()[self.menu.selected];
+					if let Some((min, max, _)) = Self::slider(ctx, row) {
+						Self::set_number(ctx, row, min + fraction * (max - min));
+					}
+					Transition::None
+				},
+				MenuAction::None => Transition::None
+			}
+		}
+	}
+}
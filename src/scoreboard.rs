@@ -0,0 +1,83 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::events::{GameEvent, Side};
+use crate::game::GameState;
+use crate::scene::Renderer;
+use crate::text;
+
+// Implement a big scoreboard for a second screen, like a projector over the table at an event, showing only the scores and the match clock.
+// The scores follow the simulation's events, so the board never shows a score the game hasn't announced, and the clock follows the match.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Scoreboard {
+	pub scores: [u32; 2],
+	// Time played in the match, in milliseconds.
+	pub clock: f32,
+	// Who won the last match, shown until the next one starts.
+	pub winner: Option<Side>
+}
+
+impl Scoreboard {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Update the board from this frame's snapshot of the game and its events.
+	// Once a match is won, the final score and time stay up until a new match starts.
+	pub fn record(&mut self, game: &GameState, events: &[GameEvent]) {
+		for event in events {
+			match event {
+				GameEvent::MatchStarted => *self = Self::new(),
+				GameEvent::PointScored { scores, .. } => self.scores = *scores,
+				GameEvent::MatchEnded { winner, scores, .. } => {
+					self.scores = *scores;
+					self.winner = Some(*winner);
+				},
+				// Ignore anything else.
+				_ => ()
+			}
+		}
+
+		if self.winner.is_none() {
+			self.clock = game.elapsed;
+		}
+	}
+
+	// Get the match clock as minutes and seconds, e.g. "3:07".
+	pub fn clock_text(&self) -> String {
+		let seconds = (self.clock / 1000.0) as u32;
+		format!("{}:{:02}", seconds / 60, seconds % 60)
+	}
+
+	// Draw the board filling a screen of the given size: each side's score over its half, the clock underneath, and a bar under the winner's score once the match is over.
+	pub fn render(&self, renderer: &mut dyn Renderer, width: f32, height: f32) {
+		let score_scale = (height / 12.0).min(width / 30.0).floor().max(1.0);
+		let clock_scale = (score_scale / 3.0).floor().max(1.0);
+		let top = height * 0.2;
+
+		for side in [Side::Left, Side::Right] {
+			let x = match side {
+				Side::Left => width * 0.25,
+				Side::Right => width * 0.75
+			};
+			let score = self.scores[side.index()].to_string();
+			text::draw_centered(renderer, &score, x, top, score_scale);
+
+			if self.winner == Some(side) {
+				let bar = Vec2::new(text::width(&score, score_scale), score_scale);
+				renderer.rect(Vec2::new(x - bar.x / 2.0, top + score_scale * (text::GLYPH_HEIGHT + 1.0)), bar);
+			}
+		}
+
+		// A dashed line down the middle, like the court's.
+		let dash = score_scale / 2.0;
+		let mut y = top;
+		while y < top + score_scale * text::GLYPH_HEIGHT {
+			renderer.rect(Vec2::new((width - dash) / 2.0, y), Vec2::new(dash, dash));
+			y += dash * 2.0;
+		}
+
+		text::draw_centered(renderer, &self.clock_text(), width / 2.0, height * 0.7, clock_scale);
+	}
+}
@@ -0,0 +1,99 @@
+// Dependencies
+
+use glium::{Display, Program, Surface};
+use glium::glutin::{
+	event_loop::EventLoop,
+	window::{Fullscreen, Window, WindowBuilder, WindowId},
+	dpi::LogicalSize,
+	ContextBuilder, GlRequest
+};
+
+use pong::assets::Assets;
+use pong::locale::Locale;
+use pong::events::GameEvent;
+use pong::game::GameState;
+use pong::scoreboard::Scoreboard;
+use pong::viewport::Viewport;
+
+use crate::render::{self, Batch, GliumRenderer, FrameUniforms};
+use crate::error::Error;
+
+// Implement a second window showing only a big scoreboard, e.g. on a projector or a second monitor facing the audience.
+// It goes borderless fullscreen on a screen the game's window isn't on, if there is one, otherwise it opens as an ordinary window.
+
+pub struct ScoreboardWindow {
+	display: Display,
+	program: Program,
+	batch: Batch,
+	uniforms: FrameUniforms,
+	// The window's viewport, and the size in pixels it was worked out for, so it's only worked out again when the window changes size.
+	viewport: Option<((u32, u32), Viewport)>,
+	scoreboard: Scoreboard
+}
+
+impl ScoreboardWindow {
+	pub fn new(event_loop: &EventLoop<()>, game_window: &Window, gl_request: GlRequest, assets: &mut Assets, locale: &Locale) -> Result<Self, Error> {
+		let win_build = WindowBuilder::new()
+			.with_title(locale.get("window.scoreboard"))
+			.with_inner_size(LogicalSize::new(960, 540));
+		// Vsync is left off, so waiting for this window's screen doesn't hold up drawing the game.
+		let ctx_build = ContextBuilder::new()
+			.with_vsync(false)
+			.with_gl(gl_request);
+		let display = Display::new(win_build, ctx_build, event_loop)?;
+
+		// Go fullscreen once the window's been created, which works on every platform, including macOS.
+		let current = game_window.current_monitor();
+		if let Some(monitor) = event_loop.available_monitors().find(|monitor| Some(monitor) != current.as_ref()) {
+			display.gl_window().window().set_fullscreen(Some(Fullscreen::Borderless(Some(monitor))));
+		}
+
+		let program = render::load_program(&display, assets)?;
+		let batch = Batch::new(&display)?;
+		let uniforms = FrameUniforms::new(&display)?;
+
+		Ok(Self {
+			display,
+			program,
+			batch,
+			uniforms,
+			viewport: None,
+			scoreboard: Scoreboard::new()
+		})
+	}
+
+	// Get the ID of the window, to tell its events apart from the game window's.
+	pub fn id(&self) -> WindowId {
+		self.display.gl_window().window().id()
+	}
+
+	// Update the board from this frame's snapshot of the game and its events, then draw it.
+	pub fn draw(&mut self, game: &GameState, events: &[GameEvent]) -> Result<(), Error> {
+		self.scoreboard.record(game, events);
+
+		let mut frame = self.display.draw();
+		frame.clear_color(0.0, 0.0, 0.0, 1.0);
+
+		let dimensions = frame.get_dimensions();
+		let viewport = match self.viewport {
+			Some((size, viewport)) if size == dimensions => viewport,
+			_ => {
+				let (width, height) = dimensions;
+				let viewport = Viewport::new(width as f32, height as f32, self.display.gl_window().window().scale_factor() as f32);
+				self.uniforms.set_perspective(viewport.perspective());
+				self.viewport = Some((dimensions, viewport));
+				viewport
+			}
+		};
+
+		let size = viewport.size();
+		self.scoreboard.render(&mut GliumRenderer {
+			frame: &mut frame,
+			batch: &mut self.batch,
+			program: &self.program,
+			uniforms: &self.uniforms
+		}, size.x, size.y);
+
+		Ok(frame.finish()?)
+	}
+}
@@ -0,0 +1,164 @@
+// Dependencies
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::events::{Side, GameEvent};
+use crate::modes::{GameMode, Survival};
+use crate::paths;
+
+// Implement the player's best results, kept between runs in a file in the platform's data directory.
+// Like the config file, anything missing from the file starts from zero, so old files keep working as records are added.
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+	// Longest the player has lasted in survival mode, in seconds.
+	pub best_survival: u32,
+	// Most times the ball has been hit back and forth in a single point.
+	pub longest_rally: u32,
+	// Names of whoever set the best survival time and the longest rally, if they've been given.
+	pub best_survival_by: Option<String>,
+	pub longest_rally_by: Option<String>,
+	// Matches the player has played and won in each game mode, by the mode's name.
+	// This has to come after the plain values, since it's written as a table.
+	pub matches: BTreeMap<String, MatchRecord>,
+	// Records beaten since names were last given, waiting for the player's name.
+	#[serde(skip)]
+	pub unnamed: Vec<Record>,
+	// Paddle hits in the point being played now.
+	#[serde(skip)]
+	rally: u32
+}
+
+// Implement the records that are kept with the name of whoever set them.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Record {
+	BestSurvival,
+	LongestRally
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchRecord {
+	pub played: u32,
+	pub won: u32
+}
+
+impl HighScores {
+	// Get the path of the high score file in the platform's data directory.
+	pub fn path() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("scores.toml"))
+	}
+
+	// Load the high scores, starting from nothing if there aren't any saved yet.
+	pub fn load() -> Self {
+		match Self::path() {
+			Some(path) => Self::load_from(&path),
+			None => {
+				tracing::warn!("No data directory on this platform, high scores won't be saved");
+				Self::default()
+			}
+		}
+	}
+
+	// Load the high scores from a given path.
+	// If the file can't be read or parsed, it's reported and left alone rather than overwritten, so a broken file doesn't wipe the records.
+	pub fn load_from(path: &Path) -> Self {
+		if !path.exists() {
+			return Self::default();
+		}
+
+		let loaded = fs::read_to_string(path)
+			.map_err(|err| format!("Failed to read high scores {}: {}", path.display(), err))
+			.and_then(|text| toml::from_str(&text).map_err(|err| format!("Invalid high scores {}: {}", path.display(), err)));
+
+		loaded.unwrap_or_else(|err| {
+			tracing::warn!("{}, starting from nothing", err);
+			Self::default()
+		})
+	}
+
+	// Save the high scores to the platform's data directory, reporting any failure.
+	pub fn save(&self) {
+		let Some(path) = Self::path() else { return };
+		if let Err(err) = self.save_to(&path) {
+			tracing::warn!("Failed to save high scores to {}: {}", path.display(), err);
+		}
+	}
+
+	// Write the high scores to a given path, creating its directory if needed.
+	pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let text = toml::to_string_pretty(self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		fs::write(path, text)
+	}
+
+	// Update the records from the events of a match in the given mode, played by the player on the given side.
+	// Without a player, e.g. two AI paddles playing each other, only rallies count.
+	// Returns whether anything changed, meaning the high scores should be saved.
+	pub fn record(&mut self, mode: &dyn GameMode, player: Option<Side>, events: &[GameEvent]) -> bool {
+		let mut changed = false;
+
+		for event in events {
+			match event {
+				GameEvent::BallHitPaddle { .. } => self.rally += 1,
+				// A rally's length is only final once someone scores.
+				GameEvent::PointScored { .. } => {
+					if self.rally > self.longest_rally {
+						self.longest_rally = self.rally;
+						self.beaten(Record::LongestRally);
+						changed = true;
+					}
+					self.rally = 0;
+				},
+				GameEvent::MatchEnded { winner, scores, .. } => if let Some(player) = player {
+					let record = self.matches.entry(mode.name().to_string()).or_default();
+					record.played += 1;
+					if *winner == player {
+						record.won += 1;
+					}
+
+					// The player's score in survival is how many seconds they lasted.
+					if mode.name() == Survival.name() && scores[player.index()] > self.best_survival {
+						self.best_survival = scores[player.index()];
+						self.beaten(Record::BestSurvival);
+					}
+					changed = true;
+				},
+				// Ignore anything else.
+				_ => ()
+			}
+		}
+
+		changed
+	}
+
+	// Forget who held a record that's just been beaten, until the new holder gives their name.
+	fn beaten(&mut self, record: Record) {
+		match record {
+			Record::BestSurvival => self.best_survival_by = None,
+			Record::LongestRally => self.longest_rally_by = None
+		}
+		if !self.unnamed.contains(&record) {
+			self.unnamed.push(record);
+		}
+	}
+
+	// Give a name to every record beaten since names were last given.
+	pub fn name_records(&mut self, name: &str) {
+		for record in self.unnamed.drain(..) {
+			match record {
+				Record::BestSurvival => self.best_survival_by = Some(name.to_string()),
+				Record::LongestRally => self.longest_rally_by = Some(name.to_string())
+			}
+		}
+	}
+}
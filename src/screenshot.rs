@@ -0,0 +1,52 @@
+// Dependencies
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use glium::Display;
+use glium::texture::RawImage2d;
+
+use pong::paths;
+
+// Get the directory screenshots are saved to in the platform's data directory.
+
+pub fn dir() -> Option<PathBuf> {
+	paths::data_dir().map(|dir| dir.join("screenshots"))
+}
+
+// Read back the last frame shown in the window and save it as a PNG in the screenshots directory, named after the current time.
+// Returns the path it was saved to.
+
+pub fn capture(display: &Display) -> Result<PathBuf, String> {
+	let dir = dir().ok_or("No data directory on this platform")?;
+
+	let image: RawImage2d<u8> = display.read_front_buffer().map_err(|err| format!("Failed to read the window: {:?}", err))?;
+
+	let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_millis()).unwrap_or(0);
+	let path = dir.join(format!("screenshot-{}.png", millis));
+	save_png(&path, image.width, image.height, &image.data)?;
+	Ok(path)
+}
+
+// Write RGBA pixels to a PNG file.
+// OpenGL reads the pixels from the bottom row up, so they're flipped to the top-down order PNG expects.
+
+fn save_png(path: &Path, width: u32, height: u32, pixels: &[u8]) -> Result<(), String> {
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+	}
+
+	let row = width as usize * 4;
+	let flipped: Vec<u8> = pixels.chunks_exact(row).rev().flatten().copied().collect();
+
+	let file = File::create(path).map_err(|err| format!("Failed to create {}: {}", path.display(), err))?;
+	let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+	encoder.set_color(png::ColorType::Rgba);
+	encoder.set_depth(png::BitDepth::Eight);
+
+	encoder.write_header()
+		.and_then(|mut writer| writer.write_image_data(&flipped))
+		.map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
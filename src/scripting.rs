@@ -0,0 +1,210 @@
+// Dependencies
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Rhai is the embedded scripting language used for custom rules.
+// https://crates.io/crates/rhai
+
+use rhai::{Engine, AST, Scope, Map, Dynamic, CallFnOptions};
+
+use crate::structs::Vec2;
+use crate::game::{GameState, Rules};
+use crate::events::{Side, GameEvent};
+use crate::paths;
+
+// Limits on how much a script can do in each hook, so one stuck in a loop or recursing forever fails with an error instead of freezing the game.
+
+const MAX_OPERATIONS: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+
+// Implement a collection of user scripts that hook into game events to create custom rulesets.
+//
+// Scripts are `.rhai` files loaded from the scripts directory in the platform's data directory, and can define any of these functions:
+//
+//   fn on_point(side, left, right)  - a point was scored by "left" or "right", followed by both scores.
+//   fn on_match_end(winner)         - the match was won by "left" or "right".
+//
+// Inside a hook, `this` is a map of the match rules which the script can change:
+//
+//   fn on_point(side, left, right) {
+//       this.ball_speed *= 1.1;
+//       this.paddle_size = 0.75;
+//   }
+//
+// Angles in the rules, like max_bounce_angle, are in radians.
+// Gravity and wind are split into their parts, like gravity_x and gravity_y, and can be negative to push the other way.
+
+pub struct Scripts {
+	engine: Engine,
+	scripts: Vec<AST>
+}
+
+impl Scripts {
+	// Start with no scripts, in an engine that stops any script going over the limits.
+	fn new() -> Self {
+		let mut engine = Engine::new();
+		engine.set_max_operations(MAX_OPERATIONS);
+		engine.set_max_call_levels(MAX_CALL_LEVELS);
+		engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+
+		Self {
+			engine,
+			scripts: vec![]
+		}
+	}
+
+	// Get the directory scripts are loaded from in the platform's data directory.
+	pub fn dir() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("scripts"))
+	}
+
+	// Load every script in the scripts directory, or none on a platform without a data directory.
+	pub fn load_installed() -> Self {
+		match Self::dir() {
+			Some(dir) => Self::load(&dir),
+			None => {
+				tracing::warn!("No data directory on this platform, scripts not loaded");
+				Self::new()
+			}
+		}
+	}
+
+	// Load and compile every script in the given directory, in alphabetical order.
+	// Scripts that fail to compile are reported and skipped rather than stopping the game.
+	pub fn load(dir: &Path) -> Self {
+		let mut loaded = Self::new();
+
+		let mut paths: Vec<_> = match fs::read_dir(dir) {
+			Ok(entries) => entries
+				.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+				.filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+				.collect(),
+			Err(_) => vec![]
+		};
+		paths.sort();
+
+		for path in paths {
+			match loaded.engine.compile_file(path.clone()) {
+				Ok(ast) => {
+					tracing::info!("Loaded script {}", path.display());
+					loaded.scripts.push(ast);
+				},
+				Err(err) => tracing::warn!("Failed to load script {}: {}", path.display(), err)
+			}
+		}
+		loaded
+	}
+
+	// Check if any scripts were loaded.
+	pub fn is_empty(&self) -> bool {
+		self.scripts.is_empty()
+	}
+
+	// Run the script hooks for a batch of events emitted by the simulation.
+	// Returns the new rules if any script changed them.
+	pub fn handle_events(&self, game: &GameState, events: &[GameEvent]) -> Option<Rules> {
+		let mut params = rules_to_map(&game.rules);
+
+		for event in events {
+			match event {
+				GameEvent::PointScored { side, scores } => self.call("on_point", &mut params, (
+					side_name(*side),
+					scores[Side::Left.index()] as i64,
+					scores[Side::Right.index()] as i64
+				)),
+				GameEvent::MatchEnded { winner, .. } => self.call("on_match_end", &mut params, (
+					side_name(*winner),
+				)),
+				// Ignore anything else.
+				_ => ()
+			}
+		}
+
+		let rules = map_to_rules(&params, game.rules);
+		if rules != game.rules {
+			Some(rules)
+		} else {
+			None
+		}
+	}
+
+	// Call a hook in every script that defines it, with the rules map bound to `this`.
+	fn call(&self, name: &str, params: &mut Dynamic, args: impl rhai::FuncArgs + Clone) {
+		let _span = tracing::debug_span!("script_hook", name).entered();
+
+		for ast in &self.scripts {
+			if !ast.iter_functions().any(|func| func.name == name) {
+				continue;
+			}
+
+			let options = CallFnOptions::new().bind_this_ptr(params);
+			let result = self.engine.call_fn_with_options::<Dynamic>(options, &mut Scope::new(), ast, name, args.clone());
+			if let Err(err) = result {
+				tracing::warn!("Script error in {}: {}", name, err);
+			}
+		}
+	}
+}
+
+// Name of a side as seen by scripts.
+fn side_name(side: Side) -> String {
+	match side {
+		Side::Left => "left".into(),
+		Side::Right => "right".into()
+	}
+}
+
+// Convert match rules to a map that scripts can modify.
+fn rules_to_map(rules: &Rules) -> Dynamic {
+	let mut map = Map::new();
+	map.insert("ball_speed".into(), Dynamic::from_float(rules.ball_speed as f64));
+	map.insert("paddle_size".into(), Dynamic::from_float(rules.paddle_size as f64));
+	map.insert("max_bounce_angle".into(), Dynamic::from_float(rules.max_bounce_angle as f64));
+	map.insert("paddle_momentum".into(), Dynamic::from_float(rules.paddle_momentum as f64));
+	map.insert("spin_friction".into(), Dynamic::from_float(rules.spin_friction as f64));
+	map.insert("spin_curve".into(), Dynamic::from_float(rules.spin_curve as f64));
+	map.insert("paddle_restitution".into(), Dynamic::from_float(rules.paddle_restitution as f64));
+	map.insert("wall_restitution".into(), Dynamic::from_float(rules.wall_restitution as f64));
+	map.insert("gravity_x".into(), Dynamic::from_float(rules.gravity.x as f64));
+	map.insert("gravity_y".into(), Dynamic::from_float(rules.gravity.y as f64));
+	map.insert("wind_x".into(), Dynamic::from_float(rules.wind.x as f64));
+	map.insert("wind_y".into(), Dynamic::from_float(rules.wind.y as f64));
+	map.into()
+}
+
+// Read match rules back from a map modified by scripts.
+// Missing values, and values outside the range a rule allows, keep their previous setting.
+fn map_to_rules(params: &Dynamic, current: Rules) -> Rules {
+	let map = match params.read_lock::<Map>() {
+		Some(map) => map,
+		None => return current
+	};
+
+	let number = |key: &str| {
+		map.get(key)
+			.and_then(|value| value.as_float().ok().or_else(|| value.as_int().ok().map(|v| v as f64)))
+			.map(|value| value as f32)
+			.filter(|value| value.is_finite())
+	};
+
+	// Each rule is checked against the lowest and highest values it allows.
+	// Sizes, speeds and bounces have to be more than nothing, the ball can't bounce off straight up or down, and spin and momentum can be turned off.
+	let get = |key: &str, default: f32, min: f32, max: f32| number(key).filter(|value| (min..=max).contains(value)).unwrap_or(default);
+	let positive = f32::MIN_POSITIVE;
+	let any = f32::INFINITY;
+
+	Rules {
+		ball_speed: get("ball_speed", current.ball_speed, positive, any),
+		paddle_size: get("paddle_size", current.paddle_size, positive, any),
+		max_bounce_angle: get("max_bounce_angle", current.max_bounce_angle, 0.0, 89f32.to_radians()),
+		paddle_momentum: get("paddle_momentum", current.paddle_momentum, 0.0, any),
+		spin_friction: get("spin_friction", current.spin_friction, 0.0, 1.0),
+		spin_curve: get("spin_curve", current.spin_curve, 0.0, any),
+		paddle_restitution: get("paddle_restitution", current.paddle_restitution, positive, any),
+		wall_restitution: get("wall_restitution", current.wall_restitution, positive, any),
+		gravity: Vec2::new(get("gravity_x", current.gravity.x, -any, any), get("gravity_y", current.gravity.y, -any, any)),
+		wind: Vec2::new(get("wind_x", current.wind.x, -any, any), get("wind_y", current.wind.y, -any, any))
+	}
+}
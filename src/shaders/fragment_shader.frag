@@ -1,7 +1,9 @@
 #version 150
 
+in vec4 vertex_fill;
+
 out vec4 color;
 
 void main() {
-	color = vec4(1.0, 1.0, 1.0, 1.0);
+	color = vertex_fill;
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+#version 100
+
+// The same as the desktop shader, for OpenGL ES 2.0 on boards like the Raspberry Pi.
+// Fragment shaders have no default precision for floats in GLSL ES, and medium is plenty for a flat vertex_fill.
+
+precision mediump float;
+
+varying vec4 vertex_fill;
+
+void main() {
+	gl_FragColor = vertex_fill;
+}
@@ -0,0 +1,203 @@
+// Dependencies
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::game::{GameState, Command, TICK_TIME};
+use crate::replay::{Replay, ReplayBuffer};
+use crate::events::GameEvent;
+use crate::clock::{Clock, SystemClock};
+
+// How often the simulation thread wakes up while the game's in the background, running every tick that's built up since.
+// The ticks are the same length as ever, so the match plays out the same, only in bigger steps.
+
+pub const BACKGROUND_STEP_TIME: Duration = Duration::from_millis(100);
+
+// State shared between the simulation thread and the render thread.
+
+struct Shared {
+	game: GameState,
+	// When the simulation thread last advanced the game state, used to interpolate between ticks when rendering.
+	advanced_at: Duration,
+	// Recording of the match being played, if it's being recorded.
+	recording: Option<Replay>,
+	// The last few seconds of the match, always kept for instant replays.
+	buffer: ReplayBuffer
+}
+
+// Create a struct that runs the game simulation on its own thread.
+// The simulation ticks at a fixed rate regardless of how long rendering or window events take on the main thread.
+// The render loop reads a snapshot of the state each frame, and input is applied to the shared state directly.
+
+pub struct Simulation {
+	shared: Arc<Mutex<Shared>>,
+	clock: Arc<dyn Clock>,
+	running: Arc<AtomicBool>,
+	// Whether the game's in the background, so the thread wakes up less often.
+	background: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>
+}
+
+impl Simulation {
+	// Start simulating the given game state on a new thread, following real time.
+	pub fn spawn(game: GameState) -> Self {
+		Self::spawn_with_clock(game, Arc::new(SystemClock::new()))
+	}
+
+	// Start simulating the given game state on a new thread, following the given clock.
+	// With a manual clock, the simulation only advances as far as the clock is moved.
+	pub fn spawn_with_clock(game: GameState, clock: Arc<dyn Clock>) -> Self {
+		let shared = Arc::new(Mutex::new(Shared {
+			game,
+			advanced_at: clock.now(),
+			recording: None,
+			buffer: ReplayBuffer::new()
+		}));
+		let running = Arc::new(AtomicBool::new(true));
+		let background = Arc::new(AtomicBool::new(false));
+
+		let thread = {
+			let shared = Arc::clone(&shared);
+			let running = Arc::clone(&running);
+			let background = Arc::clone(&background);
+			let clock = Arc::clone(&clock);
+
+			thread::spawn(move || {
+				tracing::debug!("Simulation thread started");
+				let tick = Duration::from_secs_f32(TICK_TIME / 1000.0);
+
+				let mut last_tick = clock.now();
+				let mut next_tick = last_tick + tick;
+
+				while running.load(Ordering::Relaxed) {
+					let step = if background.load(Ordering::Relaxed) { BACKGROUND_STEP_TIME } else { tick };
+
+					// Sleep until the next step is due.
+					// This never sleeps longer than a step, in case the clock isn't moving in real time.
+					let now = clock.now();
+					if next_tick > now {
+						thread::sleep((next_tick - now).min(step));
+					}
+
+					// Advance by the time elapsed on the clock, in case the thread slept for longer than requested.
+					let now = clock.now();
+					let delta_time = now.saturating_sub(last_tick).as_nanos() as f32 / 1_000_000.0;
+					last_tick = now;
+					next_tick += step;
+
+					// If the thread has fallen far behind, don't try to catch up on every missed tick.
+					if next_tick < now {
+						tracing::debug!("Simulation thread fell behind by {:?}", now - next_tick);
+						next_tick = now + step;
+					}
+
+					let _span = tracing::trace_span!("simulate", delta_time).entered();
+					let mut shared = shared.lock().unwrap();
+					shared.game.advance(delta_time);
+					shared.advanced_at = now;
+				}
+			})
+		};
+
+		Self {
+			shared,
+			clock,
+			running,
+			background,
+			thread: Some(thread)
+		}
+	}
+
+	// Run a closure with mutable access to the game state, e.g. to apply player input.
+	pub fn with<R>(&self, f: impl FnOnce(&mut GameState) -> R) -> R {
+		f(&mut self.shared.lock().unwrap().game)
+	}
+
+	// Run the simulation in bigger, less frequent steps while the game's in the background, or at its full rate again.
+	pub fn set_background(&self, background: bool) {
+		self.background.store(background, Ordering::Relaxed);
+	}
+
+	// Apply a change to the game state from outside the simulation, recording it if the match is being recorded.
+	pub fn apply(&self, command: Command) {
+		let mut shared = self.shared.lock().unwrap();
+		let shared = &mut *shared;
+
+		if let Some(recording) = &mut shared.recording {
+			recording.record(shared.game.ticks - recording.start.ticks, command.clone());
+		}
+		shared.buffer.record(shared.game.ticks, command.clone());
+		shared.game.apply(command);
+	}
+
+	// Start recording the match from its current state, throwing away any recording already in progress.
+	pub fn start_recording(&self) {
+		let mut shared = self.shared.lock().unwrap();
+		shared.recording = Some(Replay::new(shared.game.clone()));
+	}
+
+	// Stop recording, returning the recording up to the current tick.
+	pub fn finish_recording(&self) -> Option<Replay> {
+		let mut shared = self.shared.lock().unwrap();
+		let mut replay = shared.recording.take()?;
+		replay.length = shared.game.ticks - replay.start.ticks;
+		Some(replay)
+	}
+
+	// Make an instant replay of the last few seconds of the match, up to the current tick.
+	pub fn instant_replay(&self) -> Option<Replay> {
+		let shared = self.shared.lock().unwrap();
+		shared.buffer.replay(shared.game.ticks)
+	}
+
+	// Replace the game state, e.g. with a match loaded from disk.
+	// A recording in progress starts again from the new state, and the instant replay buffer is emptied, since the commands so far don't lead to it.
+	pub fn restore(&self, game: GameState) {
+		let mut shared = self.shared.lock().unwrap();
+		if shared.recording.is_some() {
+			shared.recording = Some(Replay::new(game.clone()));
+		}
+		shared.buffer.clear();
+		shared.game = game;
+	}
+
+	// Take all events emitted by the simulation since the last call.
+	pub fn drain_events(&self) -> Vec<GameEvent> {
+		self.shared.lock().unwrap().game.events.drain().collect()
+	}
+
+	// The clock the simulation follows, so anything else timed alongside it moves at the same rate.
+	pub fn clock(&self) -> Arc<dyn Clock> {
+		Arc::clone(&self.clock)
+	}
+
+	// Take a copy of the current game state for rendering.
+	// This also returns how far the simulation is between ticks, accounting for the time since the simulation thread last ran.
+	// The instant replay buffer keeps a copy of its own every so often while it's at it, so the simulation thread never has to.
+	pub fn snapshot(&self) -> (GameState, f32) {
+		let mut shared = self.shared.lock().unwrap();
+		let shared = &mut *shared;
+		shared.buffer.update(&shared.game);
+
+		let since = self.clock.now().saturating_sub(shared.advanced_at).as_nanos() as f32 / 1_000_000.0;
+		let alpha = if shared.game.paused {
+			1.0
+		} else {
+			((shared.game.accumulator + since) / TICK_TIME).min(1.0)
+		};
+
+		(shared.game.clone(), alpha)
+	}
+}
+
+impl Drop for Simulation {
+	// Stop the simulation thread and wait for it to exit.
+	fn drop(&mut self) {
+		self.running.store(false, Ordering::Relaxed);
+		if let Some(thread) = self.thread.take() {
+			thread.join().ok();
+		}
+	}
+}
@@ -0,0 +1,78 @@
+// Dependencies
+
+use serde::{Serialize, Deserialize};
+
+use crate::events::Side;
+
+// Implement the statistics kept over a single match.
+// The game state updates these as the match is played, and hands them over with the MatchEnded event for the post-match summary and high scores.
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchStats {
+	// Times each side hit the ball.
+	pub hits: [u32; 2],
+	// Points played so far, and the hits made in all of them together.
+	pub points: u32,
+	pub rally_hits: u32,
+	// Most hits made in a single point.
+	pub longest_rally: u32,
+	// Fastest any ball has gone, in pixels per millisecond.
+	pub max_ball_speed: f32,
+	// Points each side won without the ball touching a paddle after the serve.
+	pub aces: [u32; 2],
+	// Which side won each point, in the order they were played.
+	pub timeline: Vec<Side>,
+	// How long the match took, in milliseconds.
+	// The game state keeps its own time, so this is only filled in on the stats handed over when the match ends, and isn't saved with the match.
+	#[serde(skip)]
+	pub duration: f32,
+	// Hits made in the point being played now.
+	pub rally: u32
+}
+
+impl MatchStats {
+	// Count a hit by the given side's paddle.
+	pub fn ball_hit(&mut self, side: Side) {
+		self.hits[side.index()] += 1;
+		self.rally += 1;
+	}
+
+	// Count a point won by the given side, ending the current rally.
+	pub fn point_scored(&mut self, side: Side) {
+		if self.rally == 0 {
+			self.aces[side.index()] += 1;
+		}
+
+		self.points += 1;
+		self.timeline.push(side);
+		self.rally_hits += self.rally;
+		self.longest_rally = self.longest_rally.max(self.rally);
+		self.rally = 0;
+	}
+
+	// Note how fast a ball is going, in pixels per millisecond.
+	pub fn ball_speed(&mut self, speed: f32) {
+		self.max_ball_speed = self.max_ball_speed.max(speed);
+	}
+
+	// Get the average number of hits per point, or 0.0 if no points have been played yet.
+	pub fn average_rally(&self) -> f32 {
+		if self.points > 0 {
+			self.rally_hits as f32 / self.points as f32
+		} else {
+			0.0
+		}
+	}
+
+	// Get both sides' scores after each point, starting from nothing before the first.
+	pub fn score_history(&self) -> Vec<[u32; 2]> {
+		let mut scores = [0, 0];
+		let mut history = vec![scores];
+		for side in &self.timeline {
+			scores[side.index()] += 1;
+			history.push(scores);
+		}
+		history
+	}
+}
@@ -1,28 +1,18 @@
 // Dependencies
 
-use std::ops::{Add, AddAssign, Sub};
-
-// Imports from the Glium library:
-use glium::{
-	// Struct macros.
-	implement_vertex,
-	// Imports for VBOs and VBO Indexing.
-	VertexBuffer, IndexBuffer,
-	Display,
-	index::PrimitiveType
-};
-
-// Implement a Vertex struct used to represent vertices.
-
-#[derive(Copy, Clone, Debug)]
-pub struct Vertex {
-	pub position: [f32; 2]
-}
-implement_vertex!(Vertex, position);
+use std::ops::{Add, AddAssign, Sub, Mul, Div, Neg};
+
+use serde::{Serialize, Deserialize};
+
+use crate::events::Side;
+use crate::game::Rules;
+use crate::collision::{ObjectCollider, Layers};
+use crate::math;
 
 // Implement a Vec2 (2D Vector) struct representing a co-ordinate in 2D space.
+// It's kept hand-written rather than using glam, so the simulation's maths stays exactly as written, giving bit-identical results for replays and lockstep networking, and saves, replays and the remote API keep their {x, y} format.
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Vec2 {
 	pub x: f32,
 	pub y: f32
@@ -30,13 +20,47 @@ pub struct Vec2 {
 
 impl Vec2 {
 	pub fn new(x: f32, y: f32) -> Self {
-		Self { x:x, y:y }
+		Self { x, y }
 	}
 
 	pub fn set(&mut self, x: f32, y: f32) {
 		self.x = x;
 		self.y = y;
 	}
+
+	// Get the dot product of this vector and another.
+	pub fn dot(self, other: Vec2) -> f32 {
+		self.x * other.x + self.y * other.y
+	}
+
+	// Get the length of this vector, e.g. the speed of a velocity.
+	pub fn length(self) -> f32 {
+		self.dot(self).sqrt()
+	}
+
+	// Get a vector of length 1.0 pointing the same way as this one.
+	// A zero-length vector has no direction, so it stays zero.
+	pub fn normalize(self) -> Self {
+		let length = self.length();
+		if length > 0.0 {
+			self / length
+		} else {
+			self
+		}
+	}
+
+	// Clamp each component of this vector between the components of min and max.
+	pub fn clamp(self, min: Vec2, max: Vec2) -> Self {
+		Self {
+			x: self.x.clamp(min.x, max.x),
+			y: self.y.clamp(min.y, max.y)
+		}
+	}
+
+	// Interpolate between this vector and another. A t of 0.0 gives this vector, 1.0 gives the other.
+	pub fn lerp(self, other: Vec2, t: f32) -> Self {
+		self + (other - self) * t
+	}
 }
 
 impl Add for Vec2 { // Implement + operator for this struct
@@ -65,65 +89,135 @@ impl Sub for Vec2 { // Implement - operator for this struct
 	}
 }
 
-// Implement a Rect struct representing a drawn rectangle.
+impl Mul<f32> for Vec2 { // Implement * operator for scaling this struct
+	type Output = Self;
 
-pub struct Rect {
-	pub vx_buf: VertexBuffer<Vertex>,
-	pub ix_buf: IndexBuffer<u8>
+	fn mul(mut self, scale: f32) -> Self {
+		self.x *= scale;
+		self.y *= scale;
+		self
+	}
 }
 
-impl Rect {
-	pub fn new(display: &Display, width: f32, height: f32) -> Self {
+impl Div<f32> for Vec2 { // Implement / operator for scaling this struct
+	type Output = Self;
 
-		// Create a shape given the dimensions of the rect, construct a VBO out of it.
-		let vx_buf = VertexBuffer::new(display, &[
-			Vertex { position: [0.0, 0.0] },
-			Vertex { position: [width, 0.0] },
-			Vertex { position: [width, height] },
-			Vertex { position: [0.0, height] }
-		]).unwrap();
+	fn div(mut self, scale: f32) -> Self {
+		self.x /= scale;
+		self.y /= scale;
+		self
+	}
+}
 
-		// Build an index for the vertex buffer.
-		let ix_buf = IndexBuffer::<u8>::new(display, PrimitiveType::TrianglesList, &[
-			0, 1, 2,
-			2, 3, 0
-		]).unwrap();
+impl Neg for Vec2 { // Implement unary - operator for this struct
+	type Output = Self;
 
-		// Construct the Rect object.
-		Self {
-			vx_buf: vx_buf,
-			ix_buf: ix_buf
-		}
+	fn neg(self) -> Self {
+		Self::new(-self.x, -self.y)
 	}
 }
 
 // Implement an Object struct representing a game object.
 // These objects have a type, they can be either a Ball or a Paddle.
 
-#[derive(PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum ObjectType {
 	Ball,
 	PaddleLeft,
 	PaddleRight
 }
 
+// Implement a Body enum for how an object takes part in the physics.
+// Kinematic bodies, like paddles, are moved by code and can't be pushed or bounced around, as if they had infinite mass.
+// Dynamic bodies, like balls, move under their own velocity and are pushed and bounced by whatever they hit, by an amount depending on their mass.
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Body {
+	Kinematic,
+	Dynamic
+}
+
+impl ObjectType {
+	// Get the kind of physics body objects of this type are.
+	pub fn body(&self) -> Body {
+		match self {
+			ObjectType::Ball => Body::Dynamic,
+			ObjectType::PaddleLeft | ObjectType::PaddleRight => Body::Kinematic
+		}
+	}
+
+	// Get the collision layer objects of this type are on, and the layers they collide with by default.
+	// Balls bounce off anything, paddles only ever meet balls.
+	pub fn default_layers(&self) -> (Layers, Layers) {
+		match self {
+			ObjectType::Ball => (Layers::BALL, Layers::ALL),
+			ObjectType::PaddleLeft | ObjectType::PaddleRight => (Layers::PADDLE, Layers::BALL)
+		}
+	}
+
+	// Get the side of the court this object belongs to, if any.
+	pub fn side(&self) -> Option<Side> {
+		match self {
+			ObjectType::PaddleLeft => Some(Side::Left),
+			ObjectType::PaddleRight => Some(Side::Right),
+			ObjectType::Ball => None
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Object {
 	pub obj_type: ObjectType,
 	pub position: Vec2,
+	pub prev_position: Vec2,
 	pub size: Vec2,
 	pub velocity: Vec2,
-	pub max_velocity: Vec2
+	pub max_velocity: Vec2,
+	// How fast the object is spinning, as the speed its edge moves at around its center. Positive spin is clockwise on screen.
+	#[serde(default)]
+	pub spin: f32,
+	// How much speed a ball bounces off this object with, as a multiple of the speed it hit at.
+	// Less than 1.0 soaks up some of the hit, more than 1.0 speeds the ball up. The ball's own restitution applies on top.
+	#[serde(default = "default_restitution")]
+	pub restitution: f32,
+	// The collision layers this object is on, and the layers it collides with.
+	// Objects saved before layers were added collide with everything.
+	#[serde(default = "Layers::all")]
+	pub layer: Layers,
+	#[serde(default = "Layers::all")]
+	pub mask: Layers,
+	// How heavy this object is, which decides how much it gives way when it hits another dynamic body.
+	// Kinematic bodies ignore this, since nothing can push them.
+	#[serde(default = "default_mass")]
+	pub mass: f32
+}
+
+// Objects saved before restitution was added bounce without gaining or losing speed.
+fn default_restitution() -> f32 {
+	1.0
+}
+
+// Objects saved before mass was added all weigh the same.
+fn default_mass() -> f32 {
+	1.0
 }
 
 impl Object {
 	// Build a new Object with default properties.
 	pub fn new(obj_type: ObjectType) -> Self {
+		let (layer, mask) = obj_type.default_layers();
 		Self {
-			obj_type: obj_type,
+			obj_type,
 			position: Vec2 { x:0.0, y:0.0 },
+			prev_position: Vec2 { x:0.0, y:0.0 },
 			size: Vec2 { x:1.0, y:1.0 },
 			velocity: Vec2 { x:0.0, y:0.0 },
-			max_velocity: Vec2 { x:2.0, y:2.0 }
+			max_velocity: Vec2 { x:2.0, y:2.0 },
+			spin: 0.0,
+			restitution: 1.0,
+			layer,
+			mask,
+			mass: 1.0
 		}
 	}
 
@@ -133,17 +227,24 @@ impl Object {
 		self
 	}
 
+	// Set the layers of this object inline.
+	pub fn set_layers(mut self, layer: Layers, mask: Layers) -> Self {
+		self.layer = layer;
+		self.mask = mask;
+		self
+	}
+
 	// Reset this object to its starting state.
-	pub fn reset(&mut self, width: f32, height: f32) {
+	pub fn reset(&mut self, width: f32, height: f32, rules: &Rules) {
 		match self.obj_type {
 			ObjectType::Ball => {
 				self.velocity.set(
-					width / 3200.0,
+					width / 3200.0 * rules.ball_speed,
 					0.0
 				);
 				self.max_velocity.set(
-					width / 400.0,
-					height / 400.0
+					width / 400.0 * rules.ball_speed,
+					height / 400.0 * rules.ball_speed
 				);
 				self.position.set(
 					(width / 2.0) - (self.size.x / 2.0),
@@ -151,20 +252,59 @@ impl Object {
 				);
 			},
 			ObjectType::PaddleLeft => {
-				self.size.y = height * 0.25;
+				self.size.y = rules.paddle_height(height);
+				self.restitution = rules.paddle_restitution;
 				self.position.set(
 					width * 0.05,
 					(height / 2.0) - (self.size.y / 2.0)
 				);
 			},
 			ObjectType::PaddleRight => {
-				self.size.y = height * 0.25;
+				self.size.y = rules.paddle_height(height);
+				self.restitution = rules.paddle_restitution;
 				self.position.set(
 					width * 0.95 - self.size.x,
 					(height / 2.0) - (self.size.y / 2.0)
 				);
 			}
 		}
+
+		self.spin = 0.0;
+
+		// Don't interpolate from the old position after a reset.
+		self.prev_position = self.position;
+	}
+
+	// Serve this object at an angle in radians from horizontal, keeping its current speed and direction.
+	pub fn serve(&mut self, angle: f32) {
+		let speed = self.velocity.length();
+		let direction = if self.velocity.x < 0.0 { -1.0 } else { 1.0 };
+		let (sin, cos) = math::sin_cos(angle);
+		self.velocity.set(
+			direction * speed * cos,
+			speed * sin
+		);
+	}
+
+	// Get one over this object's mass, which is how easily it's pushed around.
+	// Kinematic bodies can't be pushed at all, and neither can dynamic bodies without a positive mass.
+	pub fn inverse_mass(&self) -> f32 {
+		match self.obj_type.body() {
+			Body::Dynamic if self.mass > 0.0 => 1.0 / self.mass,
+			_ => 0.0
+		}
+	}
+
+	// Keep this object's velocity within its speed limit on each axis.
+	// This should follow anything that changes the velocity, so a ball can never build up more speed than the court allows.
+	pub fn clamp_velocity(&mut self) {
+		self.velocity = self.velocity.clamp(-self.max_velocity, self.max_velocity);
+	}
+
+	// Get the position to render this object at, interpolated between the last two simulation ticks.
+	// An alpha of 0.0 is the previous tick's position, 1.0 is the current one.
+	pub fn interpolated_position(&self, alpha: f32) -> Vec2 {
+		self.prev_position.lerp(self.position, alpha)
 	}
 
 	// Build an ObjectCollider for this object.
@@ -180,55 +320,3 @@ impl Object {
 		}
 	}
 }
-
-// Implement object colliders.
-
-// Function to check if line segments intersect.
-fn ccw(a: &Vec2, b: &Vec2, c: &Vec2) -> bool {
-	(c.y - a.y) * (b.x - a.x) > (b.y - a.y) * (c.x - a.x)
-}
-
-#[derive(Copy, Clone, Debug)]
-pub struct ObjectCollider {
-	pub min: Vec2,
-	pub max: Vec2,
-	pub center: Vec2
-}
-
-impl ObjectCollider {
-	// Build a new ObjectCollider given an Object.
-	pub fn new(obj: &Object) -> Self {
-		Self {
-			min: obj.position,
-			max: obj.position + obj.size,
-			center: obj.get_center()
-		}
-	}
-
-	pub fn get_hitbox(&self) -> [[Vec2; 2]; 4] {
-		[
-			[Vec2::new(self.min.x, self.min.y), Vec2::new(self.max.x, self.min.y)],
-			[Vec2::new(self.min.x, self.min.y), Vec2::new(self.min.x, self.max.y)],
-			[Vec2::new(self.min.x, self.max.y), Vec2::new(self.max.x, self.max.y)],
-			[Vec2::new(self.max.x, self.max.y), Vec2::new(self.max.x, self.min.y)]
-		]
-	}
-
-	// Check if this object is intercepting another collider.
-	pub fn is_colliding(&self, other: &Self) -> bool {
-		let self_hitbox = self.get_hitbox();
-		let other_hitbox = other.get_hitbox();
-
-		let mut is_colliding = false;
-		for [a, b] in &self_hitbox {
-			for [c, d] in &other_hitbox {
-				// Check if line segments intersect. If they are, then the objects are colliding.
-				let intersect = ccw(a,c,d) != ccw(b,c,d) && ccw(a,b,c) != ccw(a,b,d);
-				if !is_colliding {
-					is_colliding = intersect;
-				}
-			}
-		}
-		is_colliding
-	}
-}
\ No newline at end of file
@@ -0,0 +1,158 @@
+// Dependencies
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::game::GameState;
+use crate::events::GameEvent;
+use crate::replay::{Replay, Playback};
+use crate::structs::{Vec2, ObjectType};
+
+// Implement a tick-by-tick log of a match, exported to JSON or CSV so matches can be analysed or visualised with other tools.
+// Each sample holds where every ball was and how fast it was going at the end of a tick, along with the events emitted during it.
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize)]
+pub struct Telemetry {
+	pub samples: Vec<Sample>
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Sample {
+	// Ticks simulated since the game started, and time elapsed in the match in milliseconds.
+	pub tick: u64,
+	pub time: f32,
+	pub balls: Vec<BallSample>,
+	pub events: Vec<GameEvent>
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize)]
+pub struct BallSample {
+	pub position: Vec2,
+	// Velocity in pixels per millisecond.
+	pub velocity: Vec2
+}
+
+// A row of the CSV export. CSV can't nest, so each ball gets its own row, and the tick's events are written as JSON on the first one.
+// A tick without any balls gets a single row with the ball columns left empty.
+
+#[derive(Serialize)]
+struct CsvRow<'a> {
+	tick: u64,
+	time: f32,
+	ball: Option<usize>,
+	x: Option<f32>,
+	y: Option<f32>,
+	velocity_x: Option<f32>,
+	velocity_y: Option<f32>,
+	events: &'a str
+}
+
+// Implement the file formats telemetry can be exported to.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Format {
+	Json,
+	Csv
+}
+
+impl Format {
+	// Pick the format from a file's extension, if it's one of the supported ones.
+	pub fn from_path(path: &Path) -> Option<Self> {
+		match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+			"json" => Some(Format::Json),
+			"csv" => Some(Format::Csv),
+			_ => None
+		}
+	}
+}
+
+impl Telemetry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Take a sample of the match after a tick, along with the events emitted during it.
+	pub fn record(&mut self, game: &GameState, events: &[GameEvent]) {
+		let balls = game.objects.iter()
+			.filter(|obj| obj.obj_type == ObjectType::Ball)
+			.map(|obj| BallSample { position: obj.position, velocity: obj.velocity })
+			.collect();
+
+		self.samples.push(Sample {
+			tick: game.ticks,
+			time: game.elapsed,
+			balls,
+			events: events.to_vec()
+		});
+	}
+
+	// Play back a replay, sampling every tick of it.
+	pub fn from_replay(replay: Replay) -> Self {
+		let mut telemetry = Self::new();
+		let mut playback = Playback::new(replay);
+
+		while !playback.is_finished() {
+			playback.step(1);
+			let events: Vec<_> = playback.game_mut().events.drain().collect();
+			telemetry.record(playback.game(), &events);
+		}
+
+		telemetry
+	}
+
+	// Write the telemetry as JSON, with one object per tick.
+	pub fn to_json(&self) -> Result<String, String> {
+		serde_json::to_string_pretty(self).map_err(|err| err.to_string())
+	}
+
+	// Write the telemetry as CSV, with one row per ball per tick.
+	pub fn to_csv(&self) -> Result<String, String> {
+		let mut writer = csv::Writer::from_writer(vec![]);
+
+		for sample in &self.samples {
+			let events = if sample.events.is_empty() {
+				String::new()
+			} else {
+				serde_json::to_string(&sample.events).map_err(|err| err.to_string())?
+			};
+
+			let balls: Vec<_> = if sample.balls.is_empty() {
+				vec![None]
+			} else {
+				sample.balls.iter().enumerate().map(Some).collect()
+			};
+
+			for (row, ball) in balls.into_iter().enumerate() {
+				writer.serialize(CsvRow {
+					tick: sample.tick,
+					time: sample.time,
+					ball: ball.map(|(index, _)| index),
+					x: ball.map(|(_, ball)| ball.position.x),
+					y: ball.map(|(_, ball)| ball.position.y),
+					velocity_x: ball.map(|(_, ball)| ball.velocity.x),
+					velocity_y: ball.map(|(_, ball)| ball.velocity.y),
+					events: if row == 0 { &events } else { "" }
+				}).map_err(|err| err.to_string())?;
+			}
+		}
+
+		let data = writer.into_inner().map_err(|err| err.to_string())?;
+		String::from_utf8(data).map_err(|err| err.to_string())
+	}
+
+	// Export the telemetry to a given path, in the format given by its extension.
+	pub fn save_to(&self, path: &Path) -> Result<(), String> {
+		let text = match Format::from_path(path) {
+			Some(Format::Json) => self.to_json()?,
+			Some(Format::Csv) => self.to_csv()?,
+			None => return Err(format!("Unknown telemetry format for {}, expected a .json or .csv file", path.display()))
+		};
+
+		if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+			fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+		}
+		fs::write(path, text).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+	}
+}
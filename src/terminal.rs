@@ -0,0 +1,286 @@
+// Dependencies
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+// Crossterm is used to draw to the terminal and read the keyboard and mouse from it.
+// https://crates.io/crates/crossterm
+
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use crossterm::style::Print;
+
+use crate::structs::Vec2;
+use crate::scene::{Context, Renderer, SceneManager};
+use crate::input::{self, Input, Key};
+
+// How many points of the play area each pixel in the terminal covers.
+// Every character cell is two pixels, one above the other, so a cell is about as tall as it is wide in the game and the court isn't stretched.
+
+pub const POINTS_PER_PIXEL: f32 = 8.0;
+
+// How long each frame in the terminal lasts, in milliseconds. Terminals can't keep up with drawing as often as a window.
+
+pub const TERMINAL_FRAME_TIME: f32 = 1000.0 / 30.0;
+
+// Implement a frame drawn with block characters, with two pixels to each character cell.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TerminalFrame {
+	// The size of the frame in character cells.
+	pub columns: u16,
+	pub rows: u16,
+	// Whether each pixel is filled, a row at a time, with twice as many rows as there are cells.
+	pub pixels: Vec<bool>
+}
+
+impl TerminalFrame {
+	pub fn new(columns: u16, rows: u16) -> Self {
+		Self {
+			columns,
+			rows,
+			pixels: vec![false; columns as usize * rows as usize * 2]
+		}
+	}
+
+	// Get the size of the play area this frame shows, in points.
+	pub fn size(&self) -> Vec2 {
+		Vec2::new(self.columns as f32, self.rows as f32 * 2.0) * POINTS_PER_PIXEL
+	}
+
+	// Get the middle of a character cell in the play area, e.g. where the mouse is pointing.
+	pub fn cell_centre(column: u16, row: u16) -> Vec2 {
+		Vec2::new(column as f32 + 0.5, row as f32 * 2.0 + 1.0) * POINTS_PER_PIXEL
+	}
+
+	// Get each row of character cells as text, with a half or full block for the pixels filled in.
+	pub fn lines(&self) -> Vec<String> {
+		let width = self.columns as usize;
+		self.pixels.chunks(width * 2).map(|pair| {
+			let (top, bottom) = pair.split_at(width);
+			top.iter().zip(bottom).map(|pixels| match pixels {
+				(false, false) => ' ',
+				(true, false) => '▀',
+				(false, true) => '▄',
+				(true, true) => '█'
+			}).collect()
+		}).collect()
+	}
+}
+
+// Draw a scene into a terminal frame, filling in every pixel a rect covers at least half of.
+
+impl Renderer for TerminalFrame {
+	fn rect(&mut self, position: Vec2, size: Vec2) {
+		let (width, height) = (self.columns as usize, self.rows as usize * 2);
+		let start = position / POINTS_PER_PIXEL;
+		let end = (position + size) / POINTS_PER_PIXEL;
+
+		let left = (start.x.round().max(0.0) as usize).min(width);
+		let right = (end.x.round().max(0.0) as usize).min(width);
+		let top = (start.y.round().max(0.0) as usize).min(height);
+		let bottom = (end.y.round().max(0.0) as usize).min(height);
+
+		for y in top..bottom {
+			let row = y * width;
+			self.pixels[row + left..row + right].fill(true);
+		}
+	}
+}
+
+// Get the name of a key from the terminal, the same as winit's VirtualKeyCode names, so keybinds from the config work the same as in the window.
+
+pub fn key_name(code: KeyCode) -> Option<String> {
+	let name = match code {
+		KeyCode::Char(' ') => "Space".to_string(),
+		KeyCode::Char(c) if c.is_ascii_alphabetic() => c.to_ascii_uppercase().to_string(),
+		KeyCode::Char(c) if c.is_ascii_digit() => format!("Key{}", c),
+		KeyCode::Char(c) => match c {
+			'\'' => "Apostrophe",
+			'*' => "Asterisk",
+			'@' => "At",
+			'\\' => "Backslash",
+			':' => "Colon",
+			',' => "Comma",
+			'=' => "Equals",
+			'`' => "Grave",
+			'[' => "LBracket",
+			'-' => "Minus",
+			'.' => "Period",
+			'+' => "Plus",
+			']' => "RBracket",
+			';' => "Semicolon",
+			'/' => "Slash",
+			_ => return None
+		}.to_string(),
+		KeyCode::F(n) => format!("F{}", n),
+		KeyCode::Esc => "Escape".to_string(),
+		KeyCode::Enter => "Return".to_string(),
+		KeyCode::Backspace => "Back".to_string(),
+		KeyCode::Tab => "Tab".to_string(),
+		KeyCode::Up => "Up".to_string(),
+		KeyCode::Down => "Down".to_string(),
+		KeyCode::Left => "Left".to_string(),
+		KeyCode::Right => "Right".to_string(),
+		KeyCode::Home => "Home".to_string(),
+		KeyCode::End => "End".to_string(),
+		KeyCode::PageUp => "PageUp".to_string(),
+		KeyCode::PageDown => "PageDown".to_string(),
+		KeyCode::Insert => "Insert".to_string(),
+		KeyCode::Delete => "Delete".to_string(),
+		// Ignore anything else.
+		_ => return None
+	};
+	Some(name)
+}
+
+// Implement the translation from the terminal's keyboard and mouse to the inputs scenes respond to.
+// Terminals don't say when a key is let go of, so during a match the up and down keys nudge a pointer the paddle follows, the same as the mouse.
+
+#[derive(Clone, Debug)]
+pub struct TerminalInput {
+	// Where the pointer the paddle follows is, in points.
+	pointer: Vec2
+}
+
+impl TerminalInput {
+	// Start with the pointer in the middle of a play area of the given size.
+	pub fn new(size: Vec2) -> Self {
+		Self {
+			pointer: size / 2.0
+		}
+	}
+
+	// Translate a key press into the keys it's bound to, and during a match, where the paddle follows the pointer, into a nudge of the pointer.
+	pub fn key(&mut self, ctx: &Context, event: KeyEvent) -> Vec<Input> {
+		if event.kind == KeyEventKind::Release {
+			return vec![];
+		}
+		let Some(name) = key_name(event.code) else {
+			return vec![];
+		};
+
+		let mut inputs: Vec<_> = input::keyboard_key(&name).map(Input::KeyboardKey).into_iter().collect();
+		let key = ctx.config.key_for(&name);
+		inputs.extend(key.map(Input::KeyPressed));
+
+		// Each press moves the paddle a sixteenth of the way across the court.
+		let step = match key {
+			Some(Key::Up) => -ctx.height / 16.0,
+			Some(Key::Down) => ctx.height / 16.0,
+			_ => 0.0
+		};
+		if ctx.cursor_grabbed && step != 0.0 {
+			self.pointer = Vec2::new(ctx.width / 2.0, (self.pointer.y + step).clamp(0.0, ctx.height));
+			inputs.push(Input::MouseMoved { x: self.pointer.x, y: self.pointer.y });
+		}
+		inputs
+	}
+
+	// Translate the mouse moving or clicking over a character cell.
+	pub fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> Vec<Input> {
+		self.pointer = TerminalFrame::cell_centre(column, row);
+		let moved = Input::MouseMoved { x: self.pointer.x, y: self.pointer.y };
+		match kind {
+			MouseEventKind::Down(MouseButton::Left) => vec![moved, Input::MousePressed],
+			MouseEventKind::Up(MouseButton::Left) => vec![moved, Input::MouseReleased],
+			MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => vec![moved],
+			// Ignore anything else.
+			_ => vec![]
+		}
+	}
+}
+
+// Set the terminal up for drawing the game, putting it back how it was when this is dropped, even if the game exits with an error.
+
+struct TerminalGuard;
+
+impl TerminalGuard {
+	fn enter() -> io::Result<Self> {
+		terminal::enable_raw_mode()?;
+		execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide, event::EnableMouseCapture)?;
+		Ok(Self)
+	}
+}
+
+impl Drop for TerminalGuard {
+	fn drop(&mut self) {
+		execute!(io::stdout(), event::DisableMouseCapture, cursor::Show, terminal::LeaveAlternateScreen).ok();
+		terminal::disable_raw_mode().ok();
+	}
+}
+
+// Play the game in the terminal instead of a window, until there are no scenes left or the player presses Ctrl+C.
+// The simulation runs the same as it does under the window, the scenes are just drawn with block characters.
+// After the snapshot for each frame is taken, the window layer gets a chance to look at it before the scenes do, e.g. to record high scores.
+
+pub fn run(ctx: &mut Context, scenes: &mut SceneManager, mut on_frame: impl FnMut(&mut Context)) -> io::Result<()> {
+	let _guard = TerminalGuard::enter()?;
+	let mut stdout = io::stdout();
+
+	let (columns, rows) = terminal::size()?;
+	let mut frame = TerminalFrame::new(columns, rows);
+	let size = frame.size();
+	ctx.resize(size.x, size.y);
+	scenes.handle_input(ctx, Input::Focused(true));
+
+	let mut input = TerminalInput::new(size);
+	let mut last_lines: Vec<String> = vec![];
+	let mut last_frame = Instant::now();
+
+	while !scenes.is_empty() {
+		// Handle input until the next frame is due.
+
+		let next_frame_time = last_frame + Duration::from_secs_f32(TERMINAL_FRAME_TIME / 1000.0);
+		while event::poll(next_frame_time.saturating_duration_since(Instant::now()))? {
+			let inputs = match event::read()? {
+				Event::Key(key) if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+				Event::Key(key) => input.key(ctx, key),
+				Event::Mouse(mouse) => input.mouse(mouse.kind, mouse.column, mouse.row),
+				Event::FocusGained => vec![Input::Focused(true)],
+				Event::FocusLost => vec![Input::Focused(false)],
+				// The terminal was resized, so the play area is too.
+				Event::Resize(columns, rows) => {
+					frame = TerminalFrame::new(columns, rows);
+					let size = frame.size();
+					ctx.resize(size.x, size.y);
+					last_lines.clear();
+					vec![]
+				},
+				// Ignore anything else.
+				_ => vec![]
+			};
+
+			for input in inputs {
+				scenes.handle_input(ctx, input);
+			}
+		}
+
+		let now = Instant::now();
+		let delta_time = (now - last_frame).as_secs_f32() * 1000.0;
+		last_frame = now;
+
+		// Update the scenes from a new snapshot of the game, the same as the window does.
+
+		ctx.refresh();
+		on_frame(ctx);
+		scenes.update(ctx);
+		ctx.update_toasts(delta_time);
+		ctx.update_particles(delta_time);
+
+		// Draw the frame, only writing the rows that changed since the last one.
+
+		frame.pixels.fill(false);
+		scenes.render(ctx, &mut frame);
+
+		let lines = frame.lines();
+		for (row, line) in lines.iter().enumerate() {
+			if last_lines.get(row) != Some(line) {
+				queue!(stdout, cursor::MoveTo(0, row as u16), Print(line))?;
+			}
+		}
+		stdout.flush()?;
+		last_lines = lines;
+	}
+	Ok(())
+}
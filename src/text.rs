@@ -0,0 +1,123 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+
+// Implement simple text drawing with a blocky pixel font, built from the same rects as everything else.
+// Each character is 3 pixels wide and 5 tall, with a pixel of space after it. Lowercase letters are drawn as uppercase.
+
+pub const GLYPH_WIDTH: f32 = 3.0;
+pub const GLYPH_HEIGHT: f32 = 5.0;
+pub const GLYPH_ADVANCE: f32 = 4.0;
+
+// Get the rows of a character's glyph from top to bottom, with the highest of the 3 bits being the leftmost pixel.
+// Characters without a glyph are drawn blank.
+fn glyph(c: char) -> [u8; 5] {
+	match c.to_ascii_uppercase() {
+		'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+		'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+		'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+		'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+		'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+		'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+		'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+		'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+		'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+		'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+		'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+		'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+		'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+		'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+		'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+		'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+		'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+		'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+		'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+		'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+		'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+		'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+		'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+		'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+		'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+		'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+		'0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+		'1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+		'2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+		'3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+		'4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+		'5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+		'6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+		'7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+		'8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+		'9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+		':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+		'.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+		'-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+		'/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+		'?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+		'!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+		',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+		'\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+		'>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+		'<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+		'+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+		'=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+		'(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+		')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+		'%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+		'_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+		_ => [0; 5]
+	}
+}
+
+// Get the width of a line of text drawn with pixels of the given size.
+pub fn width(text: &str, scale: f32) -> f32 {
+	let count = text.chars().count() as f32;
+	if count > 0.0 {
+		(count * GLYPH_ADVANCE - (GLYPH_ADVANCE - GLYPH_WIDTH)) * scale
+	} else {
+		0.0
+	}
+}
+
+// Draw a line of text with its top-left corner at the given position, with pixels of the given size.
+pub fn draw(renderer: &mut dyn Renderer, text: &str, position: Vec2, scale: f32) {
+	draw_faded(renderer, text, position, scale, 1.0);
+}
+
+// Draw a line of text the same way, only partly there, with an opacity from 0.0 (invisible) to 1.0 (solid).
+pub fn draw_faded(renderer: &mut dyn Renderer, text: &str, position: Vec2, scale: f32, opacity: f32) {
+	for (index, c) in text.chars().enumerate() {
+		let left = position.x + index as f32 * GLYPH_ADVANCE * scale;
+
+		for (row, bits) in glyph(c).into_iter().enumerate() {
+			let top = position.y + row as f32 * scale;
+
+			// Draw each run of pixels in the row as a single rect.
+			let mut column = 0;
+			while column < 3 {
+				if bits & (0b100 >> column) == 0 {
+					column += 1;
+					continue;
+				}
+
+				let start = column;
+				while column < 3 && bits & (0b100 >> column) != 0 {
+					column += 1;
+				}
+				let position = Vec2::new(left + start as f32 * scale, top);
+				let size = Vec2::new((column - start) as f32 * scale, scale);
+				if opacity >= 1.0 {
+					renderer.rect(position, size);
+				} else {
+					renderer.fade(position, size, opacity);
+				}
+			}
+		}
+	}
+}
+
+// Draw a line of text centered horizontally on the given x co-ordinate, with its top at the given y co-ordinate.
+pub fn draw_centered(renderer: &mut dyn Renderer, text: &str, x: f32, y: f32, scale: f32) {
+	draw(renderer, text, Vec2::new(x - width(text, scale) / 2.0, y), scale);
+}
@@ -0,0 +1,75 @@
+// Dependencies
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::particles::Emitter;
+
+// Implement a colour theme, chosen by name in the config file or put on by the date for a holiday.
+// A theme sets the colour behind everything, and can fill it with particles, like snow.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Theme {
+	pub name: &'static str,
+	// Colour the window's cleared to before anything's drawn, as red, green and blue.
+	pub background: [f32; 3],
+	pub emitter: Option<Emitter>
+}
+
+// The themes there are. The first is the one used when the config file names one that doesn't exist.
+
+pub const THEMES: &[Theme] = &[
+	Theme { name: "classic", background: [0.0, 0.0, 0.0], emitter: None },
+	Theme { name: "winter", background: [0.02, 0.04, 0.1], emitter: Some(Emitter::Snow) },
+	Theme { name: "new-year", background: [0.02, 0.0, 0.06], emitter: Some(Emitter::Fireworks) }
+];
+
+// Implement a day of the year, without the year.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Date {
+	// From 1 (January) to 12 (December).
+	pub month: u32,
+	pub day: u32
+}
+
+impl Date {
+	pub fn new(month: u32, day: u32) -> Self {
+		Self { month, day }
+	}
+
+	// Get today's date in UTC.
+	// The game has no way of knowing the player's time zone, so holidays can start and end a few hours off.
+	pub fn today() -> Self {
+		let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+		Self::from_days((secs / 86_400) as i64)
+	}
+
+	// Get the date a number of days after 1 January 1970, using Howard Hinnant's civil_from_days algorithm.
+	pub fn from_days(days: i64) -> Self {
+		let days = days + 719_468;
+		let era = days.div_euclid(146_097);
+		let day_of_era = days - era * 146_097;
+		let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month_index = (5 * day_of_year + 2) / 153;
+		let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+		let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+		Self::new(month as u32, day as u32)
+	}
+}
+
+// Find the theme with the given name, or the first theme if there's none by that name.
+
+pub fn find(name: &str) -> &'static Theme {
+	THEMES.iter().find(|theme| theme.name == name).unwrap_or(&THEMES[0])
+}
+
+// Get the holiday theme for a date, if there is one: fireworks over New Year's Eve and Day, and snow for the rest of December.
+
+pub fn seasonal(date: Date) -> Option<&'static Theme> {
+	match (date.month, date.day) {
+		(12, 31) | (1, 1) => Some(find("new-year")),
+		(12, _) => Some(find("winter")),
+		_ => None
+	}
+}
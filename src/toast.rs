@@ -0,0 +1,122 @@
+// Dependencies
+
+use std::collections::VecDeque;
+
+use crate::structs::Vec2;
+use crate::scene::Renderer;
+use crate::events::GameEvent;
+use crate::locale::Locale;
+use crate::text;
+
+// How long a toast stays on screen, in milliseconds, including the time it spends fading out at the end.
+
+pub const TOAST_TIME: f32 = 2500.0;
+pub const TOAST_FADE_TIME: f32 = 500.0;
+
+// How many toasts are shown at once. Any more wait their turn.
+
+pub const MAX_TOASTS: usize = 3;
+
+// How much the box behind a toast darkens what's under it, from 0.0 to 1.0.
+
+pub const TOAST_SHADE: f32 = 0.6;
+
+// Size of a toast's text pixels, and the space around it.
+
+const TOAST_SCALE: f32 = 3.0;
+const TOAST_PADDING: f32 = 2.0 * TOAST_SCALE;
+
+// Implement a short message shown over every scene for a moment, e.g. to confirm a screenshot was saved.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Toast {
+	pub message: String,
+	// Time left before the toast disappears, in milliseconds.
+	pub remaining: f32
+}
+
+impl Toast {
+	pub fn new(message: impl Into<String>) -> Self {
+		Self {
+			message: message.into(),
+			remaining: TOAST_TIME
+		}
+	}
+
+	// Count down the time the toast has left, returning whether it should still be shown.
+	pub fn update(&mut self, delta_time: f32) -> bool {
+		self.remaining -= delta_time;
+		self.remaining > 0.0
+	}
+
+	// How visible the toast is, from 1.0 until it starts fading out down to 0.0 when its time is up.
+	pub fn opacity(&self) -> f32 {
+		(self.remaining / TOAST_FADE_TIME).clamp(0.0, 1.0)
+	}
+}
+
+// Implement the queue of toasts waiting to be shown, oldest first.
+// The first few are shown stacked in the bottom-right corner, and only those count down, so a burst of messages are all seen.
+
+#[derive(Clone, Default, Debug)]
+pub struct Toasts {
+	pub queue: VecDeque<Toast>
+}
+
+impl Toasts {
+	// Add a message to the end of the queue.
+	// If the same message is already waiting or showing, it's shown for longer instead of being repeated.
+	pub fn push(&mut self, message: impl Into<String>) {
+		let toast = Toast::new(message);
+		match self.queue.iter_mut().find(|other| other.message == toast.message) {
+			Some(other) => other.remaining = TOAST_TIME,
+			None => self.queue.push_back(toast)
+		}
+	}
+
+	// Queue a toast for each event worth telling the player about.
+	pub fn record(&mut self, events: &[GameEvent], locale: &Locale) {
+		for event in events {
+			match event {
+				GameEvent::PowerUpCollected { .. } => self.push(locale.get("toast.power-up")),
+				GameEvent::ConfigChanged => self.push(locale.get("toast.config-changed")),
+				_ => ()
+			}
+		}
+	}
+
+	// Count down the toasts being shown, removing any whose time is up so the next in line can take their place.
+	pub fn update(&mut self, delta_time: f32) {
+		let mut index = 0;
+		self.queue.retain_mut(|toast| {
+			index += 1;
+			index > MAX_TOASTS || toast.update(delta_time)
+		});
+	}
+
+	// Get the toasts being shown, oldest first.
+	pub fn shown(&self) -> impl Iterator<Item = &Toast> {
+		self.queue.iter().take(MAX_TOASTS)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+
+	// Draw the toasts being shown in the bottom-right corner of a window of the given size, the newest at the bottom.
+	// Each one fades out, box and all, as its time runs out.
+	pub fn render(&self, renderer: &mut dyn Renderer, width: f32, height: f32) {
+		let box_height = text::GLYPH_HEIGHT * TOAST_SCALE + TOAST_PADDING * 2.0;
+		let count = self.shown().count();
+
+		for (index, toast) in self.shown().enumerate() {
+			let opacity = toast.opacity();
+			let box_width = text::width(&toast.message, TOAST_SCALE) + TOAST_PADDING * 2.0;
+			let left = width - box_width - TOAST_PADDING;
+			let top = height - (count - index) as f32 * (box_height + TOAST_PADDING);
+
+			renderer.shade(Vec2::new(left, top), Vec2::new(box_width, box_height), TOAST_SHADE * opacity);
+			text::draw_faded(renderer, &toast.message, Vec2::new(left + TOAST_PADDING, top + TOAST_PADDING), TOAST_SCALE, opacity);
+		}
+	}
+}
@@ -0,0 +1,46 @@
+// Dependencies
+
+use crate::input::{Input, Key};
+
+// Implement the translation from a touch screen to the inputs scenes respond to.
+// The first finger down acts as the mouse, so dragging it moves the paddle and tapping presses buttons in the menus.
+// Tapping with a second finger while the first is down goes back, which pauses the game, since phones have no escape key.
+
+#[derive(Clone, Default, Debug)]
+pub struct Touches {
+	// The finger acting as the mouse, if one is down.
+	finger: Option<u64>
+}
+
+impl Touches {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Translate a finger touching the screen at the given position.
+	// The pointer moves there before pressing, so whatever's under the finger is what gets pressed.
+	pub fn start(&mut self, id: u64, x: f32, y: f32) -> Vec<Input> {
+		match self.finger {
+			Some(_) => vec![Input::KeyPressed(Key::Escape)],
+			None => {
+				self.finger = Some(id);
+				vec![Input::MouseMoved { x, y }, Input::MousePressed]
+			}
+		}
+	}
+
+	// Translate a finger moving across the screen.
+	pub fn moved(&mut self, id: u64, x: f32, y: f32) -> Option<Input> {
+		(self.finger == Some(id)).then_some(Input::MouseMoved { x, y })
+	}
+
+	// Translate a finger leaving the screen, or the system taking the touch away, e.g. to open the notification shade.
+	pub fn end(&mut self, id: u64) -> Option<Input> {
+		if self.finger != Some(id) {
+			return None;
+		}
+
+		self.finger = None;
+		Some(Input::MouseReleased)
+	}
+}
@@ -0,0 +1,122 @@
+// Dependencies
+
+use crate::structs::{Vec2, Object};
+use crate::scene::Renderer;
+use crate::events::GameEvent;
+use crate::game::GameState;
+use crate::locale::Locale;
+use crate::text;
+
+// How far the player has to move their paddle to finish the first step, as a multiple of the paddle's height.
+
+pub const MOVE_DISTANCE: f32 = 0.5;
+
+// How far from the middle of the paddle the ball has to be hit to count as a smash, from 0.0 at the middle to 1.0 at the top or bottom.
+// Hits out towards the edge send the ball off at a sharper angle.
+
+pub const SMASH_EDGE: f32 = 0.6;
+
+// How much the box behind the prompt darkens the court under it, from 0.0 to 1.0.
+
+pub const TUTORIAL_SHADE: f32 = 0.6;
+
+// Implement the steps of the tutorial, in the order they're shown.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TutorialStep {
+	// Move the paddle.
+	Move,
+	// Hit the ball back.
+	Return,
+	// Hit the ball back with the edge of the paddle.
+	Smash
+}
+
+impl TutorialStep {
+	pub const ALL: [TutorialStep; 3] = [TutorialStep::Move, TutorialStep::Return, TutorialStep::Smash];
+
+	// Key of this step's prompt in the language strings.
+	fn text_key(self) -> &'static str {
+		match self {
+			TutorialStep::Move => "tutorial.move",
+			TutorialStep::Return => "tutorial.return",
+			TutorialStep::Smash => "tutorial.smash"
+		}
+	}
+}
+
+// Implement the tutorial shown over the first match, prompting the player through the basics one at a time.
+// Each step is finished by doing what it asks, spotted from the game's events and the player's paddle.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Tutorial {
+	// Index of the step being shown, which is past the last one once they're all done.
+	pub step: usize,
+	// Height of the middle of the player's paddle when the move step was first seen.
+	start: Option<f32>
+}
+
+impl Tutorial {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Get the step being shown, if there are any left.
+	pub fn current(&self) -> Option<TutorialStep> {
+		TutorialStep::ALL.get(self.step).copied()
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.current().is_none()
+	}
+
+	// Move on from the step being shown if this frame's game state or events show the player did it.
+	// Events are checked in order, and each one finishes a step at most, so a single hit can't finish two.
+	pub fn update(&mut self, game: &GameState, events: &[GameEvent]) {
+		let Some(paddle) = game.control_id.and_then(|id| game.objects.get(id)) else { return };
+		let Some(side) = game.control_side() else { return };
+		let middle = paddle.position.y + paddle.size.y / 2.0;
+
+		if self.current() == Some(TutorialStep::Move) {
+			let start = *self.start.get_or_insert(middle);
+			if (middle - start).abs() >= paddle.size.y * MOVE_DISTANCE {
+				self.step += 1;
+			}
+		}
+
+		for event in events {
+			let GameEvent::BallHitPaddle { side: hit, point } = event else { continue };
+			if *hit != side {
+				continue;
+			}
+
+			match self.current() {
+				Some(TutorialStep::Return) => self.step += 1,
+				Some(TutorialStep::Smash) if is_smash(paddle, *point) => self.step += 1,
+				_ => ()
+			}
+		}
+	}
+
+	// Draw the prompt for the step being shown near the bottom of a window of the given size, with how far through the tutorial the player is above it.
+	pub fn render(&self, renderer: &mut dyn Renderer, locale: &Locale, width: f32, height: f32) {
+		let Some(step) = self.current() else { return };
+		let scale = (height / 150.0).floor().max(2.0);
+		let line_height = (text::GLYPH_HEIGHT + 3.0) * scale;
+
+		let progress = locale.format("tutorial.progress", &[("step", &(self.step + 1)), ("steps", &TutorialStep::ALL.len())]);
+		let prompt = locale.get(step.text_key());
+		let box_width = text::width(&progress, scale).max(text::width(prompt, scale)) + line_height;
+		let top = height * 0.7;
+
+		renderer.shade(Vec2::new((width - box_width) / 2.0, top - line_height / 2.0), Vec2::new(box_width, line_height * 3.0), TUTORIAL_SHADE);
+		text::draw_centered(renderer, &progress, width / 2.0, top, scale);
+		text::draw_centered(renderer, prompt, width / 2.0, top + line_height, scale);
+	}
+}
+
+// Check if a hit at the given point was out towards the edge of the paddle.
+fn is_smash(paddle: &Object, point: Vec2) -> bool {
+	let half = paddle.size.y / 2.0;
+	half > 0.0 && (point.y - (paddle.position.y + half)).abs() / half >= SMASH_EDGE
+}
@@ -0,0 +1,192 @@
+// Dependencies
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::paths;
+
+// How many frames a second videos are recorded at, the same as the window draws them.
+
+pub const VIDEO_FRAME_RATE: u32 = 60;
+
+// How long each frame of a video lasts, in milliseconds.
+
+pub const VIDEO_FRAME_TIME: f32 = 1000.0 / VIDEO_FRAME_RATE as f32;
+
+// The most frames that are filled in with copies of the last one when the window falls behind, e.g. while it's being dragged.
+// Any longer a gap is left out of the video instead of showing a still frame.
+
+pub const MAX_REPEATED_FRAMES: u64 = VIDEO_FRAME_RATE as u64;
+
+// How many frames can be waiting to be written to ffmpeg before drawing waits for it to catch up.
+
+const QUEUED_FRAMES: usize = 8;
+
+// Build the arguments ffmpeg is run with to turn raw frames of the given size on its stdin into an MP4 at the given path.
+// Frames are RGBA read back from OpenGL, which starts at the bottom row, so they're flipped the right way up.
+// The size is rounded down to even numbers, which the H.264 encoder needs.
+
+pub fn ffmpeg_args(path: &Path, width: u32, height: u32) -> Vec<String> {
+	[
+		"-loglevel", "error", "-y",
+		"-f", "rawvideo", "-pix_fmt", "rgba",
+		"-video_size", &format!("{}x{}", width, height),
+		"-framerate", &VIDEO_FRAME_RATE.to_string(),
+		"-i", "-",
+		"-vf", "vflip,crop=trunc(iw/2)*2:trunc(ih/2)*2",
+		"-c:v", "libx264", "-pix_fmt", "yuv420p", "-movflags", "+faststart"
+	].into_iter().map(str::to_string).chain([path.display().to_string()]).collect()
+}
+
+// Implement the timing of a video's frames.
+// The window doesn't draw at exactly the video's frame rate, so frames are dropped when it draws faster, and repeated when it falls behind, to keep the video in time with the game.
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct VideoClock {
+	// Time since the first frame, in milliseconds.
+	elapsed: f32,
+	// Frames in the video so far.
+	frames: u64
+}
+
+impl VideoClock {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Move the clock on by the time since the last frame was drawn, returning how many times it should be added to the video, if at all.
+	pub fn advance(&mut self, delta_time: f32) -> u64 {
+		if self.frames > 0 {
+			self.elapsed += delta_time;
+		}
+
+		// The first frame is due straight away, then another every VIDEO_FRAME_TIME.
+		let due = ((self.elapsed / VIDEO_FRAME_TIME) as u64 + 1).saturating_sub(self.frames);
+		let due = if due > MAX_REPEATED_FRAMES {
+			self.elapsed = self.frames as f32 * VIDEO_FRAME_TIME;
+			1
+		} else {
+			due
+		};
+		self.frames += due;
+		due
+	}
+}
+
+// Implement a recording of the window as a video, streaming each frame to an ffmpeg process as it's drawn, so matches can be recorded without any other software.
+// Frames are written on their own thread, so encoding doesn't hold up the game unless ffmpeg falls well behind.
+
+pub struct VideoRecorder {
+	path: PathBuf,
+	width: u32,
+	height: u32,
+	clock: VideoClock,
+	process: Child,
+	sender: Option<SyncSender<Vec<u8>>>,
+	writer: Option<JoinHandle<io::Result<()>>>
+}
+
+impl VideoRecorder {
+	// Get the directory videos are saved to in the platform's data directory.
+	pub fn dir() -> Option<PathBuf> {
+		paths::data_dir().map(|dir| dir.join("videos"))
+	}
+
+	// Get a new path in the videos directory to record to, named after the current time.
+	pub fn new_path() -> Option<PathBuf> {
+		let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_millis()).unwrap_or(0);
+		Self::dir().map(|dir| dir.join(format!("match-{}.mp4", millis)))
+	}
+
+	// Start ffmpeg recording frames of the given size, in pixels, to the given path.
+	// The ffmpeg on the PATH is used, unless the FFMPEG environment variable points to another one.
+	pub fn start(path: &Path, width: u32, height: u32) -> io::Result<Self> {
+		if let Some(dir) = path.parent() {
+			std::fs::create_dir_all(dir)?;
+		}
+
+		let program = std::env::var_os("FFMPEG").unwrap_or_else(|| "ffmpeg".into());
+		let mut process = Command::new(program)
+			.args(ffmpeg_args(path, width, height))
+			.stdin(Stdio::piped())
+			.stdout(Stdio::null())
+			.spawn()?;
+
+		let stdin = process.stdin.take().expect("ffmpeg's stdin is piped");
+		let (sender, receiver) = mpsc::sync_channel(QUEUED_FRAMES);
+		let writer = thread::spawn(move || write_frames(stdin, receiver));
+
+		tracing::info!("Recording video to {}", path.display());
+		Ok(Self {
+			path: path.to_path_buf(),
+			width,
+			height,
+			clock: VideoClock::new(),
+			process,
+			sender: Some(sender),
+			writer: Some(writer)
+		})
+	}
+
+	// Get the path the video is being recorded to.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	// Add the window's latest frame to the video, as many times as it's due given the time since the last one.
+	// The frame is only read from the window if it's needed, as RGBA pixels from the bottom row up, along with its size.
+	// Every frame has to be the size the recording started at, so if the window's been resized the recording has to be stopped.
+	pub fn push(&mut self, delta_time: f32, read: impl FnOnce() -> io::Result<(u32, u32, Vec<u8>)>) -> io::Result<()> {
+		let copies = self.clock.advance(delta_time);
+		if copies == 0 {
+			return Ok(());
+		}
+
+		let (width, height, pixels) = read()?;
+		if (width, height) != (self.width, self.height) {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("frame is {}x{}, but the video is {}x{}", width, height, self.width, self.height)));
+		}
+
+		let sent = self.sender.as_ref().is_some_and(|sender| {
+			(1..copies).all(|_| sender.send(pixels.clone()).is_ok()) && sender.send(pixels).is_ok()
+		});
+		if !sent {
+			// The writer only stops early if ffmpeg did, so find out why.
+			self.sender = None;
+			return match self.writer.take().map(JoinHandle::join) {
+				Some(Ok(Err(err))) => Err(err),
+				_ => Err(io::Error::new(io::ErrorKind::BrokenPipe, "ffmpeg stopped"))
+			};
+		}
+		Ok(())
+	}
+
+	// Stop recording, waiting for ffmpeg to finish writing the video. Returns the path it was saved to.
+	pub fn finish(mut self) -> io::Result<PathBuf> {
+		// Closing ffmpeg's stdin tells it there are no more frames.
+		self.sender = None;
+		let written = match self.writer.take().map(JoinHandle::join) {
+			Some(Ok(result)) => result,
+			Some(Err(_)) => Err(io::Error::other("the video writer panicked")),
+			None => Ok(())
+		};
+
+		let status = self.process.wait()?;
+		if !status.success() {
+			return Err(io::Error::other(format!("ffmpeg exited with {}", status)));
+		}
+		written.map(|_| self.path.clone())
+	}
+}
+
+// Write every frame to ffmpeg until there are no more, then close its stdin.
+fn write_frames(mut stdin: ChildStdin, receiver: mpsc::Receiver<Vec<u8>>) -> io::Result<()> {
+	for pixels in receiver {
+		stdin.write_all(&pixels)?;
+	}
+	stdin.flush()
+}
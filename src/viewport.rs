@@ -0,0 +1,110 @@
+// Dependencies
+
+use glam::{Mat4, Vec3, Vec4};
+
+use crate::structs::Vec2;
+
+// Implement the edges of the window that are covered by something, like the menu bar or the camera notch on a Mac, in logical points.
+// Nothing is drawn there, so the court is never clipped.
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Insets {
+	pub top: f32,
+	pub right: f32,
+	pub bottom: f32,
+	pub left: f32
+}
+
+// Implement the part of the window the game is drawn into.
+// The window's size is in physical pixels, but the game is laid out in logical points, so it's the same size on high-DPI displays like Retina, just sharper.
+// On a phone held upright the court is turned on its side, so it's always wider than it is tall.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Viewport {
+	// The size of the window in physical pixels.
+	pub width: f32,
+	pub height: f32,
+	// How many physical pixels there are to a logical point.
+	pub scale: f32,
+	pub insets: Insets,
+	// Whether the court is turned a quarter clockwise, with its left edge along the top of the window.
+	pub rotated: bool
+}
+
+impl Viewport {
+	pub fn new(width: f32, height: f32, scale: f32) -> Self {
+		Self {
+			width,
+			height,
+			scale: if scale > 0.0 { scale } else { 1.0 },
+			insets: Insets::default(),
+			rotated: false
+		}
+	}
+
+	pub fn with_insets(mut self, insets: Insets) -> Self {
+		self.insets = insets;
+		self
+	}
+
+	pub fn with_rotation(mut self, rotated: bool) -> Self {
+		self.rotated = rotated;
+		self
+	}
+
+	// Get the size of the window in logical points, leaving out the insets, before any rotation.
+	fn screen_size(&self) -> Vec2 {
+		Vec2::new(
+			(self.width / self.scale - self.insets.left - self.insets.right).max(0.0),
+			(self.height / self.scale - self.insets.top - self.insets.bottom).max(0.0)
+		)
+	}
+
+	// Get the size of the play area in logical points, leaving out the insets.
+	pub fn size(&self) -> Vec2 {
+		let size = self.screen_size();
+		if self.rotated {
+			Vec2::new(size.y, size.x)
+		} else {
+			size
+		}
+	}
+
+	// Build the perspective matrix, which maps the play area's logical points onto the window.
+	// The origin (0,0) is the top-left of the play area, just inside the insets.
+	pub fn perspective(&self) -> [[f32; 4]; 4] {
+		let (x_scale, y_scale) = (2.0 * self.scale / self.width, 2.0 * self.scale / self.height);
+		let scale = Mat4::from_scale(Vec3::new(x_scale, -y_scale, 1.0));
+
+		// Turned on its side, the court's x runs down the window and its y runs right to left.
+		if self.rotated {
+			let width = self.screen_size().x;
+			let origin = Vec3::new(-1.0 + (self.insets.left + width) * x_scale, 1.0 - self.insets.top * y_scale, 0.0);
+			let turn = Mat4::from_cols(Vec4::Y, -Vec4::X, Vec4::Z, Vec4::W);
+			return (Mat4::from_translation(origin) * scale * turn).to_cols_array_2d();
+		}
+
+		let origin = Vec3::new(-1.0 + self.insets.left * x_scale, 1.0 - self.insets.top * y_scale, 0.0);
+		(Mat4::from_translation(origin) * scale).to_cols_array_2d()
+	}
+
+	// Translate a position in the window, in physical pixels, to a position in the play area.
+	pub fn to_logical(&self, x: f32, y: f32) -> Vec2 {
+		let (x, y) = (x / self.scale - self.insets.left, y / self.scale - self.insets.top);
+		if self.rotated {
+			Vec2::new(y, self.screen_size().x - x)
+		} else {
+			Vec2::new(x, y)
+		}
+	}
+
+	// Translate a position in the play area to a position in the window, in physical pixels.
+	pub fn to_physical(&self, position: Vec2) -> Vec2 {
+		let (x, y) = if self.rotated {
+			(self.screen_size().x - position.y, position.x)
+		} else {
+			(position.x, position.y)
+		};
+		Vec2::new((x + self.insets.left) * self.scale, (y + self.insets.top) * self.scale)
+	}
+}
@@ -0,0 +1,251 @@
+// Dependencies
+
+use crate::structs::Vec2;
+use crate::scene::{Context, Renderer};
+use crate::input::{Input, Key};
+
+// How much a widget lights up while the pointer is over it, and while it's held down, from 0.0 to 1.0.
+
+pub const HOVER_GLOW: f32 = 0.1;
+pub const PRESS_GLOW: f32 = 0.25;
+
+// Implement a rectangle in screen space, with its position at the top-left corner.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Bounds {
+	pub position: Vec2,
+	pub size: Vec2
+}
+
+impl Bounds {
+	pub fn new(position: Vec2, size: Vec2) -> Self {
+		Self {
+			position,
+			size
+		}
+	}
+
+	// Check if a point is inside, counting the top and left edges but not the bottom and right, so neighbours never overlap.
+	pub fn contains(&self, point: Vec2) -> bool {
+		point.x >= self.position.x && point.x < self.position.x + self.size.x &&
+			point.y >= self.position.y && point.y < self.position.y + self.size.y
+	}
+
+	// Get how far across a point is, from 0.0 at the left edge to 1.0 at the right, clamped to the edges.
+	pub fn fraction(&self, x: f32) -> f32 {
+		((x - self.position.x) / self.size.x).clamp(0.0, 1.0)
+	}
+}
+
+// Implement a widget on a menu, an area that can be pointed at and clicked.
+// Sliders also have a track, which can be pressed and dragged along to pick a value.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Widget {
+	pub bounds: Bounds,
+	pub track: Option<Bounds>
+}
+
+impl Widget {
+	pub fn button(bounds: Bounds) -> Self {
+		Self {
+			bounds,
+			track: None
+		}
+	}
+
+	pub fn slider(bounds: Bounds, track: Bounds) -> Self {
+		Self {
+			bounds,
+			track: Some(track)
+		}
+	}
+}
+
+// Implement what the pointer did to a menu's widgets, by their index.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PointerAction {
+	None,
+	// The pointer moved over a widget.
+	Hover(usize),
+	// A widget was clicked.
+	Click(usize),
+	// A slider's track was pressed or dragged along, to a point from 0.0 at its left end to 1.0 at its right.
+	Drag(usize, f32)
+}
+
+// Implement the state of the pointer over a menu's widgets.
+// Menus lay their widgets out again for every input, so they always match what was last drawn.
+
+#[derive(Clone, Default, Debug)]
+pub struct Pointer {
+	// Where the pointer was last seen, if it's been seen at all.
+	pub position: Option<Vec2>,
+	// The widget under the pointer, and the widget the mouse button was pressed on while it's held down.
+	pub hovered: Option<usize>,
+	pub pressed: Option<usize>,
+	// Whether the pressed widget is a slider being dragged.
+	dragging: bool
+}
+
+impl Pointer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Follow a mouse input over the given widgets, returning what it did to them.
+	// Widgets are clicked as soon as the button is pressed, while sliders follow the pointer until it's released.
+	pub fn handle(&mut self, widgets: &[Widget], input: Input) -> PointerAction {
+		match input {
+			Input::MouseMoved { x, y } => {
+				let position = Vec2::new(x, y);
+				self.position = Some(position);
+				self.hovered = widgets.iter().position(|widget| widget.bounds.contains(position));
+
+				let dragged = self.pressed.filter(|_| self.dragging).and_then(|index| Some((index, widgets.get(index)?.track?)));
+				match (dragged, self.hovered) {
+					(Some((index, track)), _) => PointerAction::Drag(index, track.fraction(x)),
+					(None, Some(index)) => PointerAction::Hover(index),
+					(None, None) => PointerAction::None
+				}
+			},
+			Input::MousePressed => {
+				let (index, position) = match (self.hovered, self.position) {
+					(Some(index), Some(position)) if index < widgets.len() => (index, position),
+					_ => return PointerAction::None
+				};
+				self.pressed = Some(index);
+
+				match widgets[index].track {
+					Some(track) if track.contains(position) => {
+						self.dragging = true;
+						PointerAction::Drag(index, track.fraction(position.x))
+					},
+					_ => PointerAction::Click(index)
+				}
+			},
+			Input::MouseReleased => {
+				self.release();
+				PointerAction::None
+			},
+			_ => PointerAction::None
+		}
+	}
+
+	// Let go of whatever was pressed, e.g. when the menu comes back into view after a click took the player elsewhere.
+	pub fn release(&mut self) {
+		self.pressed = None;
+		self.dragging = false;
+	}
+
+	// Forget which widget the pointer is over, e.g. when the widgets are swapped for others.
+	pub fn reset(&mut self) {
+		self.hovered = None;
+		self.release();
+	}
+
+	// Light up the widget being held down, or else the one the pointer is over.
+	// This is drawn under the widgets' text.
+	pub fn render(&self, renderer: &mut dyn Renderer, widgets: &[Widget]) {
+		let (index, glow) = match (self.pressed, self.hovered) {
+			(Some(index), _) => (index, PRESS_GLOW),
+			(None, Some(index)) => (index, HOVER_GLOW),
+			(None, None) => return
+		};
+		if let Some(widget) = widgets.get(index) {
+			renderer.fade(widget.bounds.position, widget.bounds.size, glow);
+		}
+	}
+}
+
+// Implement what a list menu asks of its scene after an input.
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuAction {
+	// Nothing for the scene to do, e.g. the highlight just moved.
+	None,
+	// Do whatever the highlighted entry does.
+	Choose,
+	// The highlighted entry's slider was pressed or dragged along, to a point from 0.0 at its left end to 1.0 at its right.
+	Drag(f32)
+}
+
+// Implement a menu of entries one above the other, the kind most scenes show.
+// The highlight moves with the up and down keys, wrapping around at either end, or follows the mouse, and entries are chosen with enter, space or a click.
+// Scenes handle their own keys first, then pass anything else on here.
+
+#[derive(Clone, Default, Debug)]
+pub struct ListMenu {
+	// Index of the highlighted entry.
+	pub selected: usize,
+	// What the mouse is pointing at and holding down.
+	pub pointer: Pointer
+}
+
+impl ListMenu {
+	pub fn new(selected: usize) -> Self {
+		Self {
+			selected,
+			pointer: Pointer::new()
+		}
+	}
+
+	// Get the area of an entry that can be pointed at, from its top and height.
+	// Each entry can be pointed at across the whole width of the window, so it's easy to hit.
+	pub fn row(ctx: &Context, top: f32, height: f32) -> Bounds {
+		Bounds::new(Vec2::new(0.0, top), Vec2::new(ctx.width, height))
+	}
+
+	// Mark the highlighted entry among the entries' text.
+	pub fn mark(&self, entries: impl IntoIterator<Item = String>) -> Vec<String> {
+		entries.into_iter().enumerate().map(|(index, entry)| {
+			if index == self.selected {
+				format!("> {} <", entry)
+			} else {
+				entry
+			}
+		}).collect()
+	}
+
+	// Follow an input over the given entries' widgets, returning what the scene should do about it.
+	pub fn handle(&mut self, widgets: &[Widget], input: Input) -> MenuAction {
+		let entries = widgets.len().max(1);
+
+		match input {
+			// Move the highlight, wrapping around at either end.
+			Input::KeyPressed(Key::Up) => {
+				self.selected = (self.selected + entries - 1) % entries;
+				MenuAction::None
+			},
+			Input::KeyPressed(Key::Down) => {
+				self.selected = (self.selected + 1) % entries;
+				MenuAction::None
+			},
+			Input::KeyPressed(Key::Enter | Key::Space) => MenuAction::Choose,
+			// Highlight whatever the mouse is pointing at, and choose it when clicked.
+			Input::MouseMoved { .. } | Input::MousePressed | Input::MouseReleased => match self.pointer.handle(widgets, input) {
+				PointerAction::Hover(index) => {
+					self.selected = index;
+					MenuAction::None
+				},
+				PointerAction::Click(index) => {
+					self.selected = index;
+					MenuAction::Choose
+				},
+				PointerAction::Drag(index, fraction) => {
+					self.selected = index;
+					MenuAction::Drag(fraction)
+				},
+				PointerAction::None => MenuAction::None
+			},
+			// Ignore anything else.
+			_ => MenuAction::None
+		}
+	}
+
+	// Light up the entry the pointer is on, under the entries' text.
+	pub fn render(&self, renderer: &mut dyn Renderer, widgets: &[Widget]) {
+		self.pointer.render(renderer, widgets);
+	}
+}
@@ -0,0 +1,87 @@
+// Tests that stepping the simulation doesn't allocate once it's warmed up.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::events::GameEvent;
+use pong::game::{GameState, BROADPHASE_MIN_OBJECTS};
+use pong::modes::Classic;
+
+// Count the allocations made on each thread, so tests running alongside don't get counted.
+
+struct CountingAllocator;
+
+thread_local! {
+	static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.with(|count| count.set(count.get() + 1));
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) }
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.with(|count| count.set(count.get() + 1));
+		unsafe { System.realloc(ptr, layout, new_size) }
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+	ALLOCATIONS.with(Cell::get)
+}
+
+// Build a match between two AI paddles with enough balls in play to use the broadphase grid.
+fn multi_ball_game() -> GameState {
+	let mut game = GameState::with_mode(&Classic);
+	game.set_seed(1);
+	game.control_id = None;
+	for _ in 0..BROADPHASE_MIN_OBJECTS {
+		game.objects.push(Object::new(ObjectType::Ball).set_size(25.0, 25.0));
+	}
+	game.resize(1280.0, 720.0);
+	game.paused = false;
+
+	for (i, obj) in game.objects.iter_mut().enumerate() {
+		if obj.obj_type == ObjectType::Ball {
+			obj.position = Vec2::new(200.0 + (i * 37 % 800) as f32, 50.0 + (i * 53 % 600) as f32);
+			obj.velocity = Vec2::new(if i % 2 == 0 { 0.4 } else { -0.4 }, 0.1 * (i % 5) as f32 - 0.2);
+		}
+	}
+
+	game
+}
+
+#[test]
+fn ticks_dont_allocate() {
+	let mut game = multi_ball_game();
+
+	// Let the buffers grow to fit first.
+	for _ in 0..600 {
+		game.step(1);
+		game.events.drain();
+	}
+
+	// Scoring records the point in the match's stats, which can grow, so only ticks without a point are counted.
+	let mut counted = 0;
+	for _ in 0..600 {
+		let before = allocations();
+		game.step(1);
+		let made = allocations() - before;
+
+		let scored = game.events.drain().any(|event| matches!(event, GameEvent::PointScored { .. } | GameEvent::MatchEnded { .. }));
+		if !scored {
+			assert_eq!(made, 0, "a tick allocated {} times", made);
+			counted += 1;
+		}
+	}
+	assert!(counted > 300);
+}
@@ -0,0 +1,103 @@
+// Tests for the announcer's callouts shown mid-court.
+
+mod common;
+
+use pong::events::Side;
+use pong::modes::Classic;
+use pong::scenes::{SettingsScene, SettingsPage, SettingsRow};
+use pong::callout::{CALLOUT_TIME, RALLY_CALLOUT};
+use pong::game::TICK_RATE;
+use pong::input::{Input, Key};
+
+use common::{Harness, hit, point};
+
+// Start a match, on the gameplay scene.
+fn playing() -> Harness {
+	Harness::new(&Classic, 1)
+}
+
+// Get the text of the callout being shown, if any.
+fn callout(harness: &Harness) -> Option<String> {
+	harness.ctx.callouts.current.as_ref().map(|callout| callout.text.clone())
+}
+
+#[test]
+fn points_without_a_hit_are_aces() {
+	let mut harness = playing();
+	harness.update_with(vec![point(Side::Left, [1, 0])]);
+	assert_eq!(callout(&harness).as_deref(), Some("Ace!"));
+
+	// A point after a rally isn't.
+	harness.ctx.callouts.current = None;
+	harness.update_with(vec![hit(Side::Left), point(Side::Left, [2, 0])]);
+	assert_eq!(callout(&harness), None);
+}
+
+#[test]
+fn long_rallies_are_called_out() {
+	let mut harness = playing();
+	for _ in 0..RALLY_CALLOUT - 1 {
+		harness.update_with(vec![hit(Side::Left)]);
+	}
+	assert_eq!(callout(&harness), None);
+
+	harness.update_with(vec![hit(Side::Left)]);
+	assert_eq!(callout(&harness).as_deref(), Some("10-hit rally!"));
+}
+
+#[test]
+fn match_point_wins_over_an_ace() {
+	let mut harness = playing();
+	harness.ctx.sim.with(|game| game.scores = [10, 3]);
+	harness.frame();
+
+	harness.update_with(vec![point(Side::Left, [10, 3])]);
+	assert_eq!(callout(&harness).as_deref(), Some("Match point!"));
+}
+
+#[test]
+fn callouts_grow_in_and_fade_out() {
+	let mut harness = playing();
+	let before = harness.rects.len();
+	harness.update_with(vec![point(Side::Left, [1, 0])]);
+	harness.frame();
+	assert!(harness.rects.len() > before);
+
+	// Half size when it appears, full size a moment later, then faded out and gone once its time is up.
+	let shown = harness.ctx.callouts.current.clone().unwrap();
+	let mut game = harness.game().clone();
+	assert_eq!(shown.size(&game), 0.5);
+	assert_eq!(shown.opacity(&game), 1.0);
+
+	game.ticks += TICK_RATE as u64 / 2;
+	assert_eq!(shown.size(&game), 1.0);
+	game.ticks += (CALLOUT_TIME / 1000.0 * TICK_RATE) as u64;
+	assert_eq!(shown.opacity(&game), 0.0);
+
+	harness.run((CALLOUT_TIME / 1000.0 * TICK_RATE) as u32);
+	harness.frame();
+	assert_eq!(callout(&harness), None);
+}
+
+#[test]
+fn callouts_can_be_turned_off() {
+	let mut harness = playing();
+
+	// Switch callouts off on the gameplay page of the settings.
+	let mut scene = SettingsScene::new();
+	scene.page = SettingsPage::Gameplay;
+	scene.menu.selected = SettingsPage::Gameplay.rows().iter().position(|row| *row == SettingsRow::Callouts).unwrap();
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.input(Input::KeyPressed(Key::Right));
+	assert!(!harness.ctx.config.gameplay.callouts);
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.frame();
+
+	// The ace is still noticed, but nothing is drawn for it.
+	let before = harness.rects.len();
+	harness.update_with(vec![point(Side::Left, [1, 0])]);
+	harness.frame();
+	assert!(harness.ctx.callouts.current.is_some());
+	assert_eq!(harness.rects.len(), before);
+}
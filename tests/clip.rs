@@ -0,0 +1,88 @@
+// Tests for recording clips of the game and saving them as GIFs.
+
+mod common;
+
+use std::fs;
+
+use pong::structs::Vec2;
+use pong::modes::Classic;
+use pong::scene::Renderer;
+use pong::clip::{ClipFrame, ClipRecorder, CLIP_FRAME_TIME, CLIP_LENGTH};
+
+use common::{Harness, WIDTH, HEIGHT};
+
+#[test]
+fn rects_are_drawn_downscaled() {
+	let mut frame = ClipFrame::new(10, 10);
+	frame.rect(Vec2::new(4.0, 8.0), Vec2::new(8.0, 4.0));
+
+	let filled: Vec<_> = (0..100).filter(|index| frame.pixels[*index] == 1).map(|index| (index % 10, index / 10)).collect();
+	assert_eq!(filled, vec![(1, 2), (2, 2)]);
+
+	// Rects hanging off the edge are cut off.
+	frame.rect(Vec2::new(-20.0, 36.0), Vec2::new(100.0, 100.0));
+	assert!(frame.pixels[90..].iter().all(|pixel| *pixel == 1));
+}
+
+#[test]
+fn only_the_last_few_seconds_are_kept() {
+	let mut harness = Harness::new(&Classic, 1);
+	let mut clip = ClipRecorder::new();
+
+	for _ in 0..600 {
+		harness.run(2);
+		clip.update(1000.0 / 60.0, WIDTH, HEIGHT, |renderer| harness.scenes.render(&harness.ctx, renderer));
+	}
+
+	let frames: Vec<_> = clip.frames().collect();
+	assert_eq!(frames.len(), (CLIP_LENGTH / CLIP_FRAME_TIME) as usize);
+
+	// Every frame has the court in it, and the ball moves between them.
+	assert!(frames.iter().all(|frame| frame.pixels.contains(&1)));
+	assert_ne!(frames[0], frames[1]);
+}
+
+#[test]
+fn resizing_starts_a_new_clip() {
+	let mut clip = ClipRecorder::new();
+	clip.update(0.0, WIDTH, HEIGHT, |_| ());
+	clip.update(CLIP_FRAME_TIME, WIDTH, HEIGHT, |_| ());
+	// Frames aren't kept more often than the frame time.
+	clip.update(CLIP_FRAME_TIME / 2.0, WIDTH, HEIGHT, |_| ());
+	assert_eq!(clip.frames().count(), 2);
+
+	clip.update(CLIP_FRAME_TIME, WIDTH * 2.0, HEIGHT, |_| ());
+	let frames: Vec<_> = clip.frames().collect();
+	assert_eq!(frames.len(), 1);
+	assert_eq!((frames[0].width, frames[0].height), (400, 150));
+}
+
+#[test]
+fn saved_clip_is_an_animated_gif() {
+	let mut harness = Harness::new(&Classic, 1);
+	let mut clip = ClipRecorder::new();
+	assert!(clip.write_gif(vec![]).is_err());
+
+	for _ in 0..10 {
+		harness.run(6);
+		clip.update(CLIP_FRAME_TIME, WIDTH, HEIGHT, |renderer| harness.scenes.render(&harness.ctx, renderer));
+	}
+
+	let dir = std::env::temp_dir().join(format!("pong-clip-test-{}", std::process::id()));
+	let path = dir.join("clip.gif");
+	clip.save_to(&path).unwrap();
+
+	let mut options = gif::DecodeOptions::new();
+	options.set_color_output(gif::ColorOutput::Indexed);
+	let mut decoder = options.read_info(fs::File::open(&path).unwrap()).unwrap();
+	assert_eq!((decoder.width(), decoder.height()), (200, 150));
+
+	let mut decoded = vec![];
+	while let Some(frame) = decoder.read_next_frame().unwrap() {
+		decoded.push(frame.buffer.to_vec());
+	}
+	let frames: Vec<_> = clip.frames().map(|frame| frame.pixels.clone()).collect();
+	assert_eq!(decoded, frames);
+
+	fs::remove_dir_all(&dir).ok();
+}
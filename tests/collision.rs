@@ -0,0 +1,538 @@
+// Tests for the collision module.
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::collision::{self, ObjectCollider, Colliders, Sweep, Layers};
+use pong::events::Side;
+use pong::broadphase::{self, Grid};
+
+// The default steepest bounce off a paddle.
+const MAX_ANGLE: f32 = std::f32::consts::FRAC_PI_3;
+
+// Build a collider from its top-left corner and size.
+fn rect(x: f32, y: f32, w: f32, h: f32) -> ObjectCollider {
+	ObjectCollider::from_bounds(Vec2::new(x, y), Vec2::new(x + w, y + h))
+}
+
+// Segment intersection
+
+#[test]
+fn crossing_segments_intersect() {
+	assert!(collision::segments_intersect(
+		&Vec2::new(0.0, 0.0), &Vec2::new(10.0, 10.0),
+		&Vec2::new(0.0, 10.0), &Vec2::new(10.0, 0.0)
+	));
+}
+
+#[test]
+fn parallel_segments_dont_intersect() {
+	assert!(!collision::segments_intersect(
+		&Vec2::new(0.0, 0.0), &Vec2::new(10.0, 0.0),
+		&Vec2::new(0.0, 5.0), &Vec2::new(10.0, 5.0)
+	));
+}
+
+#[test]
+fn segments_that_would_cross_if_extended_dont_intersect() {
+	assert!(!collision::segments_intersect(
+		&Vec2::new(0.0, 0.0), &Vec2::new(4.0, 4.0),
+		&Vec2::new(0.0, 10.0), &Vec2::new(10.0, 0.0)
+	));
+}
+
+// Overlap test
+
+#[test]
+fn overlapping_rects_collide() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(5.0, 5.0, 10.0, 10.0);
+	assert!(a.is_colliding(&b));
+	assert!(b.is_colliding(&a));
+}
+
+#[test]
+fn separate_rects_dont_collide() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(20.0, 0.0, 10.0, 10.0);
+	assert!(!a.is_colliding(&b));
+	assert!(!b.is_colliding(&a));
+}
+
+#[test]
+fn rects_separated_on_one_axis_dont_collide() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(5.0, 30.0, 10.0, 10.0);
+	assert!(!a.is_colliding(&b));
+}
+
+#[test]
+fn ball_crossing_paddle_edge_collides() {
+	let paddle = rect(100.0, 100.0, 25.0, 200.0);
+	let ball = rect(90.0, 150.0, 25.0, 25.0);
+	assert!(ball.is_colliding(&paddle));
+}
+
+#[test]
+fn ball_inside_paddle_collides() {
+	// No edges cross when one collider is entirely inside the other.
+	let paddle = rect(100.0, 100.0, 25.0, 200.0);
+	let ball = rect(105.0, 150.0, 10.0, 10.0);
+	assert!(ball.is_colliding(&paddle));
+	assert!(paddle.is_colliding(&ball));
+}
+
+#[test]
+fn touching_rects_dont_collide() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(10.0, 0.0, 10.0, 10.0);
+	assert!(!a.is_colliding(&b));
+	assert!(!b.is_colliding(&a));
+}
+
+#[test]
+fn collider_matches_object_bounds() {
+	let mut obj = Object::new(ObjectType::Ball).set_size(20.0, 10.0);
+	obj.position = Vec2::new(5.0, 7.0);
+
+	let collider = obj.get_collider();
+	assert_eq!(collider.min, Vec2::new(5.0, 7.0));
+	assert_eq!(collider.max, Vec2::new(25.0, 17.0));
+	assert_eq!(collider.center, Vec2::new(15.0, 12.0));
+}
+
+#[test]
+fn colliders_are_refilled_in_place() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	let paddle = Object::new(ObjectType::PaddleLeft).set_size(10.0, 50.0);
+	let mut colliders = Colliders::new();
+	colliders.update(&[ball.clone(), paddle.clone()]);
+	assert_eq!(colliders.sides, vec![None, Some(Side::Left)]);
+
+	// Moving the objects updates the same lists, without reallocating them.
+	let list = colliders.colliders.as_ptr();
+	ball.position = Vec2::new(30.0, 40.0);
+	colliders.update(&[ball, paddle]);
+	assert_eq!(colliders.colliders.as_ptr(), list);
+	assert_eq!(colliders.colliders[0].min, Vec2::new(30.0, 40.0));
+	assert_eq!(colliders.len(), 2);
+}
+
+// Contact
+
+#[test]
+fn contact_normal_points_away_from_side_hit() {
+	let paddle = rect(100.0, 100.0, 25.0, 200.0);
+	let normal = |ball: ObjectCollider| ball.contact(&paddle).unwrap().normal;
+
+	// Overlapping the left face.
+	assert_eq!(normal(rect(80.0, 180.0, 25.0, 25.0)), Vec2::new(-1.0, 0.0));
+
+	// Overlapping the right face.
+	assert_eq!(normal(rect(120.0, 180.0, 25.0, 25.0)), Vec2::new(1.0, 0.0));
+
+	// Overlapping the top face.
+	assert_eq!(normal(rect(100.0, 80.0, 25.0, 25.0)), Vec2::new(0.0, -1.0));
+
+	// Overlapping the bottom face.
+	assert_eq!(normal(rect(100.0, 295.0, 25.0, 25.0)), Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn contact_pushes_out_through_nearest_face() {
+	let paddle = rect(100.0, 0.0, 20.0, 200.0);
+
+	// Sunk 5 pixels into the paddle's left face.
+	let contact = rect(85.0, 50.0, 20.0, 20.0).contact(&paddle).unwrap();
+	assert_eq!(contact.penetration, 5.0);
+	assert_eq!(contact.push(), Vec2::new(-5.0, 0.0));
+
+	// Sunk 3 pixels into the paddle's bottom face.
+	let contact = rect(105.0, 197.0, 10.0, 10.0).contact(&paddle).unwrap();
+	assert_eq!(contact.penetration, 3.0);
+	assert_eq!(contact.push(), Vec2::new(0.0, 3.0));
+}
+
+#[test]
+fn contact_escapes_when_fully_inside() {
+	let paddle = rect(100.0, 0.0, 20.0, 200.0);
+	let ball = rect(112.0, 50.0, 5.0, 5.0);
+
+	// The ball leaves through the right face, which is closest.
+	assert_eq!(ball.contact(&paddle).unwrap().push(), Vec2::new(8.0, 0.0));
+}
+
+#[test]
+fn contact_point_is_middle_of_overlap() {
+	let paddle = rect(100.0, 0.0, 20.0, 200.0);
+
+	// Overlapping the left face between x 100 and 105, and y 50 and 70.
+	let contact = rect(85.0, 50.0, 20.0, 20.0).contact(&paddle).unwrap();
+	assert_eq!(contact.point, Vec2::new(102.5, 60.0));
+
+	// Fully inside, so the point is the ball's own center.
+	let contact = rect(112.0, 50.0, 5.0, 5.0).contact(&paddle).unwrap();
+	assert_eq!(contact.point, Vec2::new(114.5, 52.5));
+}
+
+#[test]
+fn touching_colliders_have_no_contact() {
+	let paddle = rect(100.0, 0.0, 20.0, 200.0);
+
+	assert_eq!(rect(80.0, 50.0, 20.0, 20.0).contact(&paddle), None);
+	assert_eq!(rect(0.0, 50.0, 20.0, 20.0).contact(&paddle), None);
+}
+
+// Swept test
+
+#[test]
+fn sweep_finds_time_of_impact() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(50.0, -100.0, 10.0, 200.0);
+
+	let hit = ball.sweep(Vec2::new(80.0, 0.0), &wall);
+	assert_eq!(hit, Some(Sweep { time: 0.5, normal: Vec2::new(-1.0, 0.0), point: Vec2::new(50.0, 5.0) }));
+}
+
+#[test]
+fn sweep_moving_left_hits_right_face() {
+	let ball = rect(100.0, 0.0, 10.0, 10.0);
+	let wall = rect(50.0, -100.0, 10.0, 200.0);
+
+	let hit = ball.sweep(Vec2::new(-80.0, 0.0), &wall).unwrap();
+	assert_eq!(hit.normal, Vec2::new(1.0, 0.0));
+	assert!((hit.time - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn sweep_catches_tunnelling() {
+	// The ball moves far enough in one step to pass through the paddle entirely.
+	let ball = rect(0.0, 100.0, 10.0, 10.0);
+	let paddle = rect(100.0, 50.0, 5.0, 100.0);
+
+	let delta = Vec2::new(500.0, 0.0);
+	let mut moved = ball;
+	moved.min += delta;
+	moved.max += delta;
+	assert!(!moved.is_colliding(&paddle));
+
+	let hit = ball.sweep(delta, &paddle).unwrap();
+	assert!((hit.time - 0.18).abs() < 1e-6);
+}
+
+#[test]
+fn sweep_misses_when_path_passes_by() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let paddle = rect(50.0, 50.0, 10.0, 10.0);
+	assert_eq!(ball.sweep(Vec2::new(100.0, 0.0), &paddle), None);
+}
+
+#[test]
+fn sweep_misses_when_too_short() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(50.0, -100.0, 10.0, 200.0);
+	assert_eq!(ball.sweep(Vec2::new(20.0, 0.0), &wall), None);
+}
+
+#[test]
+fn sweep_misses_when_moving_away() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(50.0, -100.0, 10.0, 200.0);
+	assert_eq!(ball.sweep(Vec2::new(-100.0, 0.0), &wall), None);
+}
+
+#[test]
+fn sweep_without_movement_never_hits() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(50.0, -100.0, 10.0, 200.0);
+	assert_eq!(ball.sweep(Vec2::new(0.0, 0.0), &wall), None);
+}
+
+#[test]
+fn sweep_diagonal_hits_top_face() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let floor = rect(-100.0, 50.0, 300.0, 10.0);
+
+	let hit = ball.sweep(Vec2::new(40.0, 80.0), &floor).unwrap();
+	assert_eq!(hit.normal, Vec2::new(0.0, -1.0));
+	assert!((hit.time - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn sweep_into_corner_has_diagonal_normal() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let paddle = rect(50.0, 50.0, 10.0, 100.0);
+
+	// Heading straight for the paddle's top-left corner.
+	let hit = ball.sweep(Vec2::new(80.0, 80.0), &paddle).unwrap();
+	let half = std::f32::consts::FRAC_1_SQRT_2;
+	assert_eq!(hit.normal, Vec2::new(-half, -half));
+	assert!((hit.time - 0.5).abs() < 1e-6);
+	assert_eq!(hit.point, Vec2::new(50.0, 50.0));
+}
+
+#[test]
+fn sweep_just_past_corner_hits_face() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+
+	// A couple of pixels further right, so the ball drops level with the paddle before reaching it and hits the front face.
+	let hit = ball.sweep(Vec2::new(80.0, 80.0), &rect(52.0, 50.0, 10.0, 100.0)).unwrap();
+	assert_eq!(hit.normal, Vec2::new(-1.0, 0.0));
+
+	// A couple of pixels further left, so the ball is already over the paddle when it comes down and lands on the top.
+	let hit = ball.sweep(Vec2::new(80.0, 80.0), &rect(48.0, 50.0, 10.0, 100.0)).unwrap();
+	assert_eq!(hit.normal, Vec2::new(0.0, -1.0));
+}
+
+#[test]
+fn sweep_ignores_starting_overlap() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0);
+	let other = rect(5.0, 5.0, 10.0, 10.0);
+	assert_eq!(ball.sweep(Vec2::new(10.0, 0.0), &other), None);
+}
+
+// Collision response
+
+#[test]
+fn reflect_flips_velocity_along_normal() {
+	let velocity = Vec2::new(3.0, -2.0);
+	assert_eq!(collision::reflect(velocity, Vec2::new(-1.0, 0.0)), Vec2::new(-3.0, -2.0));
+	assert_eq!(collision::reflect(velocity, Vec2::new(0.0, 1.0)), Vec2::new(3.0, 2.0));
+}
+
+#[test]
+fn paddle_bounce_reverses_and_caps_ball() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, 85.0);
+	ball.velocity = Vec2::new(1.9, 0.0);
+	ball.max_velocity = Vec2::new(2.0, 2.0);
+
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	let velocity = collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15);
+
+	// Sped up by 15%, but capped at the maximum.
+	assert_eq!(velocity.x, -2.0);
+	// Hit just above the paddle's center, so the ball heads upwards.
+	assert!(velocity.y < 0.0);
+	assert!(velocity.y.abs() <= ball.max_velocity.y);
+}
+
+#[test]
+fn paddle_bounce_sends_ball_down_from_lower_half() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, 130.0);
+	ball.velocity = Vec2::new(1.0, 0.0);
+
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	let velocity = collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15);
+
+	assert!(velocity.x < 0.0);
+	assert!(velocity.y > 0.0);
+}
+
+#[test]
+fn paddle_bounce_from_center_goes_straight_back() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, 95.0);
+	ball.velocity = Vec2::new(1.0, 0.5);
+
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	let velocity = collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15);
+
+	let speed = (1.0f32 + 0.25).sqrt() * 1.15;
+	assert!((velocity.x + speed).abs() < 0.0001);
+	assert_eq!(velocity.y, 0.0);
+}
+
+#[test]
+fn paddle_bounce_scales_speed_by_restitution() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, 95.0);
+	ball.velocity = Vec2::new(1.0, 0.0);
+
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	assert_eq!(collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 0.5), Vec2::new(-0.5, 0.0));
+	assert_eq!(collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.0), Vec2::new(-1.0, 0.0));
+}
+
+#[test]
+fn paddle_bounce_from_edge_uses_max_angle() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.velocity = Vec2::new(1.0, 0.0);
+
+	// Just catching the bottom corner of the paddle, on its right side.
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	ball.position = Vec2::new(110.0, 150.0);
+	let velocity = collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15);
+
+	assert!(velocity.x > 0.0);
+	assert!(((velocity.y / velocity.x).atan() - MAX_ANGLE).abs() < 0.0001);
+
+	// Hits further out than the edge don't bounce any steeper.
+	ball.position = Vec2::new(110.0, 400.0);
+	assert_eq!(collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15), velocity);
+}
+
+#[test]
+fn paddle_bounce_angle_is_configurable() {
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, 50.0);
+	ball.velocity = Vec2::new(1.0, 0.0);
+
+	let paddle = rect(100.0, 50.0, 10.0, 100.0);
+	let shallow = collision::paddle_bounce(&ball, &paddle, 20f32.to_radians(), 1.15);
+	let steep = collision::paddle_bounce(&ball, &paddle, 70f32.to_radians(), 1.15);
+
+	assert!(shallow.y < 0.0 && steep.y < 0.0);
+	assert!(steep.y.abs() / steep.x.abs() > shallow.y.abs() / shallow.x.abs());
+	assert!((shallow.y / shallow.x).atan() <= 20f32.to_radians());
+}
+
+#[test]
+fn paddle_bounce_at_top_edge_is_finite() {
+	// Found by the collision fuzz target: a ball centered on y = 0 and level with the paddle's center gave a NaN velocity.
+	let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+	ball.position = Vec2::new(90.0, -5.0);
+	ball.velocity = Vec2::new(1.0, 0.0);
+
+	let paddle = rect(100.0, -50.0, 10.0, 100.0);
+	let velocity = collision::paddle_bounce(&ball, &paddle, MAX_ANGLE, 1.15);
+
+	assert_eq!(velocity, Vec2::new(-1.15, 0.0));
+}
+
+// Spin
+
+#[test]
+fn friction_turns_sliding_into_spin() {
+	// Sliding right along the floor, which pushes up against the ball.
+	let normal = Vec2::new(0.0, -1.0);
+	let (velocity, spin) = collision::apply_friction(Vec2::new(1.0, 0.5), 0.0, normal, Vec2::new(0.0, 0.0), 0.3);
+
+	// The ball is dragged back a little, and the rest of the slip goes into spin.
+	assert!(velocity.x < 1.0 && velocity.x > 0.0);
+	assert_eq!(velocity.y, 0.5);
+	assert!(spin != 0.0);
+	assert!(((1.0 - velocity.x) * 2.0 - spin.abs()).abs() < 0.0001);
+}
+
+#[test]
+fn full_friction_leaves_ball_rolling() {
+	let normal = Vec2::new(0.0, -1.0);
+	let (velocity, spin) = collision::apply_friction(Vec2::new(1.2, 0.5), 0.4, normal, Vec2::new(0.0, 0.0), 1.0);
+
+	// Rolling without slipping, so another contact changes nothing.
+	let (again, again_spin) = collision::apply_friction(velocity, spin, normal, Vec2::new(0.0, 0.0), 1.0);
+	assert!((again.x - velocity.x).abs() < 0.0001);
+	assert!((again_spin - spin).abs() < 0.0001);
+}
+
+#[test]
+fn no_friction_leaves_ball_alone() {
+	let (velocity, spin) = collision::apply_friction(Vec2::new(1.0, 0.5), 0.2, Vec2::new(-1.0, 0.0), Vec2::new(0.0, 1.0), 0.0);
+	assert_eq!(velocity, Vec2::new(1.0, 0.5));
+	assert_eq!(spin, 0.2);
+}
+
+#[test]
+fn spin_from_moving_surface_opposes_sliding_on_still_one() {
+	// A still ball hit by a paddle moving down picks up the opposite spin to a ball sliding down a still paddle.
+	let normal = Vec2::new(-1.0, 0.0);
+	let (_, dragged) = collision::apply_friction(Vec2::new(-1.0, 0.0), 0.0, normal, Vec2::new(0.0, 1.0), 0.3);
+	let (_, sliding) = collision::apply_friction(Vec2::new(-1.0, 1.0), 0.0, normal, Vec2::new(0.0, 0.0), 0.3);
+	assert!(dragged * sliding < 0.0);
+}
+
+#[test]
+fn curve_turns_without_changing_speed() {
+	let velocity = Vec2::new(1.0, 0.0);
+	let curved = collision::curve(velocity, 0.5, 0.001, 100.0);
+
+	assert!(curved.y > 0.0);
+	assert!(((curved.x * curved.x + curved.y * curved.y).sqrt() - 1.0).abs() < 0.0001);
+
+	// Spinning the other way curves the other way, and no spin flies straight.
+	assert!(collision::curve(velocity, -0.5, 0.001, 100.0).y < 0.0);
+	assert_eq!(collision::curve(velocity, 0.0, 0.001, 100.0), velocity);
+}
+
+// Layers
+
+#[test]
+fn balls_and_paddles_collide_by_default() {
+	let ball = Object::new(ObjectType::Ball).get_collider();
+	let left = Object::new(ObjectType::PaddleLeft).get_collider();
+	let right = Object::new(ObjectType::PaddleRight).get_collider();
+
+	assert!(ball.can_collide(&left));
+	assert!(left.can_collide(&ball));
+	assert!(ball.can_collide(&ball));
+
+	// Paddles never collide with each other.
+	assert!(!left.can_collide(&right));
+}
+
+#[test]
+fn layers_only_collide_if_both_masks_agree() {
+	let ball = rect(0.0, 0.0, 10.0, 10.0).with_layers(Layers::BALL, Layers::ALL);
+	let power_up = rect(0.0, 0.0, 10.0, 10.0).with_layers(Layers::POWER_UP, Layers::BALL);
+	let paddle = rect(0.0, 0.0, 10.0, 10.0).with_layers(Layers::PADDLE, Layers::BALL);
+	let obstacle = rect(0.0, 0.0, 10.0, 10.0).with_layers(Layers::OBSTACLE, Layers::ALL);
+	let ghost = rect(0.0, 0.0, 10.0, 10.0).with_layers(Layers::BALL, Layers::ALL & !Layers::OBSTACLE);
+
+	// Power-ups are only picked up by the ball.
+	assert!(power_up.can_collide(&ball));
+	assert!(!power_up.can_collide(&paddle));
+
+	// Ghost balls pass through obstacles, even though obstacles would collide with them.
+	assert!(ball.can_collide(&obstacle));
+	assert!(!ghost.can_collide(&obstacle));
+	assert!(ghost.can_collide(&paddle));
+}
+
+// Broad phase
+
+#[test]
+fn grid_finds_only_nearby_colliders() {
+	let colliders = [
+		rect(10.0, 10.0, 10.0, 10.0),
+		rect(300.0, 300.0, 10.0, 10.0),
+		rect(40.0, 20.0, 10.0, 10.0)
+	];
+	let grid = Grid::new(400.0, 400.0, 64.0, &colliders);
+
+	// Both of the top-left colliders share a cell with the first one, the far one doesn't.
+	assert_eq!(grid.query(&colliders[0]), vec![0, 2]);
+	assert_eq!(grid.query(&colliders[1]), vec![1]);
+}
+
+#[test]
+fn grid_lists_large_colliders_once() {
+	let colliders = [
+		rect(100.0, 0.0, 25.0, 400.0),
+		rect(90.0, 200.0, 10.0, 10.0),
+		rect(0.0, 0.0, 10.0, 10.0)
+	];
+	let grid = Grid::new(400.0, 400.0, 64.0, &colliders);
+
+	// The paddle covers a whole column of cells, but only comes up once.
+	assert_eq!(grid.query(&rect(0.0, 0.0, 400.0, 400.0)), vec![0, 1, 2]);
+	assert_eq!(grid.query(&colliders[1]), vec![0, 1]);
+}
+
+#[test]
+fn grid_files_colliders_outside_court_at_edge() {
+	let colliders = [
+		rect(-50.0, -50.0, 10.0, 10.0),
+		rect(450.0, 100.0, 10.0, 10.0)
+	];
+	let grid = Grid::new(400.0, 400.0, 64.0, &colliders);
+
+	assert_eq!(grid.query(&rect(0.0, 0.0, 1.0, 1.0)), vec![0]);
+	assert_eq!(grid.query(&rect(399.0, 100.0, 1.0, 1.0)), vec![1]);
+}
+
+#[test]
+fn swept_bounds_cover_whole_movement() {
+	let bounds = broadphase::swept_bounds(&rect(100.0, 100.0, 10.0, 10.0), Vec2::new(-50.0, 20.0));
+	assert_eq!(bounds.min, Vec2::new(50.0, 100.0));
+	assert_eq!(bounds.max, Vec2::new(110.0, 130.0));
+}
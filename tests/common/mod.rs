@@ -0,0 +1,167 @@
+// Harness for end-to-end gameplay tests.
+// This runs the game the same way the window does, with scenes, a simulation thread and a context,
+// but draws to a stub renderer, takes scripted inputs, and only moves time forward when told to.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use pong::structs::Vec2;
+use pong::game::GameState;
+use pong::events::{Side, GameEvent};
+use pong::modes::{GameMode, Classic};
+use pong::sim::Simulation;
+use pong::clock::ManualClock;
+use pong::config::Config;
+use pong::stats::MatchStats;
+use pong::input::Input;
+use pong::scene::{Context, SceneManager, Renderer};
+use pong::scenes::{PlayingScene, MainMenuScene};
+
+// Size of the window the harness pretends to have.
+
+pub const WIDTH: f32 = 800.0;
+pub const HEIGHT: f32 = 600.0;
+
+// Implement a renderer that records every rect and shade drawn instead of drawing it.
+
+#[derive(Default)]
+pub struct StubRenderer {
+	pub rects: Vec<(Vec2, Vec2)>,
+	pub shades: Vec<(Vec2, Vec2, f32)>
+}
+
+impl Renderer for StubRenderer {
+	fn rect(&mut self, position: Vec2, size: Vec2) {
+		self.rects.push((position, size));
+	}
+
+	fn shade(&mut self, position: Vec2, size: Vec2, opacity: f32) {
+		self.shades.push((position, size, opacity));
+	}
+}
+
+// Create a struct that drives a game from start to finish without a window.
+
+pub struct Harness {
+	pub ctx: Context,
+	pub scenes: SceneManager,
+	// Every event emitted since the harness started.
+	pub events: Vec<GameEvent>,
+	// Rects and shades drawn in the last frame.
+	pub rects: Vec<(Vec2, Vec2)>,
+	pub shades: Vec<(Vec2, Vec2, f32)>
+}
+
+impl Harness {
+	// Start a match in the given game mode, already on the gameplay scene.
+	// The simulation thread follows a clock that never moves, so the game only advances when run is called.
+	pub fn new(mode: &'static dyn GameMode, seed: u64) -> Self {
+		let mut game = GameState::with_mode(mode);
+		game.set_seed(seed);
+
+		// The tutorial and instant replays are left off, so they don't get in the way of tests that aren't about them.
+		let mut config = Config::default();
+		config.gameplay.tutorial = false;
+		config.gameplay.instant_replays = false;
+
+		let sim = Simulation::spawn_with_clock(game, Arc::new(ManualClock::new()));
+		let mut ctx = Context::new(sim, config);
+		ctx.resize(WIDTH, HEIGHT);
+
+		let mut scenes = SceneManager::new();
+		scenes.push(&mut ctx, Box::new(PlayingScene));
+		scenes.handle_input(&mut ctx, Input::Focused(true));
+
+		let mut harness = Self {
+			ctx,
+			scenes,
+			events: vec![],
+			rects: vec![],
+			shades: vec![]
+		};
+		harness.frame();
+		harness
+	}
+
+	// Start on the main menu instead, with a classic match waiting behind it.
+	pub fn on_menu() -> Self {
+		let mut harness = Self::new(&Classic, 1);
+		harness.scenes = SceneManager::new();
+		harness.scenes.push(&mut harness.ctx, Box::new(MainMenuScene::new()));
+		harness.frame();
+		harness
+	}
+
+	// Send an input to the active scene, as if it came from the window.
+	pub fn input(&mut self, input: Input) {
+		self.scenes.handle_input(&mut self.ctx, input);
+	}
+
+	// Update the active scene with the given events, as if the simulation had just emitted them.
+	pub fn update_with(&mut self, events: Vec<GameEvent>) {
+		self.ctx.events = events;
+		self.scenes.update(&mut self.ctx);
+	}
+
+	// Simulate a number of ticks, then run a frame.
+	pub fn run(&mut self, ticks: u32) {
+		self.ctx.sim.with(|game| game.step(ticks));
+		self.frame();
+	}
+
+	// Simulate one tick at a time until an event matching the predicate is emitted, giving up after the given number of ticks.
+	// Returns the matching event, if there was one.
+	pub fn run_until(&mut self, max_ticks: u32, predicate: impl Fn(&GameEvent) -> bool) -> Option<GameEvent> {
+		for _ in 0..max_ticks {
+			self.run(1);
+			if let Some(event) = self.ctx.events.iter().find(|event| predicate(event)) {
+				return Some(event.clone());
+			}
+		}
+		None
+	}
+
+	// Run a single frame: collect events and a snapshot from the simulation, update the active scene and draw every scene.
+	pub fn frame(&mut self) {
+		self.ctx.refresh();
+		self.events.extend(self.ctx.events.iter().cloned());
+
+		self.scenes.update(&mut self.ctx);
+
+		let mut renderer = StubRenderer::default();
+		self.scenes.render(&self.ctx, &mut renderer);
+		self.rects = renderer.rects;
+		self.shades = renderer.shades;
+	}
+
+	// The game state as of the last frame.
+	pub fn game(&self) -> &GameState {
+		&self.ctx.game
+	}
+}
+
+// Make events for tests to feed to whatever reacts to them.
+
+pub fn hit(side: Side) -> GameEvent {
+	GameEvent::BallHitPaddle { side, point: Vec2::new(0.0, 0.0) }
+}
+
+pub fn point(side: Side, scores: [u32; 2]) -> GameEvent {
+	GameEvent::PointScored { side, scores }
+}
+
+pub fn ended(winner: Side, scores: [u32; 2], stats: &MatchStats) -> GameEvent {
+	GameEvent::MatchEnded { winner, scores, stats: stats.clone() }
+}
+
+// Make an empty directory for a test to keep its files in.
+// Each test binary runs as its own process, so the process ID keeps binaries apart and the name keeps tests in one binary apart.
+pub fn temp_dir(name: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("pong-test-{}-{}", name, std::process::id()));
+	fs::remove_dir_all(&dir).ok();
+	fs::create_dir_all(&dir).unwrap();
+	dir
+}
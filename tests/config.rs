@@ -0,0 +1,148 @@
+// Tests for loading and watching the config file.
+
+mod common;
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pong::config::{Config, ConfigWatcher, SettingsExport, SETTINGS_VERSION};
+use pong::game::Difficulty;
+use pong::input::Key;
+
+use common::temp_dir;
+
+// Poll the watcher until it reports a change, giving up after a couple of seconds.
+fn wait_for_change(watcher: &ConfigWatcher) -> Option<Config> {
+	let start = Instant::now();
+	while start.elapsed() < Duration::from_secs(2) {
+		if let Some(config) = watcher.poll() {
+			return Some(config);
+		}
+		thread::sleep(Duration::from_millis(10));
+	}
+	None
+}
+
+#[test]
+fn writes_defaults_when_missing() {
+	let path = temp_dir("defaults").join("config.toml");
+
+	assert_eq!(Config::load_from(&path), Config::default());
+	assert_eq!(Config::read_from(&path), Ok(Config::default()));
+}
+
+#[test]
+fn invalid_file_falls_back_to_defaults() {
+	let path = temp_dir("invalid").join("config.toml");
+	fs::write(&path, "controls = 5").unwrap();
+
+	assert!(Config::read_from(&path).is_err());
+	assert_eq!(Config::load_from(&path), Config::default());
+}
+
+#[test]
+fn watcher_picks_up_changes() {
+	let path = temp_dir("watch").join("config.toml");
+	Config::default().save_to(&path).unwrap();
+
+	let watcher = ConfigWatcher::new(&path).unwrap();
+	assert!(watcher.poll().is_none());
+
+	let mut config = Config::default();
+	config.controls.sensitivity = 1.5;
+	config.gameplay.ai_difficulty = Difficulty::Easy;
+	config.save_to(&path).unwrap();
+
+	assert_eq!(wait_for_change(&watcher), Some(config));
+}
+
+#[test]
+fn watcher_skips_broken_edits() {
+	let path = temp_dir("broken").join("config.toml");
+	Config::default().save_to(&path).unwrap();
+
+	let watcher = ConfigWatcher::new(&path).unwrap();
+	fs::write(&path, "[controls\nsensitivity = ").unwrap();
+
+	assert_eq!(wait_for_change(&watcher), None);
+}
+
+#[test]
+fn default_keybinds_are_valid() {
+	let mut config = Config::default();
+	assert!(config.validate_keybinds().is_empty());
+	assert_eq!(config, Config::default());
+}
+
+#[test]
+fn missing_and_invalid_keybinds_fall_back_to_defaults() {
+	let path = temp_dir("keybinds").join("config.toml");
+	fs::write(&path, "[keybinds]\nup = \"W\"\ndown = \"NotAKey\"\njump = \"J\"\n").unwrap();
+
+	let config = Config::read_from(&path).unwrap();
+	let defaults = Config::default().keybinds;
+
+	assert_eq!(config.keybinds["up"], "W");
+	assert_eq!(config.keybinds["down"], defaults["down"]);
+	assert_eq!(config.keybinds["escape"], defaults["escape"]);
+	assert!(!config.keybinds.contains_key("jump"));
+	assert_eq!(config.keybinds.len(), defaults.len());
+}
+
+#[test]
+fn clashing_keybinds_are_resolved() {
+	let mut config = Config::default();
+
+	// A key the player chose wins over a default one, which is left unbound since it has nowhere else to go.
+	config.keybinds.insert("up".to_string(), "Tab".to_string());
+	assert_eq!(config.validate_keybinds().len(), 1);
+	assert_eq!(config.key_for("Tab"), Some(Key::Up));
+	assert!(!config.keybinds.contains_key("scores"));
+
+	// Between two keys the player chose, the first keeps it and the other falls back to its default.
+	config.keybinds.insert("up".to_string(), "W".to_string());
+	config.keybinds.insert("down".to_string(), "W".to_string());
+	assert_eq!(config.validate_keybinds().len(), 1);
+	assert_eq!(config.key_for("W"), Some(Key::Up));
+	assert_eq!(config.keybinds["down"], "Down");
+	assert_eq!(config.keybinds["scores"], "Tab");
+}
+
+#[test]
+fn exported_settings_import_the_same() {
+	let dir = temp_dir("export");
+	let path = dir.join("settings.toml");
+
+	let mut config = Config { theme: "neon".to_string(), ..Config::default() };
+	config.controls.sensitivity = 1.75;
+	config.keybinds.insert("up".to_string(), "W".to_string());
+	config.export_to(&path).unwrap();
+
+	assert_eq!(Config::import_from(&path), Ok(config));
+}
+
+#[test]
+fn imports_check_the_version() {
+	let dir = temp_dir("import");
+	let path = dir.join("settings.toml");
+
+	// A plain config file isn't an export.
+	Config::default().save_to(&path).unwrap();
+	assert!(Config::import_from(&path).is_err());
+
+	// Neither is an export from a newer version.
+	let export = SettingsExport { version: SETTINGS_VERSION + 1, settings: Config::default() };
+	fs::write(&path, toml::to_string(&export).unwrap()).unwrap();
+	assert!(Config::import_from(&path).unwrap_err().contains("version"));
+}
+
+#[test]
+fn imported_keybinds_are_checked() {
+	let dir = temp_dir("import-keybinds");
+	let path = dir.join("settings.toml");
+	fs::write(&path, "version = 1\n\n[settings.keybinds]\nup = \"NotAKey\"\n").unwrap();
+
+	let config = Config::import_from(&path).unwrap();
+	assert_eq!(config.keybinds, Config::default().keybinds);
+}
@@ -0,0 +1,121 @@
+// Tests for the paddle and ball skins unlocked by playing.
+
+mod common;
+
+use pong::structs::Vec2;
+use pong::config::Config;
+use pong::events::{Side, GameEvent};
+use pong::modes::Classic;
+use pong::profiles::{Profiles, LifetimeStats};
+use pong::stats::MatchStats;
+use pong::cosmetics::{self, PADDLE_SKINS, BALL_SKINS, TRAIL_LENGTH};
+use pong::scene::{Scene, Renderer};
+use pong::scenes::{SettingsScene, SettingsPage, SettingsRow};
+use pong::input::{Input, Key};
+
+use common::{Harness, StubRenderer};
+
+fn played(minutes: u64) -> LifetimeStats {
+	LifetimeStats { play_time: minutes * 60, ..LifetimeStats::default() }
+}
+
+#[test]
+fn finished_matches_add_up_play_time() {
+	let mut profiles = Profiles::default();
+	profiles.select_or_create("Ada", &Config::default());
+
+	let stats = MatchStats { duration: 95_400.0, ..MatchStats::default() };
+	profiles.record(Some(Side::Left), &[GameEvent::MatchEnded { winner: Side::Left, scores: [11, 4], stats }]);
+	assert_eq!(profiles.active().unwrap().stats.play_time, 95);
+}
+
+#[test]
+fn skins_unlock_with_play_time() {
+	let comet = &BALL_SKINS[1];
+	assert!(!comet.unlocked(&played(comet.unlock_minutes - 1)));
+	assert!(comet.unlocked(&played(comet.unlock_minutes)));
+
+	// Everyone starts with the first skins.
+	assert!(PADDLE_SKINS[0].unlocked(&LifetimeStats::default()));
+	assert!(BALL_SKINS[0].unlocked(&LifetimeStats::default()));
+
+	// Playing long enough for a skin unlocks it once, and not again after the next match.
+	let unlocked = cosmetics::newly_unlocked(BALL_SKINS, &played(comet.unlock_minutes - 1), &played(comet.unlock_minutes));
+	assert_eq!(unlocked, vec![comet]);
+	assert!(cosmetics::newly_unlocked(BALL_SKINS, &played(comet.unlock_minutes), &played(comet.unlock_minutes + 1)).is_empty());
+}
+
+#[test]
+fn locked_or_unknown_skins_fall_back_to_the_first() {
+	let stats = played(0);
+	assert_eq!(cosmetics::chosen(PADDLE_SKINS, "gold", &stats).id, "classic");
+	assert_eq!(cosmetics::chosen(PADDLE_SKINS, "no-such-skin", &stats).id, "classic");
+	assert_eq!(cosmetics::chosen(PADDLE_SKINS, "gold", &played(1000)).id, "gold");
+
+	// Guests always play with the first skins.
+	assert_eq!(cosmetics::for_profile(None), (&PADDLE_SKINS[0], &BALL_SKINS[0]));
+}
+
+#[test]
+fn choosing_a_skin_skips_locked_ones() {
+	let mut harness = Harness::new(&Classic, 1);
+	let config = harness.ctx.config.clone();
+	harness.ctx.profiles.select_or_create("Ada", &config);
+	harness.ctx.profiles.active_mut().unwrap().stats = played(BALL_SKINS[1].unlock_minutes);
+
+	let mut scene = SettingsScene::new();
+	scene.page = SettingsPage::Skins;
+	scene.menu.selected = SettingsPage::Skins.rows().iter().position(|row| *row == SettingsRow::BallSkin).unwrap();
+
+	// Only the first two ball skins are unlocked, so stepping through them wraps around after the second.
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Right));
+	assert_eq!(harness.ctx.profiles.active().unwrap().ball_skin, BALL_SKINS[1].id);
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Right));
+	assert_eq!(harness.ctx.profiles.active().unwrap().ball_skin, BALL_SKINS[0].id);
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Left));
+	assert_eq!(harness.ctx.profiles.active().unwrap().ball_skin, BALL_SKINS[1].id);
+
+	// Without a profile, there's nothing to change.
+	harness.ctx.profiles.active = None;
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Right));
+	assert!(scene.lines(&harness.ctx).iter().any(|line| line.contains("Choose a profile first")));
+}
+
+#[test]
+fn trails_only_follow_moving_objects() {
+	let comet = &BALL_SKINS[1];
+	let (position, size, velocity) = (Vec2::new(100.0, 100.0), Vec2::new(10.0, 10.0), Vec2::new(0.5, 0.0));
+
+	// A renderer that can only draw in one colour leaves out the faint copies, but still draws the ball itself.
+	let mut renderer = StubRenderer::default();
+	comet.draw(&mut renderer, position, size, velocity, true);
+	assert_eq!(renderer.rects.last(), Some(&(position, size)));
+
+	// Copies are drawn behind the ball, the way it came, when a renderer can show them.
+	let mut renderer = FillRecorder::default();
+	comet.draw(&mut renderer, position, size, velocity, true);
+	assert_eq!(renderer.fills.len(), TRAIL_LENGTH + 1);
+	assert!(renderer.fills[..TRAIL_LENGTH].iter().all(|(behind, fill)| behind.x < position.x && fill[3] < 1.0));
+
+	// A paused ball leaves no trail.
+	let mut renderer = FillRecorder::default();
+	comet.draw(&mut renderer, position, size, velocity, false);
+	assert_eq!(renderer.fills.len(), 1);
+}
+
+// Implement a renderer that records the position and colour of everything filled in.
+
+#[derive(Default)]
+struct FillRecorder {
+	fills: Vec<(Vec2, [f32; 4])>
+}
+
+impl Renderer for FillRecorder {
+	fn rect(&mut self, position: Vec2, _size: Vec2) {
+		self.fills.push((position, [1.0; 4]));
+	}
+
+	fn fill(&mut self, position: Vec2, _size: Vec2, colour: [f32; 4]) {
+		self.fills.push((position, colour));
+	}
+}
@@ -0,0 +1,79 @@
+// Tests for holding the cursor inside the window while it's captured.
+
+use pong::input::Input;
+use pong::cursor::{CursorGrab, GrabMode, Platform, REFERENCE_HEIGHT};
+
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 600.0;
+
+// How far the virtual cursor goes for each point the mouse moves, on a court this size.
+const TRAVEL: f32 = HEIGHT / REFERENCE_HEIGHT;
+
+#[test]
+fn every_platform_falls_back_to_hiding_the_cursor() {
+	for platform in [Platform::MacOs, Platform::Wayland, Platform::Other] {
+		let modes = GrabMode::fallbacks(platform);
+		assert_eq!(modes.last(), Some(&GrabMode::Free));
+	}
+
+	// Wayland doesn't let programs move the cursor, so it can't be recentered.
+	assert!(!GrabMode::fallbacks(Platform::Wayland).contains(&GrabMode::Recentering));
+	assert_eq!(GrabMode::fallbacks(Platform::MacOs)[0], GrabMode::Locked);
+}
+
+#[test]
+fn confined_cursor_moves_as_usual() {
+	let mut cursor = CursorGrab::new();
+	cursor.mode = Some(GrabMode::Confined);
+
+	assert_eq!(cursor.cursor_moved(10.0, 20.0, WIDTH, HEIGHT), Some(Input::MouseMoved { x: 10.0, y: 20.0 }));
+	assert_eq!(cursor.mouse_motion(5.0, 5.0, WIDTH, HEIGHT), None);
+	assert!(!cursor.recentering());
+}
+
+#[test]
+fn mouse_travel_is_the_same_on_any_screen() {
+	// On a standard screen, with the court at its reference size, moving the mouse 96 pixels goes an eighth of the way down.
+	let mut cursor = CursorGrab::new();
+	cursor.mode = Some(GrabMode::Locked);
+	assert_eq!(cursor.mouse_motion(0.0, 96.0, 1024.0, REFERENCE_HEIGHT), Some(Input::MouseMoved { x: 0.0, y: REFERENCE_HEIGHT / 8.0 }));
+
+	// The same movement is twice as many pixels on a Retina display, and a smaller window has a shorter court, but it still goes an eighth of the way.
+	let mut cursor = CursorGrab::new();
+	cursor.scale_factor = 2.0;
+	cursor.mode = Some(GrabMode::Locked);
+	assert_eq!(cursor.mouse_motion(0.0, 192.0, 512.0, 384.0), Some(Input::MouseMoved { x: 0.0, y: 384.0 / 8.0 }));
+
+	// Recentering measures the cursor in logical points already, so only the court's size counts.
+	let mut cursor = CursorGrab::new();
+	cursor.mode = Some(GrabMode::Recentering);
+	assert_eq!(cursor.cursor_moved(256.0, 192.0 + 96.0, 512.0, 384.0), Some(Input::MouseMoved { x: 0.0, y: 384.0 / 8.0 }));
+}
+
+#[test]
+fn locked_cursor_follows_the_mouse() {
+	let mut cursor = CursorGrab::new();
+	cursor.cursor_moved(100.0, 300.0, WIDTH, HEIGHT);
+	cursor.mode = Some(GrabMode::Locked);
+
+	// The real cursor doesn't move, so only the mouse's own movement counts, starting from where the cursor was.
+	assert_eq!(cursor.cursor_moved(400.0, 300.0, WIDTH, HEIGHT), None);
+	assert_eq!(cursor.mouse_motion(0.0, -50.0, WIDTH, HEIGHT), Some(Input::MouseMoved { x: 100.0, y: 300.0 - 50.0 * TRAVEL }));
+
+	// It can't leave the window.
+	assert_eq!(cursor.mouse_motion(0.0, 1000.0, WIDTH, HEIGHT), Some(Input::MouseMoved { x: 100.0, y: HEIGHT }));
+}
+
+#[test]
+fn recentered_cursor_moves_by_how_far_it_went() {
+	let mut cursor = CursorGrab::new();
+	cursor.cursor_moved(100.0, 300.0, WIDTH, HEIGHT);
+	cursor.mode = Some(GrabMode::Recentering);
+	assert!(cursor.recentering());
+
+	assert_eq!(cursor.cursor_moved(WIDTH / 2.0 + 20.0, HEIGHT / 2.0 + 30.0, WIDTH, HEIGHT), Some(Input::MouseMoved { x: 100.0 + 20.0 * TRAVEL, y: 300.0 + 30.0 * TRAVEL }));
+
+	// Coming back to the middle isn't a movement.
+	assert_eq!(cursor.cursor_moved(WIDTH / 2.0, HEIGHT / 2.0, WIDTH, HEIGHT), None);
+	assert_eq!(cursor.position.y, 300.0 + 30.0 * TRAVEL);
+}
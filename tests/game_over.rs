@@ -0,0 +1,122 @@
+// Tests for the game over screen shown when a match ends.
+
+mod common;
+
+use pong::events::{Side, GameEvent};
+use pong::input::{Input, Key};
+use pong::modes::{Classic, Training, GameMode};
+use pong::stats::MatchStats;
+use pong::scenes::{GameOverScene, GAME_OVER_SHADE};
+
+use common::Harness;
+
+// Play a match to a single point, with the player's paddle left out of the ball's way, stopping on the results.
+fn lost_match(harness: &mut Harness) {
+	harness.ctx.sim.with(|game| game.win_score = 1);
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+	harness.run_until(120 * 60, |event| matches!(event, GameEvent::MatchEnded { .. })).unwrap();
+	harness.frame();
+}
+
+#[test]
+fn results_stop_the_game() {
+	let mut harness = Harness::new(&Classic, 1);
+	lost_match(&mut harness);
+
+	let ticks = harness.game().ticks;
+	harness.run(120);
+	assert!(harness.game().paused);
+	assert!(!harness.ctx.cursor_grabbed);
+	assert_eq!(harness.game().ticks, ticks);
+	assert_eq!(harness.shades.iter().map(|(_, _, opacity)| *opacity).collect::<Vec<_>>(), vec![GAME_OVER_SHADE]);
+}
+
+#[test]
+fn rematch_starts_again() {
+	let mut harness = Harness::new(&Classic, 1);
+	lost_match(&mut harness);
+
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(60);
+
+	assert!(!harness.game().paused);
+	assert!(harness.ctx.cursor_grabbed);
+	assert_eq!(harness.game().scores, [0, 0]);
+	assert_eq!(harness.game().mode.name(), Classic.name());
+	assert!(harness.shades.is_empty());
+}
+
+#[test]
+fn change_mode_plays_the_other_mode() {
+	let mut harness = Harness::new(&Classic, 1);
+	lost_match(&mut harness);
+
+	// The next mode along is offered first, and going back past the one just played skips it.
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Right));
+	harness.input(Input::KeyPressed(Key::Left));
+	harness.input(Input::KeyPressed(Key::Left));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(60);
+
+	assert!(!harness.game().paused);
+	assert_eq!(harness.game().mode.name(), Training.name());
+	assert_eq!(harness.game().ai_accuracy, harness.ctx.config.gameplay.ai_difficulty.ai_accuracy());
+}
+
+#[test]
+fn back_to_menu_leaves_the_match() {
+	let mut harness = Harness::on_menu();
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+	lost_match(&mut harness);
+
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(60);
+
+	assert!(harness.game().paused);
+	assert!(harness.shades.is_empty());
+
+	// The main menu is on top again.
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn results_are_told_from_the_players_side() {
+	let harness = Harness::new(&Classic, 1);
+	let stats = MatchStats { hits: [4, 6], aces: [1, 0], longest_rally: 5, ..MatchStats::default() };
+
+	let won = GameOverScene::new(&harness.ctx, Side::Right, [3, 11], stats.clone());
+	assert_eq!(won.heading(&harness.ctx), "You win!");
+	assert_eq!(won.stats_lines(&harness.ctx), vec!["Hits: 4 - 6", "Aces: 1 - 0", "Longest rally: 5, average 0.0", "Top ball speed: 0 px/s"]);
+	assert_eq!(won.lines(&harness.ctx), vec!["> Rematch <", "Change mode: survival", "Back to menu"]);
+
+	let lost = GameOverScene::new(&harness.ctx, Side::Left, [11, 3], stats);
+	assert_eq!(lost.heading(&harness.ctx), "You lose");
+}
+
+#[test]
+fn timeline_shows_who_was_ahead() {
+	let harness = Harness::new(&Classic, 1);
+	let mut stats = MatchStats::default();
+	for side in [Side::Left, Side::Left, Side::Right, Side::Right, Side::Right] {
+		stats.point_scored(side);
+	}
+	stats.max_ball_speed = 0.8125;
+
+	let scene = GameOverScene::new(&harness.ctx, Side::Right, [2, 3], stats);
+	assert!(scene.stats_lines(&harness.ctx).contains(&"Top ball speed: 813 px/s".to_string()));
+
+	// The tied point has no bar, the left side's leads go up from the middle and the right side's go down.
+	let bars = scene.timeline_bars(&harness.ctx);
+	assert_eq!(bars.len(), 4);
+	let middle = bars[0].0.y + bars[0].1.y;
+	assert_eq!(bars[1].1.y, bars[0].1.y * 2.0);
+	assert_eq!(bars[1].0.y + bars[1].1.y, middle);
+	assert_eq!(bars[3].0.y, middle);
+	assert_eq!(bars[3].1.y, bars[0].1.y);
+	assert!(bars.windows(2).all(|pair| pair[0].0.x < pair[1].0.x));
+}
@@ -0,0 +1,79 @@
+// Tests for playing and getting around the menus with a gamepad.
+
+mod common;
+
+use pong::input::{Input, Key};
+use pong::gamepad::{Gamepad, Button};
+
+use common::{Harness, HEIGHT};
+
+// Get the game keys among a batch of inputs.
+fn keys(inputs: &[Input]) -> Vec<Key> {
+	inputs.iter().filter_map(|input| match input {
+		Input::KeyPressed(key) => Some(*key),
+		_ => None
+	}).collect()
+}
+
+#[test]
+fn buttons_stand_for_keys() {
+	let mut gamepad = Gamepad::new();
+	assert_eq!(gamepad.press(Button::South), Some(Input::KeyPressed(Key::Enter)));
+	assert_eq!(gamepad.press(Button::East), Some(Input::KeyPressed(Key::Escape)));
+	assert_eq!(gamepad.press(Button::DPadLeft), Some(Input::KeyPressed(Key::Left)));
+	assert_eq!(gamepad.press(Button::North), None);
+}
+
+#[test]
+fn stick_moves_through_menus_a_step_at_a_time() {
+	let mut gamepad = Gamepad::new();
+
+	assert_eq!(keys(&gamepad.stick(0.0, 0.8)), vec![Key::Down]);
+	// Holding the stick, or easing off a little, doesn't move again.
+	assert_eq!(keys(&gamepad.stick(0.1, 1.0)), vec![]);
+	assert_eq!(keys(&gamepad.stick(0.0, 0.4)), vec![]);
+	assert_eq!(keys(&gamepad.stick(0.0, 0.8)), vec![]);
+
+	// Letting it come back towards the middle allows another step, and a diagonal push goes the main way.
+	assert_eq!(keys(&gamepad.stick(0.0, 0.1)), vec![]);
+	assert_eq!(keys(&gamepad.stick(-0.9, 0.5)), vec![Key::Left]);
+}
+
+#[test]
+fn menus_can_be_played_without_a_mouse() {
+	let mut harness = Harness::on_menu();
+	let mut gamepad = Gamepad::new();
+
+	// Wrap around from the top of the menu to quit, then back down to play.
+	for input in gamepad.stick(0.0, -1.0).into_iter().chain(gamepad.stick(0.0, 0.0)).chain(gamepad.stick(0.0, 1.0)) {
+		harness.input(input);
+	}
+	harness.input(gamepad.press(Button::South).unwrap());
+	harness.run(1);
+	assert!(!harness.game().paused);
+
+	// The stick steers the paddle.
+	for input in gamepad.stick(0.0, -1.0) {
+		harness.input(input);
+	}
+	harness.run(120);
+	let paddle = &harness.game().objects[harness.game().control_id.unwrap()];
+	assert!(paddle.position.y < HEIGHT / 4.0);
+
+	harness.input(gamepad.press(Button::Start).unwrap());
+	harness.run(1);
+	assert!(harness.game().paused);
+}
+
+#[cfg(feature = "gamepad")]
+#[test]
+fn controller_buttons_keep_their_place() {
+	use pong::controllers::button;
+
+	assert_eq!(button(gilrs::Button::South), Some(Button::South));
+	assert_eq!(button(gilrs::Button::DPadLeft), Some(Button::DPadLeft));
+	assert_eq!(button(gilrs::Button::Select), Some(Button::Select));
+
+	// Triggers and shoulder buttons don't do anything.
+	assert_eq!(button(gilrs::Button::LeftTrigger), None);
+}
@@ -0,0 +1,336 @@
+// End-to-end gameplay tests, driven through the scenes by scripted inputs.
+
+mod common;
+
+use pong::structs::{Vec2, ObjectType};
+use pong::events::{Side, GameEvent};
+use pong::input::{Input, Key};
+use pong::game::{Difficulty, Command};
+use pong::modes::{Classic, Survival};
+use pong::scenes::{HighScoresScene, PlayingScene, MATCH_POINT_FLASH};
+use pong::toast::TOAST_TIME;
+use pong::text;
+
+use common::{Harness, WIDTH, HEIGHT};
+
+#[test]
+fn draws_every_object() {
+	let harness = Harness::new(&Classic, 1);
+
+	// The objects are drawn first, with the score over them.
+	assert!(harness.rects.len() > harness.game().objects.len());
+	for ((_, size), obj) in harness.rects.iter().zip(&harness.game().objects) {
+		assert_eq!(*size, obj.size);
+	}
+}
+
+#[test]
+fn paddle_follows_mouse() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	harness.input(Input::MouseMoved { x: 0.0, y: 200.0 });
+	harness.frame();
+
+	let paddle = &harness.game().objects[2];
+	assert_eq!(paddle.get_center().y, 200.0);
+
+	// The paddle can't leave the court.
+	harness.input(Input::MouseMoved { x: 0.0, y: -500.0 });
+	harness.frame();
+	assert_eq!(harness.game().objects[2].position.y, 0.0);
+
+	harness.input(Input::MouseMoved { x: 0.0, y: HEIGHT + 500.0 });
+	harness.frame();
+	let paddle = &harness.game().objects[2];
+	assert_eq!(paddle.position.y + paddle.size.y, HEIGHT);
+}
+
+#[test]
+fn ball_moves_while_playing() {
+	let mut harness = Harness::new(&Classic, 1);
+	let start = harness.game().objects[0].position;
+
+	harness.run(30);
+
+	assert!(!harness.game().paused);
+	assert_ne!(harness.game().objects[0].position, start);
+}
+
+#[test]
+fn escape_pauses_and_resumes() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(60);
+	assert!(harness.game().paused);
+
+	let paused_at = harness.game().objects[0].position;
+	harness.run(60);
+	assert_eq!(harness.game().objects[0].position, paused_at);
+
+	// The paddle ignores the mouse while paused.
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+	harness.frame();
+	assert_ne!(harness.game().objects[2].position.y, 0.0);
+
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(60);
+	assert!(!harness.game().paused);
+	assert_ne!(harness.game().objects[0].position, paused_at);
+}
+
+#[test]
+fn losing_focus_pauses() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	harness.input(Input::Focused(false));
+	harness.frame();
+	assert!(harness.game().paused);
+	assert!(!harness.ctx.cursor_grabbed);
+
+	harness.input(Input::Focused(true));
+	harness.frame();
+	assert!(!harness.game().paused);
+	assert!(harness.ctx.cursor_grabbed);
+}
+
+#[test]
+fn match_can_carry_on_in_the_background() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.config.window.pause_in_background = false;
+
+	harness.input(Input::Focused(false));
+	harness.frame();
+	assert!(!harness.game().paused);
+	assert!(!harness.ctx.focused);
+}
+
+#[test]
+fn player_missing_the_ball_concedes() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	// Leave the player's paddle in the top corner, out of the ball's way.
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+
+	let event = harness.run_until(120 * 60, |event| matches!(event, GameEvent::PointScored { .. }));
+	assert_eq!(event, Some(GameEvent::PointScored { side: Side::Left, scores: [1, 0] }));
+	assert_eq!(harness.game().scores, [1, 0]);
+
+	// The ball is served again from the middle of the court.
+	let ball = &harness.game().objects[0];
+	assert_eq!(ball.obj_type, ObjectType::Ball);
+	assert!((ball.get_center().x - harness.ctx.width / 2.0).abs() < 10.0);
+}
+
+#[test]
+fn match_ends_at_win_score() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+
+	let event = harness.run_until(120 * 60 * 10, |event| matches!(event, GameEvent::MatchEnded { .. }));
+	let Some(GameEvent::MatchEnded { scores, .. }) = event else {
+		panic!("match didn't end, events: {:?}", harness.events);
+	};
+	assert!(scores.contains(&harness.game().win_score));
+
+	// Every point in the match was announced before it ended.
+	let points = harness.events.iter().filter(|event| matches!(event, GameEvent::PointScored { .. })).count() as u32;
+	assert_eq!(points, scores[0] + scores[1]);
+
+	// A new match starts straight away.
+	assert_eq!(harness.game().scores, [0, 0]);
+}
+
+#[test]
+fn survival_ends_on_first_point() {
+	let mut harness = Harness::new(&Survival, 1);
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+
+	let event = harness.run_until(120 * 60, |event| matches!(event, GameEvent::MatchEnded { .. }));
+	assert!(matches!(event, Some(GameEvent::MatchEnded { winner: Side::Left, .. })));
+}
+
+#[test]
+fn same_seed_and_inputs_play_the_same() {
+	let play = || {
+		let mut harness = Harness::new(&Classic, 42);
+		for y in [100.0, 450.0, 300.0, 50.0] {
+			harness.input(Input::MouseMoved { x: 0.0, y });
+			harness.run(240);
+		}
+		(harness.game().objects.clone(), harness.game().scores, harness.events)
+	};
+
+	assert_eq!(play(), play());
+}
+
+#[test]
+fn reloaded_config_applies_live() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut config = harness.ctx.config.clone();
+	config.controls.sensitivity = 2.0;
+	config.gameplay.ai_difficulty = Difficulty::Hard;
+	config.window.width = 1920;
+	harness.ctx.apply_config(config);
+
+	assert_eq!(harness.ctx.events, vec![GameEvent::ConfigChanged]);
+	assert_eq!(harness.ctx.config.controls.sensitivity, 2.0);
+	// The window can't be resized until the next launch.
+	assert_eq!(harness.ctx.config.window.width, 1024);
+
+	// The new sensitivity moves the paddle twice as far from the middle.
+	harness.input(Input::MouseMoved { x: 0.0, y: HEIGHT / 2.0 - 50.0 });
+	harness.frame();
+	assert_eq!(harness.game().objects[2].get_center().y, HEIGHT / 2.0 - 100.0);
+	assert_eq!(harness.game().ai_accuracy, Difficulty::Hard.ai_accuracy());
+
+	// Reloading the same settings again changes nothing.
+	let config = harness.ctx.config.clone();
+	harness.ctx.apply_config(config);
+	assert!(harness.ctx.events.is_empty());
+}
+
+#[test]
+fn high_scores_screen_pauses_and_closes() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.high_scores.longest_rally = 12;
+	let rects = harness.rects.len();
+
+	harness.input(Input::KeyPressed(Key::Scores));
+	harness.run(60);
+	assert!(harness.game().paused);
+	assert!(HighScoresScene::lines(&harness.ctx).contains(&"Longest rally: 12".to_string()));
+
+	// The scores are drawn over the court.
+	assert!(harness.rects.len() > rects);
+
+	harness.input(Input::KeyPressed(Key::Scores));
+	harness.run(1);
+	assert!(!harness.game().paused);
+	assert_eq!(harness.rects.len(), rects);
+}
+
+#[test]
+fn settings_changed_in_game_are_kept_for_saving() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	// Pretend the difficulty was overridden on the command line.
+	harness.ctx.config.gameplay.ai_difficulty = Difficulty::Hard;
+	assert!(!harness.ctx.config_changed);
+
+	harness.ctx.change_config(|config| config.controls.sensitivity = 1.5);
+
+	assert!(harness.ctx.config_changed);
+	assert_eq!(harness.ctx.events, vec![GameEvent::ConfigChanged]);
+	assert_eq!(harness.ctx.config.controls.sensitivity, 1.5);
+	assert_eq!(harness.ctx.saved_config.controls.sensitivity, 1.5);
+
+	// The override applies to this run only, so it isn't saved.
+	assert_eq!(harness.ctx.config.gameplay.ai_difficulty, Difficulty::Hard);
+	assert_eq!(harness.ctx.saved_config.gameplay.ai_difficulty, Difficulty::Normal);
+}
+
+#[test]
+fn toast_shows_over_the_game_then_goes() {
+	let mut harness = Harness::new(&Classic, 1);
+	let rects = harness.rects.len();
+
+	harness.ctx.show_toast("Screenshot saved");
+	harness.frame();
+	assert!(harness.rects.len() > rects);
+
+	harness.ctx.update_toasts(TOAST_TIME - 1.0);
+	assert!(!harness.ctx.toasts.is_empty());
+	harness.ctx.update_toasts(1.0);
+	assert!(harness.ctx.toasts.is_empty());
+
+	harness.frame();
+	assert_eq!(harness.rects.len(), rects);
+}
+
+#[test]
+fn score_is_drawn_either_side_of_the_middle() {
+	let mut harness = Harness::new(&Classic, 1);
+	let objects = harness.game().objects.len();
+	let scale = PlayingScene::score_scale(&harness.ctx);
+
+	harness.ctx.sim.with(|game| game.scores = [7, 9]);
+	harness.frame();
+
+	let score = harness.rects[objects..].to_vec();
+	assert!(score.iter().all(|(position, size)| position.y + size.y <= scale * 7.0));
+	assert!(score.iter().any(|(position, _)| position.x < WIDTH / 2.0));
+	assert!(score.iter().any(|(position, _)| position.x > WIDTH / 2.0));
+
+	// A different score draws differently.
+	harness.ctx.sim.with(|game| game.scores = [1, 1]);
+	harness.frame();
+	assert_ne!(harness.rects[objects..], score);
+}
+
+#[test]
+fn scoreboard_marks_the_server() {
+	let mut harness = Harness::new(&Classic, 1);
+	let scale = PlayingScene::score_scale(&harness.ctx);
+	let (width, gap) = (text::width("0", scale), scale * 4.0);
+	let dot = |x: f32| (Vec2::new(x, scale * 4.0), Vec2::new(scale, scale));
+	let left = dot(WIDTH / 2.0 - gap - width - scale * 2.0);
+	let right = dot(WIDTH / 2.0 + gap + width + scale);
+
+	// The dot sits just outside the server's score.
+	assert!(harness.rects.contains(&left));
+	assert!(!harness.rects.contains(&right));
+
+	harness.ctx.sim.with(|game| game.server = Side::Right);
+	harness.frame();
+	assert!(!harness.rects.contains(&left));
+	assert!(harness.rects.contains(&right));
+}
+
+#[test]
+fn scoreboard_flashes_at_match_point() {
+	let mut harness = Harness::new(&Classic, 1);
+	let count = |harness: &mut Harness, elapsed: f32| {
+		harness.ctx.sim.with(|game| game.elapsed = elapsed);
+		harness.frame();
+		harness.rects.len()
+	};
+
+	let normal = count(&mut harness, MATCH_POINT_FLASH / 2.0 + 1.0);
+	harness.ctx.sim.with(|game| game.scores = [0, 10]);
+	let shown = count(&mut harness, 0.0);
+	let hidden = count(&mut harness, MATCH_POINT_FLASH / 2.0 + 1.0);
+
+	// Flashed off, the right side's score and the label disappear. Flashed on, the label's drawn too.
+	assert!(hidden < normal);
+	assert!(shown > normal);
+}
+
+#[test]
+fn ball_waits_for_the_serve_countdown() {
+	let mut harness = Harness::new(&Classic, 1);
+	let objects = harness.game().objects.len();
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+
+	harness.ctx.change_config(|config| config.gameplay.serve_countdown = 2);
+	harness.ctx.sim.apply(Command::NewMatch);
+	harness.run(1);
+	let start = harness.game().objects[0].position;
+
+	// The countdown is drawn under the score while the ball waits in the middle.
+	harness.run(120);
+	assert_eq!(harness.game().objects[0].position, start);
+	assert!(harness.game().serve_timer > 0.0);
+	let countdown: Vec<_> = harness.rects[objects..].iter().filter(|(position, _)| position.y > HEIGHT * 0.25).collect();
+	assert!(!countdown.is_empty());
+
+	harness.run(120);
+	assert_ne!(harness.game().objects[0].position, start);
+	assert_eq!(harness.game().serve_timer, 0.0);
+	assert!(harness.rects[objects..].iter().all(|(position, _)| position.y < HEIGHT * 0.25));
+
+	// After a point, the ball waits again.
+	harness.run_until(120 * 60, |event| matches!(event, GameEvent::PointScored { .. })).unwrap();
+	assert!(harness.game().serve_timer > 1000.0);
+}
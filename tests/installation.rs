@@ -0,0 +1,100 @@
+// Tests for driving the paddle from knobs and faders over OSC and MIDI.
+
+mod common;
+
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+use pong::config::InstallationConfig;
+use pong::installation::{parse_osc, MidiParser, Installation};
+use pong::input::Input;
+use pong::modes::Classic;
+
+use common::{Harness, HEIGHT};
+
+// Pad an OSC string with zero bytes to a multiple of 4 bytes.
+fn osc_string(text: &str) -> Vec<u8> {
+	let mut bytes = text.as_bytes().to_vec();
+	bytes.resize((bytes.len() / 4 + 1) * 4, 0);
+	bytes
+}
+
+// Build an OSC message sending a single float to an address.
+fn osc_float(address: &str, value: f32) -> Vec<u8> {
+	[osc_string(address), osc_string(",f"), value.to_be_bytes().to_vec()].concat()
+}
+
+#[test]
+fn osc_messages_are_read_by_address() {
+	assert_eq!(parse_osc(&osc_float("/pong/paddle", 0.25), "/pong/paddle"), Some(0.25));
+	assert_eq!(parse_osc(&osc_float("/other", 0.25), "/pong/paddle"), None);
+
+	// Integers and out of range values work too, clamped to the range.
+	let message = [osc_string("/pong/paddle"), osc_string(",i"), 3i32.to_be_bytes().to_vec()].concat();
+	assert_eq!(parse_osc(&message, "/pong/paddle"), Some(1.0));
+
+	// Cut off messages are ignored rather than read past the end.
+	let message = osc_float("/pong/paddle", 0.5);
+	assert_eq!(parse_osc(&message[..message.len() - 2], "/pong/paddle"), None);
+}
+
+#[test]
+fn osc_bundles_use_the_last_value() {
+	let mut bundle = [osc_string("#bundle"), vec![0, 0, 0, 0, 0, 0, 0, 1]].concat();
+	for message in [osc_float("/pong/paddle", 0.1), osc_float("/other", 0.9), osc_float("/pong/paddle", 0.7)] {
+		bundle.extend((message.len() as u32).to_be_bytes());
+		bundle.extend(message);
+	}
+	assert_eq!(parse_osc(&bundle, "/pong/paddle"), Some(0.7));
+}
+
+#[test]
+fn midi_control_changes_are_picked_out() {
+	let mut parser = MidiParser::new();
+	let mut feed = |bytes: &[u8]| bytes.iter().filter_map(|byte| parser.feed(*byte)).collect::<Vec<_>>();
+
+	// A control change on channel 3, then another without its status byte, with a timing clock in the middle.
+	assert_eq!(feed(&[0xB2, 1, 64, 1, 0xF8, 100]), vec![(2, 1, 64), (2, 1, 100)]);
+
+	// Notes are skipped, along with anything after them until the next control change.
+	assert_eq!(feed(&[0x90, 60, 127, 61, 127, 0xB0, 7, 0]), vec![(0, 7, 0)]);
+}
+
+#[test]
+fn osc_messages_move_the_paddle() {
+	let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+	let port = socket.local_addr().unwrap().port();
+	drop(socket);
+
+	let config = InstallationConfig { osc_port: port, ..InstallationConfig::default() };
+	let installation = Installation::start(&config).unwrap();
+	assert_eq!(installation.poll(), None);
+
+	// A fader pushed most of the way up puts the paddle near the top.
+	let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+	sender.send_to(&osc_float(&config.osc_address, 0.9), ("127.0.0.1", port)).unwrap();
+
+	let start = Instant::now();
+	let input = loop {
+		if let Some(input) = installation.poll() {
+			break input;
+		}
+		assert!(start.elapsed() < Duration::from_secs(5), "no OSC message arrived");
+		std::thread::sleep(Duration::from_millis(5));
+	};
+	let Input::PaddleMoved { y } = input else {
+		panic!("expected the paddle to move, got {:?}", input);
+	};
+	assert!((y - 0.1).abs() < 0.001);
+
+	let mut harness = Harness::new(&Classic, 1);
+	harness.input(input);
+	harness.run(120);
+	let paddle = &harness.game().objects[harness.game().control_id.unwrap()];
+	assert!(paddle.position.y < HEIGHT / 4.0);
+}
+
+#[test]
+fn nothing_is_started_unless_configured() {
+	assert!(Installation::start(&InstallationConfig::default()).is_none());
+}
@@ -0,0 +1,70 @@
+// Tests for showing the game's text in different languages.
+
+mod common;
+
+use std::fs;
+
+use pong::assets::Assets;
+use pong::locale::{Locale, DEFAULT_LANGUAGE};
+use pong::modes::Classic;
+use pong::scenes::{HighScoresScene, ProfileScene};
+
+use common::Harness;
+
+const PIRATE: &str = "
+[profiles]
+title = \"Who be sailin'?\"
+
+[high-scores]
+longest-rally = \"Longest volley: {hits} hits\"
+";
+
+#[test]
+fn strings_are_looked_up_by_key() {
+	let locale = Locale::default();
+	assert_eq!(locale.language(), DEFAULT_LANGUAGE);
+	assert_eq!(locale.get("profiles.guest"), "Guest");
+	assert_eq!(locale.format("window.title-score", &[("left", &3), ("right", &7)]), "Pong - 3 : 7");
+
+	// A key nobody has written yet shows up as itself.
+	assert_eq!(locale.get("menu.missing"), "menu.missing");
+}
+
+#[test]
+fn missing_strings_fall_back_to_the_default_language() {
+	let locale = Locale::from_text("pirate", PIRATE).unwrap();
+	assert_eq!(locale.language(), "pirate");
+	assert_eq!(locale.get("profiles.title"), "Who be sailin'?");
+	assert_eq!(locale.get("profiles.guest"), "Guest");
+	assert_eq!(locale.format("high-scores.longest-rally", &[("hits", &12)]), "Longest volley: 12 hits");
+
+	assert!(Locale::from_text("broken", "title = ").is_err());
+}
+
+#[test]
+fn languages_load_from_the_assets_overrides() {
+	let dir = std::env::temp_dir().join(format!("pong-locale-test-{}", std::process::id()));
+	fs::create_dir_all(dir.join("locales")).unwrap();
+	fs::write(dir.join("locales").join("pirate.toml"), PIRATE).unwrap();
+
+	let mut assets = Assets::new(Some(dir.clone()));
+	let locale = Locale::load(&mut assets, "pirate");
+	assert_eq!(locale, Locale::from_text("pirate", PIRATE).unwrap());
+
+	// A language that doesn't exist falls back to the default one.
+	let locale = Locale::load(&mut assets, "klingon");
+	assert_eq!(locale, Locale::default());
+
+	fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn scenes_show_the_chosen_language() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.locale = Locale::from_text("pirate", PIRATE).unwrap();
+	harness.ctx.high_scores.longest_rally = 4;
+
+	let scene = ProfileScene::new(&harness.ctx);
+	assert_eq!(scene.lines(&harness.ctx), vec!["Who be sailin'?", "", "> Guest <", "New profile"]);
+	assert!(HighScoresScene::lines(&harness.ctx).contains(&"Longest volley: 4 hits".to_string()));
+}
@@ -0,0 +1,176 @@
+// Tests for the main menu and the pause menu.
+
+mod common;
+
+use pong::input::{Input, Key};
+use pong::modes::Classic;
+use pong::scenes::{MainMenuScene, MenuEntry, PauseEntry, PAUSE_SHADE};
+
+use common::Harness;
+
+// Find the middle of the area an entry can be pointed at.
+fn entry_y(harness: &Harness, entry: MenuEntry) -> f32 {
+	let index = MenuEntry::ALL.iter().position(|other| *other == entry).unwrap();
+	let bounds = MainMenuScene::widgets(&harness.ctx)[index].bounds;
+	bounds.position.y + bounds.size.y / 2.0
+}
+
+#[test]
+fn menu_waits_for_the_player() {
+	let mut harness = Harness::on_menu();
+	harness.run(120);
+
+	assert!(harness.game().paused);
+	assert!(!harness.ctx.cursor_grabbed);
+	assert_eq!(harness.game().elapsed, 0.0);
+	assert!(!harness.rects.is_empty());
+}
+
+#[test]
+fn play_starts_a_new_match() {
+	let mut harness = Harness::on_menu();
+	harness.ctx.sim.with(|game| game.scores = [4, 2]);
+
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+
+	assert_eq!(harness.game().scores, [0, 0]);
+	assert!(!harness.game().paused);
+	assert!(harness.ctx.cursor_grabbed);
+}
+
+#[test]
+fn quit_empties_the_scene_stack() {
+	let mut harness = Harness::on_menu();
+
+	// Wrap around from the top to the last entry.
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	assert!(harness.scenes.is_empty());
+}
+
+#[test]
+fn mouse_picks_the_entry_it_points_at() {
+	let mut harness = Harness::on_menu();
+
+	// Clicking away from the entries does nothing.
+	harness.input(Input::MouseMoved { x: 10.0, y: 0.0 });
+	harness.input(Input::MousePressed);
+	assert!(!harness.scenes.is_empty());
+
+	let y = entry_y(&harness, MenuEntry::Quit);
+	harness.input(Input::MouseMoved { x: 10.0, y });
+	harness.input(Input::MousePressed);
+	assert!(harness.scenes.is_empty());
+}
+
+// Start a match from the main menu, then pause it.
+fn paused_from_menu() -> Harness {
+	let mut harness = Harness::on_menu();
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(120);
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.frame();
+	harness
+}
+
+// Highlight a pause menu entry with the keyboard and choose it.
+fn choose_pause_entry(harness: &mut Harness, entry: PauseEntry) {
+	let index = PauseEntry::ALL.iter().position(|other| *other == entry).unwrap();
+	for _ in 0..index {
+		harness.input(Input::KeyPressed(Key::Down));
+	}
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+}
+
+#[test]
+fn pause_menu_darkens_the_playfield() {
+	let harness = paused_from_menu();
+
+	assert!(harness.game().paused);
+	assert_eq!(harness.shades.len(), 1);
+	let (position, size, opacity) = harness.shades[0];
+	assert_eq!((position.x, position.y), (0.0, 0.0));
+	assert_eq!((size.x, size.y), (harness.ctx.width, harness.ctx.height));
+	assert_eq!(opacity, PAUSE_SHADE);
+}
+
+#[test]
+fn pause_menu_resumes() {
+	let mut harness = paused_from_menu();
+	let elapsed = harness.game().elapsed;
+
+	choose_pause_entry(&mut harness, PauseEntry::Resume);
+
+	assert!(!harness.game().paused);
+	assert!(harness.shades.is_empty());
+	assert!(harness.game().elapsed > elapsed);
+}
+
+#[test]
+fn pause_menu_restarts_the_match() {
+	let mut harness = paused_from_menu();
+	harness.ctx.sim.with(|game| game.scores = [3, 1]);
+
+	choose_pause_entry(&mut harness, PauseEntry::Restart);
+
+	assert!(!harness.game().paused);
+	assert_eq!(harness.game().scores, [0, 0]);
+}
+
+#[test]
+fn pause_menu_quits_to_the_main_menu() {
+	let mut harness = paused_from_menu();
+
+	choose_pause_entry(&mut harness, PauseEntry::QuitToMenu);
+	harness.run(60);
+
+	assert!(harness.game().paused);
+	assert!(harness.shades.is_empty());
+	assert!(!harness.ctx.cursor_grabbed);
+
+	// The menu is back on top, so choosing Play starts another match.
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn menu_is_hidden_under_the_match() {
+	let mut harness = Harness::on_menu();
+
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+
+	assert_eq!(harness.rects.len(), Harness::new(&Classic, 1).rects.len());
+}
+
+#[test]
+fn settings_open_from_both_menus() {
+	let mut harness = Harness::on_menu();
+	let menu_rects = harness.rects.len();
+
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+	assert_ne!(harness.rects.len(), menu_rects);
+
+	// Escape leaves the settings, back to the menu as it was.
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.frame();
+	assert_eq!(harness.rects.len(), menu_rects);
+
+	let mut harness = paused_from_menu();
+	choose_pause_entry(&mut harness, PauseEntry::Settings);
+	assert!(harness.game().paused);
+	assert!(harness.shades.is_empty());
+
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.frame();
+	assert_eq!(harness.shades.len(), 1);
+}
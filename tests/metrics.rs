@@ -0,0 +1,101 @@
+// Tests for the metrics a headless server reports to its operators.
+
+use std::io::{Read, Write, Cursor};
+use std::net::{TcpStream, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use pong::events::{Side, GameEvent};
+use pong::game::GameState;
+use pong::metrics::{Metrics, MetricsServer};
+use pong::http::{read_request, MAX_LINE_LENGTH, MAX_HEADERS};
+use pong::stats::MatchStats;
+
+fn point() -> GameEvent {
+	GameEvent::PointScored { side: Side::Left, scores: [1, 0] }
+}
+
+fn match_ended() -> GameEvent {
+	GameEvent::MatchEnded { winner: Side::Left, scores: [11, 0], stats: MatchStats::default() }
+}
+
+// Request a path from the server, returning the whole response.
+fn get(server: &MetricsServer, path: &str) -> String {
+	let mut stream = TcpStream::connect(server.addr()).unwrap();
+	write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	response
+}
+
+#[test]
+fn ticks_and_events_are_counted() {
+	let mut game = GameState::new();
+	game.control_id = None;
+
+	let mut metrics = Metrics::new();
+	let start = Instant::now();
+	metrics.record_tick(&game, start, Duration::from_micros(20), &[point()]);
+	metrics.record_tick(&game, start, Duration::from_millis(5), &[point(), match_ended()]);
+
+	assert_eq!((metrics.ticks, metrics.points, metrics.matches, metrics.players), (2, 2, 1, 0));
+
+	// The slow tick only lands in the overflow bucket.
+	let text = metrics.render();
+	assert!(text.contains("pong_matches_total 1\n"));
+	assert!(text.contains("# TYPE pong_tick_duration_seconds histogram\n"));
+	assert!(text.contains("pong_tick_duration_seconds_bucket{le=\"0.000025\"} 1\n"));
+	assert!(text.contains("pong_tick_duration_seconds_bucket{le=\"0.0025\"} 1\n"));
+	assert!(text.contains("pong_tick_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+	assert!(text.contains("pong_tick_duration_seconds_count 2\n"));
+}
+
+#[test]
+fn metrics_are_served_over_http() {
+	let metrics = Arc::new(Mutex::new(Metrics::new()));
+	let server = MetricsServer::spawn(Ipv4Addr::LOCALHOST.into(), 0, Arc::clone(&metrics)).unwrap();
+
+	metrics.lock().unwrap().record_tick(&GameState::new(), Instant::now(), Duration::ZERO, &[match_ended()]);
+
+	let response = get(&server, "/metrics");
+	assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+	assert!(response.ends_with(&metrics.lock().unwrap().render()));
+
+	assert!(get(&server, "/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[test]
+fn tick_rate_is_measured() {
+	let game = GameState::new();
+	let mut metrics = Metrics::new();
+	let start = Instant::now();
+
+	// Nothing's measured until a whole second has gone by.
+	for tick in 0..100 {
+		metrics.record_tick(&game, start + Duration::from_secs(tick) / 100, Duration::ZERO, &[]);
+	}
+	assert_eq!(metrics.tick_rate, 0.0);
+
+	metrics.record_tick(&game, start + Duration::from_secs(1), Duration::ZERO, &[]);
+	assert_eq!(metrics.tick_rate, 100.0);
+	assert!(metrics.render().contains("pong_tick_rate_hertz 100\n"));
+}
+
+#[test]
+fn requests_are_read_up_to_a_limit() {
+	let read = |text: String| read_request(&mut Cursor::new(text)).unwrap();
+
+	let request = read("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n".to_string()).unwrap();
+	assert_eq!((request.method.as_str(), request.path.as_str()), ("GET", "/metrics"));
+	assert_eq!(request.header("host"), Some("localhost"));
+
+	// Lines and headers too big to be real are turned away.
+	let long = read(format!("GET /metrics HTTP/1.1\r\nCookie: {}\r\n\r\n", "a".repeat(MAX_LINE_LENGTH)));
+	assert_eq!(long.unwrap_err().status, "431 Request Header Fields Too Large");
+
+	let many = read(format!("GET /metrics HTTP/1.1\r\n{}\r\n", "Accept: */*\r\n".repeat(MAX_HEADERS + 1)));
+	assert_eq!(many.unwrap_err().status, "431 Request Header Fields Too Large");
+
+	assert_eq!(read("Hello\r\n\r\n".to_string()).unwrap_err().status, "400 Bad Request");
+}
@@ -0,0 +1,176 @@
+// Tests for the screen for entering a player's name.
+
+mod common;
+
+use pong::events::{Side, GameEvent};
+use pong::input::{Input, Key};
+use pong::modes::Classic;
+use pong::stats::MatchStats;
+use pong::scores::Record;
+use pong::scenes::{NameEntryScene, NameFor, NameKey, ProfileScene, GameOverScene, HighScoresScene, MAX_NAME_LENGTH};
+
+use common::Harness;
+
+// Open the name entry screen over the gameplay scene.
+fn entering(purpose: NameFor) -> Harness {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.scenes.push(&mut harness.ctx, Box::new(NameEntryScene::new(purpose)));
+	harness.frame();
+	harness
+}
+
+// Type on the keyboard, the same way the window sends key presses.
+fn type_keys(harness: &mut Harness, keys: &[&'static str]) {
+	for key in keys {
+		harness.input(Input::KeyboardKey(key));
+	}
+}
+
+#[test]
+fn typed_names_are_given_to_new_records() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.high_scores.longest_rally = 9;
+	harness.ctx.high_scores.unnamed = vec![Record::LongestRally];
+
+	// A guest who set a record is asked for their name when the match ends.
+	let scene = GameOverScene::new(&harness.ctx, Side::Left, [11, 4], MatchStats::default());
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.frame();
+	assert!(harness.game().paused);
+
+	type_keys(&mut harness, &["A", "N", "Key2", "Back", "A"]);
+	harness.input(Input::KeyboardKey("Return"));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+
+	let scores = &harness.ctx.high_scores;
+	assert_eq!(scores.longest_rally_by.as_deref(), Some("ANA"));
+	assert!(scores.unnamed.is_empty());
+	assert!(HighScoresScene::lines(&harness.ctx).contains(&"Longest rally: 9 by ANA".to_string()));
+
+	// Back on the results, which don't ask again.
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn on_screen_keyboard_picks_characters() {
+	let mut harness = entering(NameFor::Records);
+	harness.ctx.high_scores.unnamed = vec![Record::BestSurvival];
+
+	// Finishing without a name does nothing.
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Left));
+	harness.input(Input::KeyPressed(Key::Enter));
+	assert_eq!(harness.ctx.high_scores.unnamed.len(), 1);
+
+	// Wrap around to the top row, keeping to the same column, and pick C then D.
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.input(Input::KeyPressed(Key::Right));
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	// Delete the D, then finish.
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Left));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.input(Input::KeyPressed(Key::Right));
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	assert_eq!(harness.ctx.high_scores.best_survival_by.as_deref(), Some("C"));
+}
+
+#[test]
+fn names_are_kept_short() {
+	let mut harness = entering(NameFor::Records);
+	harness.ctx.high_scores.unnamed = vec![Record::LongestRally];
+
+	for _ in 0..MAX_NAME_LENGTH + 5 {
+		type_keys(&mut harness, &["Z"]);
+	}
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	assert_eq!(harness.ctx.high_scores.longest_rally_by.unwrap().len(), MAX_NAME_LENGTH);
+}
+
+#[test]
+fn keys_can_be_clicked() {
+	let mut harness = entering(NameFor::Records);
+	harness.ctx.high_scores.unnamed = vec![Record::LongestRally];
+
+	let keys = NameKey::rows().concat();
+	let click = |harness: &mut Harness, key: NameKey| {
+		let index = keys.iter().position(|other| *other == key).unwrap();
+		let bounds = NameEntryScene::widgets(&harness.ctx)[index].bounds;
+		harness.input(Input::MouseMoved { x: bounds.position.x + 1.0, y: bounds.position.y + 1.0 });
+		harness.input(Input::MousePressed);
+		harness.input(Input::MouseReleased);
+	};
+
+	click(&mut harness, NameKey::Character('Q'));
+	click(&mut harness, NameKey::Character('7'));
+	click(&mut harness, NameKey::Done);
+
+	assert_eq!(harness.ctx.high_scores.longest_rally_by.as_deref(), Some("Q7"));
+}
+
+#[test]
+fn declining_leaves_records_unnamed() {
+	let mut harness = entering(NameFor::Records);
+	harness.ctx.high_scores.unnamed = vec![Record::LongestRally];
+
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(1);
+
+	assert!(harness.ctx.high_scores.unnamed.is_empty());
+	assert_eq!(harness.ctx.high_scores.longest_rally_by, None);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn new_profiles_are_named_and_played_as() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.profiles.select_or_create("Ana", &harness.ctx.saved_config.clone());
+
+	// The new profile entry comes after the guest.
+	let mut scene = ProfileScene::new(&harness.ctx);
+	scene.menu.selected = 2;
+	assert_eq!(scene.lines(&harness.ctx).last().unwrap(), "> New profile <");
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	// Going back returns to the list of profiles, where the new profile entry is the last one.
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.input(Input::KeyPressed(Key::Up));
+	harness.input(Input::KeyPressed(Key::Enter));
+
+	type_keys(&mut harness, &["B", "E", "N"]);
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+
+	assert_eq!(harness.ctx.profiles.profiles.len(), 2);
+	assert_eq!(harness.ctx.profiles.active.as_deref(), Some("BEN"));
+	assert!(!harness.game().paused);
+
+	// The results name the player.
+	let scene = GameOverScene::new(&harness.ctx, Side::Left, [11, 4], MatchStats::default());
+	assert_eq!(scene.heading(&harness.ctx), "BEN loses");
+}
+
+#[test]
+fn profiles_skip_the_name_entry() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.profiles.select_or_create("Ana", &harness.ctx.saved_config.clone());
+	harness.ctx.high_scores.unnamed = vec![Record::LongestRally];
+
+	harness.ctx.sim.with(|game| game.win_score = 1);
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+	harness.run_until(120 * 60, |event| matches!(event, GameEvent::MatchEnded { .. })).unwrap();
+	harness.frame();
+
+	// Still on the results, so enter plays a rematch.
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.run(1);
+	assert!(!harness.game().paused);
+}
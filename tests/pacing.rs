@@ -0,0 +1,41 @@
+// Tests for the timing of frames when vsync is off.
+
+use std::time::{Duration, Instant};
+
+use pong::pacing::{FramePacer, FRAME_RATE, SPIN_TIME};
+
+const FRAME: Duration = Duration::from_nanos(1_000_000_000 / FRAME_RATE as u64);
+
+#[test]
+fn late_frames_dont_drift() {
+	let start = Instant::now();
+	let mut pacer = FramePacer::new(start);
+
+	// Each frame is drawn a little late, but the next is still due on the grid.
+	for frame in 1..=10 {
+		pacer.frame_drawn(pacer.deadline() + Duration::from_millis(3));
+		assert_eq!(pacer.deadline(), start + FRAME * frame);
+	}
+}
+
+#[test]
+fn missed_frames_are_skipped() {
+	let start = Instant::now();
+	let mut pacer = FramePacer::new(start);
+
+	// Drawn two and a half frames late, the next frame is due at the next point on the grid, not straight away.
+	pacer.frame_drawn(start + FRAME * 5 / 2);
+	assert!(pacer.deadline() > start + FRAME * 5 / 2);
+	assert!(pacer.deadline() <= start + FRAME * 3);
+}
+
+#[test]
+fn wakes_ahead_of_the_frame() {
+	let start = Instant::now();
+	let mut pacer = FramePacer::new(start);
+	assert_eq!(pacer.wake_time(start), None);
+
+	pacer.frame_drawn(start);
+	assert_eq!(pacer.wake_time(start), Some(pacer.deadline() - SPIN_TIME));
+	assert_eq!(pacer.wake_time(pacer.deadline() - SPIN_TIME / 2), None);
+}
@@ -0,0 +1,29 @@
+// Tests for where the game keeps its files.
+
+use pong::paths::{self, APP_DIR};
+use pong::config::Config;
+use pong::scores::HighScores;
+use pong::replay::Replay;
+#[cfg(feature = "scripting")]
+use pong::scripting::Scripts;
+
+#[test]
+fn every_directory_is_the_games_own() {
+	for dir in [paths::config_dir(), paths::data_dir()].into_iter().flatten() {
+		assert_eq!(dir.file_name().unwrap(), APP_DIR);
+	}
+}
+
+#[test]
+fn files_go_in_the_right_directory() {
+	if let (Some(config), Some(dir)) = (Config::path(), paths::config_dir()) {
+		assert_eq!(config, dir.join("config.toml"));
+	}
+
+	if let Some(dir) = paths::data_dir() {
+		assert_eq!(HighScores::path().unwrap().parent(), Some(dir.as_path()));
+		assert_eq!(Replay::dir().unwrap().parent(), Some(dir.as_path()));
+		#[cfg(feature = "scripting")]
+		assert_eq!(Scripts::dir().unwrap().parent(), Some(dir.as_path()));
+	}
+}
@@ -0,0 +1,668 @@
+// Tests for how the simulation moves and bounces the ball.
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::events::{Side, GameEvent};
+use pong::game::{GameState, Rules, TICK_TIME, MAX_FRAME_TIME, SUBSTEP_FRACTION, BROADPHASE_MIN_OBJECTS};
+use pong::collision::Layers;
+use pong::modes::{Classic, Survival};
+
+// Build a classic match in play on an 800x600 court.
+// The right paddle is left to the player so it stays where the test puts it.
+fn playing_game() -> GameState {
+	let mut game = GameState::with_mode(&Classic);
+	game.resize(800.0, 600.0);
+	game.paused = false;
+	game
+}
+
+// Place the ball with its center level with the right paddle's center, the given distance in front of it.
+fn ball_before_right_paddle(game: &mut GameState, gap: f32, velocity: Vec2) {
+	let paddle = game.objects[2].get_collider();
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(paddle.min.x - gap - ball.size.x, paddle.center.y - ball.size.y / 2.0 - 10.0);
+	ball.velocity = velocity;
+}
+
+#[test]
+fn fast_ball_doesnt_tunnel_through_paddle() {
+	let mut game = playing_game();
+	let paddle = game.objects[2].get_collider();
+
+	// Fast enough to cover the whole court in a single tick.
+	ball_before_right_paddle(&mut game, 50.0, Vec2::new(1000.0 / TICK_TIME, 0.0));
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.x < 0.0);
+	assert!(ball.position.x + ball.size.x <= paddle.min.x);
+	assert!(game.events.drain().any(|event| matches!(event, GameEvent::BallHitPaddle { side: Side::Right, .. })));
+}
+
+#[test]
+fn ball_bounces_from_point_of_contact() {
+	let mut game = playing_game();
+	// Leave spin out of it.
+	game.rules.spin_friction = 0.0;
+	let paddle = game.objects[2].get_collider();
+	let max_speed = game.objects[0].max_velocity.x;
+
+	// Reaches the paddle halfway through the tick.
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(20.0 / TICK_TIME, 0.0));
+	game.step(1);
+
+	// The rest of the tick is spent moving away at the bounced speed.
+	let ball = &game.objects[0];
+	let expected = paddle.min.x - ball.size.x - max_speed * TICK_TIME * 0.5;
+	assert!((ball.position.x - expected).abs() < 0.01, "ball at {}, expected {}", ball.position.x, expected);
+	assert_eq!(ball.velocity.x, -max_speed);
+}
+
+#[test]
+fn ball_glances_off_top_of_paddle() {
+	let mut game = playing_game();
+	// Leave spin out of it.
+	game.rules.spin_friction = 0.0;
+	let paddle = game.objects[2].get_collider();
+
+	// Drop the ball onto the top of the paddle.
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(paddle.min.x, paddle.min.y - ball.size.y - 2.0);
+	ball.velocity = Vec2::new(0.1, 1.0);
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert_eq!(ball.velocity, Vec2::new(0.1, -1.0));
+	assert!(ball.position.y + ball.size.y <= paddle.min.y);
+}
+
+#[test]
+fn slow_ball_stops_short_of_paddle() {
+	let mut game = playing_game();
+
+	ball_before_right_paddle(&mut game, 50.0, Vec2::new(1.0, 0.0));
+	let start = game.objects[0].position;
+	game.step(1);
+
+	// Not close enough to reach the paddle yet.
+	assert_eq!(game.objects[0].position, start + Vec2::new(TICK_TIME, 0.0));
+	assert!(game.events.is_empty());
+}
+
+#[test]
+fn ball_inside_paddle_is_pushed_out_and_bounced() {
+	let mut game = playing_game();
+	let paddle = game.objects[2].get_collider();
+
+	// The paddle has moved into the ball while it was heading towards it.
+	ball_before_right_paddle(&mut game, -8.0, Vec2::new(1.0, 0.0));
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.x < 0.0);
+	assert!(ball.position.x + ball.size.x <= paddle.min.x);
+	assert!(game.events.drain().any(|event| matches!(event, GameEvent::BallHitPaddle { side: Side::Right, .. })));
+}
+
+#[test]
+fn ball_leaving_paddle_isnt_bounced_back_in() {
+	let mut game = playing_game();
+	let paddle = game.objects[2].get_collider();
+
+	// Still overlapping the paddle, but already on its way back across the court.
+	ball_before_right_paddle(&mut game, -8.0, Vec2::new(-1.0, 0.0));
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert_eq!(ball.velocity, Vec2::new(-1.0, 0.0));
+	assert!(ball.position.x + ball.size.x <= paddle.min.x);
+	assert!(game.events.is_empty());
+}
+
+#[test]
+fn ball_never_stays_inside_paddle() {
+	let mut game = playing_game();
+
+	// Bury the ball in the paddle, then keep moving the paddle onto it for a second.
+	ball_before_right_paddle(&mut game, -12.0, Vec2::new(0.5, 0.0));
+	for _ in 0..120 {
+		let ball = game.objects[0].get_collider();
+		let paddle = &mut game.objects[2];
+		paddle.position.y = ball.center.y - paddle.size.y / 2.0;
+
+		game.step(1);
+
+		let ball = game.objects[0].get_collider();
+		assert_eq!(ball.contact(&game.objects[2].get_collider()), None);
+	}
+}
+
+#[test]
+fn ai_moves_the_same_at_any_delta_time() {
+	// Run the AI for the same amount of time, split into different numbers of updates.
+	let track = |updates: u32| {
+		let mut game = playing_game();
+		game.objects[1].position.y = 0.0;
+
+		// Keep the ball heading away from the AI paddle, so it makes for the middle of the court.
+		game.objects[0].velocity = Vec2::new(0.1, 0.0);
+
+		for _ in 0..updates {
+			game.update(TICK_TIME * 12.0 / updates as f32);
+		}
+		game.objects[1].position.y
+	};
+
+	let (once, few, many) = (track(1), track(3), track(12));
+	assert!(once > 0.0);
+	assert!((once - few).abs() < 0.01, "{} vs {}", once, few);
+	assert!((once - many).abs() < 0.01, "{} vs {}", once, many);
+}
+
+#[test]
+fn long_stall_only_simulates_max_frame_time() {
+	let mut game = playing_game();
+	game.advance(60_000.0);
+
+	assert!(game.elapsed <= MAX_FRAME_TIME);
+	assert!(game.elapsed > MAX_FRAME_TIME - TICK_TIME);
+	assert!(game.accumulator < TICK_TIME);
+}
+
+#[test]
+fn negative_delta_time_is_ignored() {
+	let mut game = playing_game();
+	let start = game.objects[0].position;
+	game.advance(-1000.0);
+
+	assert_eq!(game.elapsed, 0.0);
+	assert_eq!(game.accumulator, 0.0);
+	assert_eq!(game.objects[0].position, start);
+}
+
+#[test]
+fn time_before_a_pause_doesnt_carry_over() {
+	let mut game = playing_game();
+	game.advance(TICK_TIME * 0.9);
+
+	game.paused = true;
+	game.advance(5000.0);
+	game.paused = false;
+
+	// The partial tick from before the pause was dropped, so this isn't enough for a full tick.
+	game.advance(TICK_TIME * 0.5);
+	assert_eq!(game.elapsed, 0.0);
+}
+
+#[test]
+fn fast_ball_bounces_off_walls_close_to_the_edge() {
+	let mut game = playing_game();
+	let step_distance = game.objects[1].size.x * SUBSTEP_FRACTION;
+
+	// Bounce straight up and down between the walls, covering many steps' worth of distance every tick.
+	let ball = &mut game.objects[0];
+	ball.velocity = Vec2::new(0.0, -step_distance * 5.0 / TICK_TIME);
+
+	for _ in 0..120 {
+		game.step(1);
+
+		// The ball is checked against the walls every step, so it can't get more than a step past them.
+		let ball = &game.objects[0];
+		assert!(ball.position.y > -step_distance, "ball went {} past the top wall", -ball.position.y);
+		assert!(ball.position.y + ball.size.y < game.height + step_distance, "ball went {} past the bottom wall", ball.position.y + ball.size.y - game.height);
+	}
+}
+
+#[test]
+fn moving_paddle_passes_momentum_to_ball() {
+	// Hit the ball dead center on the second tick, with the paddle still and then with the paddle moving down.
+	let bounce = |paddle_speed: f32| {
+		let mut game = playing_game();
+		// Leave spin out of it.
+		game.rules.spin_friction = 0.0;
+		ball_before_right_paddle(&mut game, 10.0, Vec2::new(1.0, 0.0));
+		game.objects[0].position.y += 10.0;
+
+		// Move the paddle between ticks the way the player does, keeping the ball level with its center.
+		game.step(1);
+		game.objects[2].position.y += paddle_speed * TICK_TIME;
+		game.objects[0].position.y += paddle_speed * TICK_TIME;
+		game.step(1);
+
+		assert!(game.objects[0].velocity.x < 0.0);
+		game.objects[0].velocity
+	};
+
+	let still = bounce(0.0);
+	let moving = bounce(0.5);
+
+	assert_eq!(still.y, 0.0);
+	let expected = 0.5 * Rules::default().paddle_momentum;
+	assert!((moving.y - expected).abs() < 0.001, "ball picked up {} from the paddle, expected {}", moving.y, expected);
+}
+
+#[test]
+fn paddle_momentum_is_capped() {
+	let mut game = playing_game();
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(1.0, 0.0));
+	game.objects[0].position.y += 10.0;
+
+	// Yank the paddle a long way in a single tick.
+	game.step(1);
+	game.objects[2].position.y -= 200.0;
+	game.objects[0].position.y -= 200.0;
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert_eq!(ball.velocity.y, -ball.max_velocity.y);
+}
+
+#[test]
+fn moving_paddle_puts_spin_on_ball_which_curves_it() {
+	let mut game = playing_game();
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(1.0, 0.0));
+	game.objects[0].position.y += 10.0;
+
+	// Sweep the paddle down across the ball as it hits.
+	game.step(1);
+	game.objects[2].position.y += 0.5 * TICK_TIME;
+	game.objects[0].position.y += 0.5 * TICK_TIME;
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.x < 0.0);
+	assert!(ball.spin != 0.0);
+
+	// In flight, the spin bends the ball's path while it slowly wears off.
+	let (velocity, spin) = (ball.velocity, ball.spin);
+	game.step(30);
+
+	let ball = &game.objects[0];
+	let heading = |v: Vec2| v.y.atan2(v.x);
+	assert!((heading(ball.velocity) - heading(velocity)).abs() > 0.001);
+	assert!(ball.spin.abs() < spin.abs());
+	assert_eq!(ball.spin.signum(), spin.signum());
+}
+
+#[test]
+fn spin_is_cleared_on_serve() {
+	let mut game = playing_game();
+	game.objects[0].spin = 1.0;
+	game.reset_objects();
+
+	assert_eq!(game.objects[0].spin, 0.0);
+}
+
+#[test]
+fn wall_grip_never_slows_or_steepens_ball() {
+	let mut game = playing_game();
+	let start = Vec2::new(0.35, 0.6);
+	game.objects[0].velocity = start;
+
+	let speed = |v: Vec2| (v.x * v.x + v.y * v.y).sqrt();
+	let steepness = |v: Vec2| (v.y / v.x).atan().abs();
+
+	// Bounce off the walls for a while, without reaching either paddle.
+	let mut bounces = 0;
+	for _ in 0..90 {
+		game.step(1);
+		bounces += game.events.drain().filter(|event| matches!(event, GameEvent::BallHitWall { .. })).count();
+
+		let ball = &game.objects[0];
+		assert!((speed(ball.velocity) - speed(start)).abs() < 0.0001);
+		assert!(steepness(ball.velocity) <= game.rules.max_bounce_angle + 0.0001);
+	}
+
+	assert!(bounces > 0);
+	assert!(game.objects[0].spin != 0.0);
+}
+
+#[test]
+fn ball_hitting_paddle_corner_bounces_back() {
+	let mut game = playing_game();
+	game.rules.spin_friction = 0.0;
+	let paddle = game.objects[2].get_collider();
+
+	// Head diagonally for the top-left corner of the paddle, reaching it partway through the tick.
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(paddle.min.x - ball.size.x - 4.0, paddle.min.y - ball.size.y - 4.0);
+	ball.velocity = Vec2::new(1.0, 1.0);
+	game.step(1);
+
+	assert_eq!(game.objects[0].velocity, Vec2::new(-1.0, -1.0));
+	assert!(game.events.drain().any(|event| matches!(event, GameEvent::BallHitPaddle { side: Side::Right, .. })));
+}
+
+#[test]
+fn ball_clipping_top_of_paddle_bounces_up() {
+	let mut game = playing_game();
+	game.rules.spin_friction = 0.0;
+	let paddle = game.objects[2].get_collider();
+
+	// Coming in from the side, but low enough to land on the paddle's top face just past the corner.
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(paddle.min.x - ball.size.x + 3.0, paddle.min.y - ball.size.y - 4.0);
+	ball.velocity = Vec2::new(1.0, 1.0);
+	game.step(1);
+
+	// Keeps going the same way across the court, but deflected upwards.
+	assert_eq!(game.objects[0].velocity, Vec2::new(1.0, -1.0));
+}
+
+#[test]
+fn ball_leaving_wall_isnt_bounced_back_in() {
+	let mut game = playing_game();
+
+	// Overlapping the top wall, but already heading back down.
+	let ball = &mut game.objects[0];
+	ball.position.y = -2.0;
+	ball.velocity = Vec2::new(0.5, 0.2);
+	game.step(1);
+
+	assert!(game.objects[0].velocity.y > 0.0);
+	assert!(!game.events.drain().any(|event| matches!(event, GameEvent::BallHitWall { .. })));
+}
+
+#[test]
+fn wall_bounce_is_capped() {
+	let mut game = playing_game();
+
+	// Heading into the bottom wall faster than the ball is allowed to go.
+	let ball = &mut game.objects[0];
+	let max = ball.max_velocity;
+	ball.position.y = game.height - ball.size.y - 1.0;
+	ball.velocity = Vec2::new(0.5, max.y * 3.0);
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.y < 0.0);
+	assert!(ball.velocity.y.abs() <= max.y);
+	assert!(ball.velocity.x.abs() <= max.x);
+}
+
+#[test]
+fn spin_never_curves_ball_past_its_speed_cap() {
+	let mut game = playing_game();
+
+	// Already at the vertical cap, with plenty of spin to keep turning it further.
+	let ball = &mut game.objects[0];
+	ball.position.y = game.height / 2.0;
+	ball.velocity = Vec2::new(ball.max_velocity.x * 0.5, ball.max_velocity.y);
+	ball.spin = 50.0;
+
+	for _ in 0..10 {
+		game.step(1);
+
+		let ball = &game.objects[0];
+		assert!(ball.velocity.x.abs() <= ball.max_velocity.x);
+		assert!(ball.velocity.y.abs() <= ball.max_velocity.y);
+	}
+}
+
+#[test]
+fn paddle_catching_up_with_ball_knocks_it_away() {
+	let mut game = playing_game();
+	game.rules.spin_friction = 0.0;
+
+	// Drifting slowly down just below the paddle, which then moves down into it much faster.
+	let paddle = game.objects[2].get_collider();
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(paddle.min.x, paddle.max.y + 1.0);
+	ball.velocity = Vec2::new(0.0, 0.1);
+	game.step(1);
+	game.objects[2].position.y += 1.0 * TICK_TIME;
+	game.step(1);
+
+	// The ball comes off faster than the paddle was going, so it's left behind.
+	assert!(game.objects[0].velocity.y > 1.0);
+	game.step(1);
+	assert!(!game.objects[0].get_collider().is_colliding(&game.objects[2].get_collider()));
+}
+
+#[test]
+fn wall_restitution_changes_bounce_speed() {
+	let mut game = playing_game();
+	game.rules.wall_restitution = 0.5;
+	game.rules.spin_friction = 0.0;
+
+	// Heading into the bottom wall.
+	let ball = &mut game.objects[0];
+	ball.position.y = game.height - ball.size.y - 1.0;
+	ball.velocity = Vec2::new(0.4, 0.6);
+	game.step(2);
+
+	assert_eq!(game.objects[0].velocity.y, -0.3);
+}
+
+#[test]
+fn wall_restitution_keeps_ball_moving_across() {
+	let mut game = playing_game();
+	game.rules.wall_restitution = 0.5;
+	game.rules.spin_friction = 0.0;
+
+	// Heading into the top wall.
+	let ball = &mut game.objects[0];
+	ball.position.y = 1.0;
+	ball.velocity = Vec2::new(0.4, -0.6);
+	game.step(2);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.y > 0.0);
+	assert_eq!(ball.velocity.x, 0.4);
+}
+
+#[test]
+fn paddle_restitution_comes_from_the_rules() {
+	let mut game = playing_game();
+	game.set_rules(Rules { paddle_restitution: 0.8, spin_friction: 0.0, ..game.rules });
+	assert!(game.objects[1..].iter().all(|paddle| paddle.restitution == 0.8));
+
+	// A dead center hit comes straight back slower than it went in.
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(1.0, 0.0));
+	game.objects[0].position.y += 10.0;
+	game.step(2);
+
+	assert_eq!(game.objects[0].velocity, Vec2::new(-0.8, 0.0));
+}
+
+#[test]
+fn hit_events_say_where_the_ball_hit() {
+	let mut game = playing_game();
+	let paddle = game.objects[2].get_collider();
+
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(2.0, 0.0));
+	let ball_center = game.objects[0].get_center();
+	game.step(1);
+
+	// Touched the front face of the paddle, level with the ball's center.
+	let point = game.events.drain().find_map(|event| match event {
+		GameEvent::BallHitPaddle { point, .. } => Some(point),
+		_ => None
+	});
+	assert_eq!(point, Some(Vec2::new(paddle.min.x, ball_center.y)));
+
+	// Send the ball into the top wall.
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(400.0, -1.0);
+	ball.velocity = Vec2::new(0.0, -0.5);
+	let ball_center = ball.get_center();
+	game.step(1);
+
+	let point = game.events.drain().find_map(|event| match event {
+		GameEvent::BallHitWall { point } => Some(point),
+		_ => None
+	});
+	assert_eq!(point, Some(Vec2::new(ball_center.x, 0.0)));
+}
+
+#[test]
+fn crowded_court_still_bounces_balls_off_paddles() {
+	let mut game = playing_game();
+
+	// Enough balls for the court to be split into a grid, kept well away from the paddles.
+	for i in 0..BROADPHASE_MIN_OBJECTS {
+		let mut ball = Object::new(ObjectType::Ball).set_size(10.0, 10.0);
+		ball.position = Vec2::new(300.0 + (i % 4) as f32 * 40.0, 100.0 + (i / 4) as f32 * 40.0);
+		game.objects.push(ball);
+	}
+
+	// The first ball is still heading for the right paddle.
+	let paddle = game.objects[2].get_collider();
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(2.0, 0.0));
+	game.step(1);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.x < 0.0);
+	assert!(ball.position.x + ball.size.x <= paddle.min.x);
+	assert!(game.events.drain().any(|event| matches!(event, GameEvent::BallHitPaddle { side: Side::Right, .. })));
+}
+
+#[test]
+fn ball_passes_through_paddle_it_doesnt_collide_with() {
+	let mut game = playing_game();
+
+	// A ghost ball that ignores paddles.
+	ball_before_right_paddle(&mut game, 10.0, Vec2::new(1.0, 0.0));
+	game.objects[0].mask = Layers::ALL & !Layers::PADDLE;
+	game.step(5);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.x > 0.0);
+	assert!(!game.events.drain().any(|event| matches!(event, GameEvent::BallHitPaddle { .. })));
+}
+
+#[test]
+fn gravity_and_wind_accelerate_ball() {
+	let mut game = playing_game();
+	game.rules.gravity = Vec2::new(0.0, 0.0005);
+	game.rules.wind = Vec2::new(-0.0002, 0.0);
+	game.objects[0].velocity = Vec2::new(0.5, 0.0);
+
+	// Stay clear of the walls and paddles.
+	game.step(10);
+
+	let elapsed = 10.0 * TICK_TIME;
+	let ball = &game.objects[0];
+	assert!((ball.velocity.x - (0.5 - 0.0002 * elapsed)).abs() < 0.0001, "ball slowed to {}", ball.velocity.x);
+	assert!((ball.velocity.y - 0.0005 * elapsed).abs() < 0.0001, "ball fell at {}", ball.velocity.y);
+}
+
+#[test]
+fn gravity_never_speeds_ball_past_its_cap() {
+	let mut game = playing_game();
+	game.rules.gravity = Vec2::new(0.0, 1.0);
+	game.step(30);
+
+	let ball = &game.objects[0];
+	assert!(ball.velocity.y.abs() <= ball.max_velocity.y);
+}
+
+// Add a second ball to the game at the given position and velocity, returning its index.
+fn add_ball(game: &mut GameState, position: Vec2, velocity: Vec2) -> usize {
+	let mut ball = Object::new(ObjectType::Ball).set_size(25.0, 25.0);
+	ball.max_velocity = game.objects[0].max_velocity;
+	ball.position = position;
+	ball.velocity = velocity;
+	game.objects.push(ball);
+	game.objects.len() - 1
+}
+
+#[test]
+fn balls_of_equal_mass_swap_velocities_head_on() {
+	let mut game = playing_game();
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(300.0, 300.0);
+	ball.velocity = Vec2::new(0.5, 0.0);
+	let other = add_ball(&mut game, Vec2::new(335.0, 300.0), Vec2::new(-0.3, 0.0));
+
+	game.step(3);
+
+	assert!((game.objects[0].velocity.x + 0.3).abs() < 0.0001, "first ball at {:?}", game.objects[0].velocity);
+	assert!((game.objects[other].velocity.x - 0.5).abs() < 0.0001, "second ball at {:?}", game.objects[other].velocity);
+}
+
+#[test]
+fn heavy_ball_barely_notices_light_one() {
+	let mut game = playing_game();
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(300.0, 300.0);
+	ball.velocity = Vec2::new(0.5, 0.0);
+	ball.mass = 100.0;
+	let other = add_ball(&mut game, Vec2::new(335.0, 300.0), Vec2::new(0.0, 0.0));
+
+	game.step(3);
+
+	// The heavy ball keeps going, knocking the light one ahead of it at close to twice its speed.
+	let (heavy, light) = (game.objects[0].velocity.x, game.objects[other].velocity.x);
+	assert!(heavy > 0.48 && heavy < 0.5, "heavy ball slowed to {}", heavy);
+	assert!(light > 0.95, "light ball only reached {}", light);
+}
+
+#[test]
+fn overlapping_balls_share_the_push_apart() {
+	let mut game = playing_game();
+	let ball = &mut game.objects[0];
+	ball.position = Vec2::new(300.0, 300.0);
+	ball.velocity = Vec2::new(0.0, 0.0);
+	let other = add_ball(&mut game, Vec2::new(315.0, 300.0), Vec2::new(0.0, 0.0));
+
+	game.step(1);
+
+	// They overlapped by 10 pixels, so each moves 5 out of the way.
+	assert_eq!(game.objects[0].position.x, 295.0);
+	assert_eq!(game.objects[other].position.x, 320.0);
+}
+
+#[test]
+fn paddles_are_never_pushed_by_the_ball() {
+	let mut game = playing_game();
+	let paddle = game.objects[2].position;
+
+	ball_before_right_paddle(&mut game, -12.0, Vec2::new(0.5, 0.0));
+	game.objects[0].mass = 1000.0;
+	game.step(5);
+
+	assert_eq!(game.objects[2].position, paddle);
+	assert!(game.objects[0].velocity.x < 0.0);
+}
+
+#[test]
+fn whoever_concedes_serves_next() {
+	let mut game = playing_game();
+	assert_eq!(game.server, Side::Left);
+	assert!(game.objects[0].velocity.x > 0.0);
+
+	// Out past the right paddle, so the left side scores and the right side serves back towards it.
+	game.objects[0].position.x = 850.0;
+	game.step(1);
+	assert_eq!(game.scores, [1, 0]);
+	assert_eq!(game.server, Side::Right);
+	assert!(game.objects[0].velocity.x < 0.0);
+
+	game.objects[0].position.x = -50.0;
+	game.step(1);
+	assert_eq!(game.server, Side::Left);
+	assert!(game.objects[0].velocity.x > 0.0);
+
+	// A new match starts with the left side serving again.
+	game.objects[0].position.x = 850.0;
+	game.step(1);
+	game.reset_match();
+	assert_eq!(game.server, Side::Left);
+}
+
+#[test]
+fn match_point_is_a_point_from_winning() {
+	let mut game = playing_game();
+	game.win_score = 5;
+	assert!(game.match_point().is_empty());
+
+	game.scores = [4, 2];
+	assert_eq!(game.match_point(), vec![Side::Left]);
+	game.scores = [4, 4];
+	assert_eq!(game.match_point(), vec![Side::Left, Side::Right]);
+
+	// Survival is always one point from ending, so it's never called out.
+	let mut survival = GameState::with_mode(&Survival);
+	survival.resize(800.0, 600.0);
+	assert!(survival.match_point().is_empty());
+}
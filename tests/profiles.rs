@@ -0,0 +1,133 @@
+// Tests for local player profiles.
+
+mod common;
+
+use std::fs;
+
+use pong::config::Config;
+use pong::events::Side;
+use pong::input::{Input, Key};
+use pong::modes::Classic;
+use pong::profiles::{Profiles, Profile};
+use pong::stats::MatchStats;
+use pong::scenes::ProfileScene;
+
+use common::{Harness, ended};
+
+fn two_profiles() -> Profiles {
+	let mut config = Config::default();
+	let mut profiles = Profiles::default();
+	profiles.select_or_create("Ana", &config);
+
+	config.controls.sensitivity = 2.5;
+	profiles.select_or_create("Ben", &config);
+	profiles.profiles[1].keybinds.insert("up".to_string(), "W".to_string());
+	profiles
+}
+
+#[test]
+fn selecting_creates_missing_profiles() {
+	let mut profiles = two_profiles();
+	assert_eq!(profiles.profiles.len(), 2);
+	assert_eq!(profiles.active().unwrap().name, "Ben");
+
+	// Selecting an existing profile doesn't make another.
+	profiles.select_or_create("Ana", &Config::default());
+	assert_eq!(profiles.profiles.len(), 2);
+	assert_eq!(profiles.active().unwrap().name, "Ana");
+}
+
+#[test]
+fn profile_preferences_apply_over_settings() {
+	let profiles = two_profiles();
+	let mut config = Config::default();
+	profiles.active().unwrap().apply(&mut config);
+
+	assert_eq!(config.controls.sensitivity, 2.5);
+	assert_eq!(config.keybinds["up"], "W");
+	// Keybinds the profile doesn't change are left alone.
+	assert_eq!(config.keybinds["down"], "Down");
+}
+
+#[test]
+fn finished_matches_add_to_the_active_profile() {
+	let mut profiles = two_profiles();
+	let stats = MatchStats { hits: [7, 12], aces: [1, 3], longest_rally: 9, max_ball_speed: 1.5, ..MatchStats::default() };
+
+	assert!(profiles.record(Some(Side::Right), &[ended(Side::Right, [11, 5], &stats), ended(Side::Left, [11, 5], &stats)]));
+
+	let ben = profiles.active().unwrap().stats;
+	assert_eq!((ben.matches_played, ben.matches_won), (2, 1));
+	assert_eq!(ben.hits, 24);
+	assert_eq!(ben.aces, 6);
+	assert_eq!(ben.longest_rally, 9);
+	assert_eq!(profiles.profiles[0].stats.matches_played, 0);
+
+	// Without a player or a profile, nothing is counted.
+	assert!(!profiles.record(None, &[ended(Side::Right, [11, 5], &stats)]));
+	profiles.active = None;
+	assert!(!profiles.record(Some(Side::Right), &[ended(Side::Right, [11, 5], &stats)]));
+}
+
+#[test]
+fn saved_profiles_load_back() {
+	let dir = std::env::temp_dir().join(format!("pong-profiles-test-{}", std::process::id()));
+	let path = dir.join("profiles.toml");
+
+	let mut profiles = two_profiles();
+	profiles.record(Some(Side::Left), &[ended(Side::Left, [11, 5], &MatchStats::default())]);
+	profiles.save_to(&path).unwrap();
+
+	assert_eq!(Profiles::load_from(&path), profiles);
+	fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn choosing_a_profile_applies_it() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.profiles = two_profiles();
+	harness.ctx.profiles.active = None;
+
+	// With nobody active, the guest is highlighted.
+	let scene = ProfileScene::new(&harness.ctx);
+	assert_eq!(scene.menu.selected, 2);
+	assert!(scene.lines(&harness.ctx).contains(&"> Guest <".to_string()));
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.frame();
+	assert!(harness.game().paused);
+
+	// Move past the new profile entry and wrap around to the first profile, then move down to the second.
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Down));
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+
+	assert_eq!(harness.ctx.profiles.active.as_deref(), Some("Ben"));
+	assert_eq!(harness.ctx.config.controls.sensitivity, 2.5);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn profiles_can_be_clicked() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.profiles = two_profiles();
+
+	let scene = ProfileScene::new(&harness.ctx);
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	let bounds = ProfileScene::widgets(&harness.ctx)[0].bounds;
+	harness.input(Input::MouseMoved { x: 10.0, y: bounds.position.y + bounds.size.y / 2.0 });
+	harness.input(Input::MousePressed);
+	harness.frame();
+
+	assert_eq!(harness.ctx.profiles.active.as_deref(), Some("Ana"));
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn old_profile_without_stats_loads() {
+	let profile: Profile = toml::from_str("name = \"Cat\"").unwrap();
+	assert_eq!(profile.name, "Cat");
+	assert_eq!(profile.sensitivity, 1.0);
+	assert_eq!(profile.stats.matches_played, 0);
+}
@@ -0,0 +1,106 @@
+// Tests for managing the game remotely over HTTP.
+
+#![cfg(feature = "remote")]
+
+mod common;
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, Ipv4Addr};
+use std::time::{Duration, Instant};
+
+use pong::game::Difficulty;
+use pong::remote::{route, token_matches, Route, RemoteCommand, RemoteServer};
+
+use common::Harness;
+
+// Send a request to the server, returning the whole response.
+fn request(server: &RemoteServer, request: &str) -> String {
+	let mut stream = TcpStream::connect(("127.0.0.1", server.addr().port())).unwrap();
+	write!(stream, "{}\r\n\r\n", request).unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	response
+}
+
+#[test]
+fn requests_are_routed() {
+	assert_eq!(route("GET", "/status"), Route::Status);
+	assert_eq!(route("POST", "/match/start"), Route::Command(RemoteCommand::StartMatch));
+	assert_eq!(route("POST", "/difficulty/hard"), Route::Command(RemoteCommand::SetDifficulty(Difficulty::Hard)));
+
+	assert!(matches!(route("GET", "/match/stop"), Route::Error("405 Method Not Allowed", _)));
+	assert!(matches!(route("POST", "/difficulty/impossible"), Route::Error("400 Bad Request", _)));
+	assert!(matches!(route("GET", "/"), Route::Error("404 Not Found", _)));
+}
+
+#[test]
+fn matches_are_started_and_stopped() {
+	let mut harness = Harness::on_menu();
+	assert!(harness.game().paused);
+
+	RemoteCommand::StartMatch.apply(&mut harness.ctx, &mut harness.scenes);
+	harness.run(1);
+	assert!(!harness.game().paused);
+
+	RemoteCommand::StopMatch.apply(&mut harness.ctx, &mut harness.scenes);
+	harness.run(1);
+	assert!(harness.game().paused);
+}
+
+#[test]
+fn commands_and_status_go_over_http() {
+	let mut harness = Harness::on_menu();
+	let server = RemoteServer::spawn(Ipv4Addr::LOCALHOST.into(), 0, Some("secret".to_string())).unwrap();
+	server.update(&mut harness.ctx, &mut harness.scenes);
+
+	assert!(request(&server, "GET /status HTTP/1.1").starts_with("HTTP/1.1 401"));
+	assert!(request(&server, "GET /status HTTP/1.1\r\nAuthorization: Bearer secrets").starts_with("HTTP/1.1 401"));
+
+	let response = request(&server, "GET /status HTTP/1.1\r\nAuthorization: Bearer secret");
+	assert!(response.starts_with("HTTP/1.1 200"));
+	assert!(response.ends_with(r#"{"mode":"classic","scores":[0,0],"paused":true,"difficulty":"normal"}"#));
+
+	let response = request(&server, "POST /difficulty/easy HTTP/1.1\r\nAuthorization: Bearer secret");
+	assert!(response.starts_with("HTTP/1.1 202"));
+
+	// The command is picked up on the game's next frame.
+	let start = Instant::now();
+	while harness.ctx.config.gameplay.ai_difficulty != Difficulty::Easy {
+		assert!(start.elapsed() < Duration::from_secs(5), "the difficulty never changed");
+		server.update(&mut harness.ctx, &mut harness.scenes);
+		std::thread::sleep(Duration::from_millis(5));
+	}
+	assert!(harness.ctx.config_changed);
+}
+
+#[test]
+fn only_this_machine_can_connect_without_a_token() {
+	assert!(RemoteServer::spawn(Ipv4Addr::UNSPECIFIED.into(), 0, None).is_err());
+	assert!(RemoteServer::spawn(Ipv4Addr::UNSPECIFIED.into(), 0, Some("secret".to_string())).is_ok());
+
+	let server = RemoteServer::spawn(Ipv4Addr::LOCALHOST.into(), 0, None).unwrap();
+	assert!(server.addr().ip().is_loopback());
+}
+
+#[test]
+fn web_pages_cant_control_the_game_without_a_token() {
+	let mut harness = Harness::on_menu();
+	let server = RemoteServer::spawn(Ipv4Addr::LOCALHOST.into(), 0, None).unwrap();
+	server.update(&mut harness.ctx, &mut harness.scenes);
+
+	assert!(request(&server, "GET /status HTTP/1.1").starts_with("HTTP/1.1 200"));
+	assert!(request(&server, "POST /match/start HTTP/1.1\r\nOrigin: http://example.com").starts_with("HTTP/1.1 403"));
+	assert!(request(&server, "POST /match/start HTTP/1.1\r\nContent-Type: text/plain").starts_with("HTTP/1.1 403"));
+
+	// With a token, a page would have to know it anyway.
+	let server = RemoteServer::spawn(Ipv4Addr::LOCALHOST.into(), 0, Some("secret".to_string())).unwrap();
+	assert!(request(&server, "POST /match/start HTTP/1.1\r\nOrigin: http://example.com\r\nAuthorization: Bearer secret").starts_with("HTTP/1.1 202"));
+}
+
+#[test]
+fn tokens_have_to_match_exactly() {
+	assert!(token_matches("secret", "secret"));
+	assert!(!token_matches("secreT", "secret"));
+	assert!(!token_matches("secre", "secret"));
+	assert!(!token_matches("", "secret"));
+}
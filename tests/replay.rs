@@ -0,0 +1,244 @@
+// Tests for recording replays and playing them back, and instant replays of each point.
+
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use pong::game::{GameState, Command, TICK_RATE};
+use pong::input::{Input, Key};
+use pong::modes::{Classic, Survival, GameMode};
+use pong::events::GameEvent;
+use pong::replay::{Replay, Playback, REPLAY_VERSION, INSTANT_REPLAY_LENGTH, INSTANT_REPLAY_INTERVAL};
+use pong::clock::ManualClock;
+use pong::scene::{Scene, Transition};
+use pong::scenes::{ReplayScene, REPLAY_SKIP, InstantReplayScene, INSTANT_REPLAY_SPEED};
+
+use common::{Harness, HEIGHT};
+
+// Play a match through the scenes while recording it, moving the paddle around so there's plenty of input.
+fn record_match() -> (Replay, GameState) {
+	let mut harness = Harness::new(&Classic, 3);
+	harness.ctx.sim.start_recording();
+
+	for y in [100.0, 450.0, 300.0, 50.0, HEIGHT, 200.0] {
+		harness.input(Input::MouseMoved { x: 0.0, y });
+		harness.run(400);
+	}
+
+	// Pausing doesn't count towards the replay, since nothing moves.
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(100);
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(200);
+
+	let replay = harness.ctx.sim.finish_recording().unwrap();
+	(replay, harness.game().clone())
+}
+
+#[test]
+fn playback_matches_the_recorded_match() {
+	let (replay, game) = record_match();
+	assert_eq!(replay.length, 2600);
+
+	let mut playback = Playback::new(replay);
+	playback.step(u64::MAX);
+
+	assert!(playback.is_finished());
+	assert_eq!(playback.game().objects, game.objects);
+	assert_eq!(playback.game().scores, game.scores);
+	assert_eq!(playback.game().stats, game.stats);
+}
+
+#[test]
+fn saved_replay_plays_back_the_same() {
+	let (replay, game) = record_match();
+
+	let dir = std::env::temp_dir().join(format!("pong-replay-test-{}", std::process::id()));
+	let path = dir.join("match.replay");
+	replay.save_to(&path).unwrap();
+
+	let mut playback = Playback::new(Replay::load_from(&path).unwrap());
+	playback.step(u64::MAX);
+	assert_eq!(playback.game().objects, game.objects);
+
+	fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn mode_changes_play_back() {
+	let mut game = GameState::with_mode(&Classic);
+	game.resize(800.0, 600.0);
+	game.paused = false;
+
+	let mut replay = Replay::new(game.clone());
+	game.step(50);
+	game.apply(Command::SetMode(&Survival));
+	replay.record(50, Command::SetMode(&Survival));
+	game.step(50);
+	replay.length = 100;
+
+	let dir = std::env::temp_dir().join(format!("pong-replay-mode-test-{}", std::process::id()));
+	let path = dir.join("match.replay");
+	replay.save_to(&path).unwrap();
+
+	let mut playback = Playback::new(Replay::load_from(&path).unwrap());
+	playback.step(u64::MAX);
+	assert_eq!(playback.game().mode.name(), Survival.name());
+	assert_eq!(playback.game().objects, game.objects);
+
+	fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn other_versions_are_refused() {
+	let (mut replay, _) = record_match();
+	replay.version = REPLAY_VERSION + 1;
+
+	let dir = std::env::temp_dir().join(format!("pong-replay-version-test-{}", std::process::id()));
+	let path = dir.join("match.replay");
+	replay.save_to(&path).unwrap();
+
+	let err = Replay::load_from(&path).err().unwrap();
+	assert!(err.contains("version"), "{}", err);
+
+	fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn seeking_matches_playing_straight_through() {
+	let (replay, _) = record_match();
+
+	let mut straight = Playback::new(replay.clone());
+	straight.step(700);
+
+	// Seek past a keyframe and back again.
+	let mut seeking = Playback::new(replay);
+	seeking.seek(2000);
+	assert_eq!(seeking.tick(), 2000);
+	seeking.seek(700);
+	assert_eq!(seeking.tick(), 700);
+	assert_eq!(seeking.game().objects, straight.game().objects);
+
+	// Seeking can't go past the end.
+	seeking.seek(100_000);
+	assert!(seeking.is_finished());
+}
+
+#[test]
+fn only_the_last_paddle_move_each_tick_is_kept() {
+	let mut replay = Replay::new(GameState::new());
+	replay.record(5, Command::MovePaddle { y: 10.0 });
+	replay.record(5, Command::MovePaddle { y: 20.0 });
+	replay.record(5, Command::SetAiAccuracy(0.8));
+	replay.record(6, Command::MovePaddle { y: 30.0 });
+
+	assert_eq!(replay.commands, vec![
+		(5, Command::MovePaddle { y: 20.0 }),
+		(5, Command::SetAiAccuracy(0.8)),
+		(6, Command::MovePaddle { y: 30.0 })
+	]);
+}
+
+#[test]
+fn replay_scene_plays_in_real_time_and_skips() {
+	let (replay, _) = record_match();
+	let mut harness = Harness::new(&Classic, 1);
+
+	let clock = Arc::new(ManualClock::new());
+	let mut scene = ReplayScene::new(replay, clock.clone());
+	scene.enter(&mut harness.ctx);
+
+	// 210ms is just over 25 ticks.
+	clock.advance(Duration::from_millis(210));
+	scene.update(&mut harness.ctx);
+	assert_eq!(scene.playback().tick(), 25);
+
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Right));
+	assert_eq!(scene.playback().tick(), 25 + REPLAY_SKIP);
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Left));
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Left));
+	assert_eq!(scene.playback().tick(), 0);
+
+	// Nothing moves while paused.
+	scene.handle_input(&mut harness.ctx, Input::KeyPressed(Key::Space));
+	clock.advance(Duration::from_secs(1));
+	scene.update(&mut harness.ctx);
+	assert_eq!(scene.playback().tick(), 0);
+	assert!(scene.paused);
+}
+
+// Play for a while, moving the paddle around, then turn instant replays on and play until the next point.
+fn play_to_point() -> Harness {
+	let mut harness = Harness::new(&Classic, 5);
+	for y in [100.0, 450.0, 300.0] {
+		harness.input(Input::MouseMoved { x: 0.0, y });
+		harness.run(200);
+	}
+
+	harness.ctx.config.gameplay.instant_replays = true;
+	assert!(harness.run_until(10_000, |event| matches!(event, GameEvent::PointScored { .. })).is_some());
+	harness
+}
+
+#[test]
+fn instant_replays_play_back_the_last_few_seconds() {
+	let harness = play_to_point();
+	let replay = harness.ctx.sim.instant_replay().unwrap();
+	assert!(replay.length >= INSTANT_REPLAY_LENGTH && replay.length < INSTANT_REPLAY_LENGTH + INSTANT_REPLAY_INTERVAL, "{}", replay.length);
+
+	let mut playback = Playback::new(replay);
+	playback.step(u64::MAX);
+	assert_eq!(playback.game().objects, harness.game().objects);
+	assert_eq!(playback.game().scores, harness.game().scores);
+}
+
+#[test]
+fn points_hold_up_the_serve_for_an_instant_replay() {
+	let mut harness = play_to_point();
+	harness.frame();
+	assert!(harness.game().paused);
+
+	// The replay can be skipped, carrying on with the match.
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+	assert!(!harness.game().paused);
+
+	// Turned off, the match carries straight on.
+	let mut harness = Harness::new(&Classic, 5);
+	assert!(harness.run_until(10_000, |event| matches!(event, GameEvent::PointScored { .. })).is_some());
+	harness.frame();
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn instant_replay_scene_plays_in_slow_motion_up_to_the_point() {
+	let mut harness = play_to_point();
+	let replay = harness.ctx.sim.instant_replay().unwrap();
+	let length = replay.length;
+
+	let clock = Arc::new(ManualClock::new());
+	let mut scene = InstantReplayScene::new(replay, clock.clone());
+	scene.enter(&mut harness.ctx);
+	let start = scene.playback().tick();
+	assert_eq!(start, length - INSTANT_REPLAY_LENGTH);
+
+	// 210ms of real time is 105ms of replay, just over 12 ticks.
+	clock.advance(Duration::from_millis(210));
+	assert!(matches!(scene.update(&mut harness.ctx), Transition::None));
+	assert_eq!(scene.playback().tick(), start + (0.21 * INSTANT_REPLAY_SPEED * TICK_RATE) as u64);
+
+	// It goes back to the match on the tick the point's scored again, before the ball's served from the middle.
+	let mut transition = Transition::None;
+	for _ in 0..100 {
+		clock.advance(Duration::from_millis(100));
+		transition = scene.update(&mut harness.ctx);
+		if !matches!(transition, Transition::None) {
+			break;
+		}
+	}
+	assert!(matches!(transition, Transition::Pop));
+	assert!(scene.playback().tick() <= length);
+	assert_eq!(scene.playback().game().scores, harness.game().scores);
+}
@@ -0,0 +1,88 @@
+// Tests for shaking a gamepad player's controller during a match.
+
+mod common;
+
+use pong::structs::ObjectType;
+use pong::modes::Classic;
+use pong::scenes::{SettingsScene, SettingsPage, SettingsRow};
+use pong::rumble::{HIT_PULSE_TIME, GOAL_PULSE_TIME, MIN_HIT_STRENGTH, SCORED_STRENGTH, CONCEDED_STRENGTH};
+use pong::input::{Input, Key};
+
+use common::{Harness, hit, point};
+
+// Start a match, with the player steering with a gamepad.
+fn on_gamepad() -> Harness {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.input(Input::StickMoved { y: 0.0 });
+	harness
+}
+
+// Set how fast the ball is going, as a fraction of its top speed.
+fn set_ball_speed(harness: &mut Harness, fraction: f32) {
+	let ball = harness.ctx.game.objects.iter_mut().find(|obj| obj.obj_type == ObjectType::Ball).unwrap();
+	ball.velocity = ball.max_velocity * fraction;
+}
+
+#[test]
+fn hits_get_stronger_with_ball_speed() {
+	let mut harness = on_gamepad();
+	let player = harness.game().control_side().unwrap();
+
+	set_ball_speed(&mut harness, 0.0);
+	harness.update_with(vec![hit(player)]);
+	let slow = harness.ctx.rumble.take().unwrap();
+	assert_eq!(slow.strength, MIN_HIT_STRENGTH);
+	assert_eq!(slow.duration, HIT_PULSE_TIME);
+
+	set_ball_speed(&mut harness, 1.0);
+	harness.update_with(vec![hit(player)]);
+	assert!((harness.ctx.rumble.take().unwrap().strength - 1.0).abs() < 1e-5);
+
+	// The opponent's hits aren't felt.
+	harness.update_with(vec![hit(player.opponent())]);
+	assert_eq!(harness.ctx.rumble.take(), None);
+}
+
+#[test]
+fn conceding_is_felt_more_than_scoring() {
+	let mut harness = on_gamepad();
+	let player = harness.game().control_side().unwrap();
+
+	harness.update_with(vec![point(player, [0, 0])]);
+	let scored = harness.ctx.rumble.take().unwrap();
+	assert_eq!((scored.strength, scored.duration), (SCORED_STRENGTH, GOAL_PULSE_TIME));
+
+	// Only the strongest pulse since the controller last took one is kept.
+	harness.update_with(vec![point(player, [0, 0]), point(player.opponent(), [0, 0])]);
+	assert_eq!(harness.ctx.rumble.take().unwrap().strength, CONCEDED_STRENGTH);
+	assert_eq!(harness.ctx.rumble.take(), None);
+}
+
+#[test]
+fn only_gamepad_players_feel_it() {
+	let mut harness = on_gamepad();
+	let player = harness.game().control_side().unwrap();
+
+	harness.input(Input::MouseMoved { x: 0.0, y: 0.0 });
+	harness.update_with(vec![point(player, [0, 0])]);
+	assert_eq!(harness.ctx.rumble.take(), None);
+}
+
+#[test]
+fn rumble_can_be_turned_off() {
+	let mut harness = on_gamepad();
+	let player = harness.game().control_side().unwrap();
+
+	// Switch rumble off on the controls page of the settings.
+	let mut scene = SettingsScene::new();
+	scene.page = SettingsPage::Controls;
+	scene.menu.selected = SettingsPage::Controls.rows().iter().position(|row| *row == SettingsRow::Rumble).unwrap();
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.input(Input::KeyPressed(Key::Enter));
+	assert!(!harness.ctx.config.controls.rumble);
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.input(Input::KeyPressed(Key::Escape));
+
+	harness.update_with(vec![point(player, [0, 0])]);
+	assert_eq!(harness.ctx.rumble.take(), None);
+}
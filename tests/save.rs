@@ -0,0 +1,103 @@
+// Tests for saving a match on quit and continuing it on the next launch.
+
+mod common;
+
+use pong::game::GameState;
+use pong::input::{Input, Key};
+use pong::modes::{Classic, Survival};
+use pong::replay::Playback;
+use pong::save::{SavedMatch, SAVE_VERSION};
+use pong::scenes::ResumeScene;
+
+use common::{Harness, WIDTH, HEIGHT, temp_dir};
+
+// Play part of a survival match, to be saved.
+fn match_in_progress() -> GameState {
+	let mut game = GameState::with_mode(&Survival);
+	game.set_seed(9);
+	game.resize(WIDTH, HEIGHT);
+	game.paused = false;
+	game.step(900);
+	game
+}
+
+#[test]
+fn saved_match_loads_back() {
+	let path = temp_dir("roundtrip").join("match.save");
+	let game = match_in_progress();
+	SavedMatch::new(game.clone()).save_to(&path).unwrap();
+
+	let loaded = SavedMatch::load_from(&path).unwrap().game;
+	assert_eq!(loaded.mode.name(), "survival");
+	assert_eq!(loaded.objects, game.objects);
+	assert_eq!(loaded.scores, game.scores);
+	assert_eq!(loaded.rng, game.rng);
+	assert_eq!(loaded.elapsed, game.elapsed);
+}
+
+#[test]
+fn other_versions_are_refused() {
+	let path = temp_dir("version").join("match.save");
+	let mut saved = SavedMatch::new(match_in_progress());
+	saved.version = SAVE_VERSION + 1;
+	saved.save_to(&path).unwrap();
+
+	assert!(SavedMatch::load_from(&path).is_err());
+}
+
+#[test]
+fn continuing_restores_the_saved_match() {
+	let path = temp_dir("continue").join("match.save");
+	let game = match_in_progress();
+	let saved = SavedMatch::new(game.clone());
+	saved.save_to(&path).unwrap();
+
+	let mut harness = Harness::new(&Classic, 1);
+	harness.scenes.push(&mut harness.ctx, Box::new(ResumeScene::new(saved, path.clone())));
+	harness.frame();
+	assert!(harness.game().paused);
+
+	harness.input(Input::KeyPressed(Key::Enter));
+	harness.frame();
+
+	assert_eq!(harness.game().mode.name(), "survival");
+	assert_eq!(harness.game().objects, game.objects);
+	assert!(!harness.game().paused);
+
+	// The save is only offered once.
+	assert!(!path.exists());
+}
+
+#[test]
+fn starting_a_new_match_throws_the_save_away() {
+	let path = temp_dir("discard").join("match.save");
+	let saved = SavedMatch::new(match_in_progress());
+	saved.save_to(&path).unwrap();
+
+	let mut harness = Harness::new(&Classic, 1);
+	let objects = harness.game().objects.clone();
+	harness.scenes.push(&mut harness.ctx, Box::new(ResumeScene::new(saved, path.clone())));
+
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.frame();
+
+	assert_eq!(harness.game().mode.name(), "classic");
+	assert_eq!(harness.game().objects, objects);
+	assert!(!path.exists());
+}
+
+#[test]
+fn recording_restarts_from_a_restored_match() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.sim.start_recording();
+	harness.run(300);
+
+	harness.ctx.sim.restore(match_in_progress());
+	harness.run(300);
+	let game = harness.game().clone();
+
+	let mut playback = Playback::new(harness.ctx.sim.finish_recording().unwrap());
+	playback.step(u64::MAX);
+	assert_eq!(playback.tick(), 300);
+	assert_eq!(playback.game().objects, game.objects);
+}
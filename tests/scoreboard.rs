@@ -0,0 +1,51 @@
+// Tests for the scoreboard shown on a second screen.
+
+mod common;
+
+use pong::events::{Side, GameEvent};
+use pong::game::Command;
+use pong::modes::Classic;
+use pong::scoreboard::Scoreboard;
+use pong::stats::MatchStats;
+
+use common::{Harness, StubRenderer};
+
+#[test]
+fn board_follows_the_match() {
+	let mut harness = Harness::new(&Classic, 1);
+	let mut board = Scoreboard::new();
+
+	harness.ctx.game.elapsed = 65_000.0;
+	board.record(&harness.ctx.game, &[GameEvent::PointScored { side: Side::Right, scores: [0, 1] }]);
+	assert_eq!(board.scores, [0, 1]);
+	assert_eq!(board.clock_text(), "1:05");
+
+	// The final score and time stay up after the match is won, even though the game's already reset.
+	board.record(&harness.ctx.game, &[GameEvent::MatchEnded { winner: Side::Right, scores: [3, 11], stats: MatchStats::default() }]);
+	harness.ctx.game.elapsed = 0.0;
+	board.record(&harness.ctx.game, &[]);
+	assert_eq!((board.scores, board.winner), ([3, 11], Some(Side::Right)));
+	assert_eq!(board.clock_text(), "1:05");
+
+	// A rematch clears the board.
+	harness.ctx.sim.apply(Command::NewMatch);
+	harness.run(0);
+	board.record(&harness.ctx.game, &harness.ctx.events);
+	assert_eq!(board, Scoreboard::new());
+}
+
+#[test]
+fn winner_is_underlined() {
+	let mut board = Scoreboard { scores: [11, 4], ..Scoreboard::new() };
+	let mut before = StubRenderer::default();
+	board.render(&mut before, 1280.0, 720.0);
+
+	board.winner = Some(Side::Left);
+	let mut after = StubRenderer::default();
+	board.render(&mut after, 1280.0, 720.0);
+
+	// One more rect, under the left half.
+	assert_eq!(after.rects.len(), before.rects.len() + 1);
+	let (position, _) = after.rects.iter().find(|rect| !before.rects.contains(rect)).unwrap();
+	assert!(position.x < 640.0);
+}
@@ -0,0 +1,98 @@
+// Tests for keeping and saving the player's high scores.
+
+mod common;
+
+use std::fs;
+
+use pong::events::Side;
+use pong::modes::{Classic, Survival};
+use pong::stats::MatchStats;
+use pong::scores::{HighScores, MatchRecord, Record};
+
+use common::{temp_dir, hit, point, ended};
+
+#[test]
+fn longest_rally_counts_when_the_point_ends() {
+	let mut scores = HighScores::default();
+
+	// Nothing's final until someone scores.
+	assert!(!scores.record(&Classic, Some(Side::Right), &[hit(Side::Left), hit(Side::Right), hit(Side::Left)]));
+	assert_eq!(scores.longest_rally, 0);
+
+	assert!(scores.record(&Classic, Some(Side::Right), &[point(Side::Left, [1, 0])]));
+	assert_eq!(scores.longest_rally, 3);
+
+	// A shorter rally doesn't beat it.
+	let events = [hit(Side::Left), point(Side::Left, [2, 0])];
+	assert!(!scores.record(&Classic, Some(Side::Right), &events));
+	assert_eq!(scores.longest_rally, 3);
+}
+
+#[test]
+fn matches_are_recorded_per_mode() {
+	let mut scores = HighScores::default();
+
+	let won = ended(Side::Right, [3, 11], &MatchStats::default());
+	let lost = ended(Side::Left, [11, 4], &MatchStats::default());
+	assert!(scores.record(&Classic, Some(Side::Right), &[won.clone(), lost]));
+	assert_eq!(scores.matches["classic"], MatchRecord { played: 2, won: 1 });
+
+	// Without a player, nobody's record changes.
+	assert!(!scores.record(&Classic, None, &[won]));
+	assert_eq!(scores.matches["classic"].played, 2);
+}
+
+#[test]
+fn best_survival_keeps_the_longest() {
+	let mut scores = HighScores::default();
+
+	scores.record(&Survival, Some(Side::Right), &[ended(Side::Left, [1, 42], &MatchStats::default())]);
+	scores.record(&Survival, Some(Side::Right), &[ended(Side::Left, [1, 17], &MatchStats::default())]);
+
+	assert_eq!(scores.best_survival, 42);
+	assert_eq!(scores.matches["survival"], MatchRecord { played: 2, won: 0 });
+}
+
+#[test]
+fn saved_scores_load_back() {
+	let path = temp_dir("roundtrip").join("scores.toml");
+
+	let mut scores = HighScores::default();
+	scores.record(&Classic, Some(Side::Right), &[hit(Side::Left), point(Side::Left, [1, 0])]);
+	scores.record(&Survival, Some(Side::Right), &[ended(Side::Left, [1, 30], &MatchStats::default())]);
+	scores.name_records("Ana");
+	scores.save_to(&path).unwrap();
+
+	assert_eq!(HighScores::load_from(&path), scores);
+}
+
+#[test]
+fn missing_or_broken_file_starts_from_nothing() {
+	let dir = temp_dir("broken");
+	assert_eq!(HighScores::load_from(&dir.join("missing.toml")), HighScores::default());
+
+	// A broken file is left alone so the records in it aren't lost.
+	let path = dir.join("scores.toml");
+	fs::write(&path, "longest_rally = \"lots\"").unwrap();
+	assert_eq!(HighScores::load_from(&path), HighScores::default());
+	assert_eq!(fs::read_to_string(&path).unwrap(), "longest_rally = \"lots\"");
+}
+
+#[test]
+fn beaten_records_wait_for_a_name() {
+	let mut scores = HighScores::default();
+	scores.record(&Survival, Some(Side::Right), &[ended(Side::Left, [1, 42], &MatchStats::default())]);
+	scores.name_records("Ana");
+	assert_eq!(scores.best_survival_by.as_deref(), Some("Ana"));
+	assert!(scores.unnamed.is_empty());
+
+	// Beating a record forgets who held it until the new holder is named.
+	let events = [hit(Side::Left), point(Side::Left, [1, 0]), ended(Side::Left, [1, 50], &MatchStats::default())];
+	scores.record(&Survival, Some(Side::Right), &events);
+	assert_eq!(scores.best_survival_by, None);
+	assert_eq!(scores.unnamed, vec![Record::LongestRally, Record::BestSurvival]);
+
+	scores.name_records("Ben");
+	assert_eq!(scores.best_survival_by.as_deref(), Some("Ben"));
+	assert_eq!(scores.longest_rally_by.as_deref(), Some("Ben"));
+}
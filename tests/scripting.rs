@@ -0,0 +1,62 @@
+// Tests for custom rule scripts.
+
+#![cfg(feature = "scripting")]
+
+mod common;
+
+use std::fs;
+
+use pong::game::{GameState, Rules};
+use pong::events::{Side, GameEvent};
+use pong::scripting::Scripts;
+
+use common::temp_dir;
+
+// Load the given scripts, by file name and source, and run their hooks for a point scored from a fresh match.
+fn run_point(name: &str, scripts: &[(&str, &str)]) -> (GameState, Option<Rules>) {
+	let dir = temp_dir(name);
+	for (file, source) in scripts {
+		fs::write(dir.join(file), source).unwrap();
+	}
+
+	let scripts = Scripts::load(&dir);
+	let game = GameState::new();
+	let rules = scripts.handle_events(&game, &[GameEvent::PointScored { side: Side::Left, scores: [1, 0] }]);
+	fs::remove_dir_all(&dir).ok();
+	(game, rules)
+}
+
+#[test]
+fn rules_are_kept_in_range() {
+	let (game, rules) = run_point("ranges", &[("rules.rhai", "
+		fn on_point(side, left, right) {
+			this.spin_friction = 0.0;
+			this.paddle_momentum = 0;
+			this.ball_speed = 0.0;
+			this.max_bounce_angle = 2.0;
+			this.gravity_y = -0.001;
+		}
+	")]);
+	let rules = rules.unwrap();
+
+	// Spin and momentum can be turned off, and forces can push either way.
+	assert_eq!(rules.spin_friction, 0.0);
+	assert_eq!(rules.paddle_momentum, 0.0);
+	assert_eq!(rules.gravity.y, -0.001);
+
+	// A ball that doesn't move, or bounces off straight up, keeps the rule it had.
+	assert_eq!(rules.ball_speed, game.rules.ball_speed);
+	assert_eq!(rules.max_bounce_angle, game.rules.max_bounce_angle);
+}
+
+#[test]
+fn runaway_scripts_are_stopped() {
+	let (_, rules) = run_point("runaway", &[
+		("a-loop.rhai", "fn on_point(side, left, right) { loop {} }"),
+		("b-recursion.rhai", "fn deeper(n) { deeper(n + 1) } fn on_point(side, left, right) { deeper(0) }"),
+		("c-rules.rhai", "fn on_point(side, left, right) { this.ball_speed = 2.0; }")
+	]);
+
+	// The scripts after them still run.
+	assert_eq!(rules.unwrap().ball_speed, 2.0);
+}
@@ -0,0 +1,179 @@
+// Tests for the in-game settings menu.
+
+mod common;
+
+use pong::input::{Input, Key};
+use pong::game::Difficulty;
+use pong::modes::Classic;
+use pong::scene::Scene;
+use pong::scenes::{SettingsScene, SettingsPage, SettingsRow, MIN_SENSITIVITY, MAX_WIN_SCORE};
+
+use common::{Harness, WIDTH};
+
+// Press a keyboard key the way the window does, sending the key itself and then the game key it's bound to.
+fn press(harness: &mut Harness, scene: &mut SettingsScene, name: &'static str) {
+	scene.handle_input(&mut harness.ctx, Input::KeyboardKey(name));
+	if let Some(key) = harness.ctx.config.key_for(name) {
+		scene.handle_input(&mut harness.ctx, Input::KeyPressed(key));
+	}
+}
+
+// Open a page of the settings and highlight one of its rows.
+fn on_row(harness: &mut Harness, page: SettingsPage, row: SettingsRow) -> SettingsScene {
+	let mut scene = SettingsScene::new();
+	let index = SettingsPage::Categories.rows().iter().position(|other| *other == SettingsRow::Page(page)).unwrap();
+	scene.menu.selected = index;
+	press(harness, &mut scene, "Return");
+	assert_eq!(scene.page, page);
+
+	scene.menu.selected = page.rows().iter().position(|other| *other == row).unwrap();
+	scene
+}
+
+#[test]
+fn changes_apply_live_and_are_kept_for_saving() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Video, SettingsRow::Fullscreen);
+	press(&mut harness, &mut scene, "Return");
+	assert!(harness.ctx.config.window.fullscreen);
+	assert!(harness.ctx.saved_config.window.fullscreen);
+	assert!(harness.ctx.config_changed);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Gameplay, SettingsRow::Difficulty);
+	press(&mut harness, &mut scene, "Right");
+	scene.menu.selected += 1;
+	press(&mut harness, &mut scene, "Left");
+	harness.run(1);
+
+	assert_eq!(harness.ctx.config.gameplay.ai_difficulty, Difficulty::Hard);
+	assert_eq!(harness.game().ai_accuracy, Difficulty::Hard.ai_accuracy());
+	assert_eq!(harness.ctx.saved_config.gameplay.win_score, 10);
+	assert_eq!(harness.game().win_score, 10);
+	assert!(scene.lines(&harness.ctx).contains(&"> Points to win: 10 <".to_string()));
+}
+
+#[test]
+fn vsync_waits_for_a_restart() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Video, SettingsRow::Vsync);
+	press(&mut harness, &mut scene, "Return");
+
+	assert!(harness.ctx.config.window.vsync);
+	assert!(!harness.ctx.saved_config.window.vsync);
+	assert!(scene.lines(&harness.ctx).contains(&"> Vsync (after restart): Off <".to_string()));
+}
+
+#[test]
+fn sensitivity_steps_within_limits_and_follows_the_profile() {
+	let mut harness = Harness::new(&Classic, 1);
+	let config = harness.ctx.config.clone();
+	harness.ctx.profiles.select_or_create("Ada", &config);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Controls, SettingsRow::Sensitivity);
+	for _ in 0..20 {
+		press(&mut harness, &mut scene, "Left");
+	}
+	assert_eq!(harness.ctx.config.controls.sensitivity, MIN_SENSITIVITY);
+
+	press(&mut harness, &mut scene, "Right");
+	press(&mut harness, &mut scene, "Right");
+	assert!((harness.ctx.config.controls.sensitivity - 0.3).abs() < 1e-6);
+	assert_eq!(harness.ctx.profiles.active().unwrap().sensitivity, harness.ctx.config.controls.sensitivity);
+}
+
+#[test]
+fn rebinding_a_key_swaps_it_with_whatever_had_it() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Controls, SettingsRow::Keybind(Key::Scores));
+	press(&mut harness, &mut scene, "Return");
+	assert_eq!(scene.rebinding, Some(Key::Scores));
+	assert!(scene.lines(&harness.ctx).contains(&"> High scores: Press a key... <".to_string()));
+
+	// Up is now bound to the high scores, so the Scores key it triggers isn't taken as a move through the menu.
+	let selected = scene.menu.selected;
+	press(&mut harness, &mut scene, "Up");
+	assert_eq!(scene.rebinding, None);
+	assert_eq!(scene.menu.selected, selected);
+
+	assert_eq!(harness.ctx.config.keybinds["scores"], "Up");
+	assert_eq!(harness.ctx.config.keybinds["up"], "Tab");
+	assert_eq!(harness.ctx.saved_config.keybinds["scores"], "Up");
+
+	// The next key press is handled as usual.
+	press(&mut harness, &mut scene, "Tab");
+	assert_eq!(scene.menu.selected, selected - 1);
+}
+
+#[test]
+fn escape_cancels_rebinding() {
+	let mut harness = Harness::new(&Classic, 1);
+	let keybinds = harness.ctx.config.keybinds.clone();
+
+	let mut scene = on_row(&mut harness, SettingsPage::Controls, SettingsRow::Keybind(Key::Up));
+	press(&mut harness, &mut scene, "Return");
+	press(&mut harness, &mut scene, "Escape");
+
+	assert_eq!(scene.rebinding, None);
+	assert_eq!(scene.page, SettingsPage::Controls);
+	assert_eq!(harness.ctx.config.keybinds, keybinds);
+	assert!(!harness.ctx.config_changed);
+}
+
+#[test]
+fn escape_goes_back_a_page_then_leaves() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Gameplay, SettingsRow::WinScore);
+	press(&mut harness, &mut scene, "Escape");
+	assert_eq!(scene.page, SettingsPage::Categories);
+	assert_eq!(SettingsPage::Categories.rows()[scene.menu.selected], SettingsRow::Page(SettingsPage::Gameplay));
+
+	// Leaving the settings returns to the scene they were opened from.
+	harness.scenes.push(&mut harness.ctx, Box::new(scene));
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.run(1);
+	assert!(!harness.game().paused);
+}
+
+#[test]
+fn sliders_follow_the_mouse() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Gameplay, SettingsRow::Difficulty);
+	let index = SettingsPage::Gameplay.rows().iter().position(|row| *row == SettingsRow::WinScore).unwrap();
+	let track = scene.widgets(&harness.ctx)[index].track.unwrap();
+	let y = track.position.y + 1.0;
+
+	scene.handle_input(&mut harness.ctx, Input::MouseMoved { x: track.position.x, y });
+	scene.handle_input(&mut harness.ctx, Input::MousePressed);
+	assert_eq!(scene.menu.selected, index);
+	assert_eq!(harness.ctx.config.gameplay.win_score, 1);
+
+	// Dragging past the end of the track stops at the highest value.
+	scene.handle_input(&mut harness.ctx, Input::MouseMoved { x: WIDTH, y: 0.0 });
+	assert_eq!(harness.ctx.config.gameplay.win_score, MAX_WIN_SCORE);
+	assert_eq!(harness.ctx.saved_config.gameplay.win_score, MAX_WIN_SCORE);
+
+	// Once let go, moving the mouse only highlights rows.
+	scene.handle_input(&mut harness.ctx, Input::MouseReleased);
+	scene.handle_input(&mut harness.ctx, Input::MouseMoved { x: track.position.x, y });
+	assert_eq!(harness.ctx.config.gameplay.win_score, MAX_WIN_SCORE);
+}
+
+#[test]
+fn rows_without_a_slider_are_clicked() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	let mut scene = on_row(&mut harness, SettingsPage::Video, SettingsRow::Vsync);
+	let widgets = scene.widgets(&harness.ctx);
+	assert!(widgets.iter().all(|widget| widget.track.is_none()));
+
+	let bounds = widgets[0].bounds;
+	scene.handle_input(&mut harness.ctx, Input::MouseMoved { x: 10.0, y: bounds.position.y + 1.0 });
+	scene.handle_input(&mut harness.ctx, Input::MousePressed);
+	assert_eq!(scene.menu.selected, 0);
+	assert!(harness.ctx.config.window.fullscreen);
+}
@@ -0,0 +1,34 @@
+// Tests for the shaders built into the game.
+
+use pong::assets::Assets;
+
+const SHADERS: [&str; 2] = ["vertex_shader.vsh", "fragment_shader.frag"];
+
+#[test]
+fn every_shader_has_an_opengl_es_copy() {
+	let mut assets = Assets::new(None);
+	for name in SHADERS {
+		let desktop = assets.text(&format!("shaders/{}", name)).unwrap();
+		let gles = assets.text(&format!("shaders/gles/{}", name)).unwrap();
+		assert!(desktop.starts_with("#version 150"), "{} isn't GLSL 1.50", name);
+		assert!(gles.starts_with("#version 100"), "the OpenGL ES {} isn't GLSL ES 1.00", name);
+
+		// Both take the same inputs.
+		for input in ["position", "fill", "perspective"] {
+			assert_eq!(desktop.contains(input), gles.contains(input), "{} differs in {}", name, input);
+		}
+	}
+}
+
+#[test]
+fn opengl_es_fragment_shader_sets_a_precision() {
+	let shader = Assets::new(None).text("shaders/gles/fragment_shader.frag").unwrap();
+	assert!(shader.contains("precision mediump float;"));
+}
+
+#[test]
+fn desktop_perspective_is_in_a_uniform_block() {
+	let mut assets = Assets::new(None);
+	assert!(assets.text("shaders/vertex_shader.vsh").unwrap().contains("uniform Frame {"));
+	assert!(assets.text("shaders/gles/vertex_shader.vsh").unwrap().contains("uniform mat4 perspective;"));
+}
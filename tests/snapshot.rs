@@ -0,0 +1,107 @@
+// Tests for serializing game state snapshots.
+
+use pong::structs::{Vec2, Object, ObjectType};
+use pong::game::GameState;
+use pong::collision::Layers;
+use pong::modes::{self, Classic};
+
+// Build a classic match that's already in play.
+fn playing_game(seed: u64) -> GameState {
+	let mut game = GameState::with_mode(&Classic);
+	game.set_seed(seed);
+	game.control_id = None;
+	game.resize(800.0, 600.0);
+	game.paused = false;
+	game
+}
+
+#[test]
+fn vec2_matches_golden_json() {
+	let json = serde_json::to_string(&Vec2::new(1.5, -2.0)).unwrap();
+	assert_eq!(json, r#"{"x":1.5,"y":-2.0}"#);
+}
+
+#[test]
+fn object_round_trips() {
+	let mut obj = Object::new(ObjectType::PaddleLeft).set_size(25.0, 100.0);
+	obj.position = Vec2::new(40.0, 250.0);
+	obj.velocity = Vec2::new(0.0, 0.5);
+
+	let json = serde_json::to_string(&obj).unwrap();
+	let loaded: Object = serde_json::from_str(&json).unwrap();
+	assert_eq!(loaded, obj);
+}
+
+#[test]
+fn object_saved_without_restitution_bounces_normally() {
+	let mut json = serde_json::to_value(Object::new(ObjectType::Ball)).unwrap();
+	json.as_object_mut().unwrap().remove("restitution");
+
+	let loaded: Object = serde_json::from_value(json).unwrap();
+	assert_eq!(loaded.restitution, 1.0);
+}
+
+#[test]
+fn object_saved_without_layers_collides_with_everything() {
+	let mut json = serde_json::to_value(Object::new(ObjectType::PaddleLeft)).unwrap();
+	json.as_object_mut().unwrap().remove("layer");
+	json.as_object_mut().unwrap().remove("mask");
+
+	let loaded: Object = serde_json::from_value(json).unwrap();
+	assert_eq!(loaded.layer, Layers::ALL);
+	assert_eq!(loaded.mask, Layers::ALL);
+}
+
+#[test]
+fn game_state_round_trips() {
+	let mut game = playing_game(7);
+	game.step(500);
+
+	let json = serde_json::to_string(&game).unwrap();
+	let loaded: GameState = serde_json::from_str(&json).unwrap();
+	assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+	assert_eq!(loaded.mode.name(), "classic");
+}
+
+#[test]
+fn restored_game_state_continues_identically() {
+	let mut game = playing_game(42);
+	game.step(300);
+
+	let mut restored: GameState = serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+
+	game.step(1000);
+	restored.step(1000);
+
+	assert_eq!(serde_json::to_string(&restored).unwrap(), serde_json::to_string(&game).unwrap());
+}
+
+#[test]
+fn game_mode_is_saved_by_name() {
+	let game = GameState::with_mode(modes::find("survival").unwrap());
+	let json = serde_json::to_value(&game).unwrap();
+	assert_eq!(json["mode"], "survival");
+}
+
+#[test]
+fn unknown_game_mode_fails_to_load() {
+	let mut json = serde_json::to_value(playing_game(1)).unwrap();
+	json["mode"] = "not-a-mode".into();
+
+	let result: Result<GameState, _> = serde_json::from_value(json);
+	assert!(result.is_err());
+}
+
+// With deterministic maths, a seeded match must play out the same on every platform, down to the last bit.
+// The expected hash comes from the JSON snapshot of the match, so if the simulation changes on purpose, update it to the new value.
+#[cfg(feature = "deterministic")]
+#[test]
+fn deterministic_match_matches_golden_hash() {
+	let mut game = playing_game(1234);
+	game.step(5000);
+
+	// FNV-1a, which is simple enough to spell out here.
+	let json = serde_json::to_string(&game).unwrap();
+	let hash = json.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+	assert_eq!(hash, 9782514271655684075);
+}
@@ -0,0 +1,75 @@
+// Tests for the statistics kept over a match.
+
+use pong::events::{Side, GameEvent};
+use pong::game::GameState;
+use pong::modes::Classic;
+use pong::stats::MatchStats;
+
+#[test]
+fn rallies_and_aces() {
+	let mut stats = MatchStats::default();
+
+	// A point straight from the serve is an ace.
+	stats.point_scored(Side::Left);
+	assert_eq!(stats.aces, [1, 0]);
+
+	stats.ball_hit(Side::Left);
+	stats.ball_hit(Side::Right);
+	stats.ball_hit(Side::Left);
+	stats.point_scored(Side::Right);
+	assert_eq!(stats.aces, [1, 0]);
+
+	assert_eq!(stats.hits, [2, 1]);
+	assert_eq!(stats.points, 2);
+	assert_eq!(stats.longest_rally, 3);
+	assert_eq!(stats.average_rally(), 1.5);
+	assert_eq!(stats.rally, 0);
+}
+
+#[test]
+fn timeline_follows_the_score() {
+	let mut stats = MatchStats::default();
+	assert_eq!(stats.score_history(), vec![[0, 0]]);
+
+	stats.point_scored(Side::Right);
+	stats.point_scored(Side::Left);
+	stats.point_scored(Side::Left);
+	assert_eq!(stats.timeline, vec![Side::Right, Side::Left, Side::Left]);
+	assert_eq!(stats.score_history(), vec![[0, 0], [0, 1], [1, 1], [2, 1]]);
+}
+
+#[test]
+fn average_rally_with_no_points() {
+	assert_eq!(MatchStats::default().average_rally(), 0.0);
+}
+
+#[test]
+fn match_ends_with_its_stats() {
+	let mut game = GameState::with_mode(&Classic);
+	game.resize(800.0, 600.0);
+	game.paused = false;
+	game.win_score = 3;
+
+	let mut events = vec![];
+	for _ in 0..120 * 60 * 5 {
+		game.step(1);
+		events.extend(game.events.drain());
+		if events.iter().any(|event| matches!(event, GameEvent::MatchEnded { .. })) {
+			break;
+		}
+	}
+
+	let hits = events.iter().filter(|event| matches!(event, GameEvent::BallHitPaddle { .. })).count() as u32;
+	let Some(GameEvent::MatchEnded { scores, stats, .. }) = events.last() else {
+		panic!("match didn't end, events: {:?}", events);
+	};
+
+	assert_eq!(stats.hits[0] + stats.hits[1], hits);
+	assert_eq!(stats.points, scores[0] + scores[1]);
+	assert_eq!(stats.score_history().last(), Some(scores));
+	assert!(stats.max_ball_speed > 0.0);
+
+	// The next match starts its stats from scratch.
+	assert_eq!(game.stats.points, 0);
+	assert_eq!(game.stats.hits, [0, 0]);
+}
@@ -0,0 +1,87 @@
+// Tests for exporting match telemetry.
+
+use std::fs;
+use std::path::Path;
+
+use pong::game::{GameState, Command};
+use pong::events::GameEvent;
+use pong::modes::Classic;
+use pong::replay::Replay;
+use pong::telemetry::{Telemetry, Format};
+
+// Record a match long enough for a few points to be scored, with the player's paddle parked at the top.
+fn replay(ticks: u64) -> Replay {
+	let mut game = GameState::with_mode(&Classic);
+	game.resize(1024.0, 768.0);
+	game.set_seed(5);
+	game.paused = false;
+
+	let mut replay = Replay::new(game);
+	replay.record(0, Command::MovePaddle { y: 0.0 });
+	replay.length = ticks;
+	replay
+}
+
+#[test]
+fn replay_is_sampled_every_tick() {
+	let telemetry = Telemetry::from_replay(replay(3000));
+
+	assert_eq!(telemetry.samples.len(), 3000);
+	assert_eq!(telemetry.samples[0].tick, 1);
+	assert_eq!(telemetry.samples[2999].tick, 3000);
+	assert!(telemetry.samples.iter().all(|sample| sample.balls.len() == 1));
+
+	// Events turn up on the tick they happened.
+	let points = telemetry.samples.iter()
+		.flat_map(|sample| &sample.events)
+		.filter(|event| matches!(event, GameEvent::PointScored { .. }))
+		.count();
+	assert!(points > 0);
+
+	// Balls move by their velocity between ticks.
+	let (first, second) = (telemetry.samples[10].balls[0], telemetry.samples[11].balls[0]);
+	assert_ne!(first.position, second.position);
+	assert_ne!(first.velocity.x, 0.0);
+}
+
+#[test]
+fn csv_has_a_row_per_ball_per_tick() {
+	let telemetry = Telemetry::from_replay(replay(300));
+	let csv = telemetry.to_csv().unwrap();
+	let mut lines = csv.lines();
+
+	assert_eq!(lines.next(), Some("tick,time,ball,x,y,velocity_x,velocity_y,events"));
+	assert_eq!(lines.count(), 300);
+}
+
+#[test]
+fn json_holds_every_sample() {
+	let telemetry = Telemetry::from_replay(replay(300));
+	let json: serde_json::Value = serde_json::from_str(&telemetry.to_json().unwrap()).unwrap();
+
+	let samples = json["samples"].as_array().unwrap();
+	assert_eq!(samples.len(), 300);
+	assert_eq!(samples[0]["tick"], 1);
+	assert!(samples[0]["balls"][0]["velocity"]["x"].is_number());
+}
+
+#[test]
+fn format_follows_the_file_extension() {
+	assert_eq!(Format::from_path(Path::new("match.json")), Some(Format::Json));
+	assert_eq!(Format::from_path(Path::new("out/match.CSV")), Some(Format::Csv));
+	assert_eq!(Format::from_path(Path::new("match.txt")), None);
+	assert_eq!(Format::from_path(Path::new("match")), None);
+}
+
+#[test]
+fn exports_to_the_given_file() {
+	let dir = std::env::temp_dir().join(format!("pong-telemetry-test-{}", std::process::id()));
+	let telemetry = Telemetry::from_replay(replay(10));
+
+	let path = dir.join("match.csv");
+	telemetry.save_to(&path).unwrap();
+	assert_eq!(fs::read_to_string(&path).unwrap(), telemetry.to_csv().unwrap());
+
+	assert!(telemetry.save_to(&dir.join("match.txt")).is_err());
+	fs::remove_dir_all(&dir).ok();
+}
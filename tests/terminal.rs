@@ -0,0 +1,62 @@
+// Tests for playing in the terminal, drawn with block characters.
+
+#![cfg(feature = "terminal")]
+
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+
+use pong::structs::Vec2;
+use pong::input::{Input, Key};
+use pong::modes::Classic;
+use pong::scene::Renderer;
+use pong::terminal::{self, TerminalFrame, TerminalInput, POINTS_PER_PIXEL};
+
+use common::{Harness, WIDTH, HEIGHT};
+
+#[test]
+fn rects_are_drawn_with_half_blocks() {
+	let mut frame = TerminalFrame::new(4, 2);
+	assert_eq!(frame.size(), Vec2::new(4.0, 4.0) * POINTS_PER_PIXEL);
+
+	// A rect one pixel tall fills the top half of a cell, and one two pixels tall fills it all.
+	frame.rect(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0) * POINTS_PER_PIXEL);
+	frame.rect(Vec2::new(2.0, 1.0) * POINTS_PER_PIXEL, Vec2::new(1.0, 2.0) * POINTS_PER_PIXEL);
+	assert_eq!(frame.lines(), vec!["▀ ▄ ".to_string(), "  ▀ ".to_string()]);
+}
+
+#[test]
+fn keys_are_named_like_the_window() {
+	assert_eq!(terminal::key_name(KeyCode::Char('w')).as_deref(), Some("W"));
+	assert_eq!(terminal::key_name(KeyCode::Char('1')).as_deref(), Some("Key1"));
+	assert_eq!(terminal::key_name(KeyCode::Enter).as_deref(), Some("Return"));
+	assert_eq!(terminal::key_name(KeyCode::Esc).as_deref(), Some("Escape"));
+	assert_eq!(terminal::key_name(KeyCode::CapsLock), None);
+}
+
+#[test]
+fn up_and_down_move_the_paddle_during_a_match() {
+	let harness = Harness::new(&Classic, 1);
+	let mut input = TerminalInput::new(Vec2::new(WIDTH, HEIGHT));
+
+	let inputs = input.key(&harness.ctx, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+	assert_eq!(inputs, vec![
+		Input::KeyboardKey("Down"),
+		Input::KeyPressed(Key::Down),
+		Input::MouseMoved { x: WIDTH / 2.0, y: HEIGHT / 2.0 + HEIGHT / 16.0 }
+	]);
+
+	// Keys that don't move the paddle are passed on as they are.
+	let inputs = input.key(&harness.ctx, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+	assert_eq!(inputs, vec![Input::KeyboardKey("Escape"), Input::KeyPressed(Key::Escape)]);
+}
+
+#[test]
+fn mouse_points_at_the_middle_of_a_cell() {
+	let mut input = TerminalInput::new(Vec2::new(WIDTH, HEIGHT));
+	let centre = TerminalFrame::cell_centre(3, 1);
+	assert_eq!(centre, Vec2::new(3.5, 3.0) * POINTS_PER_PIXEL);
+
+	assert_eq!(input.mouse(MouseEventKind::Down(MouseButton::Left), 3, 1), vec![Input::MouseMoved { x: centre.x, y: centre.y }, Input::MousePressed]);
+	assert_eq!(input.mouse(MouseEventKind::ScrollUp, 3, 1), vec![]);
+}
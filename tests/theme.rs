@@ -0,0 +1,74 @@
+// Tests for colour themes, the holiday themes put on by the date, and the particles they fill the background with.
+
+mod common;
+
+use pong::modes::Classic;
+use pong::rng::Rng;
+use pong::theme::{self, Date};
+use pong::particles::{Particles, Emitter, MAX_PARTICLES};
+
+use common::{Harness, StubRenderer, WIDTH, HEIGHT};
+
+#[test]
+fn dates_are_counted_from_1970() {
+	assert_eq!(Date::from_days(0), Date::new(1, 1));
+	assert_eq!(Date::from_days(19_722), Date::new(12, 31));
+	// 2024 was a leap year.
+	assert_eq!(Date::from_days(19_723 + 59), Date::new(2, 29));
+	assert_eq!(Date::from_days(19_723 + 60), Date::new(3, 1));
+}
+
+#[test]
+fn holidays_have_their_own_themes() {
+	assert_eq!(theme::seasonal(Date::new(12, 5)).map(|theme| theme.name), Some("winter"));
+	assert_eq!(theme::seasonal(Date::new(12, 31)).map(|theme| theme.name), Some("new-year"));
+	assert_eq!(theme::seasonal(Date::new(1, 1)).map(|theme| theme.name), Some("new-year"));
+	assert_eq!(theme::seasonal(Date::new(1, 2)), None);
+	assert_eq!(theme::seasonal(Date::new(7, 14)), None);
+}
+
+#[test]
+fn holiday_themes_can_be_turned_off() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.config.theme = "no-such-theme".to_string();
+	assert_eq!(harness.ctx.theme().name, "classic");
+
+	harness.ctx.season = theme::seasonal(Date::new(12, 31));
+	assert_eq!(harness.ctx.theme().name, "new-year");
+
+	harness.ctx.config.seasonal_themes = false;
+	harness.ctx.config.theme = "winter".to_string();
+	assert_eq!(harness.ctx.theme().name, "winter");
+}
+
+#[test]
+fn snow_falls_and_clears_once_it_stops() {
+	let mut particles = Particles::new(Rng::new(1));
+	for _ in 0..100 {
+		particles.update(Some(Emitter::Snow), true, 16.0, WIDTH, HEIGHT);
+	}
+	assert!(!particles.is_empty());
+
+	let mut renderer = StubRenderer::default();
+	particles.render(&mut renderer);
+	assert_eq!(renderer.rects.len(), particles.len());
+
+	// Without new flakes, the last ones fall out of the window.
+	for _ in 0..2000 {
+		particles.update(Some(Emitter::Snow), false, 16.0, WIDTH, HEIGHT);
+	}
+	assert!(particles.is_empty());
+}
+
+#[test]
+fn fireworks_fade_out_and_never_overflow() {
+	let mut particles = Particles::new(Rng::new(1));
+
+	// A long stall spawns a lot of fireworks at once, but only up to the limit.
+	particles.update(Some(Emitter::Fireworks), true, 60_000.0, WIDTH, HEIGHT);
+	assert_eq!(particles.len(), MAX_PARTICLES);
+
+	// Sparks only last a second or so.
+	particles.update(None, true, 1500.0, WIDTH, HEIGHT);
+	assert!(particles.is_empty());
+}
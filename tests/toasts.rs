@@ -0,0 +1,81 @@
+// Tests for the queue of toasts shown in the corner of the window.
+
+mod common;
+
+use pong::structs::Vec2;
+use pong::events::{Side, GameEvent};
+use pong::modes::Classic;
+use pong::toast::{Toasts, TOAST_TIME, TOAST_FADE_TIME, MAX_TOASTS};
+
+use common::{Harness, WIDTH, HEIGHT};
+
+// Get the messages of the toasts being shown, oldest first.
+fn shown(toasts: &Toasts) -> Vec<&str> {
+	toasts.shown().map(|toast| toast.message.as_str()).collect()
+}
+
+#[test]
+fn extra_toasts_wait_their_turn() {
+	let mut toasts = Toasts::default();
+	for message in ["One", "Two", "Three", "Four"] {
+		toasts.push(message);
+	}
+	assert_eq!(MAX_TOASTS, 3);
+	assert_eq!(shown(&toasts), vec!["One", "Two", "Three"]);
+
+	// Only the toasts being shown count down, so the waiting one gets its full time once it's shown.
+	toasts.update(TOAST_TIME);
+	assert_eq!(shown(&toasts), vec!["Four"]);
+	assert_eq!(toasts.queue[0].remaining, TOAST_TIME);
+}
+
+#[test]
+fn repeated_messages_are_shown_for_longer_instead() {
+	let mut toasts = Toasts::default();
+	toasts.push("Screenshot saved");
+	toasts.update(TOAST_TIME - 1.0);
+	toasts.push("Screenshot saved");
+
+	assert_eq!(toasts.queue.len(), 1);
+	assert_eq!(toasts.queue[0].remaining, TOAST_TIME);
+}
+
+#[test]
+fn toasts_fade_out_in_the_corner() {
+	let mut harness = Harness::new(&Classic, 1);
+	let rects = harness.rects.len();
+
+	harness.ctx.show_toast("Clip saved");
+	harness.frame();
+	let (position, size, _) = *harness.shades.last().unwrap();
+	assert!(position.x > WIDTH / 2.0 && position.y > HEIGHT / 2.0);
+	assert!(position.x + size.x < WIDTH && position.y + size.y < HEIGHT);
+	let drawn = harness.rects.len();
+
+	// Renderers without blending keep the text until it's mostly faded.
+	harness.ctx.update_toasts(TOAST_TIME - TOAST_FADE_TIME / 2.0);
+	assert_eq!(harness.ctx.toasts.queue[0].opacity(), 0.5);
+	harness.frame();
+	assert_eq!(harness.rects.len(), drawn);
+
+	harness.ctx.update_toasts(1.0);
+	harness.frame();
+	assert_eq!(harness.rects.len(), rects);
+	assert!(harness.shades.last().unwrap().2 < 0.5);
+}
+
+#[test]
+fn events_are_told_with_toasts() {
+	let mut harness = Harness::new(&Classic, 1);
+
+	harness.ctx.events = vec![
+		GameEvent::PowerUpCollected { side: Side::Left },
+		GameEvent::BallHitWall { point: Vec2::new(0.0, 0.0) }
+	];
+	harness.ctx.update_toasts(0.0);
+	assert_eq!(shown(&harness.ctx.toasts), vec!["Power-up!"]);
+
+	harness.ctx.change_config(|config| config.gameplay.win_score = 5);
+	harness.ctx.update_toasts(0.0);
+	assert_eq!(shown(&harness.ctx.toasts), vec!["Power-up!", "Settings applied"]);
+}
@@ -0,0 +1,28 @@
+// Tests for playing and getting around the menus on a touch screen.
+
+use pong::input::{Input, Key};
+use pong::touch::Touches;
+
+#[test]
+fn first_finger_acts_as_the_mouse() {
+	let mut touches = Touches::new();
+	assert_eq!(touches.start(1, 10.0, 20.0), vec![Input::MouseMoved { x: 10.0, y: 20.0 }, Input::MousePressed]);
+	assert_eq!(touches.moved(1, 15.0, 40.0), Some(Input::MouseMoved { x: 15.0, y: 40.0 }));
+	assert_eq!(touches.end(1), Some(Input::MouseReleased));
+
+	// Once it's lifted, the next finger down takes over.
+	assert_eq!(touches.start(2, 0.0, 0.0), vec![Input::MouseMoved { x: 0.0, y: 0.0 }, Input::MousePressed]);
+}
+
+#[test]
+fn second_finger_goes_back() {
+	let mut touches = Touches::new();
+	touches.start(1, 10.0, 20.0);
+
+	assert_eq!(touches.start(2, 300.0, 20.0), vec![Input::KeyPressed(Key::Escape)]);
+
+	// It doesn't move the pointer, and lifting it doesn't let go of what the first finger is holding.
+	assert_eq!(touches.moved(2, 310.0, 20.0), None);
+	assert_eq!(touches.end(2), None);
+	assert_eq!(touches.moved(1, 10.0, 30.0), Some(Input::MouseMoved { x: 10.0, y: 30.0 }));
+}
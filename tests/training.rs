@@ -0,0 +1,73 @@
+// Tests for training mode and the ball's predicted path it shows the player.
+
+mod common;
+
+use pong::structs::{Vec2, ObjectType};
+use pong::events::Side;
+use pong::game::Command;
+use pong::modes::{self, GameMode, Training};
+use pong::prediction::{self, Trajectory};
+
+use common::{Harness, HEIGHT};
+
+#[test]
+fn path_bounces_off_the_walls() {
+	// Heading up and to the left, it hits the top wall halfway there, then comes back down to where it started.
+	let points: Vec<Vec2> = Trajectory::new(Vec2::new(400.0, 100.0), Vec2::new(-1.0, -0.5), 0.0)
+		.with_walls(0.0, HEIGHT, 4)
+		.collect();
+	assert_eq!(points, vec![Vec2::new(200.0, 0.0), Vec2::new(0.0, 100.0)]);
+
+	// Without walls, it carries straight on, which is all the AI looks at.
+	let points: Vec<Vec2> = Trajectory::new(Vec2::new(400.0, 100.0), Vec2::new(-1.0, -0.5), 0.0).collect();
+	assert_eq!(points, vec![Vec2::new(0.0, -100.0)]);
+}
+
+#[test]
+fn path_stops_following_bounces_eventually() {
+	// A ball going almost straight up and down would bounce thousands of times before getting anywhere.
+	let trajectory = Trajectory::new(Vec2::new(400.0, 100.0), Vec2::new(-0.001, 1.0), 0.0).with_walls(0.0, HEIGHT, prediction::MAX_BOUNCES);
+	assert_eq!(trajectory.count(), prediction::MAX_BOUNCES as usize + 1);
+}
+
+#[test]
+fn only_an_incoming_ball_is_predicted() {
+	let mut harness = Harness::new(&Training, 1);
+	harness.ctx.sim.apply(Command::SetMode(&Training));
+	harness.ctx.sim.with(|game| {
+		let ball = game.objects.iter_mut().find(|obj| obj.obj_type == ObjectType::Ball).unwrap();
+		ball.velocity = Vec2::new(-0.5, 0.2);
+	});
+	harness.frame();
+
+	// The ball's going left, towards the AI, so there's nothing to show the player on the right.
+	let game = harness.game();
+	assert_eq!(game.control_side(), Some(Side::Right));
+	assert!(prediction::incoming(game, Side::Right).is_none());
+
+	// Its path to the left paddle ends touching the paddle's face, inside the court.
+	let (start, trajectory) = prediction::incoming(game, Side::Left).unwrap();
+	let ball = game.objects.iter().find(|obj| obj.obj_type == ObjectType::Ball).unwrap();
+	let paddle = game.objects.iter().find(|obj| obj.obj_type == ObjectType::PaddleLeft).unwrap();
+	assert_eq!(start, ball.get_center());
+	let end = trajectory.last().unwrap();
+	assert_eq!(end.x, paddle.position.x + paddle.size.x + ball.size.x / 2.0);
+	assert!(end.y >= ball.size.y / 2.0 && end.y <= game.height - ball.size.y / 2.0);
+}
+
+#[test]
+fn training_never_ends() {
+	let mut harness = Harness::new(&Training, 1);
+	harness.ctx.sim.apply(Command::SetMode(&Training));
+	harness.ctx.sim.with(|game| {
+		for _ in 0..50 {
+			game.score(Side::Right);
+		}
+	});
+	harness.frame();
+
+	assert_eq!(harness.game().scores[Side::Right.index()], 50);
+	assert_eq!(Training.winner(harness.game()), None);
+	assert!(modes::find("training").is_some_and(|mode| mode.shows_trajectory()));
+	assert!(!modes::find("classic").unwrap().shows_trajectory());
+}
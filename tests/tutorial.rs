@@ -0,0 +1,107 @@
+// Tests for the tutorial shown over the first match.
+
+mod common;
+
+use pong::structs::Vec2;
+use pong::events::GameEvent;
+use pong::input::{Input, Key};
+use pong::modes::Classic;
+use pong::scenes::PlayingScene;
+use pong::tutorial::{Tutorial, TutorialStep, TUTORIAL_SHADE};
+
+use common::{Harness, HEIGHT};
+
+// Start a match with the tutorial turned on, as on the first run.
+fn first_match() -> Harness {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.config.gameplay.tutorial = true;
+	harness.scenes.push(&mut harness.ctx, Box::new(PlayingScene));
+	harness.frame();
+	harness
+}
+
+// Make an event for the player hitting the ball the given distance from the middle of their paddle, from -1.0 at the top to 1.0 at the bottom.
+fn player_hit(harness: &Harness, along: f32) -> GameEvent {
+	let game = harness.game();
+	let paddle = &game.objects[game.control_id.unwrap()];
+	let middle = paddle.position.y + paddle.size.y / 2.0;
+	GameEvent::BallHitPaddle { side: game.control_side().unwrap(), point: Vec2::new(paddle.position.x, middle + along * paddle.size.y / 2.0) }
+}
+
+#[test]
+fn moving_the_paddle_finishes_the_first_step() {
+	let mut harness = first_match();
+	assert_eq!(harness.ctx.tutorial.as_ref().unwrap().current(), Some(TutorialStep::Move));
+	assert!(harness.shades.iter().any(|(_, _, opacity)| *opacity == TUTORIAL_SHADE));
+
+	// A small nudge isn't enough.
+	let paddle = harness.game().objects[harness.game().control_id.unwrap()].clone();
+	let middle = paddle.position.y + paddle.size.y / 2.0;
+	harness.input(Input::MouseMoved { x: 0.0, y: middle + 2.0 });
+	harness.run(1);
+	assert_eq!(harness.ctx.tutorial.as_ref().unwrap().current(), Some(TutorialStep::Move));
+
+	harness.input(Input::MouseMoved { x: 0.0, y: HEIGHT });
+	harness.run(1);
+	assert_eq!(harness.ctx.tutorial.as_ref().unwrap().current(), Some(TutorialStep::Return));
+}
+
+#[test]
+fn hits_finish_the_rest() {
+	let mut harness = first_match();
+	harness.ctx.tutorial.as_mut().unwrap().step = 1;
+
+	// The opponent's hits don't count, and a single hit only finishes one step.
+	let side = harness.game().control_side().unwrap();
+	let theirs = GameEvent::BallHitPaddle { side: side.opponent(), point: Vec2::new(0.0, 0.0) };
+	harness.update_with(vec![theirs]);
+	assert_eq!(harness.ctx.tutorial.as_ref().unwrap().current(), Some(TutorialStep::Return));
+
+	let edge = player_hit(&harness, 0.9);
+	harness.update_with(vec![edge]);
+	assert_eq!(harness.ctx.tutorial.as_ref().unwrap().current(), Some(TutorialStep::Smash));
+
+	// Only a hit out towards the edge of the paddle counts as a smash.
+	let middle = player_hit(&harness, 0.1);
+	harness.update_with(vec![middle]);
+	assert!(harness.ctx.tutorial.is_some());
+
+	let edge = player_hit(&harness, -0.8);
+	harness.update_with(vec![edge]);
+	assert_eq!(harness.ctx.tutorial, None);
+	assert!(!harness.ctx.config.gameplay.tutorial);
+	assert!(!harness.ctx.saved_config.gameplay.tutorial);
+	assert!(harness.ctx.config_changed);
+	assert_eq!(harness.ctx.toasts.queue[0].message, "Tutorial complete!");
+}
+
+#[test]
+fn finished_tutorial_stays_off() {
+	let mut harness = first_match();
+	harness.ctx.tutorial.as_mut().unwrap().step = TutorialStep::ALL.len() - 1;
+	let edge = player_hit(&harness, 1.0);
+	harness.update_with(vec![edge]);
+
+	// Pausing and resuming doesn't start it again.
+	harness.ctx.toasts.queue.clear();
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.input(Input::KeyPressed(Key::Escape));
+	harness.frame();
+	assert_eq!(harness.ctx.tutorial, None);
+	assert!(!harness.shades.iter().any(|(_, _, opacity)| *opacity == TUTORIAL_SHADE));
+}
+
+#[test]
+fn tutorial_needs_a_player() {
+	let mut harness = Harness::new(&Classic, 1);
+	harness.ctx.config.gameplay.tutorial = true;
+	harness.ctx.sim.with(|game| game.control_id = None);
+	harness.frame();
+	harness.scenes.push(&mut harness.ctx, Box::new(PlayingScene));
+	assert_eq!(harness.ctx.tutorial, None);
+
+	// Without a player, the steps can't be followed either.
+	let mut tutorial = Tutorial::new();
+	tutorial.update(harness.game(), &[]);
+	assert_eq!(tutorial.current(), Some(TutorialStep::Move));
+}
@@ -0,0 +1,51 @@
+// Tests for the vector maths used by the physics and AI.
+
+use pong::structs::Vec2;
+
+#[test]
+fn scaling_and_negation() {
+	let v = Vec2::new(3.0, -4.0);
+	assert_eq!(v * 2.0, Vec2::new(6.0, -8.0));
+	assert_eq!(v / 2.0, Vec2::new(1.5, -2.0));
+	assert_eq!(-v, Vec2::new(-3.0, 4.0));
+}
+
+#[test]
+fn dot_product() {
+	assert_eq!(Vec2::new(1.0, 2.0).dot(Vec2::new(3.0, 4.0)), 11.0);
+
+	// Perpendicular vectors have a dot product of zero.
+	assert_eq!(Vec2::new(1.0, 0.0).dot(Vec2::new(0.0, 5.0)), 0.0);
+}
+
+#[test]
+fn length_and_normalize() {
+	let v = Vec2::new(3.0, -4.0);
+	assert_eq!(v.length(), 5.0);
+
+	let unit = v.normalize();
+	assert!((unit.length() - 1.0).abs() < 1e-6);
+	assert_eq!(unit, Vec2::new(0.6, -0.8));
+}
+
+#[test]
+fn normalizing_zero_stays_zero() {
+	let zero = Vec2::new(0.0, 0.0);
+	assert_eq!(zero.normalize(), zero);
+}
+
+#[test]
+fn clamp_each_component() {
+	let max = Vec2::new(10.0, 5.0);
+	assert_eq!(Vec2::new(20.0, -8.0).clamp(-max, max), Vec2::new(10.0, -5.0));
+	assert_eq!(Vec2::new(1.0, 2.0).clamp(-max, max), Vec2::new(1.0, 2.0));
+}
+
+#[test]
+fn lerp_between_vectors() {
+	let a = Vec2::new(0.0, 10.0);
+	let b = Vec2::new(10.0, 20.0);
+	assert_eq!(a.lerp(b, 0.0), a);
+	assert_eq!(a.lerp(b, 1.0), b);
+	assert_eq!(a.lerp(b, 0.25), Vec2::new(2.5, 12.5));
+}
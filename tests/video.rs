@@ -0,0 +1,63 @@
+// Tests for recording videos of the game through ffmpeg.
+
+mod common;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use pong::video::{ffmpeg_args, VideoClock, VideoRecorder, VIDEO_FRAME_TIME, MAX_REPEATED_FRAMES};
+
+use common::temp_dir;
+
+#[test]
+fn frames_keep_time_with_the_video() {
+	let mut clock = VideoClock::new();
+	assert_eq!(clock.advance(100.0), 1);
+
+	// Drawing twice as often as the video drops every other frame.
+	let counts: Vec<_> = (0..4).map(|_| clock.advance(VIDEO_FRAME_TIME / 2.0)).collect();
+	assert_eq!(counts.iter().sum::<u64>(), 2);
+
+	// Falling behind repeats the frame, unless it's fallen so far behind the gap is skipped.
+	assert_eq!(clock.advance(VIDEO_FRAME_TIME * 3.0), 3);
+	assert_eq!(clock.advance(VIDEO_FRAME_TIME * (MAX_REPEATED_FRAMES + 10) as f32), 1);
+	assert_eq!(clock.advance(VIDEO_FRAME_TIME), 1);
+}
+
+#[test]
+fn ffmpeg_reads_raw_frames_from_stdin() {
+	let args = ffmpeg_args(Path::new("match.mp4"), 640, 480);
+	let after = |flag: &str| args.iter().position(|arg| arg == flag).map(|index| args[index + 1].as_str());
+
+	assert_eq!(after("-f"), Some("rawvideo"));
+	assert_eq!(after("-video_size"), Some("640x480"));
+	assert_eq!(after("-i"), Some("-"));
+	assert_eq!(args.last().map(String::as_str), Some("match.mp4"));
+}
+
+#[cfg(unix)]
+#[test]
+fn frames_are_piped_to_ffmpeg() {
+	use std::os::unix::fs::PermissionsExt;
+
+	// Stand in for ffmpeg with a script that writes whatever it's sent to the output path, its last argument.
+	let dir = temp_dir("pipe");
+	let script = dir.join("ffmpeg");
+	fs::write(&script, "#!/bin/sh\nfor last; do :; done\ncat > \"$last\"\n").unwrap();
+	fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+	std::env::set_var("FFMPEG", &script);
+
+	let path = dir.join("videos").join("match.mp4");
+	let mut video = VideoRecorder::start(&path, 2, 1).unwrap();
+	video.push(0.0, || Ok((2, 1, vec![1; 8]))).unwrap();
+	// Two frames' time later, the next frame is written twice.
+	video.push(VIDEO_FRAME_TIME * 2.0, || Ok((2, 1, vec![2; 8]))).unwrap();
+
+	// Frames that aren't due aren't even read, and ones the wrong size are refused.
+	video.push(0.0, || Err(io::Error::other("read when not due"))).unwrap();
+	assert!(video.push(VIDEO_FRAME_TIME, || Ok((4, 4, vec![0; 64]))).is_err());
+
+	assert_eq!(video.finish().unwrap(), path);
+	assert_eq!(fs::read(&path).unwrap(), [vec![1; 8], vec![2; 16]].concat());
+}
@@ -0,0 +1,65 @@
+// Tests for laying the play area out in the window on high-DPI displays and around anything covering the screen.
+
+use pong::structs::Vec2;
+use pong::viewport::{Viewport, Insets};
+
+// Apply the perspective matrix to a point in the play area, giving where it ends up in the window from -1 to 1.
+fn project(viewport: &Viewport, x: f32, y: f32) -> (f32, f32) {
+	let m = viewport.perspective();
+	(m[0][0] * x + m[3][0], m[1][1] * y + m[3][1])
+}
+
+#[test]
+fn play_area_is_in_logical_points() {
+	let viewport = Viewport::new(2048.0, 1536.0, 2.0);
+	assert_eq!(viewport.size(), Vec2::new(1024.0, 768.0));
+
+	// The play area still fills the window, drawn at its full resolution.
+	assert_eq!(project(&viewport, 0.0, 0.0), (-1.0, 1.0));
+	assert_eq!(project(&viewport, 1024.0, 768.0), (1.0, -1.0));
+
+	// The cursor is reported in pixels.
+	assert_eq!(viewport.to_logical(1024.0, 768.0), Vec2::new(512.0, 384.0));
+}
+
+#[test]
+fn insets_are_kept_clear() {
+	let insets = Insets { top: 32.0, ..Default::default() };
+	let viewport = Viewport::new(2000.0, 1264.0, 2.0).with_insets(insets);
+	assert_eq!(viewport.size(), Vec2::new(1000.0, 600.0));
+
+	// The top of the play area starts below the inset, and the bottom still reaches the bottom of the window.
+	let (x, y) = project(&viewport, 0.0, 0.0);
+	assert_eq!(x, -1.0);
+	assert!((y - (1.0 - 64.0 / 632.0)).abs() < 1e-6);
+	assert_eq!(project(&viewport, 1000.0, 600.0), (1.0, -1.0));
+
+	// Positions round trip between the window and the play area.
+	let position = Vec2::new(250.0, 100.0);
+	assert_eq!(viewport.to_physical(position), Vec2::new(500.0, 264.0));
+	assert_eq!(viewport.to_logical(500.0, 264.0), position);
+}
+
+#[test]
+fn invalid_scale_is_ignored() {
+	let viewport = Viewport::new(800.0, 600.0, 0.0);
+	assert_eq!(viewport.size(), Vec2::new(800.0, 600.0));
+}
+
+#[test]
+fn upright_screen_turns_the_court() {
+	let viewport = Viewport::new(720.0, 1280.0, 1.0).with_rotation(true);
+	assert_eq!(viewport.size(), Vec2::new(1280.0, 720.0));
+
+	// The court's left edge runs along the top of the window, so its top-left corner is in the window's top-right.
+	let m = viewport.perspective();
+	let project = |x: f32, y: f32| (m[0][0] * x + m[1][0] * y + m[3][0], m[0][1] * x + m[1][1] * y + m[3][1]);
+	assert_eq!(project(0.0, 0.0), (1.0, 1.0));
+	assert_eq!(project(1280.0, 720.0), (-1.0, -1.0));
+
+	// Touches land in the same place on the court as they're drawn.
+	assert_eq!(viewport.to_logical(720.0, 0.0), Vec2::new(0.0, 0.0));
+	assert_eq!(viewport.to_logical(0.0, 1280.0), Vec2::new(1280.0, 720.0));
+	let position = Vec2::new(100.0, 200.0);
+	assert_eq!(viewport.to_logical(viewport.to_physical(position).x, viewport.to_physical(position).y), position);
+}
@@ -0,0 +1,77 @@
+// Tests for the pointer handling shared by the menus.
+
+use pong::structs::Vec2;
+use pong::input::{Input, Key};
+use pong::widget::{Widget, Bounds, Pointer, PointerAction, ListMenu, MenuAction};
+
+// A button on top, and a slider under it with its track on the right half.
+fn widgets() -> Vec<Widget> {
+	vec![
+		Widget::button(Bounds::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 10.0))),
+		Widget::slider(Bounds::new(Vec2::new(0.0, 10.0), Vec2::new(100.0, 10.0)), Bounds::new(Vec2::new(50.0, 10.0), Vec2::new(40.0, 10.0)))
+	]
+}
+
+#[test]
+fn buttons_are_hovered_then_clicked() {
+	let widgets = widgets();
+	let mut pointer = Pointer::new();
+
+	// Pressing with nothing under the pointer does nothing.
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 5.0, y: 30.0 }), PointerAction::None);
+	assert_eq!(pointer.handle(&widgets, Input::MousePressed), PointerAction::None);
+	assert_eq!(pointer.pressed, None);
+
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 5.0, y: 5.0 }), PointerAction::Hover(0));
+	assert_eq!(pointer.handle(&widgets, Input::MousePressed), PointerAction::Click(0));
+	assert_eq!(pointer.pressed, Some(0));
+
+	// The button stays held down until the mouse is let go, wherever the pointer goes.
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 5.0, y: 15.0 }), PointerAction::Hover(1));
+	assert_eq!(pointer.pressed, Some(0));
+	assert_eq!(pointer.handle(&widgets, Input::MouseReleased), PointerAction::None);
+	assert_eq!(pointer.pressed, None);
+}
+
+#[test]
+fn sliders_are_dragged_along_their_track() {
+	let widgets = widgets();
+	let mut pointer = Pointer::new();
+
+	// Pressing a slider away from its track clicks it like a button.
+	pointer.handle(&widgets, Input::MouseMoved { x: 5.0, y: 15.0 });
+	assert_eq!(pointer.handle(&widgets, Input::MousePressed), PointerAction::Click(1));
+	pointer.handle(&widgets, Input::MouseReleased);
+
+	pointer.handle(&widgets, Input::MouseMoved { x: 60.0, y: 15.0 });
+	assert_eq!(pointer.handle(&widgets, Input::MousePressed), PointerAction::Drag(1, 0.25));
+
+	// Dragging keeps going past either end of the track, and off the slider altogether.
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 200.0, y: 5.0 }), PointerAction::Drag(1, 1.0));
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 0.0, y: 15.0 }), PointerAction::Drag(1, 0.0));
+
+	pointer.handle(&widgets, Input::MouseReleased);
+	assert_eq!(pointer.handle(&widgets, Input::MouseMoved { x: 60.0, y: 15.0 }), PointerAction::Hover(1));
+}
+
+#[test]
+fn list_menus_wrap_and_follow_the_pointer() {
+	let widgets = widgets();
+	let mut menu = ListMenu::new(0);
+
+	// Up from the top goes round to the bottom, and back.
+	assert_eq!(menu.handle(&widgets, Input::KeyPressed(Key::Up)), MenuAction::None);
+	assert_eq!(menu.selected, 1);
+	menu.handle(&widgets, Input::KeyPressed(Key::Down));
+	assert_eq!(menu.selected, 0);
+	assert_eq!(menu.mark(["Play".to_string(), "Quit".to_string()]), vec!["> Play <", "Quit"]);
+
+	// Pointing at an entry highlights it, and dragging a slider highlights it too.
+	menu.handle(&widgets, Input::MouseMoved { x: 5.0, y: 5.0 });
+	assert_eq!(menu.handle(&widgets, Input::MousePressed), MenuAction::Choose);
+	menu.handle(&widgets, Input::MouseReleased);
+	menu.handle(&widgets, Input::MouseMoved { x: 70.0, y: 15.0 });
+	assert_eq!(menu.handle(&widgets, Input::MousePressed), MenuAction::Drag(0.5));
+	assert_eq!(menu.selected, 1);
+	assert_eq!(menu.handle(&widgets, Input::KeyPressed(Key::Enter)), MenuAction::Choose);
+}